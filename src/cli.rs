@@ -1,6 +1,8 @@
 use clap::{Args, Parser, Subcommand, ValueEnum};
 use std::path::PathBuf;
 
+use crate::filter_expr::FilterExpr;
+
 /// Monitor Claude Code sessions in real-time
 #[derive(Parser, Debug)]
 #[command(
@@ -14,16 +16,31 @@ pub struct Cli {
     #[arg(long)]
     pub project: Option<PathBuf>,
 
+    /// Watch every project under ~/.claude/projects/ at once, with the
+    /// sidebar grouped by project.
+    #[arg(long, default_value_t = false, conflicts_with = "project")]
+    pub all_projects: bool,
+
     /// Attach to a specific session UUID (prefix match supported).
     /// Default: auto-attach to the most recently active session.
     #[arg(long)]
     pub session: Option<String>,
 
     /// Show progress entries and additional metadata.
-    /// Writes debug info to stderr.
+    /// Writes debug info to the log.
     #[arg(long, default_value_t = false)]
     pub verbose: bool,
 
+    /// Write cc-tail's own log to this file. Default: the TUI logs to
+    /// ~/.local/state/cc-tail/cctail.log, subcommands to stderr.
+    #[arg(long)]
+    pub log_file: Option<PathBuf>,
+
+    /// Detect running Claude Code processes (Linux) to tell live, idle and
+    /// dead sessions apart instead of relying on file modification times.
+    #[arg(long, default_value_t = false)]
+    pub detect_processes: bool,
+
     /// Color theme: dark or light
     #[arg(long, value_enum)]
     pub theme: Option<Theme>,
@@ -44,6 +61,12 @@ pub struct Cli {
 pub enum Commands {
     /// Lightweight streaming mode that tails a single JSONL file to stdout
     Stream(StreamArgs),
+    /// Search every session in the project (including subagent logs)
+    Search(SearchArgs),
+    /// List the project's sessions as a table, JSON or NDJSON
+    Sessions(SessionsArgs),
+    /// Export a session transcript as Markdown, HTML or JSON
+    Export(ExportArgs),
 }
 
 #[derive(Args, Debug)]
@@ -63,6 +86,123 @@ pub struct StreamArgs {
     /// Color theme for ANSI output
     #[arg(long, value_enum)]
     pub theme: Option<Theme>,
+
+    /// Only print entries matching a filter expression,
+    /// e.g. "role:assistant AND tool:Bash AND NOT text:/cargo test/"
+    #[arg(long, value_parser = FilterExpr::parse)]
+    pub filter: Option<FilterExpr>,
+
+    /// Print thinking blocks in full instead of a one-line preview
+    #[arg(long, default_value_t = false)]
+    pub thinking: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct SearchArgs {
+    /// Text to search for (case-insensitive substring by default)
+    pub query: String,
+
+    /// Interpret the query as a regular expression
+    #[arg(long, default_value_t = false, conflicts_with = "smart_case")]
+    pub regex: bool,
+
+    /// Match case only if the query contains an uppercase letter
+    #[arg(long, default_value_t = false)]
+    pub smart_case: bool,
+
+    /// Maximum number of matches listed per session
+    #[arg(long, default_value_t = 20)]
+    pub limit: usize,
+
+    /// Create the on-disk search index if it does not exist yet
+    /// (an existing index is always used and kept up to date)
+    #[arg(long, default_value_t = false)]
+    pub index: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct SessionsArgs {
+    /// Output format
+    #[arg(long, value_enum, default_value_t = ListFormat::Table)]
+    pub format: ListFormat,
+
+    /// Sort key (largest / most recent first)
+    #[arg(long, value_enum, default_value_t = SessionSort::Modified)]
+    pub sort: SessionSort,
+
+    /// Reverse the sort order (smallest / oldest first)
+    #[arg(long, default_value_t = false)]
+    pub reverse: bool,
+
+    /// Print at most this many sessions
+    #[arg(long)]
+    pub limit: Option<usize>,
+}
+
+#[derive(Args, Debug)]
+pub struct ExportArgs {
+    /// Session UUID to export (prefix match supported).
+    /// Default: the most recently active session.
+    #[arg(long)]
+    pub session: Option<String>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = ExportFormat::Md)]
+    pub format: ExportFormat,
+
+    /// Write to this file instead of stdout
+    #[arg(long, short = 'o')]
+    pub output: Option<PathBuf>,
+
+    /// Only export entries matching a filter expression
+    #[arg(long, value_parser = FilterExpr::parse)]
+    pub filter: Option<FilterExpr>,
+
+    /// Leave out tool calls and tool results
+    #[arg(long, default_value_t = false)]
+    pub hide_tools: bool,
+
+    /// Leave out meta entries (injected reminders, command caveats)
+    #[arg(long, default_value_t = false)]
+    pub hide_meta: bool,
+
+    /// Only export entries of this subagent
+    #[arg(long)]
+    pub agent: Option<String>,
+}
+
+/// Output format for `cctail export`.
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum ExportFormat {
+    /// Markdown
+    Md,
+    /// Single self-contained HTML file
+    Html,
+    /// Structured JSON
+    Json,
+}
+
+/// Output format for list-style subcommands.
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum ListFormat {
+    Table,
+    Json,
+    Ndjson,
+}
+
+/// Sort key for `cctail sessions`.
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum SessionSort {
+    /// Last modification time of any of the session's files
+    Modified,
+    /// Timestamp of the first entry
+    Started,
+    /// Time between the first and last entry
+    Duration,
+    /// Number of entries
+    Entries,
+    /// Total size of the session's files
+    Size,
 }
 
 #[derive(Clone, Debug, PartialEq, ValueEnum)]
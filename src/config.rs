@@ -1,7 +1,14 @@
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 use crate::cli::{Cli, Theme};
+use crate::log_warn;
+use crate::pricing::{ModelPrice, PriceTable};
+use crate::watcher::{WatchBackend, DEFAULT_POLL_INTERVAL_MS};
+
+/// Smallest accepted `[watcher] poll_interval_ms`.
+const MIN_POLL_INTERVAL_MS: u64 = 200;
 
 // ---------------------------------------------------------------------------
 // TOML-deserializable config (intermediate representation)
@@ -14,9 +21,13 @@ use crate::cli::{Cli, Theme};
 #[serde(default)]
 struct FileConfig {
     verbose: Option<bool>,
+    log_file: Option<PathBuf>,
     theme: Option<String>,
     ascii: Option<bool>,
+    detect_processes: Option<bool>,
     display: FileDisplayConfig,
+    watcher: FileWatcherConfig,
+    pricing: HashMap<String, FileModelPrice>,
 }
 
 #[derive(Debug, Deserialize, Default)]
@@ -26,6 +37,23 @@ struct FileDisplayConfig {
     timestamp_format: Option<String>,
 }
 
+#[derive(Debug, Deserialize, Default)]
+#[serde(default)]
+struct FileWatcherConfig {
+    backend: Option<String>,
+    poll_interval_ms: Option<u64>,
+}
+
+/// One `[pricing."<model>"]` table. Rates are USD per million tokens.
+#[derive(Debug, Deserialize, Default)]
+#[serde(default)]
+struct FileModelPrice {
+    input: Option<f64>,
+    output: Option<f64>,
+    cache_write: Option<f64>,
+    cache_read: Option<f64>,
+}
+
 // ---------------------------------------------------------------------------
 // Effective (merged) config
 // ---------------------------------------------------------------------------
@@ -33,11 +61,21 @@ struct FileDisplayConfig {
 #[derive(Debug, Clone, PartialEq)]
 pub struct AppConfig {
     pub project: Option<PathBuf>,
+    /// Watch every project at once (`--all-projects`).
+    pub all_projects: bool,
     pub session: Option<String>,
     pub verbose: bool,
+    /// Where cc-tail's own log goes (`--log-file`); `None` for the default.
+    pub log_file: Option<PathBuf>,
     pub theme: Theme,
     pub ascii: bool,
+    /// Combine process detection with the mtime heuristic for session
+    /// status (`--detect-processes`, Linux only).
+    pub detect_processes: bool,
     pub display: DisplayConfig,
+    pub watcher: WatcherConfig,
+    /// Per-model token prices used for the estimated cost display.
+    pub pricing: PriceTable,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -46,15 +84,28 @@ pub struct DisplayConfig {
     pub timestamp_format: String,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct WatcherConfig {
+    /// How file changes are detected (`auto`, `native` or `poll`).
+    pub backend: WatchBackend,
+    /// Interval between scans of the polling backend.
+    pub poll_interval_ms: u64,
+}
+
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
             project: None,
+            all_projects: false,
             session: None,
             verbose: false,
+            log_file: None,
             theme: Theme::Dark,
             ascii: false,
+            detect_processes: false,
             display: DisplayConfig::default(),
+            watcher: WatcherConfig::default(),
+            pricing: PriceTable::default(),
         }
     }
 }
@@ -68,6 +119,15 @@ impl Default for DisplayConfig {
     }
 }
 
+impl Default for WatcherConfig {
+    fn default() -> Self {
+        Self {
+            backend: WatchBackend::Auto,
+            poll_interval_ms: DEFAULT_POLL_INTERVAL_MS,
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Config loading
 // ---------------------------------------------------------------------------
@@ -89,20 +149,12 @@ fn load_file_config(path: &Path) -> Option<FileConfig> {
         Ok(contents) => match toml::from_str::<FileConfig>(&contents) {
             Ok(cfg) => Some(cfg),
             Err(e) => {
-                eprintln!(
-                    "cc-tail: warning: failed to parse config file {}: {}",
-                    path.display(),
-                    e
-                );
+                log_warn!("failed to parse config file {}: {}", path.display(), e);
                 None
             }
         },
         Err(e) => {
-            eprintln!(
-                "cc-tail: warning: failed to read config file {}: {}",
-                path.display(),
-                e
-            );
+            log_warn!("failed to read config file {}: {}", path.display(), e);
             None
         }
     }
@@ -115,15 +167,60 @@ fn parse_theme(s: &str) -> Option<Theme> {
         "dark" => Some(Theme::Dark),
         "light" => Some(Theme::Light),
         other => {
-            eprintln!(
-                "cc-tail: warning: unknown theme \"{}\", using default",
-                other
-            );
+            log_warn!("unknown theme \"{}\", using default", other);
             None
         }
     }
 }
 
+/// Parse a `[watcher] backend` string. Returns `None` if the string is not
+/// recognized (caller uses default).
+fn parse_watch_backend(s: &str) -> Option<WatchBackend> {
+    let backend = WatchBackend::parse(s);
+    if backend.is_none() {
+        log_warn!("unknown watcher backend \"{}\", using default", s);
+    }
+    backend
+}
+
+/// Merge a `[pricing."<model>"]` table into the price table.
+///
+/// Keys missing from the file fall back to the model's existing price (when
+/// overriding a built-in entry). Cache rates missing from both are derived
+/// from the input rate. A new model without both `input` and `output` is
+/// ignored with a warning.
+fn apply_model_price(table: &mut PriceTable, model: &str, file_price: &FileModelPrice) {
+    let base = table.lookup(model).copied();
+    let input = file_price.input.or(base.map(|p| p.input));
+    let output = file_price.output.or(base.map(|p| p.output));
+
+    let (input, output) = match (input, output) {
+        (Some(i), Some(o)) => (i, o),
+        _ => {
+            log_warn!(
+                "pricing for \"{}\" needs both input and output rates, ignoring",
+                model
+            );
+            return;
+        }
+    };
+
+    let derived = ModelPrice::from_io(input, output);
+    let price = ModelPrice {
+        input,
+        output,
+        cache_write: file_price.cache_write.unwrap_or(match base {
+            Some(p) if file_price.input.is_none() => p.cache_write,
+            _ => derived.cache_write,
+        }),
+        cache_read: file_price.cache_read.unwrap_or(match base {
+            Some(p) if file_price.input.is_none() => p.cache_read,
+            _ => derived.cache_read,
+        }),
+    };
+    table.set(model, price);
+}
+
 /// Build the effective `AppConfig` by merging defaults, config file, and CLI args.
 ///
 /// Precedence (highest wins):
@@ -144,6 +241,9 @@ pub fn build_config(cli: &Cli) -> AppConfig {
             if let Some(v) = file_cfg.verbose {
                 config.verbose = v;
             }
+            if let Some(ref path) = file_cfg.log_file {
+                config.log_file = Some(path.clone());
+            }
             if let Some(ref t) = file_cfg.theme {
                 if let Some(theme) = parse_theme(t) {
                     config.theme = theme;
@@ -152,22 +252,33 @@ pub fn build_config(cli: &Cli) -> AppConfig {
             if let Some(a) = file_cfg.ascii {
                 config.ascii = a;
             }
+            if let Some(d) = file_cfg.detect_processes {
+                config.detect_processes = d;
+            }
             if let Some(ts) = file_cfg.display.timestamps {
                 config.display.timestamps = ts;
             }
             if let Some(ref fmt) = file_cfg.display.timestamp_format {
                 config.display.timestamp_format = fmt.clone();
             }
+            if let Some(ref b) = file_cfg.watcher.backend {
+                if let Some(backend) = parse_watch_backend(b) {
+                    config.watcher.backend = backend;
+                }
+            }
+            if let Some(ms) = file_cfg.watcher.poll_interval_ms {
+                config.watcher.poll_interval_ms = ms.max(MIN_POLL_INTERVAL_MS);
+            }
+            for (model, price) in &file_cfg.pricing {
+                apply_model_price(&mut config.pricing, model, price);
+            }
         } else if cli.config.is_some() {
             // User explicitly specified --config but file could not be loaded.
             // The warning was already printed by load_file_config if the file
             // existed but was malformed. If the file didn't exist at all,
             // print a warning here.
             if !path.exists() {
-                eprintln!(
-                    "cc-tail: warning: config file not found: {}",
-                    path.display()
-                );
+                log_warn!("config file not found: {}", path.display());
             }
         }
     }
@@ -176,15 +287,24 @@ pub fn build_config(cli: &Cli) -> AppConfig {
     if cli.project.is_some() {
         config.project = cli.project.clone();
     }
+    if cli.all_projects {
+        config.all_projects = true;
+    }
     if cli.session.is_some() {
         config.session = cli.session.clone();
     }
     if cli.verbose {
         config.verbose = true;
     }
+    if cli.log_file.is_some() {
+        config.log_file = cli.log_file.clone();
+    }
     if cli.ascii {
         config.ascii = true;
     }
+    if cli.detect_processes {
+        config.detect_processes = true;
+    }
     if let Some(ref theme) = cli.theme {
         config.theme = theme.clone();
     }
@@ -218,8 +338,11 @@ mod tests {
     fn default_cli() -> Cli {
         Cli {
             project: None,
+            all_projects: false,
             session: None,
             verbose: false,
+            log_file: None,
+            detect_processes: false,
             theme: None,
             ascii: false,
             config: None,
@@ -338,6 +461,112 @@ theme = "dark"
         assert!(result.is_none());
     }
 
+    // -- Pricing tests --------------------------------------------------------
+
+    #[test]
+    fn test_parse_pricing_tables() {
+        let toml = r#"
+[pricing."claude-sonnet-4"]
+input = 2.0
+output = 10.0
+
+[pricing.my-proxy-model]
+input = 1.0
+output = 2.0
+cache_read = 0.05
+"#;
+        let cfg = parse_file_config(toml).unwrap();
+        assert_eq!(cfg.pricing.len(), 2);
+        let sonnet = &cfg.pricing["claude-sonnet-4"];
+        assert_eq!(sonnet.input, Some(2.0));
+        assert_eq!(sonnet.cache_write, None);
+        assert_eq!(cfg.pricing["my-proxy-model"].cache_read, Some(0.05));
+    }
+
+    #[test]
+    fn test_apply_model_price_new_model_derives_cache_rates() {
+        let mut table = PriceTable::empty();
+        let fp = FileModelPrice {
+            input: Some(2.0),
+            output: Some(8.0),
+            ..Default::default()
+        };
+        apply_model_price(&mut table, "custom", &fp);
+        let p = table.lookup("custom").unwrap();
+        assert_eq!(p.output, 8.0);
+        assert!((p.cache_write - 2.5).abs() < 1e-9);
+        assert!((p.cache_read - 0.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_apply_model_price_partial_override_keeps_builtin() {
+        let mut table = PriceTable::builtin();
+        let fp = FileModelPrice {
+            output: Some(20.0),
+            ..Default::default()
+        };
+        apply_model_price(&mut table, "claude-sonnet-4", &fp);
+        let p = table.lookup("claude-sonnet-4").unwrap();
+        assert_eq!(p.input, 3.0);
+        assert_eq!(p.output, 20.0);
+        assert!((p.cache_write - 3.75).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_apply_model_price_incomplete_new_model_ignored() {
+        let mut table = PriceTable::empty();
+        let fp = FileModelPrice {
+            input: Some(2.0),
+            ..Default::default()
+        };
+        apply_model_price(&mut table, "custom", &fp);
+        assert!(table.lookup("custom").is_none());
+    }
+
+    #[test]
+    fn test_build_config_pricing_from_file() {
+        let toml = r#"
+[pricing.custom-model]
+input = 4.0
+output = 16.0
+"#;
+        let mut f = NamedTempFile::new().unwrap();
+        f.write_all(toml.as_bytes()).unwrap();
+
+        let cli = Cli {
+            config: Some(f.path().to_path_buf()),
+            ..default_cli()
+        };
+        let config = build_config(&cli);
+        assert_eq!(config.pricing.lookup("custom-model").unwrap().input, 4.0);
+        // Built-in entries are still present.
+        assert!(config.pricing.lookup("claude-opus-4-6").is_some());
+    }
+
+    #[test]
+    fn test_build_config_watcher_from_file() {
+        let toml = r#"
+[watcher]
+backend = "poll"
+poll_interval_ms = 10
+"#;
+        let mut f = NamedTempFile::new().unwrap();
+        f.write_all(toml.as_bytes()).unwrap();
+
+        let cli = Cli {
+            config: Some(f.path().to_path_buf()),
+            ..default_cli()
+        };
+        let config = build_config(&cli);
+        assert_eq!(config.watcher.backend, WatchBackend::Poll);
+        // Intervals below the minimum are raised to it.
+        assert_eq!(config.watcher.poll_interval_ms, MIN_POLL_INTERVAL_MS);
+
+        let defaults = AppConfig::default().watcher;
+        assert_eq!(defaults.backend, WatchBackend::Auto);
+        assert_eq!(defaults.poll_interval_ms, DEFAULT_POLL_INTERVAL_MS);
+    }
+
     // -- Theme parsing tests --------------------------------------------------
 
     #[test]
@@ -408,9 +637,12 @@ theme = "light"
         let cli = Cli {
             config: Some(f.path().to_path_buf()),
             verbose: true,
+            log_file: None,
+            detect_processes: false,
             theme: Some(Theme::Dark),
             ascii: false,
             project: Some(PathBuf::from("/my/project")),
+            all_projects: false,
             session: Some("abc123".to_string()),
             command: None,
         };
@@ -443,6 +675,58 @@ verbose = true
         assert!(config.verbose);
     }
 
+    #[test]
+    fn test_build_config_cli_all_projects() {
+        let cli = Cli {
+            all_projects: true,
+            ..default_cli()
+        };
+        assert!(build_config(&cli).all_projects);
+        assert!(!build_config(&default_cli()).all_projects);
+    }
+
+    #[test]
+    fn test_build_config_log_file() {
+        let mut f = NamedTempFile::new().unwrap();
+        f.write_all(b"log_file = \"/tmp/from-file.log\"\n").unwrap();
+        let cli = Cli {
+            config: Some(f.path().to_path_buf()),
+            ..default_cli()
+        };
+        assert_eq!(
+            build_config(&cli).log_file,
+            Some(PathBuf::from("/tmp/from-file.log"))
+        );
+
+        let cli = Cli {
+            config: Some(f.path().to_path_buf()),
+            log_file: Some(PathBuf::from("/tmp/from-cli.log")),
+            ..default_cli()
+        };
+        assert_eq!(
+            build_config(&cli).log_file,
+            Some(PathBuf::from("/tmp/from-cli.log"))
+        );
+        assert_eq!(build_config(&default_cli()).log_file, None);
+    }
+
+    #[test]
+    fn test_build_config_detect_processes() {
+        let mut f = NamedTempFile::new().unwrap();
+        f.write_all(b"detect_processes = true\n").unwrap();
+        let cli = Cli {
+            config: Some(f.path().to_path_buf()),
+            ..default_cli()
+        };
+        assert!(build_config(&cli).detect_processes);
+
+        let cli = Cli {
+            detect_processes: true,
+            ..default_cli()
+        };
+        assert!(build_config(&cli).detect_processes);
+    }
+
     #[test]
     fn test_build_config_partial_file() {
         // File only sets theme, rest should be defaults
@@ -1,6 +1,10 @@
+use std::collections::HashMap;
+
 use serde_json::Value;
 
-use crate::tool_summary::summarize_tool_use;
+use crate::log_entry::CompactMetadata;
+use crate::session_stats::format_token_count;
+use crate::tool_summary::{summarize_tool_result, summarize_tool_use};
 
 // ---------------------------------------------------------------------------
 // Public types
@@ -11,15 +15,52 @@ use crate::tool_summary::summarize_tool_use;
 /// Each variant carries a ready-to-display `String`:
 /// - `Text` — a line of plain text from a `"text"` content block.
 /// - `ToolUse` — a one-line summary of a `"tool_use"` content block.
+/// - `ToolResult` — a one-line outcome summary of a `"tool_result"` block;
+///   `is_error` mirrors the block's `is_error` flag.
+/// - `Thinking` — the full text of a `"thinking"` block (may span several
+///   lines). Renderers show [`collapsed_thinking()`] unless expanded.
+/// - `RedactedThinking` — a `"redacted_thinking"` block, whose content is
+///   encrypted; rendered as [`REDACTED_THINKING_LABEL`].
 /// - `Unknown` — an indicator for an unrecognised block type, showing
 ///   the type label and the serialised size of the block.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum RenderedLine {
     Text(String),
     ToolUse(String),
+    ToolResult { summary: String, is_error: bool },
+    Thinking(String),
+    RedactedThinking,
     Unknown(String),
 }
 
+impl RenderedLine {
+    /// Whether this line belongs to a tool call (`tool_use` or
+    /// `tool_result`), i.e. is subject to the tool-call filter.
+    pub fn is_tool_line(&self) -> bool {
+        matches!(
+            self,
+            RenderedLine::ToolUse(_) | RenderedLine::ToolResult { .. }
+        )
+    }
+}
+
+/// Placeholder shown for a `redacted_thinking` block.
+pub const REDACTED_THINKING_LABEL: &str = "[redacted thinking]";
+
+/// Label of a collapsed compaction summary (see [`collapsed_text()`]).
+pub const COMPACT_SUMMARY_LABEL: &str = "compact summary";
+
+/// Maximum number of characters of the first thinking line shown in the
+/// collapsed form.
+const THINKING_PREVIEW_CHARS: usize = 80;
+
+/// Map from `tool_use` block ID to tool name.
+///
+/// `tool_result` blocks only carry a `tool_use_id`, so callers that render
+/// entries in order record each `tool_use` with [`collect_tool_names()`] and
+/// pass the map to [`render_content_blocks_with_tools()`].
+pub type ToolNames = HashMap<String, String>;
+
 // ---------------------------------------------------------------------------
 // Public API
 // ---------------------------------------------------------------------------
@@ -32,28 +73,124 @@ pub enum RenderedLine {
 /// - **String**: wraps as `RenderedLine::Text` lines (split on newlines).
 /// - **Null / other**: returns an empty `Vec`.
 pub fn render_content_blocks(content: &Value) -> Vec<RenderedLine> {
+    render_content_blocks_with_tools(content, &ToolNames::new())
+}
+
+/// Like [`render_content_blocks()`], but resolves the tool name of each
+/// `tool_result` block through `tool_names` so the result can be summarized
+/// per tool. Results whose `tool_use` is not in the map get a generic summary.
+pub fn render_content_blocks_with_tools(
+    content: &Value,
+    tool_names: &ToolNames,
+) -> Vec<RenderedLine> {
     match content {
-        Value::Array(blocks) => render_array(blocks),
+        Value::Array(blocks) => render_array(blocks, tool_names),
         Value::String(s) => split_text_lines(s),
         _ => Vec::new(),
     }
 }
 
+/// Record the `id -> name` of every `tool_use` block in `content`.
+pub fn collect_tool_names(content: &Value, tool_names: &mut ToolNames) {
+    let blocks = match content.as_array() {
+        Some(b) => b,
+        None => return,
+    };
+    for block in blocks {
+        if block.get("type").and_then(Value::as_str) != Some("tool_use") {
+            continue;
+        }
+        if let (Some(id), Some(name)) = (
+            block.get("id").and_then(Value::as_str),
+            block.get("name").and_then(Value::as_str),
+        ) {
+            tool_names.insert(id.to_string(), name.to_string());
+        }
+    }
+}
+
+/// One-line collapsed form of a thinking block: the first non-empty line
+/// (truncated) and the size of the full text, e.g.
+/// `"[thinking] Let me check the tests... (3.2KB)"`.
+pub fn collapsed_thinking(text: &str) -> String {
+    collapsed_text("thinking", text)
+}
+
+/// One-line collapsed form of a long text under `label`, in the same shape
+/// as [`collapsed_thinking()`].
+pub fn collapsed_text(label: &str, text: &str) -> String {
+    let first = text.lines().map(str::trim).find(|l| !l.is_empty());
+    let size = format_size(text.len());
+    match first {
+        None => format!("[{}] ({})", label, size),
+        Some(line) if line.chars().count() > THINKING_PREVIEW_CHARS => {
+            let preview: String = line.chars().take(THINKING_PREVIEW_CHARS).collect();
+            format!("[{}] {}... ({})", label, preview, size)
+        }
+        Some(line) => format!("[{}] {} ({})", label, line, size),
+    }
+}
+
+/// Label of the divider drawn for a compaction boundary, e.g.
+/// `"Context compacted (auto, 155.4k tokens)"`.
+pub fn compact_boundary_label(meta: Option<&CompactMetadata>) -> String {
+    let mut details: Vec<String> = Vec::new();
+    if let Some(trigger) = meta.and_then(|m| m.trigger.as_deref()) {
+        details.push(trigger.to_string());
+    }
+    if let Some(tokens) = meta.and_then(|m| m.pre_tokens) {
+        details.push(format!("{} tokens", format_token_count(tokens)));
+    }
+    if details.is_empty() {
+        "Context compacted".to_string()
+    } else {
+        format!("Context compacted ({})", details.join(", "))
+    }
+}
+
 /// Check whether a `message.content` value would produce any visible output.
 ///
 /// This is a lightweight check that avoids the allocation cost of building the
 /// full `Vec<RenderedLine>` — it returns `true` as soon as it finds a renderable
 /// block, without constructing any strings.
 pub fn has_renderable_content(content: &Value) -> bool {
+    match content {
+        Value::String(_) => true,
+        Value::Array(blocks) => blocks.iter().any(Value::is_object),
+        _ => false,
+    }
+}
+
+/// IDs of the `tool_use` blocks in a `message.content` value, in order.
+///
+/// Yields one element per [`RenderedLine::ToolUse`] produced by
+/// [`render_content_blocks()`], so the two can be zipped to look up each
+/// call's status; blocks without an `"id"` yield `None`.
+pub fn tool_use_ids(content: &Value) -> Vec<Option<&str>> {
+    match content {
+        Value::Array(blocks) => blocks
+            .iter()
+            .filter(|b| b.get("type").and_then(Value::as_str) == Some("tool_use"))
+            .map(|b| b.get("id").and_then(Value::as_str))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Check whether a `message.content` value produces any visible output
+/// other than tool lines (`tool_use` / `tool_result`).
+///
+/// Used to hide entries that would render nothing while the tool-call
+/// filter is active.
+pub fn has_non_tool_content(content: &Value) -> bool {
     match content {
         Value::String(_) => true,
         Value::Array(blocks) => blocks.iter().any(|block| {
-            let obj = match block.as_object() {
-                Some(o) => o,
+            let block_type = match block.as_object() {
+                Some(obj) => obj.get("type").and_then(Value::as_str).unwrap_or("unknown"),
                 None => return false,
             };
-            let block_type = obj.get("type").and_then(Value::as_str).unwrap_or("unknown");
-            block_type != "tool_result"
+            block_type != "tool_use" && block_type != "tool_result"
         }),
         _ => false,
     }
@@ -64,7 +201,7 @@ pub fn has_renderable_content(content: &Value) -> bool {
 // ---------------------------------------------------------------------------
 
 /// Render an array of content blocks.
-fn render_array(blocks: &[Value]) -> Vec<RenderedLine> {
+fn render_array(blocks: &[Value], tool_names: &ToolNames) -> Vec<RenderedLine> {
     let mut lines = Vec::new();
     for block in blocks {
         // Non-object array elements are silently skipped.
@@ -92,8 +229,23 @@ fn render_array(blocks: &[Value]) -> Vec<RenderedLine> {
                 lines.push(RenderedLine::ToolUse(summary));
             }
             "tool_result" => {
-                // Explicitly skipped per spec.
+                let name = obj
+                    .get("tool_use_id")
+                    .and_then(Value::as_str)
+                    .and_then(|id| tool_names.get(id))
+                    .map(String::as_str);
+                let content = obj.get("content").unwrap_or(&Value::Null);
+                let is_error = obj.get("is_error").and_then(Value::as_bool) == Some(true);
+                lines.push(RenderedLine::ToolResult {
+                    summary: summarize_tool_result(name, content, is_error),
+                    is_error,
+                });
+            }
+            "thinking" => {
+                let text = obj.get("thinking").and_then(Value::as_str).unwrap_or("");
+                lines.push(RenderedLine::Thinking(text.to_string()));
             }
+            "redacted_thinking" => lines.push(RenderedLine::RedactedThinking),
             _ => {
                 let size_bytes = serde_json::to_string(block).map(|s| s.len()).unwrap_or(0);
                 let label = format!("[{}] ({})", block_type, format_size(size_bytes));
@@ -120,7 +272,7 @@ fn split_text_lines(s: &str) -> Vec<RenderedLine> {
 /// - `< 1024`       → `"NB"`    (e.g. `"42B"`)
 /// - `>= 1024, < 1M` → `"N.NKB"` (e.g. `"12.3KB"`)
 /// - `>= 1M`         → `"N.NMB"` (e.g. `"1.5MB"`)
-fn format_size(bytes: usize) -> String {
+pub(crate) fn format_size(bytes: usize) -> String {
     const KB: usize = 1024;
     const MB: usize = 1024 * 1024;
 
@@ -275,18 +427,94 @@ mod tests {
     }
 
     // -----------------------------------------------------------------------
-    // 3. tool_result block tests (should be skipped)
+    // 3. tool_result block tests
     // -----------------------------------------------------------------------
 
     #[test]
-    fn test_tool_result_is_skipped() {
+    fn test_tool_result_without_known_tool_is_generic() {
         let content = json!([{
             "type": "tool_result",
             "tool_use_id": "tool-1",
             "content": "file contents here"
         }]);
         let result = render_content_blocks(&content);
-        assert!(result.is_empty());
+        assert_eq!(
+            result,
+            vec![RenderedLine::ToolResult {
+                summary: "[result] 1 line".to_string(),
+                is_error: false,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_tool_result_resolves_tool_name() {
+        let mut names = ToolNames::new();
+        collect_tool_names(
+            &json!([{"type": "tool_use", "id": "t1", "name": "Bash", "input": {"command": "false"}}]),
+            &mut names,
+        );
+        let content = json!([{
+            "type": "tool_result",
+            "tool_use_id": "t1",
+            "content": "Exit code 1\nboom",
+            "is_error": true
+        }]);
+        let result = render_content_blocks_with_tools(&content, &names);
+        assert_eq!(
+            result,
+            vec![RenderedLine::ToolResult {
+                summary: "[Bash] exit 1: boom".to_string(),
+                is_error: true,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_tool_result_is_error_false_when_absent_or_not_bool() {
+        let content = json!([
+            {"type": "tool_result", "tool_use_id": "t1", "content": "ok"},
+            {"type": "tool_result", "tool_use_id": "t2", "content": "ok", "is_error": "yes"}
+        ]);
+        let result = render_content_blocks(&content);
+        assert!(result.iter().all(|l| matches!(
+            l,
+            RenderedLine::ToolResult {
+                is_error: false,
+                ..
+            }
+        )));
+    }
+
+    #[test]
+    fn test_collect_tool_names_ignores_incomplete_blocks() {
+        let mut names = ToolNames::new();
+        collect_tool_names(
+            &json!([
+                {"type": "tool_use", "id": "t1", "name": "Read"},
+                {"type": "tool_use", "id": "t2"},
+                {"type": "tool_use", "name": "Grep"},
+                {"type": "text", "text": "hi"}
+            ]),
+            &mut names,
+        );
+        assert_eq!(names.len(), 1);
+        assert_eq!(names.get("t1").map(String::as_str), Some("Read"));
+
+        collect_tool_names(&json!("string content"), &mut names);
+        assert_eq!(names.len(), 1);
+    }
+
+    #[test]
+    fn test_is_tool_line() {
+        assert!(RenderedLine::ToolUse("x".into()).is_tool_line());
+        assert!(RenderedLine::ToolResult {
+            summary: "x".into(),
+            is_error: false
+        }
+        .is_tool_line());
+        assert!(!RenderedLine::Text("x".into()).is_tool_line());
+        assert!(!RenderedLine::Unknown("x".into()).is_tool_line());
     }
 
     #[test]
@@ -297,13 +525,10 @@ mod tests {
             {"type": "text", "text": "after"}
         ]);
         let result = render_content_blocks(&content);
-        assert_eq!(
-            result,
-            vec![
-                RenderedLine::Text("before".to_string()),
-                RenderedLine::Text("after".to_string()),
-            ]
-        );
+        assert_eq!(result.len(), 3);
+        assert_eq!(result[0], RenderedLine::Text("before".to_string()));
+        assert!(matches!(&result[1], RenderedLine::ToolResult { .. }));
+        assert_eq!(result[2], RenderedLine::Text("after".to_string()));
     }
 
     // -----------------------------------------------------------------------
@@ -311,20 +536,66 @@ mod tests {
     // -----------------------------------------------------------------------
 
     #[test]
-    fn test_unknown_block_thinking() {
+    fn test_thinking_block() {
         let content = json!([{
             "type": "thinking",
-            "thinking": "Let me consider this..."
+            "thinking": "Let me consider this...\nThen decide.",
+            "signature": "abc"
         }]);
         let result = render_content_blocks(&content);
-        assert_eq!(result.len(), 1);
-        match &result[0] {
-            RenderedLine::Unknown(s) => {
-                assert!(s.starts_with("[thinking]"));
-                assert!(s.contains('B') || s.contains("KB") || s.contains("MB"));
-            }
-            other => panic!("expected Unknown, got {:?}", other),
-        }
+        assert_eq!(
+            result,
+            vec![RenderedLine::Thinking(
+                "Let me consider this...\nThen decide.".to_string()
+            )]
+        );
+        assert!(!result[0].is_tool_line());
+    }
+
+    #[test]
+    fn test_redacted_thinking_block() {
+        let content = json!([{"type": "redacted_thinking", "data": "EmwKAhgBEgy..."}]);
+        let result = render_content_blocks(&content);
+        assert_eq!(result, vec![RenderedLine::RedactedThinking]);
+    }
+
+    #[test]
+    fn test_collapsed_thinking() {
+        assert_eq!(
+            collapsed_thinking("\n  Let me check.\nMore"),
+            "[thinking] Let me check. (21B)"
+        );
+        assert_eq!(collapsed_thinking(""), "[thinking] (0B)");
+        let long = "x".repeat(100);
+        assert_eq!(
+            collapsed_thinking(&long),
+            format!("[thinking] {}... (100B)", "x".repeat(80))
+        );
+    }
+
+    #[test]
+    fn test_compact_boundary_label() {
+        let meta = CompactMetadata {
+            trigger: Some("auto".to_string()),
+            pre_tokens: Some(155_432),
+        };
+        assert_eq!(
+            compact_boundary_label(Some(&meta)),
+            "Context compacted (auto, 155.4k tokens)"
+        );
+        let manual = CompactMetadata {
+            trigger: Some("manual".to_string()),
+            pre_tokens: None,
+        };
+        assert_eq!(
+            compact_boundary_label(Some(&manual)),
+            "Context compacted (manual)"
+        );
+        assert_eq!(compact_boundary_label(None), "Context compacted");
+        assert_eq!(
+            collapsed_text(COMPACT_SUMMARY_LABEL, "Summary:\n1. Fixed"),
+            "[compact summary] Summary: (17B)"
+        );
     }
 
     #[test]
@@ -389,23 +660,28 @@ mod tests {
         assert_eq!(result.len(), 4);
         assert!(matches!(&result[0], RenderedLine::Text(s) if s == "Hello"));
         assert!(matches!(&result[1], RenderedLine::ToolUse(_)));
-        assert!(matches!(&result[2], RenderedLine::Unknown(_)));
+        assert!(matches!(&result[2], RenderedLine::Thinking(s) if s == "hmm"));
         assert!(matches!(&result[3], RenderedLine::Text(s) if s == "Goodbye"));
     }
 
     #[test]
-    fn test_mixed_with_tool_result_skipped() {
+    fn test_mixed_with_tool_result() {
         let content = json!([
             {"type": "text", "text": "Start"},
             {"type": "tool_use", "id": "t1", "name": "Read", "input": {"file_path": "a.rs"}},
             {"type": "tool_result", "tool_use_id": "t1", "content": "file data"},
             {"type": "text", "text": "End"}
         ]);
-        let result = render_content_blocks(&content);
-        assert_eq!(result.len(), 3);
+        let mut names = ToolNames::new();
+        collect_tool_names(&content, &mut names);
+        let result = render_content_blocks_with_tools(&content, &names);
+        assert_eq!(result.len(), 4);
         assert!(matches!(&result[0], RenderedLine::Text(s) if s == "Start"));
         assert!(matches!(&result[1], RenderedLine::ToolUse(_)));
-        assert!(matches!(&result[2], RenderedLine::Text(s) if s == "End"));
+        assert!(
+            matches!(&result[2], RenderedLine::ToolResult { summary, .. } if summary == "[Read] 1 line")
+        );
+        assert!(matches!(&result[3], RenderedLine::Text(s) if s == "End"));
     }
 
     #[test]
@@ -529,27 +805,28 @@ mod tests {
     }
 
     #[test]
-    fn test_all_blocks_skipped() {
+    fn test_all_blocks_tool_results() {
         let content = json!([
             {"type": "tool_result", "tool_use_id": "t1", "content": "data1"},
             {"type": "tool_result", "tool_use_id": "t2", "content": "data2"}
         ]);
         let result = render_content_blocks(&content);
-        assert!(result.is_empty());
+        assert_eq!(result.len(), 2);
+        assert!(result.iter().all(RenderedLine::is_tool_line));
     }
 
     #[test]
     fn test_unknown_block_size_is_reasonable() {
         let content = json!([{
-            "type": "thinking",
-            "thinking": "x"
+            "type": "document",
+            "data": "x"
         }]);
         let result = render_content_blocks(&content);
         assert_eq!(result.len(), 1);
         // The serialised block should be small (< 1024 bytes), so expect "NB)" format.
         match &result[0] {
             RenderedLine::Unknown(s) => {
-                assert!(s.contains("[thinking]"));
+                assert!(s.contains("[document]"));
                 assert!(s.contains("B)"));
             }
             other => panic!("expected Unknown, got {:?}", other),
@@ -593,13 +870,12 @@ mod tests {
     #[case(json!([{"type": "tool_use", "name": "Read", "input": {}}]))]
     #[case(json!([{"type": "tool_result", "tool_use_id": "t1", "content": "data"}, {"type": "text", "text": "visible"}]))]
     #[case(json!([{"type": "thinking", "thinking": "hmm"}]))]
+    #[case(json!([{"type": "tool_result", "tool_use_id": "t1", "content": "data"}]))]
     fn test_has_renderable_content_true(#[case] content: Value) {
         assert!(has_renderable_content(&content));
     }
 
     #[rstest]
-    #[case(json!([{"type": "tool_result", "tool_use_id": "t1", "content": "data"}]))]
-    #[case(json!([{"type": "tool_result", "tool_use_id": "t1", "content": "data1"}, {"type": "tool_result", "tool_use_id": "t2", "content": "data2"}]))]
     #[case(json!([]))]
     #[case(json!(null))]
     #[case(json!(42))]
@@ -608,6 +884,37 @@ mod tests {
         assert!(!has_renderable_content(&content));
     }
 
+    #[rstest]
+    #[case(json!("hello"), true)]
+    #[case(json!([{"type": "text", "text": "hi"}, {"type": "tool_use", "name": "Read", "input": {}}]), true)]
+    #[case(json!([{"type": "tool_use", "name": "Read", "input": {}}]), false)]
+    #[case(json!([{"type": "tool_result", "tool_use_id": "t1", "content": "data1"}, {"type": "tool_result", "tool_use_id": "t2", "content": "data2"}]), false)]
+    #[case(json!([]), false)]
+    #[case(json!(null), false)]
+    fn test_has_non_tool_content(#[case] content: Value, #[case] expected: bool) {
+        assert_eq!(has_non_tool_content(&content), expected);
+    }
+
+    #[test]
+    fn test_tool_use_ids_align_with_tool_use_lines() {
+        let content = json!([
+            {"type": "text", "text": "a\nb"},
+            {"type": "tool_use", "id": "t1", "name": "Read", "input": {}},
+            {"type": "tool_result", "tool_use_id": "t0", "content": "x"},
+            {"type": "tool_use", "name": "Bash", "input": {}},
+        ]);
+        let ids = tool_use_ids(&content);
+        assert_eq!(ids, vec![Some("t1"), None]);
+
+        let tool_use_lines = render_content_blocks(&content)
+            .iter()
+            .filter(|l| matches!(l, RenderedLine::ToolUse(_)))
+            .count();
+        assert_eq!(ids.len(), tool_use_lines);
+
+        assert!(tool_use_ids(&json!("text")).is_empty());
+    }
+
     // -----------------------------------------------------------------------
     // 10. Edge case tests (continued)
     // -----------------------------------------------------------------------
@@ -0,0 +1,545 @@
+//! Conversation tree built from `uuid` / `parentUuid` links.
+//!
+//! Every entry names the entry it follows. A linear conversation is a
+//! single chain, but rewinding or editing an earlier prompt starts a new
+//! chain from that point, so the file holds a tree whose old branches are
+//! interleaved with the current one. [`ConversationTree`] rebuilds that
+//! tree, finds the active leaf (the newest main-chain entry of each
+//! session) and marks the branches that were abandoned.
+//!
+//! Parallel tool calls also fork: each `tool_use` of one response is its
+//! own entry chained to the previous one, and each `tool_result` hangs off
+//! its `tool_use`, so only the last result lies on the chain. Such results
+//! are side entries of the chain, not abandoned branches.
+//!
+//! Only main-chain entries with a `uuid` take part. Subagent (sidechain)
+//! entries and entries without a `uuid` are never abandoned; in tree
+//! layout they stay after the entry they followed in the timeline.
+//!
+//! The tree owns its data (keyed by `uuid`), so it can be kept across
+//! frames and rebuilt only when the entries change.
+
+use std::collections::HashMap;
+
+use crate::log_entry::{EntryType, LogEntry};
+use crate::tool_calls::{call_blocks, CallBlock};
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// The `uuid` / `parentUuid` tree of a set of entries.
+#[derive(Debug, Default)]
+pub struct ConversationTree {
+    /// `uuid` of each main-chain entry, in input order.
+    uuids: Vec<String>,
+    /// Session of each node.
+    sessions: Vec<Option<String>>,
+    /// `uuid` -> index into `uuids`.
+    index: HashMap<String, usize>,
+    /// Parent of each node, if it is in the tree.
+    parents: Vec<Option<usize>>,
+    /// Children of each node, in input order.
+    children: Vec<Vec<usize>>,
+    /// Nodes on the path from a root to an active leaf.
+    active: Vec<bool>,
+    /// Nodes in an abandoned branch.
+    abandoned: Vec<bool>,
+    /// Subtree contains a user or assistant entry.
+    has_messages: Vec<bool>,
+    /// Childless node answering a `tool_use` of its parent: a result of a
+    /// parallel tool call.
+    parallel_result: Vec<bool>,
+    /// Number of abandoned branches.
+    branch_count: usize,
+}
+
+/// One entry in tree layout.
+#[derive(Debug, Clone, Copy)]
+pub struct TreeRow<'a> {
+    pub entry: &'a LogEntry,
+    /// Fork depth: 0 for the active branch, +1 for each fork left.
+    pub depth: usize,
+    /// First shown entry of a branch that forks off at `depth - 1`.
+    pub branch_start: bool,
+    /// Entry belongs to an abandoned branch.
+    pub abandoned: bool,
+}
+
+// ---------------------------------------------------------------------------
+// Building
+// ---------------------------------------------------------------------------
+
+impl ConversationTree {
+    /// Build the tree of `entries`, given in timeline order.
+    ///
+    /// An entry's parent is its `parentUuid`, or its `logicalParentUuid`
+    /// for compaction boundaries. Entries whose parent is not present (the
+    /// start of a session, or an entry evicted from the ring buffer) are
+    /// roots.
+    pub fn build<'a>(entries: impl IntoIterator<Item = &'a LogEntry>) -> Self {
+        let mut nodes: Vec<&LogEntry> = Vec::new();
+        let mut index = HashMap::new();
+        for entry in entries {
+            if entry.is_sidechain == Some(true) {
+                continue;
+            }
+            let Some(uuid) = entry.uuid.as_deref() else {
+                continue;
+            };
+            if !index.contains_key(uuid) {
+                index.insert(uuid.to_string(), nodes.len());
+                nodes.push(entry);
+            }
+        }
+
+        let n = nodes.len();
+        let mut parents = vec![None; n];
+        let mut children = vec![Vec::new(); n];
+        for (i, entry) in nodes.iter().enumerate() {
+            let parent = entry
+                .parent_uuid
+                .as_deref()
+                .or(entry.logical_parent_uuid.as_deref())
+                .and_then(|p| index.get(p).copied())
+                .filter(|&p| p != i);
+            if let Some(p) = parent {
+                parents[i] = Some(p);
+                children[p].push(i);
+            }
+        }
+        let parallel_result = (0..n)
+            .map(|i| {
+                children[i].is_empty()
+                    && parents[i].is_some_and(|p| answers_tool_use(nodes[i], nodes[p]))
+            })
+            .collect();
+
+        let mut tree = ConversationTree {
+            uuids: nodes
+                .iter()
+                .map(|e| e.uuid.clone().unwrap_or_default())
+                .collect(),
+            sessions: nodes.iter().map(|e| e.session_id.clone()).collect(),
+            index,
+            parents,
+            children,
+            active: vec![false; n],
+            abandoned: vec![false; n],
+            has_messages: vec![false; n],
+            parallel_result,
+            branch_count: 0,
+        };
+        tree.mark_messages(&nodes);
+        tree.mark_active();
+        tree.mark_abandoned();
+        tree
+    }
+
+    fn len(&self) -> usize {
+        self.uuids.len()
+    }
+
+    /// Nodes without a parent in the tree.
+    fn roots(&self) -> Vec<usize> {
+        (0..self.len())
+            .filter(|&i| self.parents[i].is_none())
+            .collect()
+    }
+
+    /// Whether a child that does not continue the chain still belongs to
+    /// it: it holds no messages, or it is a parallel tool result.
+    fn is_side_entry(&self, i: usize) -> bool {
+        !self.has_messages[i] || self.parallel_result[i]
+    }
+
+    /// Nodes in depth-first pre-order, each visited once.
+    fn preorder(&self) -> Vec<usize> {
+        let mut seen = vec![false; self.len()];
+        let mut order = Vec::with_capacity(self.len());
+        let starts = self.roots().into_iter().chain(0..self.len());
+        for start in starts {
+            let mut stack = vec![start];
+            while let Some(i) = stack.pop() {
+                if std::mem::replace(&mut seen[i], true) {
+                    continue;
+                }
+                order.push(i);
+                stack.extend(self.children[i].iter().rev());
+            }
+        }
+        order
+    }
+
+    fn mark_messages(&mut self, nodes: &[&LogEntry]) {
+        for i in self.preorder().into_iter().rev() {
+            let is_message = matches!(nodes[i].entry_type, EntryType::User | EntryType::Assistant);
+            let below = self.children[i].iter().any(|&c| self.has_messages[c]);
+            self.has_messages[i] = is_message || below;
+        }
+    }
+
+    /// Walk up from the newest node of each session.
+    fn mark_active(&mut self) {
+        let mut leaves: HashMap<Option<&str>, usize> = HashMap::new();
+        for (i, session) in self.sessions.iter().enumerate() {
+            leaves.insert(session.as_deref(), i);
+        }
+        for leaf in leaves.into_values() {
+            let mut current = Some(leaf);
+            while let Some(i) = current {
+                if std::mem::replace(&mut self.active[i], true) {
+                    break;
+                }
+                current = self.parents[i];
+            }
+        }
+    }
+
+    /// A child of an active node that is not itself active starts an
+    /// abandoned branch, unless it is a side entry: its subtree holds no
+    /// messages (e.g. an `api_error` system entry the retried request does
+    /// not follow), or it is the result of a parallel tool call.
+    fn mark_abandoned(&mut self) {
+        for i in 0..self.len() {
+            if !self.active[i] {
+                continue;
+            }
+            for c in self.children[i].clone() {
+                if self.active[c] || self.is_side_entry(c) {
+                    continue;
+                }
+                self.branch_count += 1;
+                let mut stack = vec![c];
+                while let Some(j) = stack.pop() {
+                    if !std::mem::replace(&mut self.abandoned[j], true) {
+                        stack.extend(&self.children[j]);
+                    }
+                }
+            }
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Queries
+    // -----------------------------------------------------------------------
+
+    fn node(&self, entry: &LogEntry) -> Option<usize> {
+        if entry.is_sidechain == Some(true) {
+            return None;
+        }
+        self.index.get(entry.uuid.as_deref()?).copied()
+    }
+
+    /// Whether `entry` belongs to an abandoned branch.
+    pub fn is_abandoned(&self, entry: &LogEntry) -> bool {
+        self.node(entry).is_some_and(|i| self.abandoned[i])
+    }
+
+    /// Number of abandoned branches.
+    pub fn branch_count(&self) -> usize {
+        self.branch_count
+    }
+
+    /// `uuid` of the active leaf of `session_id` (the newest main-chain
+    /// entry of that session).
+    pub fn active_leaf(&self, session_id: Option<&str>) -> Option<&str> {
+        let i = self
+            .sessions
+            .iter()
+            .rposition(|s| s.as_deref() == session_id)?;
+        Some(&self.uuids[i])
+    }
+
+    /// Lay out `shown` (a timeline-ordered subset of the entries the tree
+    /// was built from) as a tree: each branch is listed contiguously, the
+    /// branch continuing a chain comes last at the same depth, and every
+    /// other branch holding messages is indented one level.
+    pub fn layout<'a>(&self, shown: &[&'a LogEntry]) -> Vec<TreeRow<'a>> {
+        // Shown tree nodes, plus the other shown entries attached to the
+        // shown node before them.
+        let mut visible: Vec<Option<&'a LogEntry>> = vec![None; self.len()];
+        let mut attached: HashMap<usize, Vec<&'a LogEntry>> = HashMap::new();
+        let mut leading = Vec::new();
+        let mut anchor = None;
+        for &entry in shown {
+            match self.node(entry) {
+                Some(i) => {
+                    visible[i].get_or_insert(entry);
+                    anchor = Some(i);
+                }
+                None => match anchor {
+                    Some(a) => attached.entry(a).or_default().push(entry),
+                    None => leading.push(entry),
+                },
+            }
+        }
+
+        let plain = |entry, depth| TreeRow {
+            entry,
+            depth,
+            branch_start: false,
+            abandoned: false,
+        };
+        let mut rows: Vec<TreeRow<'a>> = leading.into_iter().map(|e| plain(e, 0)).collect();
+        let mut seen = vec![false; self.len()];
+        let starts = self.roots().into_iter().chain(0..self.len());
+        for start in starts {
+            // (node, depth, branch start not yet shown)
+            let mut stack = vec![(start, 0, false)];
+            while let Some((i, depth, mark)) = stack.pop() {
+                if std::mem::replace(&mut seen[i], true) {
+                    continue;
+                }
+                let mut mark = mark;
+                if let Some(entry) = visible[i] {
+                    rows.push(TreeRow {
+                        entry,
+                        depth,
+                        branch_start: mark,
+                        abandoned: self.abandoned[i],
+                    });
+                    mark = false;
+                }
+                if let Some(extra) = attached.get(&i) {
+                    rows.extend(extra.iter().map(|&e| plain(e, depth)));
+                }
+
+                // The active child, or else the newest, continues the chain.
+                let kids = &self.children[i];
+                let next = kids
+                    .iter()
+                    .position(|&c| self.active[c])
+                    .or_else(|| kids.len().checked_sub(1));
+                // Pushed in reverse so forks pop first and the
+                // continuation last.
+                if let Some(n) = next {
+                    stack.push((kids[n], depth, mark));
+                }
+                for (k, &c) in kids.iter().enumerate().rev() {
+                    if Some(k) == next {
+                        continue;
+                    }
+                    if self.is_side_entry(c) {
+                        stack.push((c, depth, false));
+                    } else {
+                        stack.push((c, depth + 1, true));
+                    }
+                }
+            }
+        }
+        rows
+    }
+}
+
+/// Whether `entry` holds a `tool_result` for a `tool_use` of `parent`.
+fn answers_tool_use(entry: &LogEntry, parent: &LogEntry) -> bool {
+    call_blocks(entry).any(|result| match result {
+        CallBlock::Result { id, .. } => call_blocks(parent)
+            .any(|call| matches!(call, CallBlock::Use { id: use_id, .. } if use_id == id)),
+        CallBlock::Use { .. } => false,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log_entry::parse_jsonl_line;
+
+    fn entry(kind: &str, uuid: &str, parent: Option<&str>) -> LogEntry {
+        let parent = parent
+            .map(|p| format!(r#""{}""#, p))
+            .unwrap_or_else(|| "null".to_string());
+        parse_jsonl_line(&format!(
+            r#"{{"type": "{}", "sessionId": "s1", "uuid": "{}", "parentUuid": {}, "message": {{"role": "{}", "content": "{}"}}}}"#,
+            kind, uuid, parent, kind, uuid
+        ))
+        .unwrap()
+    }
+
+    /// a -> b -> c1 -> d1 (abandoned), b -> c2 -> d2 (rewound prompt).
+    fn rewound() -> Vec<LogEntry> {
+        vec![
+            entry("user", "a", None),
+            entry("assistant", "b", Some("a")),
+            entry("user", "c1", Some("b")),
+            entry("assistant", "d1", Some("c1")),
+            entry("user", "c2", Some("b")),
+            entry("assistant", "d2", Some("c2")),
+        ]
+    }
+
+    fn uuids(rows: &[TreeRow<'_>]) -> Vec<String> {
+        rows.iter()
+            .map(|r| {
+                format!(
+                    "{}{}{}{}",
+                    "  ".repeat(r.depth),
+                    if r.branch_start { "+" } else { "" },
+                    r.entry.uuid.as_deref().unwrap_or("-"),
+                    if r.abandoned { " (abandoned)" } else { "" }
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_linear_chain_has_no_branches() {
+        let entries = vec![
+            entry("user", "a", None),
+            entry("assistant", "b", Some("a")),
+            entry("user", "c", Some("b")),
+        ];
+        let tree = ConversationTree::build(&entries);
+        assert_eq!(tree.branch_count(), 0);
+        assert_eq!(tree.active_leaf(Some("s1")), Some("c"));
+        assert!(entries.iter().all(|e| !tree.is_abandoned(e)));
+    }
+
+    #[test]
+    fn test_rewind_marks_old_branch_abandoned() {
+        let entries = rewound();
+        let tree = ConversationTree::build(&entries);
+        assert_eq!(tree.branch_count(), 1);
+        assert_eq!(tree.active_leaf(Some("s1")), Some("d2"));
+        let abandoned: Vec<&str> = entries
+            .iter()
+            .filter(|e| tree.is_abandoned(e))
+            .filter_map(|e| e.uuid.as_deref())
+            .collect();
+        assert_eq!(abandoned, vec!["c1", "d1"]);
+    }
+
+    #[test]
+    fn test_layout_indents_forks() {
+        let entries = rewound();
+        let tree = ConversationTree::build(&entries);
+        let shown: Vec<&LogEntry> = entries.iter().collect();
+        assert_eq!(
+            uuids(&tree.layout(&shown)),
+            vec![
+                "a",
+                "b",
+                "  +c1 (abandoned)",
+                "  d1 (abandoned)",
+                "c2",
+                "d2"
+            ]
+        );
+
+        // A hidden branch start passes its marker to the next shown entry.
+        let shown: Vec<&LogEntry> = entries
+            .iter()
+            .filter(|e| e.uuid.as_deref() != Some("c1"))
+            .collect();
+        assert_eq!(uuids(&tree.layout(&shown))[2], "  +d1 (abandoned)");
+    }
+
+    #[test]
+    fn test_side_entries_without_messages_are_not_branches() {
+        // An api_error written before the retry succeeded hangs off the
+        // prompt, but the conversation continues from the prompt itself.
+        let error = parse_jsonl_line(
+            r#"{"type": "system", "sessionId": "s1", "uuid": "err", "parentUuid": "a", "subtype": "api_error", "content": "Overloaded"}"#,
+        )
+        .unwrap();
+        let entries = vec![
+            entry("user", "a", None),
+            error,
+            entry("assistant", "b", Some("a")),
+        ];
+        let tree = ConversationTree::build(&entries);
+        assert_eq!(tree.branch_count(), 0);
+        assert!(!tree.is_abandoned(&entries[1]));
+        let shown: Vec<&LogEntry> = entries.iter().collect();
+        assert_eq!(uuids(&tree.layout(&shown)), vec!["a", "err", "b"]);
+    }
+
+    #[test]
+    fn test_sidechain_and_uuidless_entries_follow_their_predecessor() {
+        let mut entries = rewound();
+        let sub = parse_jsonl_line(
+            r#"{"type": "assistant", "sessionId": "s1", "isSidechain": true, "agentId": "x", "uuid": "sub", "parentUuid": null}"#,
+        )
+        .unwrap();
+        entries.insert(4, sub);
+        let tree = ConversationTree::build(&entries);
+        assert!(!tree.is_abandoned(&entries[4]));
+        let shown: Vec<&LogEntry> = entries.iter().collect();
+        assert_eq!(
+            uuids(&tree.layout(&shown)),
+            vec![
+                "a",
+                "b",
+                "  +c1 (abandoned)",
+                "  d1 (abandoned)",
+                "  sub",
+                "c2",
+                "d2"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compact_boundary_follows_logical_parent() {
+        let boundary = parse_jsonl_line(
+            r#"{"type": "system", "sessionId": "s1", "subtype": "compact_boundary", "uuid": "cb", "parentUuid": null, "logicalParentUuid": "b"}"#,
+        )
+        .unwrap();
+        let entries = vec![
+            entry("user", "a", None),
+            entry("assistant", "b", Some("a")),
+            boundary,
+            entry("user", "c", Some("cb")),
+        ];
+        let tree = ConversationTree::build(&entries);
+        assert_eq!(tree.branch_count(), 0);
+        assert!(entries.iter().all(|e| !tree.is_abandoned(e)));
+    }
+
+    #[test]
+    fn test_parallel_tool_results_are_not_branches() {
+        let line = |uuid: &str, parent: &str, kind: &str, block: &str| {
+            parse_jsonl_line(&format!(
+                r#"{{"type": "{}", "sessionId": "s1", "uuid": "{}", "parentUuid": "{}", "message": {{"role": "{}", "content": [{}]}}}}"#,
+                kind, uuid, parent, kind, block
+            ))
+            .unwrap()
+        };
+        let call = |id: &str| {
+            format!(
+                r#"{{"type": "tool_use", "id": "{}", "name": "Read", "input": {{}}}}"#,
+                id
+            )
+        };
+        let result = |id: &str| {
+            format!(
+                r#"{{"type": "tool_result", "tool_use_id": "{}", "content": "x"}}"#,
+                id
+            )
+        };
+        // One response with two tool calls: r1 hangs off a1, the chain
+        // continues through a2 -> r2.
+        let entries = vec![
+            entry("user", "p", None),
+            line("a1", "p", "assistant", &call("t1")),
+            line("a2", "a1", "assistant", &call("t2")),
+            line("r1", "a1", "user", &result("t1")),
+            line("r2", "a2", "user", &result("t2")),
+            entry("assistant", "next", Some("r2")),
+        ];
+        let tree = ConversationTree::build(&entries);
+        assert_eq!(tree.branch_count(), 0);
+        assert!(entries.iter().all(|e| !tree.is_abandoned(e)));
+
+        let shown: Vec<&LogEntry> = entries.iter().collect();
+        assert_eq!(
+            uuids(&tree.layout(&shown)),
+            vec!["p", "a1", "r1", "a2", "r2", "next"]
+        );
+    }
+}
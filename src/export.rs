@@ -0,0 +1,653 @@
+//! Session transcript export for the `cctail export` subcommand.
+//!
+//! Loads the full session with [`load_full_session`], renders every entry
+//! with the same [`content_render`](crate::content_render) /
+//! [`tool_summary`](crate::tool_summary) pipeline as the TUI, and writes
+//! the result as Markdown, a single self-contained HTML file, or JSON.
+//! The main conversation and each subagent get their own section, every
+//! message keeps its timestamp, and all text passes through
+//! [`redact_secrets`] so transcripts can be shared safely.
+
+use std::fmt::Write as _;
+use std::io::Write as _;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::cli::{ExportArgs, ExportFormat};
+use crate::config::AppConfig;
+use crate::content_render::{
+    collect_tool_names, render_content_blocks_with_tools, RenderedLine, REDACTED_THINKING_LABEL,
+};
+use crate::filter::FilterState;
+use crate::log_entry::{EntryType, LogEntry};
+use crate::project_path::detect_project_path;
+use crate::replay::load_full_session;
+use crate::session::{discover_sessions, resolve_session, Session};
+use crate::tool_summary::{clean_detail_text, redact_secrets};
+
+// ---------------------------------------------------------------------------
+// Transcript model
+// ---------------------------------------------------------------------------
+
+/// A rendered session, ready to be written in any format.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Transcript {
+    pub session_id: String,
+    /// Main conversation first, then one section per subagent in order of
+    /// first appearance. Sections without messages are omitted.
+    pub sections: Vec<Section>,
+}
+
+/// The messages of one agent.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Section {
+    /// `None` for the main conversation.
+    pub agent_id: Option<String>,
+    pub slug: Option<String>,
+    pub messages: Vec<ExportMessage>,
+}
+
+/// One log entry.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExportMessage {
+    /// `"user"`, `"assistant"` or `"system"`.
+    pub role: &'static str,
+    pub timestamp: Option<String>,
+    pub uuid: Option<String>,
+    pub blocks: Vec<ExportBlock>,
+}
+
+/// A rendered piece of a message.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ExportBlock {
+    /// Consecutive text lines, joined with newlines.
+    Text { text: String },
+    /// One-line tool call summary, e.g. `[Bash] cargo test`.
+    ToolUse { summary: String },
+    /// One-line tool result summary.
+    ToolResult { summary: String, is_error: bool },
+    /// Full text of an extended-thinking block.
+    Thinking { text: String },
+    /// Placeholder for a `redacted_thinking` block.
+    RedactedThinking,
+    /// Indicator for a content block type cc-tail does not render.
+    Unknown { label: String },
+}
+
+/// Build the transcript of `session`.
+///
+/// Tool names are collected from every entry so results resolve even when
+/// the filter hides the corresponding call; the filter then decides which
+/// entries (and, with `hide_tool_calls`, which blocks) are kept.
+pub fn build_transcript(session: &Session, filter: &FilterState) -> Transcript {
+    let (entries, _) = load_full_session(session, &FilterState::default());
+
+    let mut tool_names = Default::default();
+    for entry in &entries {
+        if let Some(msg) = &entry.message {
+            collect_tool_names(&msg.content, &mut tool_names);
+        }
+    }
+
+    let mut sections = vec![Section {
+        agent_id: None,
+        slug: None,
+        messages: Vec::new(),
+    }];
+
+    for entry in &entries {
+        if !filter.matches(entry) {
+            continue;
+        }
+        let msg = match &entry.message {
+            Some(m) => m,
+            None => continue,
+        };
+        let lines = render_content_blocks_with_tools(&msg.content, &tool_names);
+        let blocks = to_blocks(lines, filter.is_tool_line_visible());
+        if blocks.is_empty() {
+            continue;
+        }
+
+        let agent_id = match entry.agent_id.as_deref() {
+            Some(id) if entry.is_sidechain == Some(true) => Some(id),
+            _ => None,
+        };
+        let idx = match sections
+            .iter()
+            .position(|s| s.agent_id.as_deref() == agent_id)
+        {
+            Some(idx) => idx,
+            None => {
+                sections.push(Section {
+                    agent_id: agent_id.map(str::to_string),
+                    slug: entry.slug.as_deref().map(redact_secrets),
+                    messages: Vec::new(),
+                });
+                sections.len() - 1
+            }
+        };
+
+        sections[idx].messages.push(ExportMessage {
+            role: role_name(entry),
+            timestamp: entry.timestamp.clone(),
+            uuid: entry.uuid.clone(),
+            blocks,
+        });
+    }
+
+    sections.retain(|s| !s.messages.is_empty());
+    Transcript {
+        session_id: session.id.clone(),
+        sections,
+    }
+}
+
+/// Convert rendered lines into blocks, merging consecutive text lines and
+/// redacting text.
+fn to_blocks(lines: Vec<RenderedLine>, show_tools: bool) -> Vec<ExportBlock> {
+    let mut blocks: Vec<ExportBlock> = Vec::new();
+    for line in lines {
+        if line.is_tool_line() && !show_tools {
+            continue;
+        }
+        match line {
+            RenderedLine::Text(text) => {
+                let text = clean_detail_text(&text);
+                match blocks.last_mut() {
+                    Some(ExportBlock::Text { text: prev }) => {
+                        prev.push('\n');
+                        prev.push_str(&text);
+                    }
+                    _ => blocks.push(ExportBlock::Text { text }),
+                }
+            }
+            RenderedLine::ToolUse(summary) => blocks.push(ExportBlock::ToolUse { summary }),
+            RenderedLine::ToolResult { summary, is_error } => {
+                blocks.push(ExportBlock::ToolResult { summary, is_error })
+            }
+            RenderedLine::Thinking(text) => blocks.push(ExportBlock::Thinking {
+                text: clean_detail_text(&text),
+            }),
+            RenderedLine::RedactedThinking => blocks.push(ExportBlock::RedactedThinking),
+            RenderedLine::Unknown(label) => blocks.push(ExportBlock::Unknown { label }),
+        }
+    }
+
+    // Drop text blocks that are only whitespace (e.g. a lone newline).
+    blocks.retain(|b| !matches!(b, ExportBlock::Text { text } if text.trim().is_empty()));
+    blocks
+}
+
+fn role_name(entry: &LogEntry) -> &'static str {
+    match entry.entry_type {
+        EntryType::Assistant => "assistant",
+        EntryType::System => "system",
+        _ => "user",
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Rendering
+// ---------------------------------------------------------------------------
+
+/// Render `transcript` in `format`.
+pub fn render(transcript: &Transcript, format: ExportFormat) -> String {
+    match format {
+        ExportFormat::Md => render_markdown(transcript),
+        ExportFormat::Html => render_html(transcript),
+        ExportFormat::Json => {
+            let mut json = serde_json::to_string_pretty(transcript).unwrap_or_default();
+            json.push('\n');
+            json
+        }
+    }
+}
+
+/// Render as Markdown: one `##` heading per section, one `###` heading per
+/// message, tool calls and results as list items.
+fn render_markdown(t: &Transcript) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "# Session {}", t.session_id);
+
+    for section in &t.sections {
+        let _ = writeln!(out, "\n## {}", section_title(section));
+        for msg in &section.messages {
+            let _ = writeln!(
+                out,
+                "\n### {} · {}\n",
+                role_label(msg.role),
+                format_time(msg.timestamp.as_deref())
+            );
+            for block in &msg.blocks {
+                match block {
+                    ExportBlock::Text { text } => {
+                        let _ = writeln!(out, "{}\n", text);
+                    }
+                    ExportBlock::ToolUse { summary } => {
+                        let _ = writeln!(out, "- tool: {}", code_span(summary));
+                    }
+                    ExportBlock::ToolResult { summary, is_error } => {
+                        let label = if *is_error { "**error**" } else { "result" };
+                        let _ = writeln!(out, "- {}: {}", label, code_span(summary));
+                    }
+                    ExportBlock::Thinking { text } => {
+                        let _ = writeln!(out, "> _thinking_");
+                        for line in text.lines() {
+                            let _ = writeln!(
+                                out,
+                                ">{}{}",
+                                if line.is_empty() { "" } else { " " },
+                                line
+                            );
+                        }
+                        out.push('\n');
+                    }
+                    ExportBlock::RedactedThinking => {
+                        let _ = writeln!(out, "- _{}_", REDACTED_THINKING_LABEL);
+                    }
+                    ExportBlock::Unknown { label } => {
+                        let _ = writeln!(out, "- _{}_", label);
+                    }
+                }
+            }
+        }
+    }
+
+    // Collapse the blank lines left between list items and headings.
+    let mut out = out.replace("\n\n\n", "\n\n");
+    if !out.ends_with('\n') {
+        out.push('\n');
+    }
+    out
+}
+
+/// Render as a single HTML document with inline CSS and no external
+/// resources or scripts.
+fn render_html(t: &Transcript) -> String {
+    let mut out = String::new();
+    let title = format!("Session {}", t.session_id);
+    let _ = write!(
+        out,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{}</title>\n<style>\n{}</style>\n</head>\n<body>\n<h1>{}</h1>\n",
+        escape_html(&title),
+        HTML_STYLE,
+        escape_html(&title)
+    );
+
+    for section in &t.sections {
+        let _ = writeln!(
+            out,
+            "<section>\n<h2>{}</h2>",
+            escape_html(&section_title(section))
+        );
+        for msg in &section.messages {
+            let _ = writeln!(
+                out,
+                "<article class=\"msg {}\">\n<header><span class=\"role\">{}</span> <time datetime=\"{}\">{}</time></header>",
+                msg.role,
+                role_label(msg.role),
+                escape_html(msg.timestamp.as_deref().unwrap_or("")),
+                escape_html(&format_time(msg.timestamp.as_deref()))
+            );
+            for block in &msg.blocks {
+                let _ = match block {
+                    ExportBlock::Text { text } => {
+                        writeln!(out, "<div class=\"text\">{}</div>", escape_html(text))
+                    }
+                    ExportBlock::ToolUse { summary } => {
+                        writeln!(out, "<div class=\"tool\">{}</div>", escape_html(summary))
+                    }
+                    ExportBlock::ToolResult { summary, is_error } => writeln!(
+                        out,
+                        "<div class=\"result{}\">{}</div>",
+                        if *is_error { " error" } else { "" },
+                        escape_html(summary)
+                    ),
+                    ExportBlock::Thinking { text } => writeln!(
+                        out,
+                        "<details class=\"thinking\"><summary>thinking</summary>{}</details>",
+                        escape_html(text)
+                    ),
+                    ExportBlock::RedactedThinking => writeln!(
+                        out,
+                        "<div class=\"unknown\">{}</div>",
+                        escape_html(REDACTED_THINKING_LABEL)
+                    ),
+                    ExportBlock::Unknown { label } => {
+                        writeln!(out, "<div class=\"unknown\">{}</div>", escape_html(label))
+                    }
+                };
+            }
+            out.push_str("</article>\n");
+        }
+        out.push_str("</section>\n");
+    }
+
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+const HTML_STYLE: &str = "\
+body { font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', sans-serif; max-width: 960px; margin: 2em auto; padding: 0 1em; color: #1f2328; }
+h1 { font-size: 1.4em; }
+h2 { font-size: 1.15em; border-bottom: 1px solid #d0d7de; padding-bottom: .3em; margin-top: 2em; }
+article { border-left: 3px solid #d0d7de; margin: 1em 0; padding: .2em 0 .2em 1em; }
+article.user { border-color: #0969da; }
+article.assistant { border-color: #8250df; }
+article.system { border-color: #9a6700; }
+header { font-size: .85em; color: #656d76; margin-bottom: .4em; }
+.role { font-weight: 600; }
+.text { white-space: pre-wrap; margin: .4em 0; }
+.tool, .result, .unknown { font-family: ui-monospace, SFMono-Regular, Menlo, monospace; font-size: .85em; white-space: pre-wrap; }
+.tool { color: #9a6700; }
+.result { color: #1a7f37; }
+.result.error { color: #cf222e; }
+.unknown { color: #656d76; font-style: italic; }
+.thinking { color: #656d76; font-style: italic; white-space: pre-wrap; margin: .4em 0; }
+.thinking summary { cursor: pointer; }
+";
+
+fn section_title(section: &Section) -> String {
+    match (&section.agent_id, &section.slug) {
+        (None, _) => "Main conversation".to_string(),
+        (Some(id), Some(slug)) => format!("Subagent {} ({})", slug, id),
+        (Some(id), None) => format!("Subagent {}", id),
+    }
+}
+
+fn role_label(role: &str) -> &'static str {
+    match role {
+        "assistant" => "Assistant",
+        "system" => "System",
+        _ => "User",
+    }
+}
+
+/// Format an ISO 8601 timestamp as `YYYY-MM-DD HH:MM:SS UTC`.
+///
+/// UTC keeps exports identical regardless of who runs them. Missing or
+/// malformed timestamps render as `unknown time`.
+fn format_time(ts: Option<&str>) -> String {
+    ts.and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+        .map(|dt| {
+            dt.with_timezone(&Utc)
+                .format("%Y-%m-%d %H:%M:%S UTC")
+                .to_string()
+        })
+        .unwrap_or_else(|| "unknown time".to_string())
+}
+
+/// Wrap `s` in a Markdown code span, using a longer fence if `s` contains
+/// backticks.
+fn code_span(s: &str) -> String {
+    let mut longest = 0;
+    let mut run = 0;
+    for c in s.chars() {
+        if c == '`' {
+            run += 1;
+            longest = longest.max(run);
+        } else {
+            run = 0;
+        }
+    }
+    let fence = "`".repeat(longest + 1);
+    if longest > 0 {
+        format!("{} {} {}", fence, s, fence)
+    } else {
+        format!("{}{}{}", fence, s, fence)
+    }
+}
+
+fn escape_html(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+// ---------------------------------------------------------------------------
+// `cctail export`
+// ---------------------------------------------------------------------------
+
+/// Run the `export` subcommand: resolve the session (`--session` prefix or
+/// the most recent one) and write the transcript to stdout or `--output`.
+pub fn run_export(args: &ExportArgs, config: &AppConfig) -> Result<(), Box<dyn std::error::Error>> {
+    let cwd = std::env::current_dir().unwrap_or_default();
+    let project_dir = detect_project_path(&cwd, config.project.as_deref())?;
+    let sessions = discover_sessions(&project_dir, usize::MAX)?;
+    let prefix = args.session.as_deref().or(config.session.as_deref());
+    let session = resolve_session(&sessions, prefix)?;
+
+    let filter = FilterState {
+        hide_tool_calls: args.hide_tools,
+        hide_meta: args.hide_meta,
+        errors_only: false,
+        selected_agent: args.agent.clone(),
+        expression: args.filter.clone(),
+        ..Default::default()
+    };
+    let output = render(&build_transcript(session, &filter), args.format);
+
+    match &args.output {
+        Some(path) => std::fs::write(path, output)?,
+        None => {
+            let stdout = std::io::stdout();
+            let mut out = stdout.lock();
+            // BrokenPipe (e.g. piping into `head`) ends output quietly.
+            match out.write_all(output.as_bytes()).and_then(|()| out.flush()) {
+                Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => {}
+                result => result?,
+            }
+        }
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filter_expr::FilterExpr;
+    use crate::test_support::{session, write_file};
+    use tempfile::TempDir;
+
+    const PROMPT: &str = r#"{"type":"user","uuid":"u1","timestamp":"2025-01-15T10:00:00Z","message":{"role":"user","content":"Fix <b>the</b> test with key sk-ant-REDACTED"}}"#;
+    const CALL: &str = r#"{"type":"assistant","uuid":"u2","timestamp":"2025-01-15T10:00:01Z","message":{"role":"assistant","content":[{"type":"text","text":"Running `cargo test`"},{"type":"tool_use","id":"t1","name":"Bash","input":{"command":"cargo test"}}]}}"#;
+    const RESULT: &str = r#"{"type":"user","uuid":"u3","timestamp":"2025-01-15T10:00:02Z","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"t1","content":"boom","is_error":true}]}}"#;
+    const SUB: &str = r#"{"type":"user","uuid":"s1","timestamp":"2025-01-15T10:00:03Z","isSidechain":true,"agentId":"a1","slug":"explorer","message":{"role":"user","content":"Look around"}}"#;
+
+    fn fixture(dir: &TempDir) -> Session {
+        let main = write_file(dir.path(), "sess.jsonl", &[PROMPT, CALL, RESULT]);
+        let sub = write_file(dir.path(), "sess/subagents/agent-a1.jsonl", &[SUB]);
+        session("sess", vec![(None, main), (Some("a1"), sub)])
+    }
+
+    #[test]
+    fn test_build_transcript_sections_and_blocks() {
+        let dir = TempDir::new().unwrap();
+        let t = build_transcript(&fixture(&dir), &FilterState::default());
+
+        assert_eq!(t.sections.len(), 2);
+        let main = &t.sections[0];
+        assert_eq!(main.agent_id, None);
+        assert_eq!(main.messages.len(), 3);
+        assert_eq!(main.messages[0].role, "user");
+        assert_eq!(
+            main.messages[0].timestamp.as_deref(),
+            Some("2025-01-15T10:00:00Z")
+        );
+        assert_eq!(
+            main.messages[1].blocks[1],
+            ExportBlock::ToolUse {
+                summary: "[Bash] cargo test".to_string()
+            }
+        );
+        assert!(matches!(
+            main.messages[2].blocks[0],
+            ExportBlock::ToolResult { is_error: true, .. }
+        ));
+
+        let sub = &t.sections[1];
+        assert_eq!(sub.agent_id.as_deref(), Some("a1"));
+        assert_eq!(sub.slug.as_deref(), Some("explorer"));
+    }
+
+    #[test]
+    fn test_build_transcript_redacts_secrets() {
+        let dir = TempDir::new().unwrap();
+        let t = build_transcript(&fixture(&dir), &FilterState::default());
+        let json = render(&t, ExportFormat::Json);
+        assert!(!json.contains("abcdefghijklmnopqrstuvwxyz"));
+    }
+
+    #[test]
+    fn test_build_transcript_honors_filter() {
+        let dir = TempDir::new().unwrap();
+        let filter = FilterState {
+            hide_tool_calls: true,
+            expression: Some(FilterExpr::parse("role:assistant").unwrap()),
+            ..Default::default()
+        };
+        let t = build_transcript(&fixture(&dir), &filter);
+
+        assert_eq!(t.sections.len(), 1);
+        assert_eq!(t.sections[0].messages.len(), 1);
+        assert_eq!(
+            t.sections[0].messages[0].blocks,
+            vec![ExportBlock::Text {
+                text: "Running `cargo test`".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_build_transcript_agent_filter() {
+        let dir = TempDir::new().unwrap();
+        let filter = FilterState {
+            selected_agent: Some("a1".to_string()),
+            ..Default::default()
+        };
+        let t = build_transcript(&fixture(&dir), &filter);
+        assert_eq!(t.sections.len(), 1);
+        assert_eq!(t.sections[0].agent_id.as_deref(), Some("a1"));
+    }
+
+    #[test]
+    fn test_render_markdown() {
+        let dir = TempDir::new().unwrap();
+        let md = render(
+            &build_transcript(&fixture(&dir), &FilterState::default()),
+            ExportFormat::Md,
+        );
+        assert!(md.starts_with("# Session sess\n"));
+        assert!(md.contains("## Main conversation"));
+        assert!(md.contains("### User · 2025-01-15 10:00:00 UTC"));
+        assert!(md.contains("- tool: `[Bash] cargo test`"));
+        assert!(md.contains("- **error**: `"));
+        assert!(md.contains("## Subagent explorer (a1)"));
+        assert!(!md.contains("\n\n\n"));
+    }
+
+    #[test]
+    fn test_render_html_is_escaped_and_self_contained() {
+        let dir = TempDir::new().unwrap();
+        let html = render(
+            &build_transcript(&fixture(&dir), &FilterState::default()),
+            ExportFormat::Html,
+        );
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<style>"));
+        assert!(html.contains("Fix &lt;b&gt;the&lt;/b&gt; test"));
+        assert!(html.contains("<time datetime=\"2025-01-15T10:00:00Z\">"));
+        assert!(html.contains("class=\"result error\""));
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("<link"));
+    }
+
+    #[test]
+    fn test_render_json_round_trips() {
+        let dir = TempDir::new().unwrap();
+        let json = render(
+            &build_transcript(&fixture(&dir), &FilterState::default()),
+            ExportFormat::Json,
+        );
+        let v: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(v["session_id"], "sess");
+        assert_eq!(
+            v["sections"][0]["messages"][1]["blocks"][1]["kind"],
+            "tool_use"
+        );
+    }
+
+    #[test]
+    fn test_thinking_blocks_in_all_formats() {
+        let blocks = to_blocks(
+            vec![
+                RenderedLine::Thinking("Step one.\n\nStep <two>.".to_string()),
+                RenderedLine::RedactedThinking,
+            ],
+            true,
+        );
+        assert_eq!(
+            blocks,
+            vec![
+                ExportBlock::Thinking {
+                    text: "Step one.\n\nStep <two>.".to_string()
+                },
+                ExportBlock::RedactedThinking,
+            ]
+        );
+        let transcript = Transcript {
+            session_id: "s".to_string(),
+            sections: vec![Section {
+                agent_id: None,
+                slug: None,
+                messages: vec![ExportMessage {
+                    role: "assistant",
+                    timestamp: None,
+                    uuid: None,
+                    blocks,
+                }],
+            }],
+        };
+
+        let md = render(&transcript, ExportFormat::Md);
+        assert!(md.contains("> _thinking_\n> Step one.\n>\n> Step <two>.\n"));
+        assert!(md.contains("- _[redacted thinking]_"));
+
+        let html = render(&transcript, ExportFormat::Html);
+        assert!(html.contains(
+            "<details class=\"thinking\"><summary>thinking</summary>Step one.\n\nStep &lt;two&gt;.</details>"
+        ));
+
+        let json = render(&transcript, ExportFormat::Json);
+        assert!(json.contains("\"kind\": \"redacted_thinking\""));
+    }
+
+    #[test]
+    fn test_format_helpers() {
+        assert_eq!(
+            format_time(Some("2025-01-15T12:00:00+02:00")),
+            "2025-01-15 10:00:00 UTC"
+        );
+        assert_eq!(format_time(None), "unknown time");
+        assert_eq!(code_span("ls"), "`ls`");
+        assert_eq!(code_span("echo `x`"), "`` echo `x` ``");
+        assert_eq!(escape_html("a&\"b'"), "a&amp;&quot;b&#39;");
+    }
+}
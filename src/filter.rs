@@ -1,47 +1,363 @@
 //! Filter system for log entries.
 //!
-//! Provides a simple [`FilterState`] that controls two independent
+//! Provides a simple [`FilterState`] that controls five independent
 //! filter dimensions:
 //!
 //! 1. **Tool call hiding** (`hide_tool_calls`): When true, tool call
-//!    lines (`RenderedLine::ToolUse`) are hidden at the rendering level.
-//! 2. **Agent filtering** (`selected_agent`): When `Some(id)`, only
+//!    lines (`RenderedLine::ToolUse` / `RenderedLine::ToolResult`) are hidden at the rendering level.
+//! 2. **Meta hiding** (`hide_meta`): When true, entries Claude Code marks
+//!    as `isMeta` are hidden.
+//! 3. **Errors only** (`errors_only`): When true, only entries matching
+//!    [`ErrorFilter`] are shown.
+//! 4. **Agent filtering** (`selected_agent`): When `Some(id)`, only
 //!    entries from the specified subagent are shown. When `None`, all
 //!    agents (main + subagents) are shown.
+//! 5. **Filter expression** (`expression`): An optional
+//!    [`FilterExpr`](crate::filter_expr::FilterExpr) such as
+//!    `role:assistant AND tool:Bash`, built from the [`MessageFilter`]
+//!    implementations in this module.
 //!
-//! Entry-level filtering is done via `matches()` (agent filtering).
-//! Line-level filtering (tool call hiding) is done in the UI renderer.
+//! Entry-level filtering is done via `matches()` (meta, errors, agent +
+//! expression). Line-level filtering (tool call hiding) is done in the UI
+//! renderer.
 
-use crate::log_entry::LogEntry;
+use std::collections::BTreeSet;
+
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde_json::Value;
+
+use crate::filter_expr::FilterExpr;
+use crate::log_entry::{EntryType, LogEntry};
+use crate::system_event::SystemEvent;
+
+// ---------------------------------------------------------------------------
+// MessageFilter trait
+// ---------------------------------------------------------------------------
+
+/// An entry-level predicate over log entries.
+///
+/// New filters are added by implementing this trait; [`AndFilter`],
+/// [`OrFilter`] and [`NotFilter`] combine them.
+pub trait MessageFilter: Send + Sync + std::fmt::Debug {
+    /// Returns `true` if the entry passes this filter.
+    fn matches(&self, entry: &LogEntry) -> bool;
+}
+
+/// Matches entries whose `message.role` equals `role` (case-insensitive).
+///
+/// Entries without a message (e.g. system entries) fall back to their
+/// entry type, so `role:system` matches system entries.
+#[derive(Debug, Clone)]
+pub struct RoleFilter {
+    pub role: String,
+}
+
+impl MessageFilter for RoleFilter {
+    fn matches(&self, entry: &LogEntry) -> bool {
+        match entry.message.as_ref().and_then(|m| m.role.as_deref()) {
+            Some(role) => role.eq_ignore_ascii_case(&self.role),
+            None => entry_type_name(&entry.entry_type) == Some(self.role.as_str()),
+        }
+    }
+}
+
+/// Matches entries of a given [`EntryType`].
+#[derive(Debug, Clone)]
+pub struct EntryTypeFilter {
+    pub entry_type: EntryType,
+}
+
+impl MessageFilter for EntryTypeFilter {
+    fn matches(&self, entry: &LogEntry) -> bool {
+        entry.entry_type == self.entry_type
+    }
+}
+
+/// Matches entries whose `message.model` contains `pattern`
+/// (case-insensitive), so `model:opus` matches `claude-opus-4-6`.
+#[derive(Debug, Clone)]
+pub struct ModelFilter {
+    pub pattern: String,
+}
+
+impl MessageFilter for ModelFilter {
+    fn matches(&self, entry: &LogEntry) -> bool {
+        let pattern = self.pattern.to_lowercase();
+        entry
+            .message
+            .as_ref()
+            .and_then(|m| m.model.as_deref())
+            .is_some_and(|model| model.to_lowercase().contains(&pattern))
+    }
+}
+
+/// Matches entries containing a `tool_use` block for the named tool
+/// (case-insensitive).
+///
+/// `tool_result` blocks only carry a `tool_use_id`, so result entries are
+/// not matched by name.
+#[derive(Debug, Clone)]
+pub struct ToolFilter {
+    pub name: String,
+}
+
+impl MessageFilter for ToolFilter {
+    fn matches(&self, entry: &LogEntry) -> bool {
+        content_blocks(entry).iter().any(|block| {
+            block.get("type").and_then(Value::as_str) == Some("tool_use")
+                && block
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .is_some_and(|n| n.eq_ignore_ascii_case(&self.name))
+        })
+    }
+}
+
+/// Matches entries from an agent.
+///
+/// `main` matches the main agent (non-sidechain entries). Any other value
+/// matches subagents whose `agentId` starts with it or whose slug equals it.
+#[derive(Debug, Clone)]
+pub struct AgentFilter {
+    pub agent: String,
+}
+
+impl MessageFilter for AgentFilter {
+    fn matches(&self, entry: &LogEntry) -> bool {
+        let is_sidechain = entry.is_sidechain == Some(true);
+        if self.agent == "main" {
+            return !is_sidechain;
+        }
+        is_sidechain
+            && (entry
+                .agent_id
+                .as_deref()
+                .is_some_and(|id| id.starts_with(&self.agent))
+                || entry.slug.as_deref() == Some(self.agent.as_str()))
+    }
+}
+
+/// Matches entries whose timestamp falls in `[after, before)`.
+///
+/// Entries without a parseable timestamp never match.
+#[derive(Debug, Clone)]
+pub struct TimeRangeFilter {
+    pub after: Option<DateTime<Utc>>,
+    pub before: Option<DateTime<Utc>>,
+}
+
+impl MessageFilter for TimeRangeFilter {
+    fn matches(&self, entry: &LogEntry) -> bool {
+        let ts = match entry
+            .timestamp
+            .as_deref()
+            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+        {
+            Some(dt) => dt.with_timezone(&Utc),
+            None => return false,
+        };
+        self.after.is_none_or(|after| ts >= after) && self.before.is_none_or(|before| ts < before)
+    }
+}
+
+/// Matches entries whose text matches `regex`.
+///
+/// The searched text is the concatenation of text blocks, tool inputs and
+/// tool result contents (see [`entry_text()`]).
+#[derive(Debug, Clone)]
+pub struct TextFilter {
+    pub regex: Regex,
+}
+
+impl MessageFilter for TextFilter {
+    fn matches(&self, entry: &LogEntry) -> bool {
+        self.regex.is_match(&entry_text(entry))
+    }
+}
+
+/// Matches entries reporting a failure: API errors, failed hooks and
+/// error-level system entries (see [`SystemEvent`]), and entries carrying a
+/// `tool_result` block with `is_error: true`.
+#[derive(Debug, Clone, Copy)]
+pub struct ErrorFilter;
+
+impl MessageFilter for ErrorFilter {
+    fn matches(&self, entry: &LogEntry) -> bool {
+        SystemEvent::classify(entry).is_some_and(|e| e.is_error())
+            || content_blocks(entry).iter().any(|block| {
+                block.get("type").and_then(Value::as_str) == Some("tool_result")
+                    && block.get("is_error").and_then(Value::as_bool) == Some(true)
+            })
+    }
+}
+
+/// Matches when every inner filter matches (an empty list matches everything).
+#[derive(Debug, Default)]
+pub struct AndFilter {
+    pub filters: Vec<Box<dyn MessageFilter>>,
+}
+
+impl MessageFilter for AndFilter {
+    fn matches(&self, entry: &LogEntry) -> bool {
+        self.filters.iter().all(|f| f.matches(entry))
+    }
+}
+
+/// Matches when any inner filter matches (an empty list matches nothing).
+#[derive(Debug, Default)]
+pub struct OrFilter {
+    pub filters: Vec<Box<dyn MessageFilter>>,
+}
+
+impl MessageFilter for OrFilter {
+    fn matches(&self, entry: &LogEntry) -> bool {
+        self.filters.iter().any(|f| f.matches(entry))
+    }
+}
+
+/// Inverts an inner filter (the exclude form of any filter).
+#[derive(Debug)]
+pub struct NotFilter {
+    pub inner: Box<dyn MessageFilter>,
+}
+
+impl MessageFilter for NotFilter {
+    fn matches(&self, entry: &LogEntry) -> bool {
+        !self.inner.matches(entry)
+    }
+}
+
+/// The JSON name of an entry type, or `None` for `Unknown`.
+pub fn entry_type_name(entry_type: &EntryType) -> Option<&'static str> {
+    match entry_type {
+        EntryType::User => Some("user"),
+        EntryType::Assistant => Some("assistant"),
+        EntryType::Progress => Some("progress"),
+        EntryType::FileHistorySnapshot => Some("file-history-snapshot"),
+        EntryType::System => Some("system"),
+        EntryType::QueueOperation => Some("queue-operation"),
+        EntryType::Summary => Some("summary"),
+        EntryType::Unknown => None,
+    }
+}
+
+/// The content blocks of an entry's message (empty for string content).
+fn content_blocks(entry: &LogEntry) -> &[Value] {
+    entry
+        .message
+        .as_ref()
+        .and_then(|m| m.content.as_array())
+        .map(Vec::as_slice)
+        .unwrap_or(&[])
+}
+
+/// Collect the searchable text of an entry, one block per line.
+///
+/// Includes text blocks, serialized `tool_use` inputs and the text of
+/// `tool_result` contents.
+pub fn entry_text(entry: &LogEntry) -> String {
+    let content = match entry.message.as_ref() {
+        Some(msg) => &msg.content,
+        None => return String::new(),
+    };
+    if let Value::String(s) = content {
+        return s.clone();
+    }
+
+    let mut parts: Vec<String> = Vec::new();
+    for block in content_blocks(entry) {
+        match block.get("type").and_then(Value::as_str) {
+            Some("text") => {
+                if let Some(text) = block.get("text").and_then(Value::as_str) {
+                    parts.push(text.to_string());
+                }
+            }
+            Some("tool_use") => {
+                if let Some(input) = block.get("input") {
+                    parts.push(input.to_string());
+                }
+            }
+            Some("tool_result") => match block.get("content") {
+                Some(Value::String(s)) => parts.push(s.clone()),
+                Some(Value::Array(items)) => {
+                    for item in items {
+                        if let Some(text) = item.get("text").and_then(Value::as_str) {
+                            parts.push(text.to_string());
+                        }
+                    }
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+    parts.join("\n")
+}
 
 // ---------------------------------------------------------------------------
 // FilterState
 // ---------------------------------------------------------------------------
 
-/// Simple filter state with two dimensions.
+/// Simple filter state with six dimensions.
 ///
-/// Stored in `App` and updated by the filter menu overlay.
+/// Stored in `App` and updated by the filter menu overlay and the filter
+/// expression prompt.
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct FilterState {
-    /// When true, `RenderedLine::ToolUse` lines are hidden during rendering.
+    /// When true, `RenderedLine::ToolUse` and `RenderedLine::ToolResult`
+    /// lines are hidden during rendering.
     pub hide_tool_calls: bool,
+    /// When true, entries Claude Code marks as `isMeta` (injected
+    /// reminders, local command caveats) are hidden.
+    pub hide_meta: bool,
+    /// When true, only entries matching [`ErrorFilter`] are shown.
+    pub errors_only: bool,
     /// When `Some(agent_id)`, only entries from that subagent are shown.
     /// When `None`, all agents (main + subagents) are shown.
     pub selected_agent: Option<String>,
+    /// When `Some`, only entries matching the expression are shown.
+    pub expression: Option<FilterExpr>,
+    /// Sessions whose entries are hidden. Set from the filter menu of the
+    /// all-sessions view.
+    pub hidden_sessions: BTreeSet<String>,
 }
 
 impl FilterState {
     /// Returns `true` if any filter dimension is active.
     pub fn is_active(&self) -> bool {
-        self.hide_tool_calls || self.selected_agent.is_some()
+        self.hide_tool_calls
+            || self.hide_meta
+            || self.errors_only
+            || self.selected_agent.is_some()
+            || self.expression.is_some()
+            || !self.hidden_sessions.is_empty()
     }
 
-    /// Test whether a log entry passes the entry-level filter (agent filtering).
+    /// Test whether a log entry passes the entry-level filter (meta
+    /// entries, errors only, hidden sessions, agent filtering and the
+    /// filter expression).
     ///
-    /// If no agent filter is active (`selected_agent` is `None`), all entries pass.
-    /// If an agent filter is active, only subagent entries matching the selected
-    /// agent_id pass. Main agent entries are hidden when an agent filter is active.
+    /// If no agent filter is active (`selected_agent` is `None`), all entries pass
+    /// the agent check. If an agent filter is active, only subagent entries
+    /// matching the selected agent_id pass. Main agent entries are hidden when an
+    /// agent filter is active.
     pub fn matches(&self, entry: &LogEntry) -> bool {
+        !(self.hide_meta && entry.is_meta == Some(true))
+            && (!self.errors_only || ErrorFilter.matches(entry))
+            && entry
+                .session_id
+                .as_ref()
+                .is_none_or(|id| !self.hidden_sessions.contains(id))
+            && self.matches_agent(entry)
+            && self
+                .expression
+                .as_ref()
+                .is_none_or(|expr| expr.matches(entry))
+    }
+
+    /// The agent dimension of [`matches()`](Self::matches).
+    fn matches_agent(&self, entry: &LogEntry) -> bool {
         match &self.selected_agent {
             None => true,
             Some(agent_id) => {
@@ -66,7 +382,8 @@ impl FilterState {
     ///
     /// Returns `None` if no filters are active.
     /// Returns e.g. `"[filter: no tools]"`, `"[filter: agent cook]"`,
-    /// or `"[filter: no tools, agent cook]"`.
+    /// `"[filter: no tools, no meta, agent cook]"`, `"[filter: role:user]"`
+    /// or `"[filter: 2 sessions hidden]"`.
     pub fn display(&self) -> Option<String> {
         if !self.is_active() {
             return None;
@@ -78,10 +395,28 @@ impl FilterState {
             parts.push("no tools".to_string());
         }
 
+        if self.hide_meta {
+            parts.push("no meta".to_string());
+        }
+
+        if self.errors_only {
+            parts.push("errors only".to_string());
+        }
+
         if let Some(ref agent_id) = self.selected_agent {
             parts.push(format!("agent {}", agent_id));
         }
 
+        if let Some(ref expr) = self.expression {
+            parts.push(expr.source().to_string());
+        }
+
+        match self.hidden_sessions.len() {
+            0 => {}
+            1 => parts.push("1 session hidden".to_string()),
+            n => parts.push(format!("{} sessions hidden", n)),
+        }
+
         Some(format!("[filter: {}]", parts.join(", ")))
     }
 }
@@ -188,6 +523,7 @@ mod tests {
         let state = FilterState {
             hide_tool_calls: true,
             selected_agent: None,
+            ..Default::default()
         };
         assert!(state.is_active());
     }
@@ -197,6 +533,7 @@ mod tests {
         let state = FilterState {
             hide_tool_calls: false,
             selected_agent: Some("abc".to_string()),
+            ..Default::default()
         };
         assert!(state.is_active());
     }
@@ -206,6 +543,7 @@ mod tests {
         let state = FilterState {
             hide_tool_calls: true,
             selected_agent: Some("abc".to_string()),
+            ..Default::default()
         };
         assert!(state.is_active());
     }
@@ -225,6 +563,7 @@ mod tests {
         let state = FilterState {
             hide_tool_calls: false,
             selected_agent: Some("abc".to_string()),
+            ..Default::default()
         };
         // Main agent entries should be hidden
         assert!(!state.matches(&user_entry("test")));
@@ -236,6 +575,7 @@ mod tests {
         let state = FilterState {
             hide_tool_calls: false,
             selected_agent: Some("abc".to_string()),
+            ..Default::default()
         };
         assert!(state.matches(&subagent_entry("test", "abc")));
     }
@@ -245,6 +585,7 @@ mod tests {
         let state = FilterState {
             hide_tool_calls: false,
             selected_agent: Some("abc".to_string()),
+            ..Default::default()
         };
         assert!(!state.matches(&subagent_entry("test", "xyz")));
     }
@@ -255,6 +596,7 @@ mod tests {
         let state = FilterState {
             hide_tool_calls: true,
             selected_agent: None,
+            ..Default::default()
         };
         assert!(state.matches(&user_entry("test")));
         assert!(state.matches(&assistant_entry("test")));
@@ -266,11 +608,55 @@ mod tests {
         let state = FilterState {
             hide_tool_calls: false,
             selected_agent: Some("abc".to_string()),
+            ..Default::default()
         };
         // System entries with no sidechain marker are treated as main agent
         assert!(!state.matches(&entry_no_message()));
     }
 
+    #[test]
+    fn test_matches_hide_meta() {
+        let meta = parse_jsonl_line(
+            r#"{"type": "user", "isMeta": true, "message": {"role": "user", "content": "Caveat"}}"#,
+        )
+        .unwrap();
+        let state = FilterState {
+            hide_meta: true,
+            ..Default::default()
+        };
+        assert!(state.is_active());
+        assert!(!state.matches(&meta));
+        assert!(state.matches(&user_entry("hello")));
+        assert!(FilterState::default().matches(&meta));
+        assert_eq!(state.display(), Some("[filter: no meta]".to_string()));
+    }
+
+    #[test]
+    fn test_matches_errors_only() {
+        let state = FilterState {
+            errors_only: true,
+            ..Default::default()
+        };
+        let api_error = parse_jsonl_line(
+            r#"{"type": "system", "subtype": "api_error", "error": {"status": 529}}"#,
+        )
+        .unwrap();
+        let tool_error = parse_jsonl_line(
+            r#"{"type": "user", "message": {"role": "user", "content": [{"type": "tool_result", "tool_use_id": "t1", "content": "boom", "is_error": true}]}}"#,
+        )
+        .unwrap();
+        let info = parse_jsonl_line(
+            r#"{"type": "system", "subtype": "informational", "content": "note"}"#,
+        )
+        .unwrap();
+
+        assert!(state.matches(&api_error));
+        assert!(state.matches(&tool_error));
+        assert!(!state.matches(&info));
+        assert!(!state.matches(&user_entry("hello")));
+        assert_eq!(state.display(), Some("[filter: errors only]".to_string()));
+    }
+
     // -- is_tool_line_visible tests ---------------------------------------
 
     #[test]
@@ -278,6 +664,7 @@ mod tests {
         let state = FilterState {
             hide_tool_calls: false,
             selected_agent: None,
+            ..Default::default()
         };
         assert!(state.is_tool_line_visible());
     }
@@ -287,6 +674,7 @@ mod tests {
         let state = FilterState {
             hide_tool_calls: true,
             selected_agent: None,
+            ..Default::default()
         };
         assert!(!state.is_tool_line_visible());
     }
@@ -304,6 +692,7 @@ mod tests {
         let state = FilterState {
             hide_tool_calls: true,
             selected_agent: None,
+            ..Default::default()
         };
         assert_eq!(state.display(), Some("[filter: no tools]".to_string()));
     }
@@ -313,6 +702,7 @@ mod tests {
         let state = FilterState {
             hide_tool_calls: false,
             selected_agent: Some("cook".to_string()),
+            ..Default::default()
         };
         assert_eq!(state.display(), Some("[filter: agent cook]".to_string()));
     }
@@ -322,6 +712,7 @@ mod tests {
         let state = FilterState {
             hide_tool_calls: true,
             selected_agent: Some("cook".to_string()),
+            ..Default::default()
         };
         assert_eq!(
             state.display(),
@@ -329,6 +720,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_hidden_sessions_filter_and_display() {
+        let state = FilterState {
+            hidden_sessions: BTreeSet::from(["sess-001".to_string()]),
+            ..Default::default()
+        };
+        assert!(state.is_active());
+        assert!(!state.matches(&user_entry("test")));
+        assert!(!state.matches(&entry_no_message()));
+        let other = r#"{"type": "system", "sessionId": "sess-002"}"#;
+        assert!(state.matches(&parse_jsonl_line(other).unwrap()));
+        assert_eq!(
+            state.display(),
+            Some("[filter: 1 session hidden]".to_string())
+        );
+    }
+
     // -- Combined filter tests --------------------------------------------
 
     #[test]
@@ -336,6 +744,7 @@ mod tests {
         let state = FilterState {
             hide_tool_calls: true,
             selected_agent: Some("abc".to_string()),
+            ..Default::default()
         };
         // Entry-level: agent filtering
         assert!(!state.matches(&user_entry("test")));
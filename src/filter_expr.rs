@@ -0,0 +1,734 @@
+//! Filter expression language.
+//!
+//! Parses expressions such as
+//! `role:assistant AND tool:Bash AND NOT text:/cargo test/` into a tree of
+//! [`MessageFilter`]s.
+//!
+//! Grammar (keywords are upper-case):
+//!
+//! ```text
+//! expr   := and ("OR" and)*
+//! and    := unary (["AND"] unary)*      -- juxtaposition means AND
+//! unary  := ("NOT" | "-" | "!") unary | "(" expr ")" | term
+//! term   := key ":" value | value       -- a bare value is a text search
+//! value  := word | "quoted string" | /regex/ | /regex/i
+//! ```
+//!
+//! Keys: `role`, `type`, `model`, `tool`, `agent`, `after`, `before`, `text`.
+//! `after` / `before` take an RFC 3339 timestamp, a `YYYY-MM-DD` date, or a
+//! relative age such as `30m`, `2h` or `1d`. Plain `text` values are
+//! case-insensitive substrings; `/.../` values are regular expressions.
+
+use std::fmt;
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use regex::RegexBuilder;
+
+use crate::filter::{
+    AgentFilter, AndFilter, EntryTypeFilter, MessageFilter, ModelFilter, NotFilter, OrFilter,
+    RoleFilter, TextFilter, TimeRangeFilter, ToolFilter,
+};
+use crate::log_entry::{EntryType, LogEntry};
+
+// ---------------------------------------------------------------------------
+// Error type
+// ---------------------------------------------------------------------------
+
+/// Errors produced while parsing a filter expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilterParseError {
+    /// The expression is empty or contains only whitespace.
+    Empty,
+    /// Input ended where a term, `)` or value was expected.
+    UnexpectedEnd,
+    /// A token appeared where it is not allowed (e.g. a stray `)` or `OR`).
+    UnexpectedToken(String),
+    /// A quoted string or `/regex/` is missing its closing delimiter.
+    Unterminated(char),
+    /// The key of a `key:value` term is not recognised.
+    UnknownKey(String),
+    /// The value is not valid for its key (e.g. `type:banana`).
+    InvalidValue { key: String, value: String },
+    /// A `/regex/` value failed to compile.
+    InvalidRegex(String),
+}
+
+impl fmt::Display for FilterParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FilterParseError::Empty => write!(f, "empty filter expression"),
+            FilterParseError::UnexpectedEnd => write!(f, "unexpected end of expression"),
+            FilterParseError::UnexpectedToken(tok) => write!(f, "unexpected '{}'", tok),
+            FilterParseError::Unterminated(delim) => write!(f, "missing closing {}", delim),
+            FilterParseError::UnknownKey(key) => write!(
+                f,
+                "unknown filter key '{}' (expected role, type, model, tool, agent, after, before or text)",
+                key
+            ),
+            FilterParseError::InvalidValue { key, value } => {
+                write!(f, "invalid value '{}' for {}:", value, key)
+            }
+            FilterParseError::InvalidRegex(msg) => write!(f, "invalid regex: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for FilterParseError {}
+
+// ---------------------------------------------------------------------------
+// FilterExpr
+// ---------------------------------------------------------------------------
+
+/// A parsed filter expression together with its source text.
+///
+/// Cheap to clone (the compiled filter is shared). Two expressions compare
+/// equal when their source text is equal.
+#[derive(Clone)]
+pub struct FilterExpr {
+    source: String,
+    filter: Arc<dyn MessageFilter>,
+}
+
+impl FilterExpr {
+    /// Parse an expression, evaluating relative times against the current time.
+    pub fn parse(input: &str) -> Result<Self, FilterParseError> {
+        Self::parse_at(input, Utc::now())
+    }
+
+    /// Parse an expression, evaluating relative times (`after:30m`) against `now`.
+    pub fn parse_at(input: &str, now: DateTime<Utc>) -> Result<Self, FilterParseError> {
+        let tokens = tokenize(input)?;
+        if tokens.is_empty() {
+            return Err(FilterParseError::Empty);
+        }
+        let mut parser = Parser {
+            tokens,
+            pos: 0,
+            now,
+        };
+        let filter = parser.parse_or()?;
+        if let Some(tok) = parser.peek() {
+            return Err(FilterParseError::UnexpectedToken(tok.to_string()));
+        }
+        Ok(Self {
+            source: input.trim().to_string(),
+            filter: Arc::from(filter),
+        })
+    }
+
+    /// The expression text as typed (trimmed).
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+}
+
+impl MessageFilter for FilterExpr {
+    fn matches(&self, entry: &LogEntry) -> bool {
+        self.filter.matches(entry)
+    }
+}
+
+impl fmt::Debug for FilterExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("FilterExpr").field(&self.source).finish()
+    }
+}
+
+impl PartialEq for FilterExpr {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl Eq for FilterExpr {}
+
+// ---------------------------------------------------------------------------
+// Tokenizer
+// ---------------------------------------------------------------------------
+
+/// A term value as written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Value {
+    /// Bare word or quoted string.
+    Plain(String),
+    /// `/pattern/` (with `case_insensitive` set by a trailing `i`).
+    Regex {
+        pattern: String,
+        case_insensitive: bool,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    LParen,
+    RParen,
+    And,
+    Or,
+    Not,
+    Term { key: Option<String>, value: Value },
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::LParen => write!(f, "("),
+            Token::RParen => write!(f, ")"),
+            Token::And => write!(f, "AND"),
+            Token::Or => write!(f, "OR"),
+            Token::Not => write!(f, "NOT"),
+            Token::Term { key, value } => {
+                if let Some(key) = key {
+                    write!(f, "{}:", key)?;
+                }
+                match value {
+                    Value::Plain(s) => write!(f, "{}", s),
+                    Value::Regex { pattern, .. } => write!(f, "/{}/", pattern),
+                }
+            }
+        }
+    }
+}
+
+/// Split an expression into tokens.
+fn tokenize(input: &str) -> Result<Vec<Token>, FilterParseError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        match c {
+            '(' => {
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            // `-term` / `!term` exclude prefixes.
+            '-' | '!' if chars.get(i + 1).is_some_and(|n| !n.is_whitespace()) => {
+                tokens.push(Token::Not);
+                i += 1;
+            }
+            '"' | '/' => {
+                let value = read_value(&chars, &mut i)?;
+                tokens.push(Token::Term { key: None, value });
+            }
+            _ => {
+                let start = i;
+                while i < chars.len()
+                    && !chars[i].is_whitespace()
+                    && !matches!(chars[i], '(' | ')' | ':')
+                {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect();
+                if chars.get(i) == Some(&':') {
+                    i += 1;
+                    let value = read_value(&chars, &mut i)?;
+                    tokens.push(Token::Term {
+                        key: Some(word.to_lowercase()),
+                        value,
+                    });
+                } else {
+                    tokens.push(match word.as_str() {
+                        "AND" => Token::And,
+                        "OR" => Token::Or,
+                        "NOT" => Token::Not,
+                        _ => Token::Term {
+                            key: None,
+                            value: Value::Plain(word),
+                        },
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Read a value starting at `chars[*i]`: a quoted string, a `/regex/`
+/// or a bare word running to the next whitespace or parenthesis.
+fn read_value(chars: &[char], i: &mut usize) -> Result<Value, FilterParseError> {
+    match chars.get(*i) {
+        Some(&delim @ ('"' | '/')) => {
+            *i += 1;
+            let mut out = String::new();
+            loop {
+                match chars.get(*i) {
+                    None => return Err(FilterParseError::Unterminated(delim)),
+                    Some(&c) if c == delim => {
+                        *i += 1;
+                        break;
+                    }
+                    // `\"` and `\/` escape the delimiter; other escapes are
+                    // kept verbatim so regex classes like `\d` still work.
+                    Some('\\') if chars.get(*i + 1) == Some(&delim) => {
+                        out.push(delim);
+                        *i += 2;
+                    }
+                    Some(&c) => {
+                        out.push(c);
+                        *i += 1;
+                    }
+                }
+            }
+            if delim == '"' {
+                return Ok(Value::Plain(out));
+            }
+            let case_insensitive = chars.get(*i) == Some(&'i')
+                && chars
+                    .get(*i + 1)
+                    .is_none_or(|c| c.is_whitespace() || *c == ')');
+            if case_insensitive {
+                *i += 1;
+            }
+            Ok(Value::Regex {
+                pattern: out,
+                case_insensitive,
+            })
+        }
+        _ => {
+            let start = *i;
+            while *i < chars.len() && !chars[*i].is_whitespace() && !matches!(chars[*i], '(' | ')')
+            {
+                *i += 1;
+            }
+            Ok(Value::Plain(chars[start..*i].iter().collect()))
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Parser
+// ---------------------------------------------------------------------------
+
+/// Recursive-descent parser over the token list.
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    now: DateTime<Utc>,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let tok = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        tok
+    }
+
+    fn parse_or(&mut self) -> Result<Box<dyn MessageFilter>, FilterParseError> {
+        let mut filters = vec![self.parse_and()?];
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            filters.push(self.parse_and()?);
+        }
+        Ok(if filters.len() == 1 {
+            filters.remove(0)
+        } else {
+            Box::new(OrFilter { filters })
+        })
+    }
+
+    fn parse_and(&mut self) -> Result<Box<dyn MessageFilter>, FilterParseError> {
+        let mut filters = vec![self.parse_unary()?];
+        loop {
+            match self.peek() {
+                Some(Token::And) => {
+                    self.pos += 1;
+                    filters.push(self.parse_unary()?);
+                }
+                Some(Token::Not | Token::LParen | Token::Term { .. }) => {
+                    filters.push(self.parse_unary()?);
+                }
+                _ => break,
+            }
+        }
+        Ok(if filters.len() == 1 {
+            filters.remove(0)
+        } else {
+            Box::new(AndFilter { filters })
+        })
+    }
+
+    fn parse_unary(&mut self) -> Result<Box<dyn MessageFilter>, FilterParseError> {
+        match self.next() {
+            None => Err(FilterParseError::UnexpectedEnd),
+            Some(Token::Not) => Ok(Box::new(NotFilter {
+                inner: self.parse_unary()?,
+            })),
+            Some(Token::LParen) => {
+                let inner = self.parse_or()?;
+                match self.next() {
+                    Some(Token::RParen) => Ok(inner),
+                    Some(tok) => Err(FilterParseError::UnexpectedToken(tok.to_string())),
+                    None => Err(FilterParseError::UnexpectedEnd),
+                }
+            }
+            Some(Token::Term { key, value }) => build_term(key.as_deref(), value, self.now),
+            Some(tok) => Err(FilterParseError::UnexpectedToken(tok.to_string())),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Term construction
+// ---------------------------------------------------------------------------
+
+/// Build the filter for a single `key:value` term.
+fn build_term(
+    key: Option<&str>,
+    value: Value,
+    now: DateTime<Utc>,
+) -> Result<Box<dyn MessageFilter>, FilterParseError> {
+    let key = key.unwrap_or("text");
+    let plain = |value: Value| -> Result<String, FilterParseError> {
+        match value {
+            Value::Plain(s) if !s.is_empty() => Ok(s),
+            Value::Plain(s) => Err(FilterParseError::InvalidValue {
+                key: key.to_string(),
+                value: s,
+            }),
+            Value::Regex { pattern, .. } => Err(FilterParseError::InvalidValue {
+                key: key.to_string(),
+                value: format!("/{}/", pattern),
+            }),
+        }
+    };
+
+    match key {
+        "role" => Ok(Box::new(RoleFilter {
+            role: plain(value)?.to_lowercase(),
+        })),
+        "type" => {
+            let name = plain(value)?;
+            let entry_type = parse_entry_type(&name).ok_or(FilterParseError::InvalidValue {
+                key: key.to_string(),
+                value: name,
+            })?;
+            Ok(Box::new(EntryTypeFilter { entry_type }))
+        }
+        "model" => Ok(Box::new(ModelFilter {
+            pattern: plain(value)?,
+        })),
+        "tool" => Ok(Box::new(ToolFilter {
+            name: plain(value)?,
+        })),
+        "agent" => Ok(Box::new(AgentFilter {
+            agent: plain(value)?,
+        })),
+        "after" | "before" => {
+            let raw = plain(value)?;
+            let time = parse_time(&raw, now).ok_or(FilterParseError::InvalidValue {
+                key: key.to_string(),
+                value: raw,
+            })?;
+            Ok(Box::new(if key == "after" {
+                TimeRangeFilter {
+                    after: Some(time),
+                    before: None,
+                }
+            } else {
+                TimeRangeFilter {
+                    after: None,
+                    before: Some(time),
+                }
+            }))
+        }
+        "text" => {
+            let regex = match value {
+                Value::Plain(s) => RegexBuilder::new(&regex::escape(&s))
+                    .case_insensitive(true)
+                    .build(),
+                Value::Regex {
+                    pattern,
+                    case_insensitive,
+                } => RegexBuilder::new(&pattern)
+                    .case_insensitive(case_insensitive)
+                    .build(),
+            }
+            .map_err(|e| FilterParseError::InvalidRegex(e.to_string()))?;
+            Ok(Box::new(TextFilter { regex }))
+        }
+        other => Err(FilterParseError::UnknownKey(other.to_string())),
+    }
+}
+
+/// Parse an entry type name (`user`, `assistant`, `system`, `progress`,
+/// `file-history-snapshot`, `queue-operation`, `summary`).
+fn parse_entry_type(name: &str) -> Option<EntryType> {
+    match name.to_lowercase().as_str() {
+        "user" => Some(EntryType::User),
+        "assistant" => Some(EntryType::Assistant),
+        "progress" => Some(EntryType::Progress),
+        "file-history-snapshot" => Some(EntryType::FileHistorySnapshot),
+        "system" => Some(EntryType::System),
+        "queue-operation" => Some(EntryType::QueueOperation),
+        "summary" => Some(EntryType::Summary),
+        _ => None,
+    }
+}
+
+/// Parse an `after:` / `before:` value: RFC 3339, `YYYY-MM-DD` (midnight
+/// UTC) or a relative age (`45s`, `30m`, `2h`, `1d`) counted back from `now`.
+fn parse_time(s: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Some(dt.with_timezone(&Utc));
+    }
+    if let Ok(dt) = DateTime::parse_from_rfc3339(&format!("{}T00:00:00Z", s)) {
+        return Some(dt.with_timezone(&Utc));
+    }
+
+    let unit = s.chars().last()?;
+    let amount: i64 = s[..s.len() - unit.len_utf8()].parse().ok()?;
+    let age = match unit {
+        's' => Duration::try_seconds(amount)?,
+        'm' => Duration::try_minutes(amount)?,
+        'h' => Duration::try_hours(amount)?,
+        'd' => Duration::try_days(amount)?,
+        _ => return None,
+    };
+    now.checked_sub_signed(age)
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log_entry::parse_jsonl_line;
+
+    // -- Helpers ----------------------------------------------------------
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2025-01-15T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn parse(expr: &str) -> FilterExpr {
+        FilterExpr::parse_at(expr, now()).unwrap()
+    }
+
+    fn assistant_tool(tool: &str, command: &str, ts: &str) -> LogEntry {
+        let json = format!(
+            r#"{{
+                "type": "assistant",
+                "timestamp": "{}",
+                "message": {{
+                    "role": "assistant",
+                    "model": "claude-opus-4-6",
+                    "content": [{{"type": "tool_use", "id": "t1", "name": "{}", "input": {{"command": "{}"}}}}]
+                }}
+            }}"#,
+            ts, tool, command
+        );
+        parse_jsonl_line(&json).unwrap()
+    }
+
+    fn user_text(text: &str) -> LogEntry {
+        let json = format!(
+            r#"{{
+                "type": "user",
+                "timestamp": "2025-01-15T10:00:00Z",
+                "message": {{"role": "user", "content": "{}"}}
+            }}"#,
+            text
+        );
+        parse_jsonl_line(&json).unwrap()
+    }
+
+    fn subagent(agent_id: &str, slug: &str) -> LogEntry {
+        let json = format!(
+            r#"{{
+                "type": "assistant",
+                "isSidechain": true,
+                "agentId": "{}",
+                "slug": "{}",
+                "message": {{"role": "assistant", "model": "claude-haiku-4-5", "content": []}}
+            }}"#,
+            agent_id, slug
+        );
+        parse_jsonl_line(&json).unwrap()
+    }
+
+    // -- Terms ------------------------------------------------------------
+
+    #[test]
+    fn test_role_term() {
+        let expr = parse("role:assistant");
+        assert!(expr.matches(&assistant_tool("Bash", "ls", "2025-01-15T10:00:00Z")));
+        assert!(!expr.matches(&user_text("hi")));
+    }
+
+    #[test]
+    fn test_role_system_matches_system_entry_without_message() {
+        let entry = parse_jsonl_line(r#"{"type": "system", "content": "x"}"#).unwrap();
+        assert!(parse("role:system").matches(&entry));
+    }
+
+    #[test]
+    fn test_type_term() {
+        assert!(parse("type:user").matches(&user_text("hi")));
+        assert!(!parse("type:assistant").matches(&user_text("hi")));
+    }
+
+    #[test]
+    fn test_model_term_is_substring() {
+        let entry = assistant_tool("Bash", "ls", "2025-01-15T10:00:00Z");
+        assert!(parse("model:opus").matches(&entry));
+        assert!(parse("model:OPUS").matches(&entry));
+        assert!(!parse("model:haiku").matches(&entry));
+    }
+
+    #[test]
+    fn test_tool_term() {
+        let entry = assistant_tool("Bash", "ls", "2025-01-15T10:00:00Z");
+        assert!(parse("tool:Bash").matches(&entry));
+        assert!(parse("tool:bash").matches(&entry));
+        assert!(!parse("tool:Read").matches(&entry));
+    }
+
+    #[test]
+    fn test_agent_term() {
+        let entry = subagent("a0d0bbc", "effervescent-cook");
+        assert!(parse("agent:a0d0").matches(&entry));
+        assert!(parse("agent:effervescent-cook").matches(&entry));
+        assert!(!parse("agent:main").matches(&entry));
+        assert!(parse("agent:main").matches(&user_text("hi")));
+    }
+
+    #[test]
+    fn test_time_terms() {
+        let early = assistant_tool("Bash", "ls", "2025-01-15T10:00:00Z");
+        let late = assistant_tool("Bash", "ls", "2025-01-15T11:45:00Z");
+        assert!(parse("after:2025-01-15T11:00:00Z").matches(&late));
+        assert!(!parse("after:2025-01-15T11:00:00Z").matches(&early));
+        assert!(parse("before:2025-01-15T11:00:00Z").matches(&early));
+        // Relative ages count back from `now` (12:00).
+        assert!(parse("after:30m").matches(&late));
+        assert!(!parse("after:30m").matches(&early));
+        assert!(parse("after:2025-01-15").matches(&early));
+    }
+
+    #[test]
+    fn test_text_plain_is_case_insensitive_substring() {
+        assert!(parse("text:HELLO").matches(&user_text("well hello there")));
+        assert!(parse("hello").matches(&user_text("well hello there")));
+        assert!(!parse("text:goodbye").matches(&user_text("well hello there")));
+    }
+
+    #[test]
+    fn test_text_quoted_value() {
+        assert!(parse(r#"text:"hello there""#).matches(&user_text("well hello there")));
+        assert!(!parse(r#"text:"hello world""#).matches(&user_text("well hello there")));
+    }
+
+    #[test]
+    fn test_text_regex_with_spaces() {
+        let entry = assistant_tool("Bash", "cargo test --all", "2025-01-15T10:00:00Z");
+        assert!(parse("text:/cargo test/").matches(&entry));
+        assert!(parse("text:/cargo\\s+t.st/").matches(&entry));
+        assert!(!parse("text:/CARGO/").matches(&entry));
+        assert!(parse("text:/CARGO/i").matches(&entry));
+    }
+
+    // -- Combinators --------------------------------------------------------
+
+    #[test]
+    fn test_example_expression() {
+        let expr = parse("role:assistant AND tool:Bash AND NOT text:/cargo test/");
+        assert!(expr.matches(&assistant_tool("Bash", "ls -la", "2025-01-15T10:00:00Z")));
+        assert!(!expr.matches(&assistant_tool(
+            "Bash",
+            "cargo test",
+            "2025-01-15T10:00:00Z"
+        )));
+        assert!(!expr.matches(&assistant_tool("Read", "ls -la", "2025-01-15T10:00:00Z")));
+        assert!(!expr.matches(&user_text("ls -la")));
+    }
+
+    #[test]
+    fn test_or_and_precedence() {
+        // AND binds tighter than OR.
+        let expr = parse("role:user OR role:assistant AND tool:Read");
+        assert!(expr.matches(&user_text("hi")));
+        assert!(!expr.matches(&assistant_tool("Bash", "ls", "2025-01-15T10:00:00Z")));
+        assert!(expr.matches(&assistant_tool("Read", "ls", "2025-01-15T10:00:00Z")));
+    }
+
+    #[test]
+    fn test_parentheses() {
+        let expr = parse("(role:user OR tool:Read) AND NOT text:secret");
+        assert!(expr.matches(&user_text("hi")));
+        assert!(!expr.matches(&user_text("a secret")));
+    }
+
+    #[test]
+    fn test_implicit_and() {
+        let expr = parse("role:assistant tool:Bash");
+        assert!(expr.matches(&assistant_tool("Bash", "ls", "2025-01-15T10:00:00Z")));
+        assert!(!expr.matches(&assistant_tool("Read", "ls", "2025-01-15T10:00:00Z")));
+    }
+
+    #[test]
+    fn test_exclude_prefixes() {
+        let entry = assistant_tool("Bash", "ls", "2025-01-15T10:00:00Z");
+        assert!(!parse("-tool:Bash").matches(&entry));
+        assert!(!parse("!tool:Bash").matches(&entry));
+        assert!(parse("-tool:Read").matches(&entry));
+    }
+
+    // -- Errors -------------------------------------------------------------
+
+    #[test]
+    fn test_errors() {
+        let err = |s: &str| FilterExpr::parse_at(s, now()).unwrap_err();
+        assert_eq!(err("   "), FilterParseError::Empty);
+        assert_eq!(err("role:user AND"), FilterParseError::UnexpectedEnd);
+        assert_eq!(err("(role:user"), FilterParseError::UnexpectedEnd);
+        assert_eq!(
+            err("role:user)"),
+            FilterParseError::UnexpectedToken(")".into())
+        );
+        assert_eq!(
+            err("OR role:user"),
+            FilterParseError::UnexpectedToken("OR".into())
+        );
+        assert_eq!(err("text:/abc"), FilterParseError::Unterminated('/'));
+        assert_eq!(
+            err("colour:red"),
+            FilterParseError::UnknownKey("colour".into())
+        );
+        assert!(matches!(
+            err("type:banana"),
+            FilterParseError::InvalidValue { .. }
+        ));
+        assert!(matches!(
+            err("after:soon"),
+            FilterParseError::InvalidValue { .. }
+        ));
+        assert!(matches!(err("text:/(/"), FilterParseError::InvalidRegex(_)));
+    }
+
+    #[test]
+    fn test_source_and_equality() {
+        let a = parse("  role:user ");
+        assert_eq!(a.source(), "role:user");
+        assert_eq!(a, parse("role:user"));
+        assert_ne!(a, parse("role:assistant"));
+    }
+}
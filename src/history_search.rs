@@ -0,0 +1,585 @@
+//! Cross-session full-text search over a project's history.
+//!
+//! Unlike [`search`](crate::search), which highlights matches in the
+//! in-memory ring buffer of the active session, this module scans every
+//! session file on disk (main and subagent logs) and returns the matching
+//! entries grouped by session. It backs the `cctail search` subcommand and
+//! the TUI's global search overlay.
+//!
+//! Entries are matched on the same text the expression filter uses
+//! ([`entry_text()`]): text blocks, tool inputs and tool result contents,
+//! cleaned and with secrets redacted as in the detail pane, so snippets
+//! never print a secret. Only entry types that the log stream shows are
+//! considered.
+//!
+//! When a [`SearchIndex`] is available it narrows down which lines of each
+//! file are read; files it cannot answer for are scanned linearly.
+
+use std::io::{self, BufRead, BufReader, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use crate::cli::SearchArgs;
+use crate::config::AppConfig;
+use crate::filter::entry_text;
+use crate::log_entry::{parse_jsonl_line, LogEntry};
+use crate::log_warn;
+use crate::project_path::detect_project_path;
+use crate::replay::is_visible_type;
+use crate::search::{MatchMode, Matcher};
+use crate::search_index::{open_updated, SearchIndex};
+use crate::session::{discover_sessions, Session};
+use crate::tool_summary::clean_detail_text;
+
+// ---------------------------------------------------------------------------
+// Constants
+// ---------------------------------------------------------------------------
+
+/// Maximum snippet length in characters.
+const SNIPPET_MAX_CHARS: usize = 100;
+
+/// Characters of context kept before the match when a snippet is shortened.
+const SNIPPET_LEAD_CHARS: usize = 30;
+
+// ---------------------------------------------------------------------------
+// Result types
+// ---------------------------------------------------------------------------
+
+/// A single matching entry in a session file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryMatch {
+    /// Session the entry belongs to.
+    pub session_id: String,
+    /// Subagent ID, or `None` for the main session log.
+    pub agent_id: Option<String>,
+    /// The JSONL file containing the entry.
+    pub path: PathBuf,
+    /// Byte offset of the entry's line within `path`.
+    pub byte_offset: u64,
+    /// Entry `uuid`, used to locate the entry after switching sessions.
+    pub uuid: Option<String>,
+    /// Entry timestamp (ISO 8601).
+    pub timestamp: Option<String>,
+    /// One-line excerpt around the first match.
+    pub snippet: String,
+}
+
+/// All matches found in one session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionMatches {
+    pub session: Session,
+    /// Matches in timestamp order, capped at the per-session limit.
+    pub matches: Vec<HistoryMatch>,
+    /// Number of matching entries before the cap was applied.
+    pub total: usize,
+}
+
+// ---------------------------------------------------------------------------
+// Public API
+// ---------------------------------------------------------------------------
+
+/// Search every agent log of every session in `sessions`.
+///
+/// Returns one [`SessionMatches`] per session with at least one match, in
+/// the order of `sessions` (most recent first when it comes from
+/// `discover_sessions`). At most `max_per_session` matches are kept per
+/// session; `total` still counts all of them. Unreadable files are skipped.
+///
+/// With an `index`, only the candidate lines it reports are read.
+pub fn search_sessions(
+    sessions: &[Session],
+    matcher: &Matcher,
+    max_per_session: usize,
+    index: Option<&SearchIndex>,
+) -> Vec<SessionMatches> {
+    let mut results = Vec::new();
+
+    for session in sessions {
+        let mut matches = Vec::new();
+        for agent in &session.agents {
+            let path = &agent.log_path;
+            let agent_id = agent.agent_id.as_deref();
+            let found = match index.and_then(|i| i.candidates(path, matcher)) {
+                Some(offsets) => search_lines_at(path, &offsets, &session.id, agent_id, matcher),
+                None => search_file(path, &session.id, agent_id, matcher),
+            };
+            if let Ok(found) = found {
+                matches.extend(found);
+            }
+        }
+        if matches.is_empty() {
+            continue;
+        }
+
+        matches.sort_by(|a, b| {
+            let ts_a = a.timestamp.as_deref().unwrap_or("");
+            let ts_b = b.timestamp.as_deref().unwrap_or("");
+            ts_a.cmp(ts_b)
+        });
+        let total = matches.len();
+        matches.truncate(max_per_session);
+
+        results.push(SessionMatches {
+            session: session.clone(),
+            matches,
+            total,
+        });
+    }
+
+    results
+}
+
+/// Search a single JSONL file, returning matching visible entries in file order.
+pub fn search_file(
+    path: &Path,
+    session_id: &str,
+    agent_id: Option<&str>,
+    matcher: &Matcher,
+) -> io::Result<Vec<HistoryMatch>> {
+    let mut reader = BufReader::new(std::fs::File::open(path)?);
+    let mut matches = Vec::new();
+    let mut offset: u64 = 0;
+    let mut bytes = Vec::new();
+
+    loop {
+        bytes.clear();
+        let read = match reader.read_until(b'\n', &mut bytes) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) => {
+                log_warn!("search: failed to read {}: {}", path.display(), e);
+                break;
+            }
+        };
+        let line_offset = offset;
+        offset += read as u64;
+
+        // Reading raw bytes keeps offsets exact past a line that is not
+        // valid UTF-8; such a line is skipped.
+        let line = match std::str::from_utf8(&bytes) {
+            Ok(line) => line,
+            Err(e) => {
+                log_warn!(
+                    "search: skipping line at byte {} of {}: {}",
+                    line_offset,
+                    path.display(),
+                    e
+                );
+                continue;
+            }
+        };
+        let location = (path, line_offset, session_id, agent_id);
+        if let Some(m) = match_line(line, location, matcher) {
+            matches.push(m);
+        }
+    }
+
+    Ok(matches)
+}
+
+/// Search only the lines of `path` starting at `offsets` (as reported by
+/// the index), returning matches in file order.
+fn search_lines_at(
+    path: &Path,
+    offsets: &[u64],
+    session_id: &str,
+    agent_id: Option<&str>,
+    matcher: &Matcher,
+) -> io::Result<Vec<HistoryMatch>> {
+    let mut reader = BufReader::new(std::fs::File::open(path)?);
+    let mut matches = Vec::new();
+    let mut line = String::new();
+
+    for &offset in offsets {
+        reader.seek(SeekFrom::Start(offset))?;
+        line.clear();
+        if reader.read_line(&mut line).is_err() {
+            continue;
+        }
+        if let Some(m) = match_line(&line, (path, offset, session_id, agent_id), matcher) {
+            matches.push(m);
+        }
+    }
+
+    Ok(matches)
+}
+
+/// Parse one JSONL line and match it, returning `None` for blank,
+/// malformed, hidden or non-matching entries.
+fn match_line(
+    line: &str,
+    (path, byte_offset, session_id, agent_id): (&Path, u64, &str, Option<&str>),
+    matcher: &Matcher,
+) -> Option<HistoryMatch> {
+    let trimmed = line.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+    let entry = parse_jsonl_line(trimmed).ok()?;
+    if !is_visible_type(&entry) {
+        return None;
+    }
+
+    let text = searchable_text(&entry);
+    let (start, len) = matcher.find_all(&text).into_iter().next()?;
+
+    Some(HistoryMatch {
+        session_id: session_id.to_string(),
+        agent_id: agent_id.map(str::to_string),
+        path: path.to_path_buf(),
+        byte_offset,
+        uuid: entry.uuid,
+        timestamp: entry.timestamp,
+        snippet: snippet(&text, start, len),
+    })
+}
+
+/// The text an entry is searched and indexed by: [`entry_text()`] with
+/// control characters stripped and secrets redacted.
+pub(crate) fn searchable_text(entry: &LogEntry) -> String {
+    clean_detail_text(&entry_text(entry))
+}
+
+/// Format a match timestamp as `YYYY-MM-DD HH:MM:SS` in local time.
+///
+/// Falls back to `"---------- --:--:--"` if absent or malformed.
+pub fn format_match_time(ts: Option<&str>) -> String {
+    ts.and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
+        .map(|dt| {
+            dt.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+        })
+        .unwrap_or_else(|| "---------- --:--:--".to_string())
+}
+
+// ---------------------------------------------------------------------------
+// `cctail search`
+// ---------------------------------------------------------------------------
+
+/// Run the `search` subcommand: search all sessions of the detected (or
+/// `--project`) project and print the results grouped by session.
+pub fn run_search(args: &SearchArgs, config: &AppConfig) -> Result<(), Box<dyn std::error::Error>> {
+    let mode = if args.regex {
+        MatchMode::Regex
+    } else if args.smart_case {
+        MatchMode::SmartCase
+    } else {
+        MatchMode::Plain
+    };
+    let matcher = Matcher::new(&args.query, mode)?;
+
+    let cwd = std::env::current_dir().unwrap_or_default();
+    let project_dir = detect_project_path(&cwd, config.project.as_deref())?;
+    let sessions = discover_sessions(&project_dir, usize::MAX)?;
+
+    // Use the index if there is one (or build it with --index); any index
+    // problem falls back to a linear scan.
+    let index = match open_updated(&project_dir, &sessions, args.index) {
+        Ok(index) => index,
+        Err(e) => {
+            log_warn!("search index unavailable: {}", e);
+            None
+        }
+    };
+    let results = search_sessions(&sessions, &matcher, args.limit, index.as_ref());
+
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    // BrokenPipe (e.g. piping into `head`) ends output quietly.
+    match write_results(&mut out, &results) {
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => {}
+        result => result?,
+    }
+    Ok(())
+}
+
+/// Print results grouped by session:
+///
+/// ```text
+/// 3f2a9c1e-...  (2 matches)
+///   2025-01-15 10:30:00  edit migrations/0042_add.sql
+///   2025-01-15 10:31:12  [a0d0bbc] ran migrations/0042 ...
+/// ```
+fn write_results<W: Write>(out: &mut W, results: &[SessionMatches]) -> io::Result<()> {
+    if results.is_empty() {
+        writeln!(out, "No matches.")?;
+        return Ok(());
+    }
+
+    for (i, group) in results.iter().enumerate() {
+        if i > 0 {
+            writeln!(out)?;
+        }
+        let noun = if group.total == 1 { "match" } else { "matches" };
+        writeln!(out, "{}  ({} {})", group.session.id, group.total, noun)?;
+        for m in &group.matches {
+            let agent = m
+                .agent_id
+                .as_deref()
+                .map(|id| format!("[{}] ", id))
+                .unwrap_or_default();
+            writeln!(
+                out,
+                "  {}  {}{}",
+                format_match_time(m.timestamp.as_deref()),
+                agent,
+                m.snippet
+            )?;
+        }
+        if group.total > group.matches.len() {
+            writeln!(out, "  ... {} more", group.total - group.matches.len())?;
+        }
+    }
+    out.flush()
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// Build a one-line excerpt of the line of `text` containing the match at
+/// `start..start + len`, shortened around the match if needed.
+fn snippet(text: &str, start: usize, len: usize) -> String {
+    let line_start = text[..start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = text[start + len..]
+        .find('\n')
+        .map_or(text.len(), |i| start + len + i);
+    let line = &text[line_start..line_end];
+    let match_char = line[..start - line_start].chars().count();
+
+    let chars: Vec<char> = line
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect();
+    if chars.len() <= SNIPPET_MAX_CHARS {
+        return chars.into_iter().collect::<String>().trim().to_string();
+    }
+
+    let from = match_char.saturating_sub(SNIPPET_LEAD_CHARS);
+    let to = (from + SNIPPET_MAX_CHARS).min(chars.len());
+    let mut out = String::new();
+    if from > 0 {
+        out.push_str("...");
+    }
+    out.extend(&chars[from..to]);
+    if to < chars.len() {
+        out.push_str("...");
+    }
+    out
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{session, user_line, write_file};
+
+    #[test]
+    fn test_search_file_records_offsets_and_ids() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let first = user_line("u1", "2025-01-15T10:00:00Z", "nothing here");
+        let second = user_line("u2", "2025-01-15T10:01:00Z", "edit migrations/0042_add.sql");
+        let path = write_file(tmp.path(), "s1.jsonl", &[&first, &second]);
+
+        let matcher = Matcher::new("migrations/0042", MatchMode::Plain).unwrap();
+        let found = search_file(&path, "s1", None, &matcher).unwrap();
+
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].uuid.as_deref(), Some("u2"));
+        assert_eq!(found[0].byte_offset, first.len() as u64 + 1);
+        assert_eq!(found[0].snippet, "edit migrations/0042_add.sql");
+    }
+
+    #[test]
+    fn test_search_file_skips_hidden_types_and_malformed_lines() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = write_file(
+            tmp.path(),
+            "s1.jsonl",
+            &[
+                r#"{"type": "progress", "message": {"role": "user", "content": "needle"}}"#,
+                "{not json needle",
+            ],
+        );
+        let matcher = Matcher::new("needle", MatchMode::Plain).unwrap();
+        assert!(search_file(&path, "s1", None, &matcher).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_search_file_continues_past_invalid_utf8_line() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("s1.jsonl");
+        let first = user_line("u1", "2025-01-15T10:00:00Z", "needle one");
+        let second = user_line("u2", "2025-01-15T10:01:00Z", "needle two");
+        let mut bytes = first.clone().into_bytes();
+        bytes.extend_from_slice(b"\n\xff\xfe needle\n");
+        bytes.extend_from_slice(second.as_bytes());
+        bytes.push(b'\n');
+        std::fs::write(&path, &bytes).unwrap();
+
+        let matcher = Matcher::new("needle", MatchMode::Plain).unwrap();
+        let found = search_file(&path, "s1", None, &matcher).unwrap();
+
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[1].uuid.as_deref(), Some("u2"));
+        assert_eq!(found[1].byte_offset, first.len() as u64 + 1 + 10);
+    }
+
+    #[test]
+    fn test_search_file_redacts_secrets_in_snippets() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let line = user_line(
+            "u1",
+            "2025-01-15T10:00:00Z",
+            "deploy with API_KEY=abc123secret now",
+        );
+        let path = write_file(tmp.path(), "s1.jsonl", &[&line]);
+
+        let matcher = Matcher::new("deploy", MatchMode::Plain).unwrap();
+        let found = search_file(&path, "s1", None, &matcher).unwrap();
+        assert_eq!(found[0].snippet, "deploy with API_KEY=[REDACTED] now");
+
+        let secret = Matcher::new("abc123secret", MatchMode::Plain).unwrap();
+        assert!(search_file(&path, "s1", None, &secret).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_search_sessions_groups_and_includes_subagents() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let main = write_file(
+            tmp.path(),
+            "s1.jsonl",
+            &[&user_line("u1", "2025-01-15T10:02:00Z", "needle in main")],
+        );
+        let sub = write_file(
+            tmp.path(),
+            "s1/subagents/agent-abc.jsonl",
+            &[&user_line("u2", "2025-01-15T10:01:00Z", "needle in sub")],
+        );
+        let other = write_file(
+            tmp.path(),
+            "s2.jsonl",
+            &[&user_line("u3", "2025-01-15T10:00:00Z", "haystack")],
+        );
+
+        let sessions = vec![
+            session("s1", vec![(None, main), (Some("abc"), sub)]),
+            session("s2", vec![(None, other)]),
+        ];
+        let matcher = Matcher::new("NEEDLE", MatchMode::Plain).unwrap();
+        let results = search_sessions(&sessions, &matcher, 10, None);
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].session.id, "s1");
+        assert_eq!(results[0].total, 2);
+        // Sorted by timestamp: the subagent entry comes first.
+        assert_eq!(results[0].matches[0].agent_id.as_deref(), Some("abc"));
+        assert_eq!(results[0].matches[1].agent_id, None);
+    }
+
+    #[test]
+    fn test_search_sessions_with_index_matches_linear_scan() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = write_file(
+            tmp.path(),
+            "s1.jsonl",
+            &[
+                &user_line("u1", "2025-01-15T10:00:00Z", "run cargo test"),
+                &user_line("u2", "2025-01-15T10:01:00Z", "cargo build"),
+                &user_line("u3", "2025-01-15T10:02:00Z", "Test the cargo crate"),
+            ],
+        );
+        let sessions = [session("s1", vec![(None, path.clone())])];
+        let mut index = SearchIndex::new();
+        index.update(&sessions);
+
+        for (query, mode) in [
+            ("cargo test", MatchMode::Plain),
+            ("Test", MatchMode::SmartCase),
+            ("carg", MatchMode::Plain),
+            ("cargo (test|build)", MatchMode::Regex),
+        ] {
+            let matcher = Matcher::new(query, mode).unwrap();
+            assert_eq!(
+                search_sessions(&sessions, &matcher, 10, Some(&index)),
+                search_sessions(&sessions, &matcher, 10, None),
+                "query {:?}",
+                query
+            );
+        }
+
+        // The index only reads candidate lines.
+        let matcher = Matcher::new("build", MatchMode::Plain).unwrap();
+        assert!(index.candidates(&path, &matcher).is_some());
+        let results = search_sessions(&sessions, &matcher, 10, Some(&index));
+        assert_eq!(results[0].matches[0].uuid.as_deref(), Some("u2"));
+    }
+
+    #[test]
+    fn test_search_sessions_caps_matches_per_session() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let lines: Vec<String> = (0..5)
+            .map(|i| user_line(&format!("u{}", i), "2025-01-15T10:00:00Z", "needle"))
+            .collect();
+        let refs: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let path = write_file(tmp.path(), "s1.jsonl", &refs);
+
+        let matcher = Matcher::new("needle", MatchMode::Plain).unwrap();
+        let results = search_sessions(&[session("s1", vec![(None, path)])], &matcher, 2, None);
+        assert_eq!(results[0].matches.len(), 2);
+        assert_eq!(results[0].total, 5);
+    }
+
+    #[test]
+    fn test_snippet_shortens_long_lines_around_match() {
+        let text = format!("{}needle{}", "a".repeat(200), "b".repeat(200));
+        let s = snippet(&text, 200, 6);
+        assert!(s.starts_with("..."));
+        assert!(s.ends_with("..."));
+        assert!(s.contains("needle"));
+        assert_eq!(s.chars().count(), SNIPPET_MAX_CHARS + 6);
+    }
+
+    #[test]
+    fn test_snippet_uses_matching_line() {
+        let text = "first line\nsecond needle line\nthird";
+        assert_eq!(snippet(text, 18, 6), "second needle line");
+    }
+
+    #[test]
+    fn test_write_results_groups_by_session() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let lines: Vec<String> = (0..3)
+            .map(|i| user_line(&format!("u{}", i), "bad-ts", "needle"))
+            .collect();
+        let refs: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let path = write_file(tmp.path(), "s1.jsonl", &refs);
+
+        let matcher = Matcher::new("needle", MatchMode::Plain).unwrap();
+        let results = search_sessions(&[session("s1", vec![(None, path)])], &matcher, 2, None);
+
+        let mut out = Vec::new();
+        write_results(&mut out, &results).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(
+            text,
+            "s1  (3 matches)\n  ---------- --:--:--  needle\n  ---------- --:--:--  needle\n  ... 1 more\n"
+        );
+    }
+
+    #[test]
+    fn test_write_results_empty() {
+        let mut out = Vec::new();
+        write_results(&mut out, &[]).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "No matches.\n");
+    }
+
+    #[test]
+    fn test_format_match_time_fallback() {
+        assert_eq!(format_match_time(None), "---------- --:--:--");
+        assert_eq!(format_match_time(Some("garbage")), "---------- --:--:--");
+    }
+}
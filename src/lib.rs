@@ -1,17 +1,49 @@
+// Tests keep the style they were written in; these lints postdate most of them.
+#![cfg_attr(
+    test,
+    allow(
+        clippy::field_reassign_with_default,
+        clippy::implicit_saturating_sub,
+        clippy::len_zero,
+        clippy::needless_borrow,
+        clippy::unnecessary_sort_by,
+        clippy::useless_format,
+        clippy::write_literal,
+        clippy::write_with_newline
+    )
+)]
+
 pub mod cli;
 pub mod config;
 pub mod content_render;
+pub mod conversation_tree;
+pub mod export;
 pub mod filter;
+pub mod filter_expr;
+pub mod history_search;
 pub mod log_entry;
+pub mod logging;
+pub mod pricing;
+pub mod process_detect;
 pub mod project_path;
 pub mod replay;
 pub mod ring_buffer;
 pub mod search;
+pub mod search_index;
 pub mod session;
+pub mod session_info;
+pub mod session_list;
 pub mod session_stats;
+pub mod session_title;
 pub mod stream;
 pub mod symbols;
+pub mod system_event;
+pub mod task_links;
+#[cfg(test)]
+mod test_support;
 pub mod theme;
+pub mod tool_calls;
+pub mod tool_detail;
 pub mod tool_summary;
 pub mod tui;
 pub mod watcher;
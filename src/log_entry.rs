@@ -1,4 +1,5 @@
-use serde::{Deserialize, Serialize};
+use serde::de::IgnoredAny;
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
 
 // ---------------------------------------------------------------------------
@@ -20,6 +21,9 @@ pub enum EntryType {
     FileHistorySnapshot,
     System,
     QueueOperation,
+    /// Conversation title (`summary`) for the conversation ending at
+    /// `leafUuid`, written by Claude Code when a session is resumed.
+    Summary,
     /// Catch-all for entry types not yet modelled.
     #[default]
     #[serde(other)]
@@ -38,9 +42,118 @@ pub enum EntryType {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 #[serde(default)]
 pub struct Message {
+    /// API message identifier (e.g. `"msg_01..."`). Claude Code writes one
+    /// JSONL line per content block, so several entries can share an `id`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
     pub role: Option<String>,
     pub content: Value,
     pub model: Option<String>,
+    /// Token usage reported by the API. Present on assistant entries.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usage: Option<Usage>,
+}
+
+// ---------------------------------------------------------------------------
+// Usage struct
+// ---------------------------------------------------------------------------
+
+/// Token counts from the `message.usage` block of an assistant entry.
+///
+/// Unknown keys (`service_tier`, `server_tool_use`, ...) are ignored and
+/// missing counts default to zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct Usage {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_creation_input_tokens: u64,
+    pub cache_read_input_tokens: u64,
+}
+
+impl Usage {
+    /// Sum of all four token counters.
+    pub fn total_tokens(&self) -> u64 {
+        self.input_tokens
+            + self.output_tokens
+            + self.cache_creation_input_tokens
+            + self.cache_read_input_tokens
+    }
+
+    /// Add another usage record into this one.
+    pub fn accumulate(&mut self, other: &Usage) {
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.cache_creation_input_tokens += other.cache_creation_input_tokens;
+        self.cache_read_input_tokens += other.cache_read_input_tokens;
+    }
+
+    /// Take another usage record out of this one.
+    pub fn subtract(&mut self, other: &Usage) {
+        self.input_tokens = self.input_tokens.saturating_sub(other.input_tokens);
+        self.output_tokens = self.output_tokens.saturating_sub(other.output_tokens);
+        self.cache_creation_input_tokens = self
+            .cache_creation_input_tokens
+            .saturating_sub(other.cache_creation_input_tokens);
+        self.cache_read_input_tokens = self
+            .cache_read_input_tokens
+            .saturating_sub(other.cache_read_input_tokens);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// CompactMetadata struct
+// ---------------------------------------------------------------------------
+
+/// The `compactMetadata` object of a `compact_boundary` system entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct CompactMetadata {
+    /// `"auto"` when Claude Code compacted on its own, `"manual"` for
+    /// `/compact`.
+    pub trigger: Option<String>,
+    /// Context size in tokens just before compaction.
+    #[serde(rename = "preTokens")]
+    pub pre_tokens: Option<u64>,
+}
+
+/// `subtype` of the system entry Claude Code writes where it compacted the
+/// conversation.
+pub const COMPACT_BOUNDARY_SUBTYPE: &str = "compact_boundary";
+
+// ---------------------------------------------------------------------------
+// ToolUseResult struct
+// ---------------------------------------------------------------------------
+
+/// The fields cctail reads from an entry's `toolUseResult`.
+///
+/// The rest of the payload (stdout, file contents, patches, ...) often
+/// repeats the whole tool output and is not kept. A result that is not an
+/// object (e.g. a plain error string) yields the default.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Default)]
+pub struct ToolUseResult {
+    /// Subagent that ran a `Task` call.
+    #[serde(rename = "agentId", skip_serializing_if = "Option::is_none")]
+    pub agent_id: Option<String>,
+}
+
+impl<'de> Deserialize<'de> for ToolUseResult {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Object {
+                #[serde(rename = "agentId")]
+                agent_id: Option<String>,
+            },
+            Other(IgnoredAny),
+        }
+
+        Ok(match Raw::deserialize(deserializer)? {
+            Raw::Object { agent_id } => ToolUseResult { agent_id },
+            Raw::Other(_) => ToolUseResult::default(),
+        })
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -92,6 +205,85 @@ pub struct LogEntry {
 
     /// Opaque data payload used by some entry types (e.g. progress).
     pub data: Option<Value>,
+
+    /// Working directory of the Claude Code process when the entry was
+    /// written. Changes mid-session after a `cd`.
+    pub cwd: Option<String>,
+
+    /// Git branch checked out in `cwd` (empty outside a repository).
+    #[serde(rename = "gitBranch")]
+    pub git_branch: Option<String>,
+
+    /// Claude Code version that wrote the entry (e.g. `"1.0.71"`).
+    pub version: Option<String>,
+
+    /// Who drove the session (e.g. `"external"`).
+    #[serde(rename = "userType")]
+    pub user_type: Option<String>,
+
+    /// API request identifier. Present on assistant entries.
+    #[serde(rename = "requestId")]
+    pub request_id: Option<String>,
+
+    /// `true` for entries Claude Code injects rather than the user typing
+    /// them (e.g. system reminders, local command caveats).
+    #[serde(rename = "isMeta")]
+    pub is_meta: Option<bool>,
+
+    /// `true` for the user entry holding a context compaction summary.
+    #[serde(rename = "isCompactSummary")]
+    pub is_compact_summary: Option<bool>,
+
+    /// Structured tool result attached to the user entry carrying the
+    /// `tool_result` block, reduced to the fields cctail uses.
+    #[serde(rename = "toolUseResult")]
+    pub tool_use_result: Option<ToolUseResult>,
+
+    /// Kind of `system` entry (e.g. `"compact_boundary"`, `"api_error"`).
+    pub subtype: Option<String>,
+
+    /// Text of a `system` entry. Usually a string; kept as raw JSON.
+    pub content: Option<Value>,
+
+    /// Severity of a `system` entry: `"info"`, `"warning"` or `"error"`.
+    pub level: Option<String>,
+
+    /// API error of an `api_error` system entry. Its nesting depends on the
+    /// Claude Code version, so it is kept as raw JSON.
+    pub error: Option<Value>,
+
+    /// Delay before Claude Code retries a failed API request.
+    #[serde(rename = "retryInMs")]
+    pub retry_in_ms: Option<f64>,
+
+    /// Retry attempt number (1-based) of an `api_error` entry.
+    #[serde(rename = "retryAttempt")]
+    pub retry_attempt: Option<u32>,
+
+    /// Maximum number of retries of an `api_error` entry.
+    #[serde(rename = "maxRetries")]
+    pub max_retries: Option<u32>,
+
+    /// Errors reported by hooks (`stop_hook_summary` entries).
+    #[serde(rename = "hookErrors")]
+    pub hook_errors: Option<Value>,
+
+    /// Trigger and token count of a compaction. Present on
+    /// `compact_boundary` entries.
+    #[serde(rename = "compactMetadata")]
+    pub compact_metadata: Option<CompactMetadata>,
+
+    /// Last entry before a compaction boundary. The boundary itself starts
+    /// a new root (`parentUuid` is null), so this is the only link back.
+    #[serde(rename = "logicalParentUuid")]
+    pub logical_parent_uuid: Option<String>,
+
+    /// Conversation title of a `summary` entry.
+    pub summary: Option<String>,
+
+    /// Last entry of the conversation a `summary` entry describes.
+    #[serde(rename = "leafUuid")]
+    pub leaf_uuid: Option<String>,
 }
 
 // ---------------------------------------------------------------------------
@@ -100,8 +292,7 @@ pub struct LogEntry {
 
 /// Parse a single JSONL line into a `LogEntry`.
 ///
-/// Returns `Err` for malformed JSON. The caller decides whether to skip
-/// or warn (e.g. silent skip in normal mode, stderr warning in verbose).
+/// Returns `Err` for malformed JSON. The caller decides how to report it.
 pub fn parse_jsonl_line(line: &str) -> Result<LogEntry, serde_json::Error> {
     serde_json::from_str(line)
 }
@@ -111,6 +302,12 @@ pub fn parse_jsonl_line(line: &str) -> Result<LogEntry, serde_json::Error> {
 // ---------------------------------------------------------------------------
 
 impl LogEntry {
+    /// Whether this is the system entry marking a context compaction.
+    pub fn is_compact_boundary(&self) -> bool {
+        self.entry_type == EntryType::System
+            && self.subtype.as_deref() == Some(COMPACT_BOUNDARY_SUBTYPE)
+    }
+
     /// Returns an estimated byte size by re-serializing the entry to JSON.
     ///
     /// Used for ring-buffer accounting (byte-budget eviction). The result
@@ -409,7 +606,72 @@ mod tests {
         assert!(msg.content.as_array().unwrap().is_empty());
     }
 
-    // -- 16. estimated_byte_size returns reasonable value ---------------------
+    // -- 16. Parse assistant usage block ---------------------------------------
+
+    #[test]
+    fn test_parse_assistant_usage() {
+        let json = r#"{
+            "type": "assistant",
+            "message": {
+                "id": "msg_01abc",
+                "role": "assistant",
+                "model": "claude-opus-4-6",
+                "content": [],
+                "usage": {
+                    "input_tokens": 12,
+                    "output_tokens": 345,
+                    "cache_creation_input_tokens": 1000,
+                    "cache_read_input_tokens": 20000,
+                    "service_tier": "standard"
+                }
+            }
+        }"#;
+
+        let entry = parse_jsonl_line(json).unwrap();
+        let msg = entry.message.as_ref().unwrap();
+        assert_eq!(msg.id.as_deref(), Some("msg_01abc"));
+        let usage = msg.usage.unwrap();
+        assert_eq!(usage.input_tokens, 12);
+        assert_eq!(usage.output_tokens, 345);
+        assert_eq!(usage.cache_creation_input_tokens, 1000);
+        assert_eq!(usage.cache_read_input_tokens, 20000);
+        assert_eq!(usage.total_tokens(), 21357);
+    }
+
+    // -- 17. Partial usage block defaults missing counts to zero ---------------
+
+    #[test]
+    fn test_parse_partial_usage() {
+        let json = r#"{"type": "assistant", "message": {"usage": {"output_tokens": 7}}}"#;
+        let entry = parse_jsonl_line(json).unwrap();
+        let usage = entry.message.unwrap().usage.unwrap();
+        assert_eq!(usage.input_tokens, 0);
+        assert_eq!(usage.output_tokens, 7);
+    }
+
+    #[test]
+    fn test_usage_accumulate() {
+        let mut total = Usage::default();
+        total.accumulate(&Usage {
+            input_tokens: 1,
+            output_tokens: 2,
+            cache_creation_input_tokens: 3,
+            cache_read_input_tokens: 4,
+        });
+        total.accumulate(&Usage {
+            input_tokens: 10,
+            output_tokens: 20,
+            cache_creation_input_tokens: 30,
+            cache_read_input_tokens: 40,
+        });
+        assert_eq!(total.input_tokens, 11);
+        assert_eq!(total.output_tokens, 22);
+        assert_eq!(total.cache_creation_input_tokens, 33);
+        assert_eq!(total.cache_read_input_tokens, 44);
+        assert_eq!(total.total_tokens(), 110);
+    }
+
+    // -- 18. estimated_byte_size returns reasonable value ---------------------
 
     #[test]
     fn test_estimated_byte_size() {
@@ -448,4 +710,114 @@ mod tests {
             size
         );
     }
+
+    // -- 19. Top-level session metadata fields ---------------------------------
+
+    #[test]
+    fn test_parse_session_metadata_fields() {
+        let json = r#"{
+            "type": "user",
+            "cwd": "/home/dev/project",
+            "gitBranch": "feature/x",
+            "version": "1.0.71",
+            "userType": "external",
+            "requestId": "req_011",
+            "isMeta": true,
+            "isCompactSummary": false,
+            "toolUseResult": {"stdout": "ok", "stderr": "", "agentId": "a9"},
+            "message": {"role": "user", "content": "hi"}
+        }"#;
+
+        let entry = parse_jsonl_line(json).unwrap();
+        assert_eq!(entry.cwd.as_deref(), Some("/home/dev/project"));
+        assert_eq!(entry.git_branch.as_deref(), Some("feature/x"));
+        assert_eq!(entry.version.as_deref(), Some("1.0.71"));
+        assert_eq!(entry.user_type.as_deref(), Some("external"));
+        assert_eq!(entry.request_id.as_deref(), Some("req_011"));
+        assert_eq!(entry.is_meta, Some(true));
+        assert_eq!(entry.is_compact_summary, Some(false));
+        assert_eq!(
+            entry.tool_use_result.and_then(|r| r.agent_id).as_deref(),
+            Some("a9")
+        );
+    }
+
+    // -- 20. toolUseResult may be a plain string --------------------------------
+
+    #[test]
+    fn test_parse_string_tool_use_result() {
+        let json = r#"{"type": "user", "toolUseResult": "Error: file not found"}"#;
+        let entry = parse_jsonl_line(json).unwrap();
+        assert_eq!(entry.tool_use_result, Some(ToolUseResult::default()));
+        assert_eq!(entry.cwd, None);
+        assert_eq!(entry.is_meta, None);
+    }
+
+    // -- 21. Compact boundary system entry -------------------------------------
+
+    #[test]
+    fn test_parse_compact_boundary() {
+        let json = r#"{
+            "type": "system",
+            "subtype": "compact_boundary",
+            "content": "Conversation compacted",
+            "parentUuid": null,
+            "logicalParentUuid": "uuid-before",
+            "uuid": "uuid-boundary",
+            "compactMetadata": {"trigger": "auto", "preTokens": 155432}
+        }"#;
+
+        let entry = parse_jsonl_line(json).unwrap();
+        assert!(entry.is_compact_boundary());
+        assert_eq!(entry.parent_uuid, None);
+        assert_eq!(entry.logical_parent_uuid.as_deref(), Some("uuid-before"));
+        assert_eq!(
+            entry.compact_metadata,
+            Some(CompactMetadata {
+                trigger: Some("auto".to_string()),
+                pre_tokens: Some(155432),
+            })
+        );
+
+        // Other system entries are not boundaries.
+        let other = parse_jsonl_line(r#"{"type": "system", "subtype": "informational"}"#).unwrap();
+        assert!(!other.is_compact_boundary());
+    }
+
+    // -- 22. Summary entry ------------------------------------------------------
+
+    #[test]
+    fn test_parse_summary_entry() {
+        let json =
+            r#"{"type": "summary", "summary": "Fix flaky auth test", "leafUuid": "uuid-leaf"}"#;
+
+        let entry = parse_jsonl_line(json).unwrap();
+        assert_eq!(entry.entry_type, EntryType::Summary);
+        assert_eq!(entry.summary.as_deref(), Some("Fix flaky auth test"));
+        assert_eq!(entry.leaf_uuid.as_deref(), Some("uuid-leaf"));
+        assert!(!entry.is_compact_boundary());
+    }
+
+    // -- 23. API error system entry --------------------------------------------
+
+    #[test]
+    fn test_parse_api_error_entry() {
+        let json = r#"{
+            "type": "system",
+            "subtype": "api_error",
+            "level": "error",
+            "error": {"status": 529, "error": {"type": "error", "error": {"type": "overloaded_error", "message": "Overloaded"}}},
+            "retryInMs": 1153.8,
+            "retryAttempt": 2,
+            "maxRetries": 10
+        }"#;
+
+        let entry = parse_jsonl_line(json).unwrap();
+        assert_eq!(entry.subtype.as_deref(), Some("api_error"));
+        assert_eq!(entry.level.as_deref(), Some("error"));
+        assert_eq!(entry.error.unwrap()["status"], 529);
+        assert_eq!(entry.retry_in_ms, Some(1153.8));
+        assert_eq!(entry.retry_attempt, Some(2));
+        assert_eq!(entry.max_retries, Some(10));
+    }
 }
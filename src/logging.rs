@@ -0,0 +1,332 @@
+//! cc-tail's own diagnostics log.
+//!
+//! Every module reports warnings and debug output through the
+//! [`log_error!`](crate::log_error), [`log_warn!`](crate::log_warn),
+//! [`log_info!`](crate::log_info) and [`log_debug!`](crate::log_debug)
+//! macros instead of `eprintln!`, which would corrupt the TUI or be hidden
+//! behind its alternate screen. [`init`] picks the destination: a log file
+//! (the default in TUI mode, see [`default_log_path`]) or stderr (the
+//! default for subcommands). Messages logged before `init` — config
+//! warnings, for instance — are buffered and written once it runs.
+
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use chrono::{DateTime, Local};
+
+/// File name of the log inside the state directory.
+const LOG_FILE_NAME: &str = "cctail.log";
+
+/// Size above which the log file is moved aside to `<path>.1` and a new
+/// one started, checked when it is opened and after every write.
+const MAX_LOG_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Messages kept before [`init`]; later ones are dropped.
+const MAX_PENDING: usize = 1000;
+
+// ---------------------------------------------------------------------------
+// Level
+// ---------------------------------------------------------------------------
+
+/// Severity of a log message. A logger at a given level writes messages at
+/// that level and above.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+impl Level {
+    /// Lowercase name, as written to stderr.
+    pub fn label(self) -> &'static str {
+        match self {
+            Level::Error => "error",
+            Level::Warn => "warning",
+            Level::Info => "info",
+            Level::Debug => "debug",
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Logger state
+// ---------------------------------------------------------------------------
+
+enum Sink {
+    Stderr,
+    File(LogFile),
+    Discard,
+}
+
+struct LogState {
+    /// Most verbose level written; `None` until [`init`].
+    level: Option<Level>,
+    sink: Option<Sink>,
+    path: Option<PathBuf>,
+    pending: Vec<(Level, DateTime<Local>, String)>,
+}
+
+static STATE: Mutex<LogState> = Mutex::new(LogState {
+    level: None,
+    sink: None,
+    path: None,
+    pending: Vec::new(),
+});
+
+/// Default log file: `cctail.log` under `$XDG_STATE_HOME/cc-tail`
+/// (`~/.local/state/cc-tail`), or the cache directory on platforms without
+/// a state directory.
+pub fn default_log_path() -> Option<PathBuf> {
+    dirs::state_dir()
+        .or_else(dirs::cache_dir)
+        .map(|d| d.join("cc-tail").join(LOG_FILE_NAME))
+}
+
+/// Start logging at `level` to the file at `path` (appending, creating its
+/// directory), or to stderr when `path` is `None`. Buffered messages are
+/// written first. The file is rotated to `<path>.1`, replacing the
+/// previous one, whenever it grows past `MAX_LOG_BYTES`.
+///
+/// # Errors
+///
+/// Returns the error if the log file cannot be opened. Buffered messages
+/// then go to stderr, which the TUI has not taken over yet, and later ones
+/// are dropped.
+pub fn init(level: Level, path: Option<&Path>) -> io::Result<()> {
+    let mut state = STATE.lock().unwrap_or_else(|e| e.into_inner());
+    let (sink, result) = match path.map(|p| LogFile::open(p, MAX_LOG_BYTES)).transpose() {
+        Ok(Some(file)) => {
+            state.path = path.map(Path::to_path_buf);
+            (Sink::File(file), Ok(()))
+        }
+        Ok(None) => (Sink::Stderr, Ok(())),
+        Err(e) => (Sink::Stderr, Err(e)),
+    };
+    state.level = Some(level);
+    state.sink = Some(sink);
+
+    let pending = std::mem::take(&mut state.pending);
+    for (msg_level, time, message) in pending {
+        if msg_level <= level {
+            state.write(msg_level, time, &message);
+        }
+    }
+    if result.is_err() {
+        state.sink = Some(Sink::Discard);
+    }
+    result
+}
+
+/// The log file in use, or `None` when logging to stderr.
+pub fn log_file_path() -> Option<PathBuf> {
+    STATE.lock().ok()?.path.clone()
+}
+
+/// Write a message at `level`. Use the `log_*!` macros instead.
+pub fn write(level: Level, args: fmt::Arguments) {
+    let mut state = STATE.lock().unwrap_or_else(|e| e.into_inner());
+    let time = Local::now();
+    match state.level {
+        Some(max) if level <= max => state.write(level, time, &args.to_string()),
+        Some(_) => {}
+        None if state.pending.len() < MAX_PENDING => {
+            state.pending.push((level, time, args.to_string()));
+        }
+        None => {}
+    }
+}
+
+impl LogState {
+    fn write(&mut self, level: Level, time: DateTime<Local>, message: &str) {
+        match &mut self.sink {
+            Some(Sink::File(file)) => file.write_line(&format_file_line(level, time, message)),
+            Some(Sink::Stderr) | None => {
+                eprintln!("cc-tail: {}: {}", level.label(), message);
+            }
+            Some(Sink::Discard) => {}
+        }
+    }
+}
+
+/// The log file, moved aside to `<path>.1` once it grows past `max_bytes`.
+struct LogFile {
+    file: File,
+    path: PathBuf,
+    /// Current size of the file.
+    len: u64,
+    max_bytes: u64,
+}
+
+impl LogFile {
+    /// Open `path` for appending, creating its directory, after rotating
+    /// it if it is already too large.
+    fn open(path: &Path, max_bytes: u64) -> io::Result<Self> {
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        match fs::metadata(path) {
+            Ok(meta) if meta.len() > max_bytes => rotate(path)?,
+            Ok(_) => {}
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            len: file.metadata()?.len(),
+            file,
+            path: path.to_path_buf(),
+            max_bytes,
+        })
+    }
+
+    fn write_line(&mut self, line: &str) {
+        if writeln!(self.file, "{}", line).is_ok() {
+            self.len += line.len() as u64 + 1;
+        }
+        if self.len > self.max_bytes {
+            // If rotating fails, keep the current file and try again once
+            // another `max_bytes` were written.
+            if let Ok(file) = rotate(&self.path).and_then(|()| {
+                OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&self.path)
+            }) {
+                self.file = file;
+            }
+            self.len = 0;
+        }
+    }
+}
+
+/// Rename the file at `path` to `<path>.1`.
+fn rotate(path: &Path) -> io::Result<()> {
+    let mut rotated = path.as_os_str().to_owned();
+    rotated.push(".1");
+    fs::rename(path, rotated)
+}
+
+/// One line of the log file: local timestamp, level and message.
+fn format_file_line(level: Level, time: DateTime<Local>, message: &str) -> String {
+    format!(
+        "{} {:<5} {}",
+        time.format("%Y-%m-%d %H:%M:%S%.3f"),
+        match level {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+        },
+        message
+    )
+}
+
+// ---------------------------------------------------------------------------
+// Macros
+// ---------------------------------------------------------------------------
+
+/// Log an error.
+#[macro_export]
+macro_rules! log_error {
+    ($($arg:tt)*) => {
+        $crate::logging::write($crate::logging::Level::Error, format_args!($($arg)*))
+    };
+}
+
+/// Log a warning.
+#[macro_export]
+macro_rules! log_warn {
+    ($($arg:tt)*) => {
+        $crate::logging::write($crate::logging::Level::Warn, format_args!($($arg)*))
+    };
+}
+
+/// Log an informational message.
+#[macro_export]
+macro_rules! log_info {
+    ($($arg:tt)*) => {
+        $crate::logging::write($crate::logging::Level::Info, format_args!($($arg)*))
+    };
+}
+
+/// Log a debug message (written with `--verbose`).
+#[macro_export]
+macro_rules! log_debug {
+    ($($arg:tt)*) => {
+        $crate::logging::write($crate::logging::Level::Debug, format_args!($($arg)*))
+    };
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_level_order() {
+        assert!(Level::Error < Level::Warn);
+        assert!(Level::Info < Level::Debug);
+        assert_eq!(Level::Warn.label(), "warning");
+    }
+
+    #[test]
+    fn test_format_file_line() {
+        let time = Local.with_ymd_and_hms(2025, 1, 15, 10, 30, 0).unwrap();
+        assert_eq!(
+            format_file_line(Level::Warn, time, "bad line"),
+            "2025-01-15 10:30:00.000 WARN  bad line"
+        );
+    }
+
+    #[test]
+    fn test_log_file_rotates_past_max_bytes() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("cctail.log");
+        let rotated = tmp.path().join("cctail.log.1");
+
+        // Over the cap when opened: moved aside first.
+        fs::write(&path, "0123456789").unwrap();
+        let mut log = LogFile::open(&path, 8).unwrap();
+        assert_eq!(fs::read_to_string(&rotated).unwrap(), "0123456789");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "");
+
+        // Growing past it while running: rotated after the write.
+        log.write_line("abcd");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "abcd\n");
+        log.write_line("efgh");
+        assert_eq!(fs::read_to_string(&rotated).unwrap(), "abcd\nefgh\n");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "");
+        log.write_line("ijkl");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "ijkl\n");
+    }
+
+    /// The only test touching the global logger: other tests in the
+    /// process log through it too.
+    #[test]
+    fn test_init_flushes_pending_and_filters_by_level() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("state").join("cctail.log");
+
+        log_warn!("before init {}", 1);
+        log_debug!("hidden debug");
+        init(Level::Info, Some(&path)).unwrap();
+        log_error!("after init");
+        log_debug!("also hidden");
+
+        assert_eq!(log_file_path(), Some(path.clone()));
+        let contents = fs::read_to_string(&path).unwrap();
+        assert!(contents.contains("WARN  before init 1"));
+        assert!(contents.contains("ERROR after init"));
+        assert!(!contents.contains("hidden"));
+    }
+}
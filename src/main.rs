@@ -1,5 +1,10 @@
 use cctail::cli::{Cli, Commands};
 use cctail::config::build_config;
+use cctail::export;
+use cctail::history_search;
+use cctail::log_debug;
+use cctail::logging::{self, Level};
+use cctail::session_list;
 use cctail::stream;
 use cctail::tui;
 use clap::Parser;
@@ -9,30 +14,60 @@ async fn main() {
     let cli = Cli::parse();
     let app_config = build_config(&cli);
 
-    if app_config.verbose {
-        eprintln!("cc-tail: effective config: {:?}", app_config);
+    // The TUI owns the terminal, so it logs to a file unless told otherwise.
+    let log_path = app_config
+        .log_file
+        .clone()
+        .or_else(|| cli.command.is_none().then(logging::default_log_path)?);
+    let level = if app_config.verbose {
+        Level::Debug
+    } else {
+        Level::Info
+    };
+    if let (Err(e), Some(path)) = (logging::init(level, log_path.as_deref()), &log_path) {
+        eprintln!(
+            "cc-tail: warning: could not open log file {}: {}",
+            path.display(),
+            e
+        );
     }
 
+    log_debug!("effective config: {:?}", app_config);
+
     match cli.command {
         Some(Commands::Stream(ref stream_args)) => {
-            if app_config.verbose {
-                eprintln!(
-                    "cc-tail: stream mode: file={}, replay={}, verbose={}, theme={:?}",
-                    stream_args.file.display(),
-                    stream_args.replay,
-                    stream_args.verbose,
-                    stream_args.theme
-                );
-            }
+            log_debug!(
+                "stream mode: file={}, replay={}, verbose={}, theme={:?}",
+                stream_args.file.display(),
+                stream_args.replay,
+                stream_args.verbose,
+                stream_args.theme
+            );
             if let Err(e) = stream::run_stream(stream_args).await {
                 eprintln!("cc-tail: stream error: {}", e);
                 std::process::exit(1);
             }
         }
-        None => {
-            if app_config.verbose {
-                eprintln!("cc-tail: TUI mode");
+        Some(Commands::Search(ref search_args)) => {
+            if let Err(e) = history_search::run_search(search_args, &app_config) {
+                eprintln!("cc-tail: search error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Some(Commands::Sessions(ref sessions_args)) => {
+            if let Err(e) = session_list::run_sessions(sessions_args, &app_config) {
+                eprintln!("cc-tail: sessions error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Some(Commands::Export(ref export_args)) => {
+            if let Err(e) = export::run_export(export_args, &app_config) {
+                eprintln!("cc-tail: export error: {}", e);
+                std::process::exit(1);
             }
+        }
+        None => {
+            log_debug!("TUI mode");
             if let Err(e) = tui::run_tui(app_config) {
                 eprintln!("cc-tail: TUI error: {}", e);
                 std::process::exit(1);
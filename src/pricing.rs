@@ -0,0 +1,194 @@
+//! Per-model token pricing used to estimate session cost.
+//!
+//! Prices are expressed in USD per million tokens. A [`PriceTable`] ships
+//! with built-in rates for the current Claude model families and can be
+//! extended or overridden from the `[pricing]` section of `config.toml`.
+//!
+//! Model names are matched exactly first, then by the longest configured
+//! prefix, so a single `claude-sonnet-4` entry covers dated variants such as
+//! `claude-sonnet-4-5-20250929`.
+
+use crate::log_entry::Usage;
+
+/// Cache writes (5-minute TTL) are billed at 1.25x the input rate.
+const CACHE_WRITE_MULTIPLIER: f64 = 1.25;
+
+/// Cache reads are billed at 0.1x the input rate.
+const CACHE_READ_MULTIPLIER: f64 = 0.1;
+
+// ---------------------------------------------------------------------------
+// ModelPrice
+// ---------------------------------------------------------------------------
+
+/// Rates for a single model, in USD per million tokens.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModelPrice {
+    pub input: f64,
+    pub output: f64,
+    pub cache_write: f64,
+    pub cache_read: f64,
+}
+
+impl ModelPrice {
+    /// Build a price from input/output rates, deriving the cache rates from
+    /// the standard multipliers.
+    pub fn from_io(input: f64, output: f64) -> Self {
+        Self {
+            input,
+            output,
+            cache_write: input * CACHE_WRITE_MULTIPLIER,
+            cache_read: input * CACHE_READ_MULTIPLIER,
+        }
+    }
+
+    /// Estimated cost in USD of the given usage at these rates.
+    pub fn cost(&self, usage: &Usage) -> f64 {
+        (usage.input_tokens as f64 * self.input
+            + usage.output_tokens as f64 * self.output
+            + usage.cache_creation_input_tokens as f64 * self.cache_write
+            + usage.cache_read_input_tokens as f64 * self.cache_read)
+            / 1_000_000.0
+    }
+}
+
+// ---------------------------------------------------------------------------
+// PriceTable
+// ---------------------------------------------------------------------------
+
+/// Lookup table from model name (or model-name prefix) to [`ModelPrice`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceTable {
+    entries: Vec<(String, ModelPrice)>,
+}
+
+impl Default for PriceTable {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl PriceTable {
+    /// An empty table: every lookup misses.
+    pub fn empty() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Built-in rates for the Claude model families used by Claude Code.
+    pub fn builtin() -> Self {
+        let mut table = Self::empty();
+        table.set("claude-opus-4", ModelPrice::from_io(15.0, 75.0));
+        table.set("claude-opus-4-5", ModelPrice::from_io(5.0, 25.0));
+        table.set("claude-opus-4-6", ModelPrice::from_io(5.0, 25.0));
+        table.set("claude-sonnet-4", ModelPrice::from_io(3.0, 15.0));
+        table.set("claude-3-7-sonnet", ModelPrice::from_io(3.0, 15.0));
+        table.set("claude-haiku-4-5", ModelPrice::from_io(1.0, 5.0));
+        table.set("claude-3-5-haiku", ModelPrice::from_io(0.8, 4.0));
+        table
+    }
+
+    /// Insert or replace the price for a model name or prefix.
+    pub fn set(&mut self, model: &str, price: ModelPrice) {
+        match self.entries.iter_mut().find(|(name, _)| name == model) {
+            Some((_, existing)) => *existing = price,
+            None => self.entries.push((model.to_string(), price)),
+        }
+    }
+
+    /// Find the price for a model: exact match first, otherwise the entry
+    /// with the longest name that is a prefix of `model`.
+    pub fn lookup(&self, model: &str) -> Option<&ModelPrice> {
+        if let Some((_, price)) = self.entries.iter().find(|(name, _)| name == model) {
+            return Some(price);
+        }
+        self.entries
+            .iter()
+            .filter(|(name, _)| model.starts_with(name.as_str()))
+            .max_by_key(|(name, _)| name.len())
+            .map(|(_, price)| price)
+    }
+
+    /// Estimated cost in USD of `usage` for `model`, or `None` if the model
+    /// has no known price.
+    pub fn estimate(&self, model: &str, usage: &Usage) -> Option<f64> {
+        self.lookup(model).map(|p| p.cost(usage))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(input: u64, output: u64, cache_write: u64, cache_read: u64) -> Usage {
+        Usage {
+            input_tokens: input,
+            output_tokens: output,
+            cache_creation_input_tokens: cache_write,
+            cache_read_input_tokens: cache_read,
+        }
+    }
+
+    #[test]
+    fn test_from_io_derives_cache_rates() {
+        let p = ModelPrice::from_io(3.0, 15.0);
+        assert!((p.cache_write - 3.75).abs() < 1e-9);
+        assert!((p.cache_read - 0.3).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_cost_per_million() {
+        let p = ModelPrice::from_io(3.0, 15.0);
+        let cost = p.cost(&usage(1_000_000, 1_000_000, 0, 0));
+        assert!((cost - 18.0).abs() < 1e-9);
+
+        let cost = p.cost(&usage(0, 0, 1_000_000, 1_000_000));
+        assert!((cost - 4.05).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_lookup_exact_match() {
+        let table = PriceTable::builtin();
+        let p = table.lookup("claude-opus-4-6").unwrap();
+        assert_eq!(p.input, 5.0);
+    }
+
+    #[test]
+    fn test_lookup_longest_prefix_wins() {
+        let table = PriceTable::builtin();
+        // "claude-opus-4-5" is longer than "claude-opus-4".
+        assert_eq!(table.lookup("claude-opus-4-5-20251101").unwrap().input, 5.0);
+        assert_eq!(
+            table.lookup("claude-opus-4-1-20250805").unwrap().input,
+            15.0
+        );
+        assert_eq!(
+            table.lookup("claude-sonnet-4-5-20250929").unwrap().input,
+            3.0
+        );
+    }
+
+    #[test]
+    fn test_lookup_unknown_model() {
+        let table = PriceTable::builtin();
+        assert!(table.lookup("<synthetic>").is_none());
+        assert!(table.estimate("gpt-4", &usage(1, 1, 1, 1)).is_none());
+    }
+
+    #[test]
+    fn test_set_overrides_existing() {
+        let mut table = PriceTable::builtin();
+        table.set("claude-sonnet-4", ModelPrice::from_io(1.0, 2.0));
+        assert_eq!(table.lookup("claude-sonnet-4-5").unwrap().input, 1.0);
+    }
+
+    #[test]
+    fn test_empty_table() {
+        let table = PriceTable::empty();
+        assert!(table.lookup("claude-opus-4-6").is_none());
+    }
+}
@@ -0,0 +1,436 @@
+//! Process-based session liveness detection (Linux).
+//!
+//! [`Session::status`](crate::session::Session::status) only looks at file
+//! modification times, which reports a session as inactive during a long
+//! thinking pause and as active for ten minutes after it was killed. On
+//! Linux, [`ProcessDetector`] scans `/proc` for running `claude` processes
+//! and matches them to sessions:
+//!
+//! 1. A process holding one of the session's log files open.
+//! 2. A process whose command line names the session (`--resume <id>`,
+//!    `--session-id <id>`).
+//! 3. A process whose working directory is the session's project; it is
+//!    credited to the most recently modified session of that project not
+//!    already matched.
+//!
+//! A matched process is [`ProcessState::Running`] while it is scheduled or
+//! has a log file open, and [`ProcessState::Idle`] otherwise. Sessions
+//! without a process are [`ProcessState::Dead`]. The detector takes the
+//! `/proc` root as a parameter so tests can point it at a fake tree.
+//!
+//! Reading `/proc` takes a while with many processes, so the TUI scans on
+//! a background thread ([`ProcessDetector::scan_in_background`]).
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::SystemTime;
+
+use crate::project_path::escape_path;
+use crate::session::Session;
+
+/// Where the kernel mounts process information on Linux.
+pub const DEFAULT_PROC_ROOT: &str = "/proc";
+
+/// Process name Claude Code sets for itself (`/proc/<pid>/comm`).
+const CLAUDE_COMM: &str = "claude";
+
+/// Path fragment identifying Claude Code run through `node`.
+const CLAUDE_SCRIPT: &str = "claude-code/cli";
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// Liveness of a session according to the process table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ProcessState {
+    /// No running process belongs to the session.
+    Dead,
+    /// A process is alive but neither scheduled nor writing the log
+    /// (e.g. waiting for the user or for the API).
+    Idle,
+    /// A process is scheduled or has the session's log open.
+    Running,
+}
+
+/// A running Claude Code process, as read from `/proc/<pid>/`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClaudeProcess {
+    pub pid: u32,
+    /// Working directory (`cwd` link).
+    pub cwd: Option<PathBuf>,
+    /// Command-line arguments (`cmdline`).
+    pub args: Vec<String>,
+    /// Targets of the open file descriptors (`fd/*` links).
+    pub open_files: Vec<PathBuf>,
+    /// Whether the process is running or in uninterruptible sleep
+    /// (state `R` or `D` in `stat`).
+    pub scheduled: bool,
+}
+
+/// Outcome of one scan.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessScan {
+    /// Process state of each session, keyed by session id.
+    pub states: HashMap<String, ProcessState>,
+    /// When the scan started. Logs written later may belong to a process
+    /// the scan did not see.
+    pub scanned_at: SystemTime,
+}
+
+/// Scans a `/proc`-like directory for Claude Code processes.
+#[derive(Debug, Clone)]
+pub struct ProcessDetector {
+    proc_root: PathBuf,
+}
+
+// ---------------------------------------------------------------------------
+// Scanning
+// ---------------------------------------------------------------------------
+
+impl ProcessDetector {
+    /// Create a detector reading process information from `proc_root`.
+    pub fn new(proc_root: impl Into<PathBuf>) -> Self {
+        Self {
+            proc_root: proc_root.into(),
+        }
+    }
+
+    /// The detector for the running system, or `None` where there is no
+    /// `/proc` (anything but Linux), leaving the mtime heuristic alone.
+    pub fn system() -> Option<Self> {
+        let root = Path::new(DEFAULT_PROC_ROOT);
+        (cfg!(target_os = "linux") && root.is_dir()).then(|| Self::new(root))
+    }
+
+    /// All Claude Code processes currently visible. Processes that exit
+    /// mid-scan or cannot be inspected are skipped.
+    pub fn scan(&self) -> Vec<ClaudeProcess> {
+        let Ok(dir) = fs::read_dir(&self.proc_root) else {
+            return Vec::new();
+        };
+        let mut processes: Vec<ClaudeProcess> = dir
+            .filter_map(Result::ok)
+            .filter_map(|e| e.file_name().to_str()?.parse::<u32>().ok())
+            .filter_map(|pid| self.read_process(pid))
+            .collect();
+        processes.sort_by_key(|p| p.pid);
+        processes
+    }
+
+    /// Process state of each of `sessions`, keyed by session id.
+    pub fn session_states(&self, sessions: &[Session]) -> HashMap<String, ProcessState> {
+        match_sessions(&self.scan(), sessions)
+    }
+
+    /// Run [`session_states`](Self::session_states) on a background
+    /// thread. The receiver yields the result once, then disconnects.
+    pub fn scan_in_background(&self, sessions: Vec<Session>) -> mpsc::Receiver<ProcessScan> {
+        let (tx, rx) = mpsc::channel();
+        let detector = self.clone();
+        std::thread::spawn(move || {
+            let scanned_at = SystemTime::now();
+            let states = detector.session_states(&sessions);
+            // The receiver is gone when the app quit mid-scan.
+            let _ = tx.send(ProcessScan { states, scanned_at });
+        });
+        rx
+    }
+
+    fn read_process(&self, pid: u32) -> Option<ClaudeProcess> {
+        let dir = self.proc_root.join(pid.to_string());
+        let comm = fs::read_to_string(dir.join("comm")).unwrap_or_default();
+        let args: Vec<String> = fs::read(dir.join("cmdline"))
+            .unwrap_or_default()
+            .split(|&b| b == 0)
+            .filter(|arg| !arg.is_empty())
+            .map(|arg| String::from_utf8_lossy(arg).into_owned())
+            .collect();
+        if !is_claude(comm.trim(), &args) {
+            return None;
+        }
+
+        let open_files = fs::read_dir(dir.join("fd"))
+            .map(|fds| {
+                fds.filter_map(Result::ok)
+                    .filter_map(|fd| fs::read_link(fd.path()).ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let scheduled = fs::read_to_string(dir.join("stat"))
+            .ok()
+            .and_then(|stat| process_state_char(&stat))
+            .is_some_and(|state| state == 'R' || state == 'D');
+
+        Some(ClaudeProcess {
+            pid,
+            cwd: fs::read_link(dir.join("cwd")).ok(),
+            args,
+            open_files,
+            scheduled,
+        })
+    }
+}
+
+/// Whether a process with name `comm` and arguments `args` is Claude Code,
+/// either the native binary or `node .../claude-code/cli.js`.
+fn is_claude(comm: &str, args: &[String]) -> bool {
+    let basename = |arg: &String| {
+        Path::new(arg)
+            .file_name()
+            .is_some_and(|name| name == CLAUDE_COMM)
+    };
+    comm == CLAUDE_COMM
+        || args.iter().take(2).any(basename)
+        || args.iter().any(|arg| arg.contains(CLAUDE_SCRIPT))
+}
+
+/// The state field of `/proc/<pid>/stat`, which follows the parenthesized
+/// command name (which may itself contain spaces and parentheses).
+fn process_state_char(stat: &str) -> Option<char> {
+    let rest = &stat[stat.rfind(')')? + 1..];
+    rest.trim_start().chars().next()
+}
+
+// ---------------------------------------------------------------------------
+// Matching
+// ---------------------------------------------------------------------------
+
+/// Match `processes` to `sessions` and return every session's state.
+pub fn match_sessions(
+    processes: &[ClaudeProcess],
+    sessions: &[Session],
+) -> HashMap<String, ProcessState> {
+    let mut states: HashMap<String, ProcessState> = sessions
+        .iter()
+        .map(|s| (s.id.clone(), ProcessState::Dead))
+        .collect();
+    // Canonical log paths, resolved once rather than per process.
+    let canonical: Vec<Vec<PathBuf>> = sessions
+        .iter()
+        .map(|s| {
+            s.agents
+                .iter()
+                .filter_map(|a| a.log_path.canonicalize().ok())
+                .collect()
+        })
+        .collect();
+    let holds_log_open = |process: &ClaudeProcess, i: usize| {
+        let session = &sessions[i];
+        process
+            .open_files
+            .iter()
+            .any(|f| session.agents.iter().any(|a| *f == a.log_path) || canonical[i].contains(f))
+    };
+    let mut claim = |session: &Session, state: ProcessState| {
+        let current = states.entry(session.id.clone()).or_insert(state);
+        *current = (*current).max(state);
+    };
+
+    // Direct matches: an open log file or the session id on the command line.
+    let mut unmatched = Vec::new();
+    for process in processes {
+        let holding = (0..sessions.len()).find(|&i| holds_log_open(process, i));
+        if let Some(i) = holding {
+            claim(&sessions[i], ProcessState::Running);
+        } else if let Some(session) = sessions.iter().find(|s| names_session(process, s)) {
+            claim(session, state_of(process));
+        } else {
+            unmatched.push(process);
+        }
+    }
+
+    // Working-directory matches take the newest session not matched yet.
+    let mut taken: Vec<&str> = (0..sessions.len())
+        .filter(|&i| {
+            processes
+                .iter()
+                .any(|p| holds_log_open(p, i) || names_session(p, &sessions[i]))
+        })
+        .map(|i| sessions[i].id.as_str())
+        .collect();
+    for process in unmatched {
+        let Some(project) = process.cwd.as_deref().map(escape_path) else {
+            continue;
+        };
+        let newest = sessions
+            .iter()
+            .filter(|s| !taken.contains(&s.id.as_str()))
+            .filter(|s| {
+                s.project_dir()
+                    .and_then(Path::file_name)
+                    .is_some_and(|name| name.to_string_lossy() == project)
+            })
+            .max_by_key(|s| s.last_modified);
+        if let Some(session) = newest {
+            taken.push(&session.id);
+            claim(session, state_of(process));
+        }
+    }
+    states
+}
+
+fn state_of(process: &ClaudeProcess) -> ProcessState {
+    if process.scheduled {
+        ProcessState::Running
+    } else {
+        ProcessState::Idle
+    }
+}
+
+fn names_session(process: &ClaudeProcess, session: &Session) -> bool {
+    process.args.iter().skip(1).any(|arg| {
+        arg == &session.id
+            || arg
+                .split_once('=')
+                .is_some_and(|(_, value)| value == session.id)
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::Agent;
+    use std::os::unix::fs::symlink;
+    use std::time::{Duration, SystemTime};
+    use tempfile::TempDir;
+
+    /// Write a fake `/proc/<pid>/` entry.
+    fn fake_process(
+        proc_root: &Path,
+        pid: u32,
+        comm: &str,
+        args: &[&str],
+        state: char,
+        cwd: &Path,
+        open_files: &[&Path],
+    ) {
+        let dir = proc_root.join(pid.to_string());
+        fs::create_dir_all(dir.join("fd")).unwrap();
+        fs::write(dir.join("comm"), format!("{}\n", comm)).unwrap();
+        fs::write(dir.join("cmdline"), args.join("\0") + "\0").unwrap();
+        fs::write(
+            dir.join("stat"),
+            format!("{} ({}) {} 1 1 1 0 -1", pid, comm, state),
+        )
+        .unwrap();
+        symlink(cwd, dir.join("cwd")).unwrap();
+        for (fd, target) in open_files.iter().enumerate() {
+            symlink(target, dir.join("fd").join(fd.to_string())).unwrap();
+        }
+    }
+
+    fn session(projects: &Path, project: &str, id: &str, age_secs: u64) -> Session {
+        Session {
+            id: id.to_string(),
+            agents: vec![Agent {
+                agent_id: None,
+                slug: None,
+                log_path: projects.join(project).join(format!("{}.jsonl", id)),
+                is_main: true,
+            }],
+            last_modified: SystemTime::now() - Duration::from_secs(age_secs),
+        }
+    }
+
+    #[test]
+    fn test_scan_finds_claude_processes() {
+        let tmp = TempDir::new().unwrap();
+        let proc_root = tmp.path().join("proc");
+        fake_process(&proc_root, 10, "claude", &["claude"], 'S', tmp.path(), &[]);
+        fake_process(
+            &proc_root,
+            11,
+            "node",
+            &[
+                "node",
+                "/usr/lib/node_modules/@anthropic-ai/claude-code/cli.js",
+            ],
+            'R',
+            tmp.path(),
+            &[],
+        );
+        fake_process(&proc_root, 12, "bash", &["bash"], 'S', tmp.path(), &[]);
+        fs::create_dir_all(proc_root.join("self")).unwrap();
+
+        let processes = ProcessDetector::new(&proc_root).scan();
+        let pids: Vec<u32> = processes.iter().map(|p| p.pid).collect();
+        assert_eq!(pids, [10, 11]);
+        assert!(!processes[0].scheduled);
+        assert!(processes[1].scheduled);
+        assert_eq!(processes[0].cwd.as_deref(), Some(tmp.path()));
+    }
+
+    #[test]
+    fn test_session_states() {
+        let tmp = TempDir::new().unwrap();
+        let projects = tmp.path().join("projects");
+        let proc_root = tmp.path().join("proc");
+        let sessions = vec![
+            session(&projects, "-srv-api", "open", 600),
+            session(&projects, "-srv-api", "resumed", 900),
+            session(&projects, "-srv-web", "newest", 5),
+            session(&projects, "-srv-web", "older", 60),
+            session(&projects, "-srv-db", "killed", 1),
+        ];
+
+        // Holds its log open: running even though the process sleeps.
+        let open_log = sessions[0].agents[0].log_path.clone();
+        fake_process(
+            &proc_root,
+            20,
+            "claude",
+            &["claude"],
+            'S',
+            Path::new("/srv/api"),
+            &[&open_log],
+        );
+        // Named on the command line.
+        fake_process(
+            &proc_root,
+            21,
+            "claude",
+            &["claude", "--resume", "resumed"],
+            'S',
+            Path::new("/srv/api"),
+            &[],
+        );
+        // Only the cwd matches: credited to the project's newest session.
+        fake_process(
+            &proc_root,
+            22,
+            "claude",
+            &["claude"],
+            'S',
+            Path::new("/srv/web"),
+            &[],
+        );
+
+        let states = ProcessDetector::new(&proc_root).session_states(&sessions);
+        assert_eq!(states["open"], ProcessState::Running);
+        assert_eq!(states["resumed"], ProcessState::Idle);
+        assert_eq!(states["newest"], ProcessState::Idle);
+        assert_eq!(states["older"], ProcessState::Dead);
+        assert_eq!(states["killed"], ProcessState::Dead);
+    }
+
+    #[test]
+    fn test_missing_proc_root_reports_no_processes() {
+        let tmp = TempDir::new().unwrap();
+        let detector = ProcessDetector::new(tmp.path().join("missing"));
+        assert!(detector.scan().is_empty());
+    }
+
+    #[test]
+    fn test_process_state_char() {
+        assert_eq!(process_state_char("42 (claude) S 1 42"), Some('S'));
+        assert_eq!(process_state_char("42 (a (b) c) R 1"), Some('R'));
+        assert_eq!(process_state_char("garbage"), None);
+    }
+}
@@ -11,8 +11,13 @@
 //! 3. Parent-directory walk (most specific ancestor wins)
 //! 4. Git repository root fallback
 //! 5. Error with all searched paths
+//!
+//! With `--all-projects` the whole `~/.claude/projects/` directory is used
+//! instead (see [`claude_projects_dir`]), and project directories are
+//! labelled with the path they stand for (see [`project_label`]).
 
 use std::fmt;
+use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
@@ -57,7 +62,7 @@ impl std::error::Error for ProjectDetectionError {}
 ///
 /// Replaces every `/`, `.`, ` ` (space), and `~` with a `-` (hyphen).
 /// The result is the directory name used under `~/.claude/projects/`.
-fn escape_path(path: &Path) -> String {
+pub(crate) fn escape_path(path: &Path) -> String {
     let s = path.to_string_lossy();
     // Normalize trailing slash before escaping
     let normalized = s.trim_end_matches('/');
@@ -242,6 +247,66 @@ pub fn detect_project_path(
     detect_project_path_with_base(&base, cwd, explicit_project, git_root)
 }
 
+/// Return `~/.claude/projects/`, the directory holding every project.
+///
+/// # Errors
+///
+/// Returns `NotFound` if the directory does not exist.
+pub fn claude_projects_dir() -> Result<PathBuf, ProjectDetectionError> {
+    let base = claude_projects_base()?;
+    if !base.is_dir() {
+        return Err(ProjectDetectionError::NotFound {
+            searched_paths: vec![base],
+        });
+    }
+    Ok(base)
+}
+
+// ---------------------------------------------------------------------------
+// Project labels
+// ---------------------------------------------------------------------------
+
+/// Number of lines of a session log scanned for a `cwd`.
+const CWD_SCAN_LINES: usize = 50;
+
+/// Best-effort inverse of [`escape_path`]: every `-` becomes `/`.
+///
+/// Escaping is lossy (`.`, ` ` and `~` also became `-`, and hyphens are
+/// kept), so prefer the `cwd` recorded in the project's logs when there is
+/// one (see [`project_label`]).
+pub fn decode_project_dir_name(name: &str) -> String {
+    name.replace('-', "/")
+}
+
+/// The first `cwd` recorded in the first lines of a session log.
+pub fn read_session_cwd(log_path: &Path) -> Option<String> {
+    let file = std::fs::File::open(log_path).ok()?;
+    BufReader::new(file)
+        .lines()
+        .take(CWD_SCAN_LINES)
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(&line).ok())
+        .find_map(|v| v.get("cwd")?.as_str().map(str::to_string))
+}
+
+/// Label for a project directory in the sidebar: the project's path, from
+/// `cwd` when known or decoded from the directory name otherwise, with the
+/// home directory shown as `~`.
+pub fn project_label(project_dir: &Path, cwd: Option<&str>) -> String {
+    let path = match cwd {
+        Some(cwd) => cwd.to_string(),
+        None => project_dir
+            .file_name()
+            .map(|n| decode_project_dir_name(&n.to_string_lossy()))
+            .unwrap_or_else(|| project_dir.display().to_string()),
+    };
+    let home = dirs::home_dir().map(|h| h.to_string_lossy().into_owned());
+    match home.as_deref().and_then(|h| path.strip_prefix(h)) {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => format!("~{}", rest),
+        _ => path,
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -560,4 +625,35 @@ mod tests {
 
         assert!(result.is_ok());
     }
+
+    // -- project labels ------------------------------------------------------
+
+    #[test]
+    fn test_decode_project_dir_name() {
+        assert_eq!(
+            decode_project_dir_name("-home-user-repo"),
+            "/home/user/repo"
+        );
+    }
+
+    #[test]
+    fn test_project_label_prefers_cwd() {
+        let dir = Path::new("/base/-srv-my-app");
+        assert_eq!(project_label(dir, Some("/srv/my-app")), "/srv/my-app");
+        // Without a cwd the lossy decoding is used.
+        assert_eq!(project_label(dir, None), "/srv/my/app");
+    }
+
+    #[test]
+    fn test_read_session_cwd() {
+        let tmp = TempDir::new().unwrap();
+        let log = tmp.path().join("s.jsonl");
+        fs::write(
+            &log,
+            "{\"type\":\"summary\"}\nnot json\n{\"type\":\"user\",\"cwd\":\"/srv/app\"}\n",
+        )
+        .unwrap();
+        assert_eq!(read_session_cwd(&log).as_deref(), Some("/srv/app"));
+        assert_eq!(read_session_cwd(&tmp.path().join("missing.jsonl")), None);
+    }
 }
@@ -14,6 +14,7 @@ use std::path::PathBuf;
 use crate::filter::FilterState;
 use crate::log_entry::{parse_jsonl_line, EntryType, LogEntry};
 use crate::session::Session;
+use crate::{log_debug, log_warn};
 
 // ---------------------------------------------------------------------------
 // Constants
@@ -44,8 +45,9 @@ pub const DEFAULT_REPLAY_COUNT: usize = 20;
 /// * `filter`           - The current filter state; entries that don't pass are
 ///   excluded from the visible set.
 /// * `max_visible`      - Maximum number of visible entries to return (default 20).
-/// * `verbose`          - If `true`, emit diagnostic messages to stderr for
-///   missing/unreadable files and parse errors.
+///
+/// Unreadable files are logged as warnings; missing files and malformed
+/// lines only at debug level.
 ///
 /// # Returns
 ///
@@ -58,7 +60,6 @@ pub fn replay_session(
     session: &Session,
     filter: &FilterState,
     max_visible: usize,
-    verbose: bool,
 ) -> (Vec<LogEntry>, HashMap<PathBuf, u64>) {
     let mut all_visible: Vec<LogEntry> = Vec::new();
     let mut eof_offsets: HashMap<PathBuf, u64> = HashMap::new();
@@ -69,10 +70,13 @@ pub fn replay_session(
         // Open the file; skip gracefully if missing or unreadable.
         let file = match std::fs::File::open(path) {
             Ok(f) => f,
+            // A log removed since discovery is not worth a warning.
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                log_debug!("replay: skipping {}: {}", path.display(), e);
+                continue;
+            }
             Err(e) => {
-                if verbose {
-                    eprintln!("cc-tail: replay: skipping {}: {}", path.display(), e);
-                }
+                log_warn!("replay: skipping {}: {}", path.display(), e);
                 continue;
             }
         };
@@ -81,9 +85,7 @@ pub fn replay_session(
         let file_len = match file.metadata() {
             Ok(m) => m.len(),
             Err(e) => {
-                if verbose {
-                    eprintln!("cc-tail: replay: could not stat {}: {}", path.display(), e);
-                }
+                log_warn!("replay: could not stat {}: {}", path.display(), e);
                 continue;
             }
         };
@@ -95,9 +97,7 @@ pub fn replay_session(
             let line = match line_result {
                 Ok(l) => l,
                 Err(e) => {
-                    if verbose {
-                        eprintln!("cc-tail: replay: read error in {}: {}", path.display(), e);
-                    }
+                    log_warn!("replay: read error in {}: {}", path.display(), e);
                     continue;
                 }
             };
@@ -111,13 +111,11 @@ pub fn replay_session(
             let entry = match parse_jsonl_line(trimmed) {
                 Ok(e) => e,
                 Err(e) => {
-                    if verbose {
-                        eprintln!(
-                            "cc-tail: replay: skipping malformed line in {}: {}",
-                            path.display(),
-                            e
-                        );
-                    }
+                    log_debug!(
+                        "replay: skipping malformed line in {}: {}",
+                        path.display(),
+                        e
+                    );
                     continue;
                 }
             };
@@ -172,9 +170,8 @@ pub fn session_file_size(session: &Session) -> u64 {
 pub fn load_full_session(
     session: &Session,
     filter: &FilterState,
-    verbose: bool,
 ) -> (Vec<LogEntry>, HashMap<PathBuf, u64>) {
-    replay_session(session, filter, usize::MAX, verbose)
+    replay_session(session, filter, usize::MAX)
 }
 
 // ---------------------------------------------------------------------------
@@ -300,7 +297,7 @@ mod tests {
         write_jsonl(&log_path, &line_refs);
 
         let session = make_session("s1", vec![log_path]);
-        let (entries, _offsets) = replay_session(&session, &default_filter(), 20, false);
+        let (entries, _offsets) = replay_session(&session, &default_filter(), 20);
 
         assert_eq!(entries.len(), 20);
         // Should be the last 20 (indices 5..25)
@@ -341,7 +338,7 @@ mod tests {
         );
 
         let session = make_session("s1", vec![main_path, sub_path]);
-        let (entries, _offsets) = replay_session(&session, &default_filter(), 20, false);
+        let (entries, _offsets) = replay_session(&session, &default_filter(), 20);
 
         assert_eq!(entries.len(), 4);
         // Verify sorted by timestamp
@@ -389,10 +386,11 @@ mod tests {
         let filter = FilterState {
             hide_tool_calls: false,
             selected_agent: Some("sub1".to_string()),
+            ..Default::default()
         };
 
         let session = make_session("s1", vec![log_path]);
-        let (entries, _offsets) = replay_session(&session, &filter, 20, false);
+        let (entries, _offsets) = replay_session(&session, &filter, 20);
 
         // Only the subagent entry should pass the agent filter
         assert_eq!(entries.len(), 1);
@@ -420,7 +418,7 @@ mod tests {
         );
 
         let session = make_session("s1", vec![log_path]);
-        let (entries, _offsets) = replay_session(&session, &default_filter(), 20, false);
+        let (entries, _offsets) = replay_session(&session, &default_filter(), 20);
 
         // Only 2 visible entries, should return all 2
         assert_eq!(entries.len(), 2);
@@ -437,7 +435,7 @@ mod tests {
         write_jsonl(&log_path, &[]);
 
         let session = make_session("s1", vec![log_path.clone()]);
-        let (entries, offsets) = replay_session(&session, &default_filter(), 20, false);
+        let (entries, offsets) = replay_session(&session, &default_filter(), 20);
 
         assert!(entries.is_empty());
         // EOF offset should still be recorded (0 for empty file)
@@ -453,7 +451,7 @@ mod tests {
         let log_path = PathBuf::from("/nonexistent/path/session.jsonl");
 
         let session = make_session("s1", vec![log_path.clone()]);
-        let (entries, offsets) = replay_session(&session, &default_filter(), 20, false);
+        let (entries, offsets) = replay_session(&session, &default_filter(), 20);
 
         assert!(entries.is_empty());
         // Missing file should not have an EOF offset
@@ -486,7 +484,7 @@ mod tests {
         );
 
         let session = make_session("s1", vec![log_path]);
-        let (entries, _offsets) = replay_session(&session, &default_filter(), 20, false);
+        let (entries, _offsets) = replay_session(&session, &default_filter(), 20);
 
         // Only User, Assistant, and System should be visible
         assert_eq!(entries.len(), 3);
@@ -514,7 +512,7 @@ mod tests {
         );
 
         let session = make_session("s1", vec![log_path]);
-        let (entries, _offsets) = replay_session(&session, &default_filter(), 20, false);
+        let (entries, _offsets) = replay_session(&session, &default_filter(), 20);
 
         assert_eq!(entries.len(), 3);
         // Entry without timestamp should sort first (empty string < any timestamp)
@@ -545,7 +543,7 @@ mod tests {
         let expected_len = std::fs::metadata(&log_path).unwrap().len();
 
         let session = make_session("s1", vec![log_path.clone()]);
-        let (_entries, offsets) = replay_session(&session, &default_filter(), 20, false);
+        let (_entries, offsets) = replay_session(&session, &default_filter(), 20);
 
         assert_eq!(offsets.get(&log_path), Some(&expected_len));
     }
@@ -573,7 +571,7 @@ mod tests {
         let sub_len = std::fs::metadata(&sub_path).unwrap().len();
 
         let session = make_session("s1", vec![main_path.clone(), sub_path.clone()]);
-        let (_entries, offsets) = replay_session(&session, &default_filter(), 20, false);
+        let (_entries, offsets) = replay_session(&session, &default_filter(), 20);
 
         assert_eq!(offsets.len(), 2);
         assert_eq!(offsets.get(&main_path), Some(&main_len));
@@ -600,7 +598,7 @@ mod tests {
         );
 
         let session = make_session("s1", vec![log_path]);
-        let (entries, _offsets) = replay_session(&session, &default_filter(), 20, false);
+        let (entries, _offsets) = replay_session(&session, &default_filter(), 20);
 
         assert_eq!(entries.len(), 2);
     }
@@ -617,7 +615,7 @@ mod tests {
         write_jsonl(&log_path, &[&user_line("2025-01-15T10:00:00Z", "msg-1")]);
 
         let session = make_session("s1", vec![log_path]);
-        let (entries, _offsets) = replay_session(&session, &default_filter(), 0, false);
+        let (entries, _offsets) = replay_session(&session, &default_filter(), 0);
 
         assert!(entries.is_empty());
     }
@@ -640,7 +638,7 @@ mod tests {
         );
 
         let session = make_session("s1", vec![log_path]);
-        let (entries, _offsets) = replay_session(&session, &default_filter(), 1, false);
+        let (entries, _offsets) = replay_session(&session, &default_filter(), 1);
 
         assert_eq!(entries.len(), 1);
         assert_eq!(
@@ -670,7 +668,7 @@ mod tests {
         let filter = FilterState::default();
 
         let session = make_session("s1", vec![log_path]);
-        let (entries, _offsets) = replay_session(&session, &filter, 20, false);
+        let (entries, _offsets) = replay_session(&session, &filter, 20);
 
         // Default filter (no agent filter) should show all visible entries
         assert_eq!(entries.len(), 3);
@@ -688,7 +686,7 @@ mod tests {
             last_modified: SystemTime::now(),
         };
 
-        let (entries, offsets) = replay_session(&session, &default_filter(), 20, false);
+        let (entries, offsets) = replay_session(&session, &default_filter(), 20);
 
         assert!(entries.is_empty());
         assert!(offsets.is_empty());
@@ -711,7 +709,7 @@ mod tests {
         writeln!(file, "{}", user_line("2025-01-15T10:01:00Z", "msg-2")).unwrap();
 
         let session = make_session("s1", vec![log_path]);
-        let (entries, _offsets) = replay_session(&session, &default_filter(), 20, false);
+        let (entries, _offsets) = replay_session(&session, &default_filter(), 20);
 
         assert_eq!(entries.len(), 2);
     }
@@ -740,17 +738,17 @@ mod tests {
     }
 
     // =====================================================================
-    // Test 18: Verbose mode emits diagnostics (no panic)
+    // Test 18: Missing files are skipped (no panic)
     // =====================================================================
 
     #[test]
-    fn test_verbose_mode_with_missing_file() {
-        let log_path = PathBuf::from("/nonexistent/verbose-test.jsonl");
+    fn test_missing_file_is_skipped() {
+        let log_path = PathBuf::from("/nonexistent/missing-test.jsonl");
         let session = make_session("s1", vec![log_path]);
 
-        // Should not panic; verbose=true just prints to stderr
-        let (entries, _offsets) = replay_session(&session, &default_filter(), 20, true);
+        let (entries, offsets) = replay_session(&session, &default_filter(), 20);
         assert!(entries.is_empty());
+        assert!(offsets.is_empty());
     }
 
     // =====================================================================
@@ -765,7 +763,7 @@ mod tests {
         write_jsonl(&log_path, &[&user_line("2025-01-15T10:00:00Z", "only-msg")]);
 
         let session = make_session("s1", vec![log_path]);
-        let (entries, _offsets) = replay_session(&session, &default_filter(), 1000, false);
+        let (entries, _offsets) = replay_session(&session, &default_filter(), 1000);
 
         assert_eq!(entries.len(), 1);
     }
@@ -847,7 +845,7 @@ mod tests {
         write_jsonl(&log_path, &line_refs);
 
         let session = make_session("s1", vec![log_path]);
-        let (entries, _offsets) = load_full_session(&session, &default_filter(), false);
+        let (entries, _offsets) = load_full_session(&session, &default_filter());
 
         // Should return ALL 30 entries, not just 20
         assert_eq!(entries.len(), 30);
@@ -1,4 +1,5 @@
 use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
 
 use crate::log_entry::LogEntry;
 
@@ -13,6 +14,14 @@ use crate::log_entry::LogEntry;
 /// evicted until there is room.
 pub const DEFAULT_BYTE_BUDGET: usize = 50 * 1024 * 1024;
 
+/// Source of [`RingBuffer::generation`] values, shared by all buffers so
+/// that a replaced buffer never repeats the generation of the old one.
+static NEXT_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+fn next_generation() -> u64 {
+    NEXT_GENERATION.fetch_add(1, Ordering::Relaxed)
+}
+
 // ---------------------------------------------------------------------------
 // Internal entry wrapper
 // ---------------------------------------------------------------------------
@@ -45,6 +54,7 @@ pub struct RingBuffer {
     entries: VecDeque<SizedEntry>,
     total_bytes: usize,
     byte_budget: usize,
+    generation: u64,
 }
 
 impl RingBuffer {
@@ -54,6 +64,7 @@ impl RingBuffer {
             entries: VecDeque::new(),
             total_bytes: 0,
             byte_budget: budget,
+            generation: next_generation(),
         }
     }
 
@@ -63,12 +74,15 @@ impl RingBuffer {
     }
 
     /// Push an entry into the buffer, evicting oldest entries as needed.
+    /// Returns the evicted entries, oldest first, so that indexes built
+    /// from the buffer can drop them.
     ///
     /// The entry's byte size is computed once via
     /// [`LogEntry::estimated_byte_size`] and cached for O(1) eviction
     /// accounting.
-    pub fn push(&mut self, entry: LogEntry) {
+    pub fn push(&mut self, entry: LogEntry) -> Vec<LogEntry> {
         let byte_size = entry.estimated_byte_size();
+        let mut evicted_entries = Vec::new();
 
         // Evict oldest entries while the new entry would exceed the budget.
         // If the single entry is larger than the budget, drain everything
@@ -77,6 +91,7 @@ impl RingBuffer {
             match self.entries.pop_front() {
                 Some(evicted) => {
                     self.total_bytes -= evicted.byte_size;
+                    evicted_entries.push(evicted.entry);
                 }
                 None => {
                     // Buffer is empty but entry still exceeds budget —
@@ -88,6 +103,8 @@ impl RingBuffer {
 
         self.total_bytes += byte_size;
         self.entries.push_back(SizedEntry { entry, byte_size });
+        self.generation = next_generation();
+        evicted_entries
     }
 
     /// Iterate over all entries in insertion order (oldest first).
@@ -124,10 +141,42 @@ impl RingBuffer {
         self.entries.is_empty()
     }
 
+    /// Iterate mutably over all entries in insertion order. Their byte
+    /// sizes stay as estimated when they were pushed.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut LogEntry> {
+        self.generation = next_generation();
+        self.entries.iter_mut().map(|se| &mut se.entry)
+    }
+
+    /// Keep only the entries for which `keep` returns `true`.
+    pub fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&LogEntry) -> bool,
+    {
+        let mut removed = 0;
+        self.entries.retain(|se| {
+            let kept = keep(&se.entry);
+            if !kept {
+                removed += se.byte_size;
+            }
+            kept
+        });
+        self.total_bytes -= removed;
+        self.generation = next_generation();
+    }
+
     /// Remove all entries and reset the byte counter to zero.
     pub fn clear(&mut self) {
         self.entries.clear();
         self.total_bytes = 0;
+        self.generation = next_generation();
+    }
+
+    /// Identifies the current contents: changes on every push, retain,
+    /// mutable iteration and clear, so data derived from the buffer can be
+    /// cached until then.
+    pub fn generation(&self) -> u64 {
+        self.generation
     }
 
     /// The configured byte budget for this buffer.
@@ -237,9 +286,11 @@ mod tests {
         buf.push(e2);
         assert_eq!(buf.len(), 2);
 
-        // Pushing a third should evict the first.
-        buf.push(e3);
+        // Pushing a third should evict the first, and return it.
+        let evicted = buf.push(e3);
         assert_eq!(buf.len(), 2);
+        assert_eq!(evicted.len(), 1);
+        assert_eq!(evicted[0].session_id.as_deref(), Some("first"));
 
         let ids: Vec<_> = buf
             .iter()
@@ -528,4 +579,48 @@ mod tests {
             .collect();
         assert_eq!(ids, vec!["two", "three", "four"]);
     }
+
+    // -- 14. Retain updates byte accounting -------------------------------
+
+    #[test]
+    fn test_retain_removes_entries_and_bytes() {
+        let mut buf = RingBuffer::new(1_000_000);
+        buf.push(make_entry_with_type("user", "keep"));
+        buf.push(make_entry_with_type("user", "drop"));
+        buf.push(make_entry_with_type("assistant", "keep"));
+        let kept_size = make_entry_with_type("user", "keep").estimated_byte_size()
+            + make_entry_with_type("assistant", "keep").estimated_byte_size();
+
+        buf.retain(|e| e.session_id.as_deref() != Some("drop"));
+
+        assert_eq!(buf.len(), 2);
+        assert_eq!(buf.byte_size(), kept_size);
+        assert!(buf.iter().all(|e| e.session_id.as_deref() == Some("keep")));
+    }
+
+    // -- 15. Generation changes with the contents ---------------------------
+
+    #[test]
+    fn test_generation_changes_on_every_mutation() {
+        let mut buf = RingBuffer::new(1_000_000);
+        let other = RingBuffer::new(1_000_000);
+        assert_ne!(buf.generation(), other.generation());
+
+        let mut seen = vec![buf.generation()];
+        buf.push(make_entry_with_type("user", "a"));
+        seen.push(buf.generation());
+        buf.retain(|_| true);
+        seen.push(buf.generation());
+        buf.iter_mut()
+            .for_each(|e| e.session_id = Some("b".to_string()));
+        seen.push(buf.generation());
+        buf.clear();
+        seen.push(buf.generation());
+
+        seen.dedup();
+        assert_eq!(seen.len(), 5);
+        let unchanged = buf.generation();
+        let _ = buf.iter().count();
+        assert_eq!(buf.generation(), unchanged);
+    }
 }
@@ -5,8 +5,11 @@
 //! - **Input**: User is typing a search query. `Enter` confirms, `Esc` cancels.
 //! - **Active**: Matches are highlighted. `n`/`N` navigate. `Esc` clears.
 //!
-//! Matching is case-insensitive plain text substring (no regex).
-//! Only operates on visible (filtered) entries.
+//! Matching follows the current [`MatchMode`] (plain case-insensitive
+//! substring, smart-case substring or regex), switched with `Tab` while
+//! typing. Only operates on visible (filtered) entries.
+
+use regex::Regex;
 
 // ---------------------------------------------------------------------------
 // Search mode enum
@@ -24,6 +27,89 @@ pub enum SearchMode {
     Active,
 }
 
+// ---------------------------------------------------------------------------
+// Match mode
+// ---------------------------------------------------------------------------
+
+/// How the search query is matched against rendered text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MatchMode {
+    /// Case-insensitive substring.
+    #[default]
+    Plain,
+    /// Substring; case-sensitive only if the query contains an uppercase letter.
+    SmartCase,
+    /// Regular expression (case-sensitive; use `(?i)` to ignore case).
+    Regex,
+}
+
+impl MatchMode {
+    /// The next mode in the `Tab` cycle: plain -> smart -> regex -> plain.
+    pub fn next(self) -> Self {
+        match self {
+            MatchMode::Plain => MatchMode::SmartCase,
+            MatchMode::SmartCase => MatchMode::Regex,
+            MatchMode::Regex => MatchMode::Plain,
+        }
+    }
+
+    /// Short label shown in the input and status bars.
+    pub fn label(self) -> &'static str {
+        match self {
+            MatchMode::Plain => "plain",
+            MatchMode::SmartCase => "smart",
+            MatchMode::Regex => "regex",
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Matcher
+// ---------------------------------------------------------------------------
+
+/// A query compiled for its [`MatchMode`].
+#[derive(Debug, Clone)]
+pub enum Matcher {
+    /// Case-insensitive substring (see [`find_matches()`]).
+    CaseInsensitive(String),
+    /// Exact substring.
+    CaseSensitive(String),
+    /// Compiled regular expression.
+    Regex(Regex),
+}
+
+impl Matcher {
+    /// Compile `query` for `mode`. Only regex mode can fail.
+    pub fn new(query: &str, mode: MatchMode) -> Result<Self, regex::Error> {
+        Ok(match mode {
+            MatchMode::Plain => Matcher::CaseInsensitive(query.to_string()),
+            MatchMode::SmartCase if query.chars().any(char::is_uppercase) => {
+                Matcher::CaseSensitive(query.to_string())
+            }
+            MatchMode::SmartCase => Matcher::CaseInsensitive(query.to_string()),
+            MatchMode::Regex => Matcher::Regex(Regex::new(query)?),
+        })
+    }
+
+    /// Find all non-overlapping matches as `(byte_start, byte_len)` tuples
+    /// into `text`. Empty regex matches are skipped (nothing to highlight).
+    pub fn find_all(&self, text: &str) -> Vec<(usize, usize)> {
+        match self {
+            Matcher::CaseInsensitive(query) => find_matches(text, query),
+            Matcher::CaseSensitive(query) if query.is_empty() => Vec::new(),
+            Matcher::CaseSensitive(query) => text
+                .match_indices(query.as_str())
+                .map(|(start, m)| (start, m.len()))
+                .collect(),
+            Matcher::Regex(re) => re
+                .find_iter(text)
+                .filter(|m| !m.is_empty())
+                .map(|m| (m.start(), m.len()))
+                .collect(),
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Search match
 // ---------------------------------------------------------------------------
@@ -60,6 +146,12 @@ pub struct SearchState {
     pub matches: Vec<SearchMatch>,
     /// Index of the currently highlighted match (for `n`/`N` navigation).
     pub current_match_index: Option<usize>,
+    /// How the query is matched. Kept across searches.
+    pub match_mode: MatchMode,
+    /// Compile error for the input buffer in regex mode (Input mode only).
+    pub error: Option<String>,
+    /// Last compiled matcher with the query and mode it was built for.
+    compiled: Option<(String, MatchMode, Result<Matcher, regex::Error>)>,
 }
 
 impl Default for SearchState {
@@ -70,6 +162,9 @@ impl Default for SearchState {
             query: String::new(),
             matches: Vec::new(),
             current_match_index: None,
+            match_mode: MatchMode::Plain,
+            error: None,
+            compiled: None,
         }
     }
 }
@@ -82,12 +177,14 @@ impl SearchState {
     pub fn start_input(&mut self) {
         self.mode = SearchMode::Input;
         self.input_buffer.clear();
+        self.error = None;
     }
 
     /// Append a character to the input buffer (Input mode only).
     pub fn on_char(&mut self, ch: char) {
         if self.mode == SearchMode::Input {
             self.input_buffer.push(ch);
+            self.validate_input();
         }
     }
 
@@ -95,7 +192,42 @@ impl SearchState {
     pub fn on_backspace(&mut self) {
         if self.mode == SearchMode::Input {
             self.input_buffer.pop();
+            self.validate_input();
+        }
+    }
+
+    /// Switch to the next [`MatchMode`] (Tab in Input mode).
+    pub fn cycle_match_mode(&mut self) {
+        if self.mode == SearchMode::Input {
+            self.match_mode = self.match_mode.next();
+            self.validate_input();
+        }
+    }
+
+    /// The query compiled for the current match mode.
+    ///
+    /// The result is cached and only recompiled after the query or the
+    /// match mode changes, so it is cheap to call on every frame.
+    pub fn matcher(&mut self) -> Result<&Matcher, &regex::Error> {
+        let stale = self
+            .compiled
+            .as_ref()
+            .is_none_or(|(query, mode, _)| *query != self.query || *mode != self.match_mode);
+        if stale {
+            self.compiled = None;
         }
+        let (_, _, matcher) = self.compiled.get_or_insert_with(|| {
+            let matcher = Matcher::new(&self.query, self.match_mode);
+            (self.query.clone(), self.match_mode, matcher)
+        });
+        matcher.as_ref()
+    }
+
+    /// Re-check the input buffer so invalid regexes are flagged while typing.
+    fn validate_input(&mut self) {
+        self.error = Matcher::new(&self.input_buffer, self.match_mode)
+            .err()
+            .map(|e| regex_error_summary(&e));
     }
 
     /// Confirm the search query (Enter in Input mode).
@@ -109,6 +241,18 @@ impl SearchState {
             return;
         }
 
+        // An invalid regex keeps the input open with the error shown.
+        let candidate = if self.input_buffer.is_empty() {
+            &self.query
+        } else {
+            &self.input_buffer
+        };
+        if let Err(e) = Matcher::new(candidate, self.match_mode) {
+            self.error = Some(regex_error_summary(&e));
+            return;
+        }
+        self.error = None;
+
         if !self.input_buffer.is_empty() {
             self.query = self.input_buffer.clone();
         }
@@ -135,6 +279,7 @@ impl SearchState {
             SearchMode::Input => {
                 self.mode = SearchMode::Inactive;
                 self.input_buffer.clear();
+                self.error = None;
             }
             SearchMode::Active => {
                 self.mode = SearchMode::Inactive;
@@ -221,6 +366,18 @@ impl SearchState {
     }
 }
 
+/// Reduce a regex error to its last line (e.g. `"error: unclosed group"`),
+/// which fits in the input bar.
+fn regex_error_summary(err: &regex::Error) -> String {
+    let text = err.to_string();
+    text.lines()
+        .rev()
+        .find(|l| !l.trim().is_empty())
+        .unwrap_or(&text)
+        .trim()
+        .to_string()
+}
+
 // ---------------------------------------------------------------------------
 // Matching engine
 // ---------------------------------------------------------------------------
@@ -387,6 +544,105 @@ mod tests {
         assert_eq!(matches, vec![(0, 2), (2, 2)]);
     }
 
+    // -- Matcher tests ----------------------------------------------------------
+
+    #[test]
+    fn test_matcher_plain_is_case_insensitive() {
+        let m = Matcher::new("Hello", MatchMode::Plain).unwrap();
+        assert_eq!(m.find_all("hello HELLO"), vec![(0, 5), (6, 5)]);
+    }
+
+    #[test]
+    fn test_matcher_smart_case() {
+        let lower = Matcher::new("error", MatchMode::SmartCase).unwrap();
+        assert_eq!(lower.find_all("Error error"), vec![(0, 5), (6, 5)]);
+        let upper = Matcher::new("Error", MatchMode::SmartCase).unwrap();
+        assert_eq!(upper.find_all("Error error"), vec![(0, 5)]);
+    }
+
+    #[test]
+    fn test_matcher_regex() {
+        let m = Matcher::new(r"error\[E\d+\]", MatchMode::Regex).unwrap();
+        assert_eq!(
+            m.find_all("error[E0308]: mismatched; error[E12]"),
+            vec![(0, 12), (26, 10)]
+        );
+        let word = Matcher::new(r"\bcat\b", MatchMode::Regex).unwrap();
+        assert_eq!(word.find_all("concat cat cats"), vec![(7, 3)]);
+    }
+
+    #[test]
+    fn test_matcher_regex_skips_empty_matches() {
+        let m = Matcher::new("x*", MatchMode::Regex).unwrap();
+        assert_eq!(m.find_all("abxxc"), vec![(2, 2)]);
+    }
+
+    #[test]
+    fn test_matcher_regex_offsets_are_byte_offsets() {
+        let m = Matcher::new("b+", MatchMode::Regex).unwrap();
+        // "é" is two bytes.
+        assert_eq!(m.find_all("ébb"), vec![(2, 2)]);
+    }
+
+    #[test]
+    fn test_matcher_invalid_regex() {
+        assert!(Matcher::new("(", MatchMode::Regex).is_err());
+        assert!(Matcher::new("(", MatchMode::Plain).is_ok());
+    }
+
+    #[test]
+    fn test_match_mode_cycle() {
+        assert_eq!(MatchMode::Plain.next(), MatchMode::SmartCase);
+        assert_eq!(MatchMode::SmartCase.next(), MatchMode::Regex);
+        assert_eq!(MatchMode::Regex.next(), MatchMode::Plain);
+    }
+
+    #[test]
+    fn test_invalid_regex_sets_error_and_blocks_confirm() {
+        let mut state = SearchState::default();
+        state.start_input();
+        state.cycle_match_mode();
+        state.cycle_match_mode();
+        assert_eq!(state.match_mode, MatchMode::Regex);
+        state.on_char('(');
+        assert!(state.error.is_some());
+
+        state.confirm();
+        assert_eq!(state.mode, SearchMode::Input);
+        assert!(state.query.is_empty());
+
+        state.on_char(')');
+        assert!(state.error.is_none());
+        state.confirm();
+        assert_eq!(state.mode, SearchMode::Active);
+        assert_eq!(state.query, "()");
+    }
+
+    #[test]
+    fn test_cycle_match_mode_revalidates_input() {
+        let mut state = SearchState::default();
+        state.start_input();
+        state.on_char('[');
+        assert!(state.error.is_none());
+        state.cycle_match_mode(); // smart
+        state.cycle_match_mode(); // regex
+        assert!(state.error.is_some());
+        state.cycle_match_mode(); // plain
+        assert!(state.error.is_none());
+    }
+
+    #[test]
+    fn test_match_mode_persists_across_searches() {
+        let mut state = SearchState::default();
+        state.start_input();
+        state.cycle_match_mode();
+        state.on_char('x');
+        state.confirm();
+        state.cancel();
+        state.start_input();
+        assert_eq!(state.match_mode, MatchMode::SmartCase);
+    }
+
     // -- SearchState state machine tests ------------------------------------
 
     #[test]
@@ -490,6 +746,23 @@ mod tests {
         assert_eq!(state.mode, SearchMode::Inactive);
     }
 
+    #[test]
+    fn test_matcher_recompiled_only_on_change() {
+        let mut state = SearchState::default();
+        state.query = "foo".to_string();
+        assert_eq!(state.matcher().unwrap().find_all("a foo"), vec![(2, 3)]);
+        assert!(state.compiled.is_some());
+
+        state.query = "bar".to_string();
+        assert!(state.matcher().unwrap().find_all("a foo").is_empty());
+
+        state.query = "(".to_string();
+        state.match_mode = MatchMode::Regex;
+        assert!(state.matcher().is_err());
+        state.match_mode = MatchMode::Plain;
+        assert_eq!(state.matcher().unwrap().find_all("(x"), vec![(0, 1)]);
+    }
+
     #[test]
     fn test_cancel_from_input_goes_inactive() {
         let mut state = SearchState::default();
@@ -0,0 +1,812 @@
+//! Persistent on-disk term index for history search.
+//!
+//! Scanning every session file for each query gets slow on projects with
+//! hundreds of sessions. The index maps lowercased alphanumeric terms to
+//! the byte offsets of the lines (entries) that contain them, per session
+//! file. [`history_search`](crate::history_search) uses it to narrow the
+//! lines it has to read, then verifies each candidate with the real
+//! matcher, so results are identical to a linear scan.
+//!
+//! Terms are taken from the same redacted text the search matches on, so
+//! the index never stores a secret. Query terms are looked up in a sorted
+//! table of term suffixes, which finds every term containing them without
+//! scanning the vocabulary.
+//!
+//! The index is optional: it lives in `<cache dir>/cc-tail/<project>.json`
+//! (`~/.cache/cc-tail/` on Linux, readable only by the owner) and is only
+//! used once it exists (`cctail search --index` creates it). Each file is
+//! indexed incrementally from the last complete line, using the same
+//! [`FileWatchState`] offsets as the watcher. A file is re-indexed from
+//! scratch when it shrinks (truncation) or when its mtime changes without
+//! it growing (rewritten in place). While the TUI runs, [`LiveIndex`]
+//! indexes new entries as the watcher reports them.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::history_search::searchable_text;
+use crate::log_entry::parse_jsonl_line;
+use crate::replay::is_visible_type;
+use crate::search::Matcher;
+use crate::session::Session;
+use crate::watcher::{read_new_lines, FileWatchState};
+
+/// Bumped whenever the on-disk format or tokenization changes; an index
+/// with a different version is ignored and rebuilt.
+const INDEX_VERSION: u32 = 2;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// Term index for all session files of one project.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SearchIndex {
+    version: u32,
+    files: HashMap<PathBuf, FileIndex>,
+    /// Lookup table over the terms of all files, built on first query and
+    /// reset whenever the index changes.
+    #[serde(skip)]
+    suffixes: OnceLock<SuffixTable>,
+}
+
+/// Every suffix of every indexed term, in sorted order, so the terms that
+/// contain a query term are found by binary search for it as a prefix.
+#[derive(Debug, Clone, Default)]
+struct SuffixTable {
+    /// Distinct terms of all indexed files.
+    terms: Vec<String>,
+    /// `(term index, byte offset)` of each suffix, ordered by suffix text.
+    suffixes: Vec<(u32, u32)>,
+}
+
+/// Index of a single JSONL file.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct FileIndex {
+    session_id: String,
+    /// Offset just past the last complete line that has been indexed.
+    indexed_offset: u64,
+    /// File size when last indexed.
+    file_len: u64,
+    /// File mtime (seconds, nanoseconds since the epoch) when last indexed.
+    mtime: Option<(u64, u32)>,
+    /// Term -> byte offsets of the lines containing it, ascending.
+    terms: HashMap<String, Vec<u64>>,
+}
+
+// ---------------------------------------------------------------------------
+// Location & persistence
+// ---------------------------------------------------------------------------
+
+/// Path of the index file for a project log directory
+/// (`~/.cache/cc-tail/<project dir name>.json` on Linux).
+pub fn index_path(project_dir: &Path) -> Option<PathBuf> {
+    let name = project_dir.file_name()?.to_string_lossy();
+    dirs::cache_dir().map(|d| d.join("cc-tail").join(format!("{}.json", name)))
+}
+
+/// Load the project's index, bring it up to date with `sessions` and save
+/// it back if it changed.
+///
+/// Returns `Ok(None)` when no index exists and `create` is `false`.
+pub fn open_updated(
+    project_dir: &Path,
+    sessions: &[Session],
+    create: bool,
+) -> io::Result<Option<SearchIndex>> {
+    let path = match index_path(project_dir) {
+        Some(p) => p,
+        None => return Ok(None),
+    };
+    let mut index = match SearchIndex::load(&path)? {
+        Some(index) => index,
+        None if create => SearchIndex::new(),
+        None => return Ok(None),
+    };
+    if index.update(sessions) || create {
+        index.save(&path)?;
+    }
+    Ok(Some(index))
+}
+
+impl SearchIndex {
+    /// Create an empty index.
+    pub fn new() -> Self {
+        Self {
+            version: INDEX_VERSION,
+            files: HashMap::new(),
+            suffixes: OnceLock::new(),
+        }
+    }
+
+    /// Load the index from `path`.
+    ///
+    /// Returns `Ok(None)` if there is no index file. An unreadable or
+    /// outdated index is treated as empty so it gets rebuilt.
+    pub fn load(path: &Path) -> io::Result<Option<Self>> {
+        let contents = match std::fs::read_to_string(path) {
+            Ok(c) => c,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        match serde_json::from_str::<SearchIndex>(&contents) {
+            Ok(index) if index.version == INDEX_VERSION => Ok(Some(index)),
+            _ => Ok(Some(Self::new())),
+        }
+    }
+
+    /// Write the index to `path`, creating parent directories.
+    ///
+    /// Writes to a temporary file first so a concurrent reader never sees
+    /// a partial index. On Unix the file is only readable by the owner,
+    /// since it holds the vocabulary of every session.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string(self).map_err(io::Error::other)?;
+        let tmp = path.with_extension("json.tmp");
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut file = options.open(&tmp)?;
+        file.write_all(json.as_bytes())?;
+        drop(file);
+        std::fs::rename(&tmp, path)
+    }
+
+    // -- Updating ------------------------------------------------------------
+
+    /// Bring the index up to date with every agent log of `sessions`.
+    ///
+    /// Files that are no longer listed are dropped. Files that cannot be
+    /// read are dropped too, so searches fall back to scanning them.
+    /// Returns `true` if anything changed.
+    pub fn update(&mut self, sessions: &[Session]) -> bool {
+        let mut changed = self.refresh(sessions);
+
+        let listed: HashSet<&Path> = sessions
+            .iter()
+            .flat_map(|s| &s.agents)
+            .map(|a| a.log_path.as_path())
+            .collect();
+        let before = self.files.len();
+        self.files.retain(|path, _| listed.contains(path.as_path()));
+        if self.files.len() != before {
+            self.suffixes = OnceLock::new();
+            changed = true;
+        }
+        changed
+    }
+
+    /// Index the lines appended to the agent logs of `sessions`, leaving
+    /// other files untouched. Unreadable files are dropped.
+    ///
+    /// Returns `true` if anything changed.
+    pub fn refresh<'a>(&mut self, sessions: impl IntoIterator<Item = &'a Session>) -> bool {
+        let mut changed = false;
+        for session in sessions {
+            for agent in &session.agents {
+                match self.update_file(&agent.log_path, &session.id) {
+                    Ok(file_changed) => changed |= file_changed,
+                    Err(_) => changed |= self.files.remove(&agent.log_path).is_some(),
+                }
+            }
+        }
+        if changed {
+            self.suffixes = OnceLock::new();
+        }
+        changed
+    }
+
+    /// Index any lines appended to `path` since the last update.
+    ///
+    /// Returns `true` if the file's entry changed.
+    fn update_file(&mut self, path: &Path, session_id: &str) -> io::Result<bool> {
+        let meta = std::fs::metadata(path)?;
+        let len = meta.len();
+        let mtime = meta.modified().ok().and_then(mtime_key);
+
+        let record = self.files.entry(path.to_path_buf()).or_default();
+        if record.file_len == len && record.mtime == mtime && record.session_id == session_id {
+            return Ok(false);
+        }
+
+        // Shrunk, or rewritten without growing: start over.
+        if len < record.file_len || (len == record.file_len && record.mtime != mtime) {
+            *record = FileIndex::default();
+        }
+        record.session_id = session_id.to_string();
+
+        let mut state = FileWatchState::new_with_offset(record.indexed_offset);
+        loop {
+            let lines = read_new_lines(path, &mut state);
+            if lines.is_empty() {
+                break;
+            }
+            for (offset, line) in lines {
+                record.add_line(offset, &line);
+            }
+        }
+        record.indexed_offset = state.complete_offset();
+        record.file_len = len;
+        record.mtime = mtime;
+        Ok(true)
+    }
+
+    // -- Querying --------------------------------------------------------------
+
+    /// Offsets of the lines in `path` that may match `matcher`.
+    ///
+    /// Returns `None` when the index cannot answer: the file is not indexed
+    /// or has changed since, the matcher is a regex, or the query has no
+    /// alphanumeric terms. Callers should scan the file instead.
+    pub fn candidates(&self, path: &Path, matcher: &Matcher) -> Option<Vec<u64>> {
+        let query = match matcher {
+            Matcher::CaseInsensitive(q) | Matcher::CaseSensitive(q) => q,
+            Matcher::Regex(_) => return None,
+        };
+        let query_terms: Vec<String> = terms(query).collect();
+        if query_terms.is_empty() {
+            return None;
+        }
+
+        let record = self.files.get(path)?;
+        let meta = std::fs::metadata(path).ok()?;
+        if meta.len() != record.file_len || meta.modified().ok().and_then(mtime_key) != record.mtime
+        {
+            return None;
+        }
+
+        // A query term may be part of a longer word in the text (substring
+        // search), so it selects every indexed term that contains it.
+        let table = self
+            .suffixes
+            .get_or_init(|| SuffixTable::build(self.files.values().flat_map(|f| f.terms.keys())));
+        let mut result: Option<BTreeSet<u64>> = None;
+        for query_term in &query_terms {
+            let offsets: BTreeSet<u64> = table
+                .containing(query_term)
+                .filter_map(|term| record.terms.get(term))
+                .flat_map(|offsets| offsets.iter().copied())
+                .collect();
+            result = Some(match result {
+                None => offsets,
+                Some(prev) => prev.intersection(&offsets).copied().collect(),
+            });
+        }
+        Some(result.unwrap_or_default().into_iter().collect())
+    }
+
+    /// Whether `path` has an entry in the index.
+    pub fn contains_file(&self, path: &Path) -> bool {
+        self.files.contains_key(path)
+    }
+}
+
+impl FileIndex {
+    /// Record the terms of one JSONL line starting at `offset`.
+    fn add_line(&mut self, offset: u64, line: &str) {
+        let entry = match parse_jsonl_line(line.trim()) {
+            Ok(e) => e,
+            Err(_) => return,
+        };
+        if !is_visible_type(&entry) {
+            return;
+        }
+
+        let text = searchable_text(&entry);
+        let unique: HashSet<String> = terms(&text).collect();
+        for term in unique {
+            self.terms.entry(term).or_default().push(offset);
+        }
+    }
+}
+
+impl SuffixTable {
+    /// Build the table for a set of terms; duplicates are ignored.
+    fn build<'a>(terms: impl Iterator<Item = &'a String>) -> Self {
+        let terms: Vec<String> = terms
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .cloned()
+            .collect();
+        let mut suffixes: Vec<(u32, u32)> = terms
+            .iter()
+            .enumerate()
+            .flat_map(|(i, term)| {
+                term.char_indices()
+                    .map(move |(at, _)| (i as u32, at as u32))
+            })
+            .collect();
+        suffixes.sort_unstable_by(|&a, &b| suffix(&terms, a).cmp(suffix(&terms, b)));
+        Self { terms, suffixes }
+    }
+
+    /// The distinct terms containing `needle`.
+    fn containing<'a>(&'a self, needle: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        let first = self
+            .suffixes
+            .partition_point(|&s| suffix(&self.terms, s) < needle);
+        let matching: BTreeSet<u32> = self.suffixes[first..]
+            .iter()
+            .take_while(|&&s| suffix(&self.terms, s).starts_with(needle))
+            .map(|&(term, _)| term)
+            .collect();
+        matching
+            .into_iter()
+            .map(|term| self.terms[term as usize].as_str())
+    }
+}
+
+/// The suffix of a term identified by `(term index, byte offset)`.
+fn suffix(terms: &[String], (term, at): (u32, u32)) -> &str {
+    &terms[term as usize][at as usize..]
+}
+
+// ---------------------------------------------------------------------------
+// Live updates
+// ---------------------------------------------------------------------------
+
+/// Keeps the indexes of the projects shown in the TUI up to date while it
+/// runs, so global search does not have to catch up on everything that
+/// was appended since the last `cctail search`.
+///
+/// Only projects that already have an index file are updated. Loaded
+/// indexes stay in memory between updates.
+#[derive(Debug, Default)]
+pub struct LiveIndex {
+    /// Loaded index and its file, per project directory.
+    indexes: HashMap<PathBuf, (PathBuf, SearchIndex)>,
+    /// Sessions with entries that are not indexed yet.
+    stale: HashSet<String>,
+}
+
+impl LiveIndex {
+    /// Note that `session_id` has new entries to index.
+    pub fn mark_stale(&mut self, session_id: &str) {
+        if !self.stale.contains(session_id) {
+            self.stale.insert(session_id.to_string());
+        }
+    }
+
+    /// Whether any session has entries that are not indexed yet.
+    pub fn has_stale(&self) -> bool {
+        !self.stale.is_empty()
+    }
+
+    /// Index the new lines of every stale session in `sessions`, and save
+    /// the indexes that changed.
+    pub fn flush(&mut self, sessions: &[Session]) -> io::Result<()> {
+        let stale = std::mem::take(&mut self.stale);
+        let mut by_project: HashMap<&Path, Vec<&Session>> = HashMap::new();
+        for session in sessions.iter().filter(|s| stale.contains(&s.id)) {
+            if let Some(dir) = session.project_dir() {
+                by_project.entry(dir).or_default().push(session);
+            }
+        }
+
+        for (project_dir, sessions) in by_project {
+            if let Some((path, index)) = self.load(project_dir)? {
+                if index.refresh(sessions) {
+                    index.save(path)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The index of `project_dir`, brought up to date with all of its
+    /// `sessions`. Returns `Ok(None)` if the project has no index.
+    pub fn updated(
+        &mut self,
+        project_dir: &Path,
+        sessions: &[Session],
+    ) -> io::Result<Option<&SearchIndex>> {
+        let Some((path, index)) = self.load(project_dir)? else {
+            return Ok(None);
+        };
+        if index.update(sessions) {
+            index.save(path)?;
+        }
+        Ok(Some(index))
+    }
+
+    /// The loaded index of `project_dir`, reading it from disk if needed.
+    fn load(&mut self, project_dir: &Path) -> io::Result<Option<&mut (PathBuf, SearchIndex)>> {
+        if !self.indexes.contains_key(project_dir) {
+            let Some(path) = index_path(project_dir) else {
+                return Ok(None);
+            };
+            let Some(index) = SearchIndex::load(&path)? else {
+                return Ok(None);
+            };
+            self.indexes
+                .insert(project_dir.to_path_buf(), (path, index));
+        }
+        Ok(self.indexes.get_mut(project_dir))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// Split `text` into lowercased runs of alphanumeric characters.
+fn terms(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(str::to_lowercase)
+}
+
+fn mtime_key(time: SystemTime) -> Option<(u64, u32)> {
+    let d = time.duration_since(UNIX_EPOCH).ok()?;
+    Some((d.as_secs(), d.subsec_nanos()))
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::MatchMode;
+    use crate::test_support::{session, user_line};
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    fn plain(query: &str) -> Matcher {
+        Matcher::new(query, MatchMode::Plain).unwrap()
+    }
+
+    /// Set a file's mtime so tests do not depend on timestamp granularity.
+    fn set_mtime(path: &Path, secs: u64) {
+        let file = std::fs::File::options().write(true).open(path).unwrap();
+        file.set_modified(UNIX_EPOCH + std::time::Duration::from_secs(secs))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_terms_split_and_lowercase() {
+        let t: Vec<String> = terms("Run `cargo test` on src/main.rs!").collect();
+        assert_eq!(t, vec!["run", "cargo", "test", "on", "src", "main", "rs"]);
+    }
+
+    #[test]
+    fn test_candidates_intersect_terms_and_match_substrings() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("s1.jsonl");
+        let first = user_line("u1", "2025-01-15T10:00:00Z", "apply migrations/0042");
+        let second = user_line("u1", "2025-01-15T10:00:00Z", "migrate the database");
+        std::fs::write(&path, format!("{}\n{}\n", first, second)).unwrap();
+
+        let mut index = SearchIndex::new();
+        assert!(index.update(&[session("s1", vec![(None, path.clone())])]));
+
+        let second_offset = first.len() as u64 + 1;
+        assert_eq!(
+            index.candidates(&path, &plain("migrat")),
+            Some(vec![0, second_offset])
+        );
+        assert_eq!(
+            index.candidates(&path, &plain("Migrations/0042")),
+            Some(vec![0])
+        );
+        assert_eq!(
+            index.candidates(&path, &plain("database apply")),
+            Some(vec![])
+        );
+    }
+
+    #[test]
+    fn test_candidates_match_inside_words() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("s1.jsonl");
+        let first = user_line("u1", "2025-01-15T10:00:00Z", "run pytest now");
+        let second = user_line("u1", "2025-01-15T10:00:00Z", "test suite");
+        std::fs::write(&path, format!("{}\n{}\n", first, second)).unwrap();
+
+        let mut index = SearchIndex::new();
+        index.update(&[session("s1", vec![(None, path.clone())])]);
+
+        let second_offset = first.len() as u64 + 1;
+        assert_eq!(
+            index.candidates(&path, &plain("test")),
+            Some(vec![0, second_offset])
+        );
+        assert_eq!(index.candidates(&path, &plain("ytes")), Some(vec![0]));
+        assert_eq!(index.candidates(&path, &plain("tests")), Some(vec![]));
+    }
+
+    #[test]
+    fn test_secrets_are_not_indexed() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("s1.jsonl");
+        std::fs::write(
+            &path,
+            format!(
+                "{}\n",
+                user_line("u1", "2025-01-15T10:00:00Z", "export API_KEY=hunter2secret")
+            ),
+        )
+        .unwrap();
+
+        let mut index = SearchIndex::new();
+        index.update(&[session("s1", vec![(None, path.clone())])]);
+
+        let record = &index.files[&path];
+        assert!(record.terms.contains_key("redacted"));
+        assert!(!record.terms.keys().any(|t| t.contains("hunter2")));
+    }
+
+    #[test]
+    fn test_candidates_none_for_regex_or_symbol_query() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("s1.jsonl");
+        std::fs::write(
+            &path,
+            format!("{}\n", user_line("u1", "2025-01-15T10:00:00Z", "hello")),
+        )
+        .unwrap();
+        let mut index = SearchIndex::new();
+        index.update(&[session("s1", vec![(None, path.clone())])]);
+
+        let regex = Matcher::new("hel+o", MatchMode::Regex).unwrap();
+        assert_eq!(index.candidates(&path, &regex), None);
+        assert_eq!(index.candidates(&path, &plain("--")), None);
+    }
+
+    #[test]
+    fn test_hidden_entry_types_not_indexed() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("s1.jsonl");
+        std::fs::write(
+            &path,
+            "{\"type\":\"progress\",\"data\":{\"text\":\"hidden\"}}\n",
+        )
+        .unwrap();
+        let mut index = SearchIndex::new();
+        index.update(&[session("s1", vec![(None, path.clone())])]);
+        assert_eq!(index.candidates(&path, &plain("hidden")), Some(vec![]));
+    }
+
+    #[test]
+    fn test_update_appends_incrementally() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("s1.jsonl");
+        let first = user_line("u1", "2025-01-15T10:00:00Z", "alpha");
+        std::fs::write(&path, format!("{}\n", first)).unwrap();
+        set_mtime(&path, 1_000);
+
+        let mut index = SearchIndex::new();
+        let sessions = [session("s1", vec![(None, path.clone())])];
+        index.update(&sessions);
+        assert!(!index.update(&sessions), "unchanged file is not re-read");
+
+        // Append a line (with a trailing partial line that must be skipped).
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+        write!(
+            file,
+            "{}\n{{\"type\":\"us",
+            user_line("u1", "2025-01-15T10:00:00Z", "beta")
+        )
+        .unwrap();
+        drop(file);
+        set_mtime(&path, 2_000);
+
+        assert!(index.update(&sessions));
+        let offset = first.len() as u64 + 1;
+        assert_eq!(index.candidates(&path, &plain("beta")), Some(vec![offset]));
+        assert_eq!(index.candidates(&path, &plain("alpha")), Some(vec![0]));
+        assert_eq!(
+            index.files[&path].indexed_offset,
+            offset + user_line("u1", "2025-01-15T10:00:00Z", "beta").len() as u64 + 1
+        );
+    }
+
+    #[test]
+    fn test_truncated_file_is_reindexed() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("s1.jsonl");
+        std::fs::write(
+            &path,
+            format!(
+                "{}\n{}\n",
+                user_line("u1", "2025-01-15T10:00:00Z", "alpha"),
+                user_line("u1", "2025-01-15T10:00:00Z", "beta")
+            ),
+        )
+        .unwrap();
+        let mut index = SearchIndex::new();
+        let sessions = [session("s1", vec![(None, path.clone())])];
+        index.update(&sessions);
+
+        std::fs::write(
+            &path,
+            format!("{}\n", user_line("u1", "2025-01-15T10:00:00Z", "gamma")),
+        )
+        .unwrap();
+        index.update(&sessions);
+        assert_eq!(index.candidates(&path, &plain("alpha")), Some(vec![]));
+        assert_eq!(index.candidates(&path, &plain("gamma")), Some(vec![0]));
+    }
+
+    #[test]
+    fn test_rewritten_file_with_same_size_is_reindexed() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("s1.jsonl");
+        std::fs::write(
+            &path,
+            format!("{}\n", user_line("u1", "2025-01-15T10:00:00Z", "alpha")),
+        )
+        .unwrap();
+        set_mtime(&path, 1_000);
+        let mut index = SearchIndex::new();
+        let sessions = [session("s1", vec![(None, path.clone())])];
+        index.update(&sessions);
+
+        std::fs::write(
+            &path,
+            format!("{}\n", user_line("u1", "2025-01-15T10:00:00Z", "omega")),
+        )
+        .unwrap();
+        set_mtime(&path, 2_000);
+        assert!(index.update(&sessions));
+        assert_eq!(index.candidates(&path, &plain("alpha")), Some(vec![]));
+        assert_eq!(index.candidates(&path, &plain("omega")), Some(vec![0]));
+    }
+
+    #[test]
+    fn test_stale_index_is_not_used() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("s1.jsonl");
+        std::fs::write(
+            &path,
+            format!("{}\n", user_line("u1", "2025-01-15T10:00:00Z", "alpha")),
+        )
+        .unwrap();
+        let mut index = SearchIndex::new();
+        index.update(&[session("s1", vec![(None, path.clone())])]);
+
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+        writeln!(
+            file,
+            "{}",
+            user_line("u1", "2025-01-15T10:00:00Z", "alpha again")
+        )
+        .unwrap();
+        assert_eq!(index.candidates(&path, &plain("alpha")), None);
+    }
+
+    #[test]
+    fn test_update_drops_unlisted_and_missing_files() {
+        let tmp = TempDir::new().unwrap();
+        let a = tmp.path().join("a.jsonl");
+        let b = tmp.path().join("b.jsonl");
+        std::fs::write(
+            &a,
+            format!("{}\n", user_line("u1", "2025-01-15T10:00:00Z", "x")),
+        )
+        .unwrap();
+        std::fs::write(
+            &b,
+            format!("{}\n", user_line("u1", "2025-01-15T10:00:00Z", "y")),
+        )
+        .unwrap();
+        let mut index = SearchIndex::new();
+        index.update(&[
+            session("a", vec![(None, a.clone())]),
+            session("b", vec![(None, b.clone())]),
+        ]);
+
+        std::fs::remove_file(&b).unwrap();
+        assert!(index.update(&[
+            session("a", vec![(None, a.clone())]),
+            session("b", vec![(None, b.clone())])
+        ]));
+        assert!(!index.contains_file(&b));
+        assert!(index.update(&[]));
+        assert!(!index.contains_file(&a));
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let tmp = TempDir::new().unwrap();
+        let log = tmp.path().join("s1.jsonl");
+        std::fs::write(
+            &log,
+            format!("{}\n", user_line("u1", "2025-01-15T10:00:00Z", "alpha")),
+        )
+        .unwrap();
+        let mut index = SearchIndex::new();
+        index.update(&[session("s1", vec![(None, log.clone())])]);
+
+        let index_file = tmp.path().join("cache").join("project.json");
+        assert!(SearchIndex::load(&index_file).unwrap().is_none());
+        index.save(&index_file).unwrap();
+
+        let loaded = SearchIndex::load(&index_file).unwrap().unwrap();
+        assert_eq!(loaded.candidates(&log, &plain("alpha")), Some(vec![0]));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_save_creates_owner_only_file() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let tmp = TempDir::new().unwrap();
+        let index_file = tmp.path().join("project.json");
+        SearchIndex::new().save(&index_file).unwrap();
+
+        let mode = std::fs::metadata(&index_file).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[test]
+    fn test_live_index_flushes_stale_sessions() {
+        let tmp = TempDir::new().unwrap();
+        let project = tmp.path().join("project");
+        std::fs::create_dir_all(&project).unwrap();
+        let log = project.join("s1.jsonl");
+        std::fs::write(
+            &log,
+            format!("{}\n", user_line("u1", "2025-01-15T10:00:00Z", "alpha")),
+        )
+        .unwrap();
+        let sessions = [session("s1", vec![(None, log.clone())])];
+
+        let mut index = SearchIndex::new();
+        index.update(&sessions);
+        let index_file = tmp.path().join("project.json");
+        let mut live = LiveIndex::default();
+        live.indexes
+            .insert(project.clone(), (index_file.clone(), index));
+
+        let mut file = std::fs::OpenOptions::new().append(true).open(&log).unwrap();
+        writeln!(file, "{}", user_line("u1", "2025-01-15T10:00:00Z", "beta")).unwrap();
+        drop(file);
+
+        live.flush(&sessions).unwrap();
+        assert!(!index_file.exists(), "nothing is stale yet");
+
+        live.mark_stale("s1");
+        assert!(live.has_stale());
+        live.flush(&sessions).unwrap();
+        assert!(!live.has_stale());
+
+        let saved = SearchIndex::load(&index_file).unwrap().unwrap();
+        let offset = user_line("u1", "2025-01-15T10:00:00Z", "alpha").len() as u64 + 1;
+        assert_eq!(saved.candidates(&log, &plain("beta")), Some(vec![offset]));
+    }
+
+    #[test]
+    fn test_load_outdated_version_is_empty() {
+        let tmp = TempDir::new().unwrap();
+        let index_file = tmp.path().join("project.json");
+        std::fs::write(&index_file, r#"{"version":0,"files":{}}"#).unwrap();
+        let loaded = SearchIndex::load(&index_file).unwrap().unwrap();
+        assert_eq!(loaded.version, INDEX_VERSION);
+        assert!(loaded.files.is_empty());
+    }
+
+    #[test]
+    fn test_index_path_uses_project_dir_name() {
+        let path = index_path(Path::new("/home/u/.claude/projects/-home-u-app"));
+        if let Some(path) = path {
+            assert!(path.ends_with("cc-tail/-home-u-app.json"));
+        }
+    }
+}
@@ -6,12 +6,17 @@
 //!
 //! This module discovers sessions from the filesystem, tracks subagent
 //! relationships, determines active/inactive status, and supports
-//! auto-attach (most recent) and `--session` prefix matching.
+//! auto-attach (most recent) and `--session` prefix matching. With
+//! `--all-projects` the same layout is found one level deeper, under each
+//! project directory of `~/.claude/projects/`.
 
 use std::fmt;
 use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+use crate::log_warn;
+use crate::process_detect::ProcessState;
+
 // ---------------------------------------------------------------------------
 // Constants
 // ---------------------------------------------------------------------------
@@ -52,6 +57,9 @@ pub struct Session {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SessionStatus {
     Active,
+    /// Its process is alive but the log has not changed recently. Only
+    /// reported with process detection (see [`Session::status_with_process`]).
+    Idle,
     Inactive,
 }
 
@@ -149,6 +157,37 @@ impl Session {
             _ => SessionStatus::Inactive,
         }
     }
+
+    /// Combine the mtime heuristic with the session's process state, if
+    /// process detection is available.
+    ///
+    /// A running process makes the session active; a dead one makes it
+    /// inactive however recently the log changed, unless the log changed
+    /// after the scan at `scanned_at` (the process may have started since).
+    /// An idle process keeps a quiet session `Idle` instead of `Inactive`.
+    pub fn status_with_process(
+        &self,
+        process: Option<ProcessState>,
+        scanned_at: SystemTime,
+    ) -> SessionStatus {
+        match (process, self.status()) {
+            (None, status) => status,
+            (Some(ProcessState::Running), _) => SessionStatus::Active,
+            (Some(ProcessState::Idle), SessionStatus::Active) => SessionStatus::Active,
+            (Some(ProcessState::Idle), _) => SessionStatus::Idle,
+            (Some(ProcessState::Dead), status) if self.last_modified > scanned_at => status,
+            (Some(ProcessState::Dead), _) => SessionStatus::Inactive,
+        }
+    }
+
+    /// The project directory holding this session's logs (the parent of
+    /// the main agent's file).
+    pub fn project_dir(&self) -> Option<&Path> {
+        self.agents
+            .iter()
+            .find(|a| a.is_main)
+            .and_then(|a| a.log_path.parent())
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -181,11 +220,7 @@ pub fn discover_sessions(
         let entry = match entry_result {
             Ok(e) => e,
             Err(e) => {
-                eprintln!(
-                    "cc-tail: warning: skipping entry in {}: {}",
-                    project_dir.display(),
-                    e
-                );
+                log_warn!("skipping entry in {}: {}", project_dir.display(), e);
                 continue;
             }
         };
@@ -227,8 +262,8 @@ pub fn discover_sessions(
                     let sub_entry = match sub_entry_result {
                         Ok(e) => e,
                         Err(e) => {
-                            eprintln!(
-                                "cc-tail: warning: skipping subagent entry in {}: {}",
+                            log_warn!(
+                                "skipping subagent entry in {}: {}",
                                 subagents_dir.display(),
                                 e
                             );
@@ -280,7 +315,7 @@ pub fn discover_sessions(
     }
 
     // Sort by last_modified descending (most recent first)
-    sessions.sort_by(|a, b| b.last_modified.cmp(&a.last_modified));
+    sessions.sort_by_key(|s| std::cmp::Reverse(s.last_modified));
 
     // Limit to max_sessions
     sessions.truncate(max_sessions);
@@ -288,6 +323,39 @@ pub fn discover_sessions(
     Ok(sessions)
 }
 
+/// Discover sessions across every project directory in `projects_dir`
+/// (`~/.claude/projects/`).
+///
+/// Returns at most `max_sessions` sessions over all projects, sorted by
+/// `last_modified` descending. Project directories that cannot be read
+/// are skipped with a warning.
+///
+/// # Errors
+///
+/// Returns `SessionDiscoveryError::Io` if `projects_dir` cannot be read.
+pub fn discover_all_sessions(
+    projects_dir: &Path,
+    max_sessions: usize,
+) -> Result<Vec<Session>, SessionDiscoveryError> {
+    let mut sessions: Vec<Session> = Vec::new();
+
+    for entry in std::fs::read_dir(projects_dir)?.flatten() {
+        let project_dir = entry.path();
+        if !project_dir.is_dir() {
+            continue;
+        }
+        match discover_sessions(&project_dir, max_sessions) {
+            Ok(found) => sessions.extend(found),
+            Err(e) => log_warn!("skipping project {}: {}", project_dir.display(), e),
+        }
+    }
+
+    sessions.sort_by_key(|s| std::cmp::Reverse(s.last_modified));
+    sessions.truncate(max_sessions);
+
+    Ok(sessions)
+}
+
 // ---------------------------------------------------------------------------
 // Session resolution
 // ---------------------------------------------------------------------------
@@ -414,6 +482,26 @@ pub fn classify_new_file(path: &Path, project_dir: &Path) -> NewFileKind {
     }
 }
 
+/// Classify a newly observed file path relative to `projects_dir`, the
+/// root holding one directory per project.
+///
+/// Returns the project directory the file belongs to, and the file's
+/// classification relative to it (see [`classify_new_file`]). Returns
+/// `None` if `path` is not inside a project directory.
+pub fn classify_new_file_in_projects(
+    path: &Path,
+    projects_dir: &Path,
+) -> Option<(PathBuf, NewFileKind)> {
+    let relative = path.strip_prefix(projects_dir).ok()?;
+    let mut components = relative.components();
+    let project = components.next()?;
+    // The project directory itself is not a file inside a project.
+    components.next()?;
+    let project_dir = projects_dir.join(project);
+    let kind = classify_new_file(path, &project_dir);
+    Some((project_dir, kind))
+}
+
 // ---------------------------------------------------------------------------
 // Helpers
 // ---------------------------------------------------------------------------
@@ -814,6 +902,52 @@ mod tests {
         assert_eq!(session.status(), SessionStatus::Inactive);
     }
 
+    // -- 20b. Process state overrides mtime ---------------------------------
+
+    #[test]
+    fn test_status_with_process() {
+        let recent =
+            create_session_with_mtime("recent", SystemTime::now() - Duration::from_secs(5));
+        let quiet =
+            create_session_with_mtime("quiet", SystemTime::now() - Duration::from_secs(3600));
+        let scanned = SystemTime::now();
+
+        // Without detection the mtime heuristic decides.
+        assert_eq!(
+            recent.status_with_process(None, scanned),
+            SessionStatus::Active
+        );
+        assert_eq!(
+            quiet.status_with_process(None, scanned),
+            SessionStatus::Inactive
+        );
+
+        // A dead process overrides a write before the scan...
+        assert_eq!(
+            recent.status_with_process(Some(ProcessState::Dead), scanned),
+            SessionStatus::Inactive
+        );
+        // ...but not one after it.
+        let earlier_scan = SystemTime::now() - Duration::from_secs(60);
+        assert_eq!(
+            recent.status_with_process(Some(ProcessState::Dead), earlier_scan),
+            SessionStatus::Active
+        );
+        // A live process keeps a quiet session from looking inactive.
+        assert_eq!(
+            quiet.status_with_process(Some(ProcessState::Idle), scanned),
+            SessionStatus::Idle
+        );
+        assert_eq!(
+            recent.status_with_process(Some(ProcessState::Idle), scanned),
+            SessionStatus::Active
+        );
+        assert_eq!(
+            quiet.status_with_process(Some(ProcessState::Running), scanned),
+            SessionStatus::Active
+        );
+    }
+
     // ========================================================================
     // Edge case tests
     // ========================================================================
@@ -1060,4 +1194,70 @@ mod tests {
         let result = classify_new_file(&path, project_dir);
         assert_eq!(result, NewFileKind::Unknown);
     }
+
+    // -- 35. Files one level below a project in the projects root ----------------
+
+    #[test]
+    fn test_classify_in_projects_adds_project_level() {
+        let root = Path::new("/home/u/.claude/projects");
+        let project = root.join("-home-u-repo");
+
+        assert_eq!(
+            classify_new_file_in_projects(&project.join("sess-001.jsonl"), root),
+            Some((
+                project.clone(),
+                NewFileKind::TopLevelSession {
+                    session_id: "sess-001".to_string()
+                }
+            ))
+        );
+        assert_eq!(
+            classify_new_file_in_projects(
+                &project.join("sess-001/subagents/agent-abc.jsonl"),
+                root
+            ),
+            Some((
+                project.clone(),
+                NewFileKind::Subagent {
+                    session_id: "sess-001".to_string(),
+                    agent_id: "abc".to_string()
+                }
+            ))
+        );
+        // A file directly in the root belongs to no project.
+        assert_eq!(
+            classify_new_file_in_projects(&root.join("stray.jsonl"), root),
+            None
+        );
+        assert_eq!(
+            classify_new_file_in_projects(Path::new("/elsewhere/s.jsonl"), root),
+            None
+        );
+    }
+
+    // -- 36. Discovery across all projects ------------------------------------
+
+    #[test]
+    fn test_discover_all_sessions() {
+        let tmp = setup_project_dir();
+        create_jsonl_file(&tmp.path().join("-repo-a"), "sess-a.jsonl");
+        create_jsonl_file(&tmp.path().join("-repo-b"), "sess-b.jsonl");
+        create_jsonl_file(
+            &tmp.path().join("-repo-b"),
+            "sess-b/subagents/agent-x.jsonl",
+        );
+        // Stray files in the root are not sessions.
+        create_jsonl_file(tmp.path(), "stray.jsonl");
+
+        let sessions = discover_all_sessions(tmp.path(), 10).unwrap();
+        let mut ids: Vec<&str> = sessions.iter().map(|s| s.id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["sess-a", "sess-b"]);
+
+        let b = sessions.iter().find(|s| s.id == "sess-b").unwrap();
+        assert_eq!(b.agents.len(), 2);
+        assert_eq!(b.project_dir(), Some(tmp.path().join("-repo-b").as_path()));
+
+        assert_eq!(discover_all_sessions(tmp.path(), 1).unwrap().len(), 1);
+    }
 }
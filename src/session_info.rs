@@ -0,0 +1,370 @@
+//! Environment a session runs in: working directory, git branch and
+//! Claude Code version.
+//!
+//! Claude Code writes `cwd`, `gitBranch` and `version` on every line, so
+//! they can change partway through a session (a `cd`, a branch switch, an
+//! update between resumes). [`SessionInfo`] keeps the current values plus
+//! a history of every change, fed from the session's main log on load and
+//! from live entries afterwards. [`SessionInfoCache`] remembers how far
+//! each log was read, so loading a session again only reads what was
+//! appended since. Shown in the TUI session info panel (`i`).
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::log_entry::LogEntry;
+use crate::session::Session;
+
+// ---------------------------------------------------------------------------
+// Constants
+// ---------------------------------------------------------------------------
+
+/// Most bytes of a log read on its first load. A larger log is read from
+/// this far before its end, so earlier changes are not listed.
+const MAX_SCAN_BYTES: u64 = 8 * 1024 * 1024;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// Which value changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InfoField {
+    Cwd,
+    GitBranch,
+    Version,
+}
+
+impl InfoField {
+    /// Short label used in the info panel.
+    pub fn label(&self) -> &'static str {
+        match self {
+            InfoField::Cwd => "cwd",
+            InfoField::GitBranch => "branch",
+            InfoField::Version => "version",
+        }
+    }
+}
+
+/// A value that differs from the one seen on an earlier entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InfoChange {
+    /// ISO 8601 timestamp of the first entry with the new value.
+    pub timestamp: Option<String>,
+    pub field: InfoField,
+    pub from: String,
+    pub to: String,
+}
+
+/// Current environment of a session and how it changed over time.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SessionInfo {
+    /// Working directory from the most recent entry.
+    pub cwd: Option<String>,
+    /// Git branch from the most recent entry (empty outside a repository).
+    pub git_branch: Option<String>,
+    /// Claude Code version from the most recent entry.
+    pub version: Option<String>,
+    /// Every change after the first value of each field, oldest first.
+    pub changes: Vec<InfoChange>,
+}
+
+/// The subset of a JSONL line [`SessionInfoCache::load`] needs. Deserializing
+/// only these keys keeps the scan cheap on large logs.
+#[derive(Deserialize)]
+struct InfoLine {
+    timestamp: Option<String>,
+    cwd: Option<String>,
+    #[serde(rename = "gitBranch")]
+    git_branch: Option<String>,
+    version: Option<String>,
+}
+
+// ---------------------------------------------------------------------------
+// Building
+// ---------------------------------------------------------------------------
+
+impl SessionInfo {
+    /// Update from a live entry. Returns `true` if anything changed.
+    ///
+    /// Subagent entries are ignored, matching [`SessionInfoCache::load`].
+    pub fn record(&mut self, entry: &LogEntry) -> bool {
+        if entry.is_sidechain == Some(true) {
+            return false;
+        }
+        self.update(
+            entry.timestamp.as_deref(),
+            entry.cwd.clone(),
+            entry.git_branch.clone(),
+            entry.version.clone(),
+        )
+    }
+
+    fn update(
+        &mut self,
+        timestamp: Option<&str>,
+        cwd: Option<String>,
+        git_branch: Option<String>,
+        version: Option<String>,
+    ) -> bool {
+        let mut changed = false;
+        for (field, value) in [
+            (InfoField::Cwd, cwd),
+            (InfoField::GitBranch, git_branch),
+            (InfoField::Version, version),
+        ] {
+            let Some(value) = value else { continue };
+            let slot = match field {
+                InfoField::Cwd => &mut self.cwd,
+                InfoField::GitBranch => &mut self.git_branch,
+                InfoField::Version => &mut self.version,
+            };
+            match slot {
+                Some(current) if *current == value => continue,
+                Some(current) => self.changes.push(InfoChange {
+                    timestamp: timestamp.map(str::to_string),
+                    field,
+                    from: std::mem::replace(current, value),
+                    to: current.clone(),
+                }),
+                None => *slot = Some(value),
+            }
+            changed = true;
+        }
+        changed
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Cache
+// ---------------------------------------------------------------------------
+
+/// How far a main log has been read into its [`SessionInfo`].
+#[derive(Debug, Default)]
+struct InfoScan {
+    /// Byte offset just past the last complete line read.
+    offset: u64,
+    info: SessionInfo,
+}
+
+impl InfoScan {
+    /// Read the lines of `path` past `offset`. A trailing line without a
+    /// newline is still looked at, but left to be read again, as it may
+    /// be half written.
+    fn resume(&mut self, path: &Path) -> io::Result<()> {
+        let file = File::open(path)?;
+        let len = file.metadata()?.len();
+        if len < self.offset {
+            // Truncated or rewritten: start over.
+            *self = InfoScan::default();
+        }
+        let mut reader = BufReader::new(file);
+        if self.offset == 0 && len > MAX_SCAN_BYTES {
+            // Start at the first line that begins within the tail.
+            let start = len - MAX_SCAN_BYTES;
+            reader.seek(SeekFrom::Start(start))?;
+            self.offset = start + reader.skip_until(b'\n')? as u64;
+        } else {
+            reader.seek(SeekFrom::Start(self.offset))?;
+        }
+
+        let mut line = Vec::new();
+        loop {
+            line.clear();
+            let read = reader.read_until(b'\n', &mut line)?;
+            if read == 0 {
+                break;
+            }
+            // Lines that are not valid UTF-8 or not JSON are skipped.
+            if let Some(l) = std::str::from_utf8(&line)
+                .ok()
+                .and_then(|text| serde_json::from_str::<InfoLine>(text).ok())
+            {
+                self.info
+                    .update(l.timestamp.as_deref(), l.cwd, l.git_branch, l.version);
+            }
+            if !line.ends_with(b"\n") {
+                break;
+            }
+            self.offset += read as u64;
+        }
+        Ok(())
+    }
+}
+
+/// Session info of main logs, kept with how far each log was read.
+#[derive(Debug, Default)]
+pub struct SessionInfoCache {
+    scans: HashMap<PathBuf, InfoScan>,
+}
+
+impl SessionInfoCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The info of `session`, read from its main log. Only the lines
+    /// appended since the last load of the same log are read, unless the
+    /// file shrank.
+    ///
+    /// Subagent logs are skipped: subagents inherit the main agent's
+    /// environment. An unreadable file yields the default.
+    pub fn load(&mut self, session: &Session) -> SessionInfo {
+        let Some(main) = session.agents.iter().find(|a| a.is_main) else {
+            return SessionInfo::default();
+        };
+        let scan = self.scans.entry(main.log_path.clone()).or_default();
+        if scan.resume(&main.log_path).is_err() {
+            *scan = InfoScan::default();
+        }
+        scan.info.clone()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log_entry::parse_jsonl_line;
+    use crate::session::Agent;
+    use std::time::SystemTime;
+    use tempfile::TempDir;
+
+    fn line(ts: &str, cwd: &str, branch: &str, version: &str) -> String {
+        format!(
+            r#"{{"type":"user","timestamp":"{}","cwd":"{}","gitBranch":"{}","version":"{}"}}"#,
+            ts, cwd, branch, version
+        )
+    }
+
+    #[test]
+    fn test_load_tracks_changes() {
+        let dir = TempDir::new().unwrap();
+        let main = dir.path().join("s.jsonl");
+        let sub = dir.path().join("agent-a.jsonl");
+        let lines = [
+            line("2025-01-15T10:00:00Z", "/repo", "main", "1.0.70"),
+            "not json".to_string(),
+            line("2025-01-15T10:01:00Z", "/repo", "main", "1.0.70"),
+            line("2025-01-15T10:02:00Z", "/repo/sub", "main", "1.0.70"),
+            line("2025-01-15T10:03:00Z", "/repo/sub", "fix/x", "1.0.71"),
+        ];
+        std::fs::write(&main, lines.join("\n")).unwrap();
+        std::fs::write(&sub, line("2025-01-15T10:04:00Z", "/elsewhere", "b", "9")).unwrap();
+        let session = Session {
+            id: "s".to_string(),
+            agents: vec![
+                Agent {
+                    agent_id: None,
+                    slug: None,
+                    is_main: true,
+                    log_path: main,
+                },
+                Agent {
+                    agent_id: Some("a".to_string()),
+                    slug: None,
+                    is_main: false,
+                    log_path: sub,
+                },
+            ],
+            last_modified: SystemTime::now(),
+        };
+
+        let info = SessionInfoCache::new().load(&session);
+        assert_eq!(info.cwd.as_deref(), Some("/repo/sub"));
+        assert_eq!(info.git_branch.as_deref(), Some("fix/x"));
+        assert_eq!(info.version.as_deref(), Some("1.0.71"));
+        assert_eq!(info.changes.len(), 3);
+        assert_eq!(
+            info.changes[0],
+            InfoChange {
+                timestamp: Some("2025-01-15T10:02:00Z".to_string()),
+                field: InfoField::Cwd,
+                from: "/repo".to_string(),
+                to: "/repo/sub".to_string(),
+            }
+        );
+        assert_eq!(info.changes[1].field, InfoField::GitBranch);
+        assert_eq!(info.changes[2].field, InfoField::Version);
+    }
+
+    #[test]
+    fn test_record_ignores_subagents_and_missing_fields() {
+        let mut info = SessionInfo::default();
+        let first = parse_jsonl_line(&line("t1", "/a", "main", "1")).unwrap();
+        assert!(info.record(&first));
+        assert!(info.changes.is_empty());
+
+        // Entries without the fields (e.g. summaries) change nothing.
+        let bare = parse_jsonl_line(r#"{"type":"summary"}"#).unwrap();
+        assert!(!info.record(&bare));
+
+        let sub = parse_jsonl_line(
+            r#"{"type":"user","isSidechain":true,"cwd":"/b","gitBranch":"main","version":"1"}"#,
+        )
+        .unwrap();
+        assert!(!info.record(&sub));
+
+        let moved = parse_jsonl_line(&line("t2", "/b", "main", "1")).unwrap();
+        assert!(info.record(&moved));
+        assert_eq!(info.cwd.as_deref(), Some("/b"));
+        assert_eq!(info.changes.len(), 1);
+    }
+
+    #[test]
+    fn test_cache_reads_only_appended_lines() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("s.jsonl");
+        let session = Session {
+            id: "s".to_string(),
+            agents: vec![Agent {
+                agent_id: None,
+                slug: None,
+                is_main: true,
+                log_path: path.clone(),
+            }],
+            last_modified: SystemTime::now(),
+        };
+        let first = line("t1", "/aa", "main", "1");
+        std::fs::write(&path, format!("{}\n", first)).unwrap();
+        let mut cache = SessionInfoCache::new();
+        assert_eq!(cache.load(&session).cwd.as_deref(), Some("/aa"));
+
+        // The first line is rewritten in place: the cache does not see it.
+        let mut bytes = format!("{}\n", line("t1", "/bb", "main", "1")).into_bytes();
+        bytes.extend_from_slice(b"\xff\xfe\n");
+        bytes.extend_from_slice(format!("{}\n", line("t2", "/aa", "main", "2")).as_bytes());
+        std::fs::write(&path, bytes).unwrap();
+
+        let info = cache.load(&session);
+        assert_eq!(info.cwd.as_deref(), Some("/aa"));
+        assert_eq!(info.version.as_deref(), Some("2"));
+        assert_eq!(info.changes.len(), 1);
+        assert_eq!(info.changes[0].field, InfoField::Version);
+    }
+
+    #[test]
+    fn test_load_missing_file_is_empty() {
+        let session = Session {
+            id: "s".to_string(),
+            agents: vec![Agent {
+                agent_id: None,
+                slug: None,
+                is_main: true,
+                log_path: "/nonexistent/s.jsonl".into(),
+            }],
+            last_modified: SystemTime::now(),
+        };
+        assert_eq!(
+            SessionInfoCache::new().load(&session),
+            SessionInfo::default()
+        );
+    }
+}
@@ -0,0 +1,477 @@
+//! Session listing for the `cctail sessions` subcommand.
+//!
+//! Summarizes every session of a project (first user message, status,
+//! agent and entry counts, size, duration) and prints them as an aligned
+//! table, a JSON array or NDJSON, so scripts can find e.g. the latest
+//! session for a repository without going through the TUI.
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::time::SystemTime;
+
+use chrono::{DateTime, Local, SecondsFormat, Utc};
+use serde::Serialize;
+
+use crate::cli::{ListFormat, SessionSort, SessionsArgs};
+use crate::config::AppConfig;
+use crate::content_render::format_size;
+use crate::log_entry::parse_jsonl_line;
+use crate::project_path::detect_project_path;
+use crate::replay::session_file_size;
+use crate::session::{discover_sessions, Session, SessionStatus};
+use crate::session_stats::format_duration_secs;
+use crate::session_title::prompt_title;
+use crate::tool_summary::truncate_chars;
+
+// ---------------------------------------------------------------------------
+// Constants
+// ---------------------------------------------------------------------------
+
+/// Characters of the first message kept in table output (before the `…`).
+const TABLE_MESSAGE_MAX_CHARS: usize = 60;
+
+// ---------------------------------------------------------------------------
+// Session summary
+// ---------------------------------------------------------------------------
+
+/// One row of `cctail sessions` output.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SessionSummary {
+    pub id: String,
+    /// First human prompt of the main session, cleaned as for the sidebar
+    /// title.
+    pub first_message: Option<String>,
+    /// Last modification time (RFC 3339, UTC).
+    pub last_modified: String,
+    /// `"active"`, `"idle"` or `"inactive"`.
+    pub status: &'static str,
+    /// Main agent plus subagents.
+    pub agent_count: usize,
+    /// Parsed JSONL entries across all agent logs.
+    pub entry_count: usize,
+    /// Total size of all agent logs in bytes.
+    pub file_size: u64,
+    /// Timestamp of the earliest entry.
+    pub started_at: Option<String>,
+    /// Timestamp of the latest entry.
+    pub ended_at: Option<String>,
+    /// Seconds between `started_at` and `ended_at`.
+    pub duration_secs: Option<i64>,
+    #[serde(skip)]
+    modified: SystemTime,
+}
+
+/// Read all of a session's agent logs and summarize them.
+///
+/// Unreadable files and malformed lines are skipped.
+pub fn summarize_session(session: &Session) -> SessionSummary {
+    let mut first_message = None;
+    let mut entry_count = 0;
+    let mut started: Option<DateTime<Utc>> = None;
+    let mut ended: Option<DateTime<Utc>> = None;
+
+    for agent in &session.agents {
+        let file = match std::fs::File::open(&agent.log_path) {
+            Ok(f) => f,
+            Err(_) => continue,
+        };
+        for line in BufReader::new(file).split(b'\n') {
+            let bytes = match line {
+                Ok(b) => b,
+                Err(_) => break,
+            };
+            // A line that is not valid UTF-8 is skipped like a malformed one.
+            let line = match std::str::from_utf8(&bytes) {
+                Ok(l) => l,
+                Err(_) => continue,
+            };
+            let entry = match parse_jsonl_line(line.trim()) {
+                Ok(e) => e,
+                Err(_) => continue,
+            };
+            entry_count += 1;
+
+            if let Some(ts) = entry
+                .timestamp
+                .as_deref()
+                .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+                .map(|dt| dt.with_timezone(&Utc))
+            {
+                started = Some(started.map_or(ts, |s| s.min(ts)));
+                ended = Some(ended.map_or(ts, |e| e.max(ts)));
+            }
+
+            if agent.is_main && first_message.is_none() {
+                first_message = prompt_title(&entry);
+            }
+        }
+    }
+
+    SessionSummary {
+        id: session.id.clone(),
+        first_message,
+        last_modified: format_rfc3339(session.last_modified.into()),
+        status: match session.status() {
+            SessionStatus::Active => "active",
+            SessionStatus::Idle => "idle",
+            SessionStatus::Inactive => "inactive",
+        },
+        agent_count: session.agents.len(),
+        entry_count,
+        file_size: session_file_size(session),
+        started_at: started.map(format_rfc3339),
+        ended_at: ended.map(format_rfc3339),
+        duration_secs: started.zip(ended).map(|(s, e)| (e - s).num_seconds()),
+        modified: session.last_modified,
+    }
+}
+
+/// Sort `summaries` by `key`, largest / most recent first unless `reverse`.
+///
+/// Sessions without a value for the key (e.g. no timestamps) sort last in
+/// either direction.
+pub fn sort_summaries(summaries: &mut [SessionSummary], key: SessionSort, reverse: bool) {
+    let missing = |s: &SessionSummary| match key {
+        SessionSort::Started => s.started_at.is_none(),
+        SessionSort::Duration => s.duration_secs.is_none(),
+        SessionSort::Modified | SessionSort::Entries | SessionSort::Size => false,
+    };
+    summaries.sort_by(|a, b| {
+        if missing(a) != missing(b) {
+            return missing(a).cmp(&missing(b));
+        }
+        let ord = match key {
+            SessionSort::Modified => a.modified.cmp(&b.modified),
+            SessionSort::Started => a.started_at.cmp(&b.started_at),
+            SessionSort::Duration => a.duration_secs.cmp(&b.duration_secs),
+            SessionSort::Entries => a.entry_count.cmp(&b.entry_count),
+            SessionSort::Size => a.file_size.cmp(&b.file_size),
+        };
+        if reverse {
+            ord
+        } else {
+            ord.reverse()
+        }
+    });
+}
+
+// ---------------------------------------------------------------------------
+// `cctail sessions`
+// ---------------------------------------------------------------------------
+
+/// Run the `sessions` subcommand for the detected (or `--project`) project.
+pub fn run_sessions(
+    args: &SessionsArgs,
+    config: &AppConfig,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let cwd = std::env::current_dir().unwrap_or_default();
+    let project_dir = detect_project_path(&cwd, config.project.as_deref())?;
+    let sessions = discover_sessions(&project_dir, usize::MAX)?;
+    let summaries = summarize_sessions(sessions, args.sort, args.reverse, args.limit);
+
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    // BrokenPipe (e.g. piping into `head`) ends output quietly.
+    match write_summaries(&mut out, &summaries, args.format) {
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => {}
+        result => result?,
+    }
+    Ok(())
+}
+
+/// Summarize `sessions`, sorted by `key` and cut to `limit`.
+///
+/// The modification time comes from file metadata, so when sorting by it
+/// the limit is applied first and only the sessions kept are read.
+fn summarize_sessions(
+    mut sessions: Vec<Session>,
+    key: SessionSort,
+    reverse: bool,
+    limit: Option<usize>,
+) -> Vec<SessionSummary> {
+    if key == SessionSort::Modified {
+        sessions.sort_by(|a, b| {
+            let ord = a.last_modified.cmp(&b.last_modified);
+            if reverse {
+                ord
+            } else {
+                ord.reverse()
+            }
+        });
+        if let Some(limit) = limit {
+            sessions.truncate(limit);
+        }
+    }
+
+    let mut summaries: Vec<SessionSummary> = sessions.iter().map(summarize_session).collect();
+    sort_summaries(&mut summaries, key, reverse);
+    if let Some(limit) = limit {
+        summaries.truncate(limit);
+    }
+    summaries
+}
+
+/// Print `summaries` in the requested format.
+fn write_summaries<W: Write>(
+    out: &mut W,
+    summaries: &[SessionSummary],
+    format: ListFormat,
+) -> io::Result<()> {
+    match format {
+        ListFormat::Json => {
+            serde_json::to_writer_pretty(&mut *out, summaries)?;
+            writeln!(out)?;
+        }
+        ListFormat::Ndjson => {
+            for summary in summaries {
+                serde_json::to_writer(&mut *out, summary)?;
+                writeln!(out)?;
+            }
+        }
+        ListFormat::Table => write_table(out, summaries)?,
+    }
+    out.flush()
+}
+
+/// Print an aligned table; the id column is shortened to 8 characters.
+fn write_table<W: Write>(out: &mut W, summaries: &[SessionSummary]) -> io::Result<()> {
+    writeln!(
+        out,
+        "{:<8}  {:<8}  {:<16}  {:>8}  {:>6}  {:>7}  {:>8}  FIRST MESSAGE",
+        "ID", "STATUS", "MODIFIED", "DURATION", "AGENTS", "ENTRIES", "SIZE"
+    )?;
+    for s in summaries {
+        let modified: DateTime<Local> = s.modified.into();
+        let message = s
+            .first_message
+            .as_deref()
+            .map(|m| truncate_chars(m, TABLE_MESSAGE_MAX_CHARS))
+            .unwrap_or_default();
+        writeln!(
+            out,
+            "{:<8}  {:<8}  {:<16}  {:>8}  {:>6}  {:>7}  {:>8}  {}",
+            s.id.chars().take(8).collect::<String>(),
+            s.status,
+            modified.format("%Y-%m-%d %H:%M"),
+            s.duration_secs
+                .map(|secs| format_duration_secs(secs.max(0) as u64))
+                .unwrap_or_else(|| "-".to_string()),
+            s.agent_count,
+            s.entry_count,
+            format_size(s.file_size as usize),
+            message
+        )?;
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+fn format_rfc3339(dt: DateTime<Utc>) -> String {
+    dt.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{session, write_file};
+    use serde_json::Value;
+    use std::time::Duration;
+
+    fn summary(id: &str, entries: usize, size: u64, age_secs: u64) -> SessionSummary {
+        let modified = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000 - age_secs);
+        SessionSummary {
+            id: id.to_string(),
+            first_message: None,
+            last_modified: format_rfc3339(modified.into()),
+            status: "inactive",
+            agent_count: 1,
+            entry_count: entries,
+            file_size: size,
+            started_at: None,
+            ended_at: None,
+            duration_secs: None,
+            modified,
+        }
+    }
+
+    const TOOL_RESULT: &str = r#"{"type":"user","timestamp":"2025-01-15T10:00:00Z","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"t1","content":"ok"}]}}"#;
+    const COMMAND: &str = r#"{"type":"user","timestamp":"2025-01-15T10:00:01Z","message":{"role":"user","content":"<command-name>/clear</command-name>"}}"#;
+    const PROMPT: &str = r#"{"type":"user","timestamp":"2025-01-15T10:00:02Z","message":{"role":"user","content":[{"type":"text","text":"Fix the\n  flaky test"}]}}"#;
+    const REPLY: &str = r#"{"type":"assistant","timestamp":"2025-01-15T10:12:05Z","message":{"role":"assistant","content":[{"type":"text","text":"Done"}]}}"#;
+    const SUB: &str = r#"{"type":"user","timestamp":"2025-01-15T10:05:00Z","message":{"role":"user","content":"subagent task"}}"#;
+
+    #[test]
+    fn test_summarize_session_counts_and_first_message() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let main = write_file(
+            tmp.path(),
+            "s1.jsonl",
+            &[TOOL_RESULT, COMMAND, "not json", PROMPT, REPLY],
+        );
+        let sub = write_file(tmp.path(), "s1/subagents/agent-a1.jsonl", &[SUB]);
+        let s = session("s1", vec![(None, main), (Some("a1"), sub)]);
+
+        let summary = summarize_session(&s);
+        assert_eq!(summary.first_message.as_deref(), Some("Fix the flaky test"));
+        assert_eq!(summary.status, "active");
+        assert_eq!(summary.agent_count, 2);
+        assert_eq!(summary.entry_count, 5);
+        assert_eq!(summary.file_size, session_file_size(&s));
+        assert_eq!(summary.started_at.as_deref(), Some("2025-01-15T10:00:00Z"));
+        assert_eq!(summary.ended_at.as_deref(), Some("2025-01-15T10:12:05Z"));
+        assert_eq!(summary.duration_secs, Some(725));
+    }
+
+    #[test]
+    fn test_summarize_session_without_timestamps() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let main = write_file(tmp.path(), "s1.jsonl", &[r#"{"type":"user"}"#]);
+        let s = Session {
+            last_modified: SystemTime::now() - Duration::from_secs(3600),
+            ..session("s1", vec![(None, main)])
+        };
+        let summary = summarize_session(&s);
+        assert_eq!(summary.status, "inactive");
+        assert_eq!(summary.entry_count, 1);
+        assert!(summary.first_message.is_none());
+        assert!(summary.duration_secs.is_none());
+    }
+
+    #[test]
+    fn test_summarize_session_skips_invalid_utf8_line() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("s1.jsonl");
+        let mut bytes = format!("{}\n", PROMPT).into_bytes();
+        bytes.extend_from_slice(b"\xff\xfe\n");
+        bytes.extend_from_slice(format!("{}\n", REPLY).as_bytes());
+        std::fs::write(&path, bytes).unwrap();
+
+        let summary = summarize_session(&session("s1", vec![(None, path)]));
+        assert_eq!(summary.entry_count, 2);
+    }
+
+    #[test]
+    fn test_summarize_sessions_sorts_and_limits() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let aged = |id: &str, age_secs: u64| Session {
+            last_modified: SystemTime::now() - Duration::from_secs(age_secs),
+            ..session(
+                id,
+                vec![(
+                    None,
+                    write_file(tmp.path(), &format!("{}.jsonl", id), &[PROMPT]),
+                )],
+            )
+        };
+        let sessions = vec![aged("old", 300), aged("new", 0), aged("mid", 100)];
+        let ids = |rows: &[SessionSummary]| rows.iter().map(|r| r.id.clone()).collect::<Vec<_>>();
+
+        let rows = summarize_sessions(sessions.clone(), SessionSort::Modified, false, Some(2));
+        assert_eq!(ids(&rows), vec!["new", "mid"]);
+        let rows = summarize_sessions(sessions.clone(), SessionSort::Modified, true, Some(1));
+        assert_eq!(ids(&rows), vec!["old"]);
+        let rows = summarize_sessions(sessions, SessionSort::Entries, false, None);
+        assert_eq!(rows.len(), 3);
+    }
+
+    #[test]
+    fn test_sort_summaries() {
+        let mut rows = vec![
+            summary("old", 5, 10, 300),
+            summary("new", 1, 30, 0),
+            summary("mid", 9, 20, 100),
+        ];
+        let ids = |rows: &[SessionSummary]| rows.iter().map(|r| r.id.clone()).collect::<Vec<_>>();
+
+        sort_summaries(&mut rows, SessionSort::Modified, false);
+        assert_eq!(ids(&rows), vec!["new", "mid", "old"]);
+        sort_summaries(&mut rows, SessionSort::Modified, true);
+        assert_eq!(ids(&rows), vec!["old", "mid", "new"]);
+        sort_summaries(&mut rows, SessionSort::Entries, false);
+        assert_eq!(ids(&rows), vec!["mid", "old", "new"]);
+        sort_summaries(&mut rows, SessionSort::Size, false);
+        assert_eq!(ids(&rows), vec!["new", "mid", "old"]);
+    }
+
+    #[test]
+    fn test_sort_summaries_missing_keys_last_in_both_directions() {
+        let mut rows = vec![
+            summary("none", 1, 1, 0),
+            SessionSummary {
+                duration_secs: Some(60),
+                ..summary("short", 1, 1, 0)
+            },
+            SessionSummary {
+                duration_secs: Some(600),
+                ..summary("long", 1, 1, 0)
+            },
+        ];
+        let ids = |rows: &[SessionSummary]| rows.iter().map(|r| r.id.clone()).collect::<Vec<_>>();
+
+        sort_summaries(&mut rows, SessionSort::Duration, false);
+        assert_eq!(ids(&rows), vec!["long", "short", "none"]);
+        sort_summaries(&mut rows, SessionSort::Duration, true);
+        assert_eq!(ids(&rows), vec!["short", "long", "none"]);
+    }
+
+    #[test]
+    fn test_first_message_keeps_prompts_starting_with_markup() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let reminder = r#"{"type":"user","message":{"role":"user","content":"<system-reminder>ctx</system-reminder>"}}"#;
+        let prompt = r#"{"type":"user","message":{"role":"user","content":"<div> renders twice"}}"#;
+        let main = write_file(tmp.path(), "s1.jsonl", &[reminder, prompt]);
+
+        let summary = summarize_session(&session("s1", vec![(None, main)]));
+        assert_eq!(
+            summary.first_message.as_deref(),
+            Some("<div> renders twice")
+        );
+    }
+
+    #[test]
+    fn test_write_ndjson_one_object_per_line() {
+        let rows = vec![summary("a", 1, 1, 0), summary("b", 2, 2, 0)];
+        let mut out = Vec::new();
+        write_summaries(&mut out, &rows, ListFormat::Ndjson).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 2);
+        let first: Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(first["id"], "a");
+        assert_eq!(first["entry_count"], 1);
+        assert_eq!(first["status"], "inactive");
+        assert!(first.get("modified").is_none());
+    }
+
+    #[test]
+    fn test_write_json_array() {
+        let rows = vec![summary("a", 1, 1, 0)];
+        let mut out = Vec::new();
+        write_summaries(&mut out, &rows, ListFormat::Json).unwrap();
+        let value: Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(value.as_array().unwrap().len(), 1);
+        assert_eq!(value[0]["file_size"], 1);
+    }
+
+    #[test]
+    fn test_write_table() {
+        let mut row = summary("0123456789abcdef", 42, 2048, 0);
+        row.duration_secs = Some(3725);
+        row.first_message = Some("x".repeat(100));
+        let mut out = Vec::new();
+        write_summaries(&mut out, &[row], ListFormat::Table).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert!(lines[0].starts_with("ID        STATUS"));
+        assert!(lines[1].starts_with("01234567  inactive  "));
+        assert!(lines[1].contains("  1h 2m  "));
+        assert!(lines[1].contains("  2.0KB  "));
+        assert!(lines[1].ends_with(&format!("{}…", "x".repeat(60))));
+    }
+}
@@ -2,13 +2,19 @@
 //!
 //! Provides [`SessionStats`] and [`compute_session_stats()`] to derive
 //! summary statistics from the entries in a ring buffer: session duration,
-//! message counts, tool call breakdown, subagent count, etc.
+//! message counts, tool call breakdown, subagent count, token usage, etc.
+//! [`StatsTracker`] keeps the same statistics up to date as entries are
+//! pushed and evicted.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap};
 
-use crate::log_entry::{EntryType, LogEntry};
+use crate::log_entry::{EntryType, LogEntry, Usage};
+use crate::pricing::PriceTable;
 use crate::ring_buffer::RingBuffer;
 
+/// Agent key used in [`SessionStats::usage_by_agent`] for the main agent.
+pub const MAIN_AGENT_KEY: &str = "main";
+
 // ---------------------------------------------------------------------------
 // SessionStats
 // ---------------------------------------------------------------------------
@@ -37,6 +43,12 @@ pub struct SessionStats {
     /// Number of unique subagents (entries with `is_sidechain == true`).
     pub subagent_count: usize,
 
+    /// Number of context compactions (`compact_boundary` entries).
+    pub compaction_count: usize,
+
+    /// Sum of the context sizes, in tokens, just before each compaction.
+    pub compacted_tokens: u64,
+
     /// ISO 8601 timestamp of the earliest entry, if available.
     pub earliest_timestamp: Option<String>,
 
@@ -46,6 +58,56 @@ pub struct SessionStats {
     /// Human-readable session duration string (e.g. "2h 15m", "45m 30s").
     /// `None` if timestamps are missing or unparseable.
     pub duration_display: Option<String>,
+
+    /// Total token usage across all loaded entries.
+    pub usage: Usage,
+
+    /// Token usage per session ID, sorted by total tokens descending.
+    pub usage_by_session: Vec<(String, Usage)>,
+
+    /// Token usage per agent (slug, agent ID, or [`MAIN_AGENT_KEY`]),
+    /// sorted by total tokens descending.
+    pub usage_by_agent: Vec<(String, Usage)>,
+
+    /// Token usage per model name, sorted by total tokens descending.
+    pub usage_by_model: Vec<(String, Usage)>,
+
+    /// Token usage per `(session ID, model)` pair, used to price a single
+    /// session. Unordered.
+    pub usage_by_session_model: Vec<(String, String, Usage)>,
+}
+
+impl SessionStats {
+    /// Estimated cost in USD of all loaded usage, priced per model.
+    ///
+    /// Returns `None` when none of the models seen has a known price.
+    pub fn estimated_cost(&self, prices: &PriceTable) -> Option<f64> {
+        estimate_cost(&self.usage_by_model, prices)
+    }
+
+    /// Estimated cost in USD of a single session's usage.
+    ///
+    /// Returns `None` when none of the session's models has a known price.
+    pub fn session_cost(&self, session_id: &str, prices: &PriceTable) -> Option<f64> {
+        let mut total: Option<f64> = None;
+        for (id, model, usage) in &self.usage_by_session_model {
+            if id != session_id {
+                continue;
+            }
+            if let Some(cost) = prices.estimate(model, usage) {
+                *total.get_or_insert(0.0) += cost;
+            }
+        }
+        total
+    }
+
+    /// Token usage recorded for a single session, if any.
+    pub fn session_usage(&self, session_id: &str) -> Option<&Usage> {
+        self.usage_by_session
+            .iter()
+            .find(|(id, _)| id == session_id)
+            .map(|(_, u)| u)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -57,91 +119,359 @@ pub struct SessionStats {
 /// Iterates all entries once and collects counts. Only `tool_use` content
 /// blocks are counted for tool stats (not `tool_result`).
 pub fn compute_session_stats(ring_buffer: &RingBuffer) -> SessionStats {
-    let mut stats = SessionStats::default();
+    let mut tracker = StatsTracker::default();
+    for entry in ring_buffer.iter() {
+        tracker.record(entry);
+    }
+    tracker.stats()
+}
 
-    let mut tool_counts: HashMap<String, usize> = HashMap::new();
-    let mut subagent_ids: HashSet<String> = HashSet::new();
-    let mut earliest: Option<&str> = None;
-    let mut latest: Option<&str> = None;
+/// Running counts behind a [`SessionStats`], updated as entries enter and
+/// leave the ring buffer so that rendering does not walk the buffer.
+#[derive(Debug, Default)]
+pub struct StatsTracker {
+    entries: usize,
+    user_messages: usize,
+    assistant_messages: usize,
+    compactions: usize,
+    compacted_tokens: u64,
+    tool_counts: HashMap<String, usize>,
+    /// Entries per subagent ID.
+    subagents: HashMap<String, usize>,
+    /// Entries per timestamp, for the earliest and latest.
+    timestamps: BTreeMap<String, usize>,
+    usage: UsageAccumulator,
+}
 
-    for entry in ring_buffer.iter() {
-        stats.entries_loaded += 1;
+impl StatsTracker {
+    /// Count an entry added to the buffer.
+    pub fn record(&mut self, entry: &LogEntry) {
+        self.entries += 1;
+        self.usage.record(entry);
+        if let Some(ref ts) = entry.timestamp {
+            *self.timestamps.entry(ts.clone()).or_default() += 1;
+        }
+        match entry.entry_type {
+            EntryType::User => self.user_messages += 1,
+            EntryType::Assistant => self.assistant_messages += 1,
+            _ => {}
+        }
+        if entry.is_compact_boundary() {
+            self.compactions += 1;
+            self.compacted_tokens += compacted_tokens(entry);
+        }
+        if let Some(agent_id) = subagent_id(entry) {
+            *self.subagents.entry(agent_id.to_string()).or_default() += 1;
+        }
+        for name in tool_use_names(entry) {
+            *self.tool_counts.entry(name.to_string()).or_default() += 1;
+        }
+    }
 
-        // Track timestamps.
+    /// Uncount an entry evicted from the buffer. Entries must be forgotten
+    /// in the order they were recorded, oldest first.
+    pub fn forget(&mut self, entry: &LogEntry) {
+        self.entries = self.entries.saturating_sub(1);
+        self.usage.forget(entry);
         if let Some(ref ts) = entry.timestamp {
-            let ts_str = ts.as_str();
-            match earliest {
-                None => earliest = Some(ts_str),
-                Some(e) if ts_str < e => earliest = Some(ts_str),
-                _ => {}
-            }
-            match latest {
-                None => latest = Some(ts_str),
-                Some(l) if ts_str > l => latest = Some(ts_str),
-                _ => {}
+            if let Some(n) = self.timestamps.get_mut(ts) {
+                *n -= 1;
+                if *n == 0 {
+                    self.timestamps.remove(ts);
+                }
             }
         }
-
-        // Count by entry type.
         match entry.entry_type {
-            EntryType::User => stats.user_message_count += 1,
-            EntryType::Assistant => stats.assistant_message_count += 1,
+            EntryType::User => self.user_messages = self.user_messages.saturating_sub(1),
+            EntryType::Assistant => {
+                self.assistant_messages = self.assistant_messages.saturating_sub(1)
+            }
             _ => {}
         }
+        if entry.is_compact_boundary() {
+            self.compactions = self.compactions.saturating_sub(1);
+            self.compacted_tokens = self
+                .compacted_tokens
+                .saturating_sub(compacted_tokens(entry));
+        }
+        if let Some(agent_id) = subagent_id(entry) {
+            decrement(&mut self.subagents, agent_id);
+        }
+        for name in tool_use_names(entry) {
+            decrement(&mut self.tool_counts, name);
+        }
+    }
+
+    /// Forget every entry.
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// The statistics of the entries currently counted.
+    pub fn stats(&self) -> SessionStats {
+        let earliest = self.timestamps.keys().next().map(String::as_str);
+        let latest = self.timestamps.keys().next_back().map(String::as_str);
+
+        // Sort tool breakdown by count descending, then name ascending.
+        let mut breakdown: Vec<(String, usize)> = self
+            .tool_counts
+            .iter()
+            .map(|(name, n)| (name.clone(), *n))
+            .collect();
+        breakdown.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        let mut stats = SessionStats {
+            entries_loaded: self.entries,
+            user_message_count: self.user_messages,
+            assistant_message_count: self.assistant_messages,
+            tool_call_count: self.tool_counts.values().sum(),
+            tool_call_breakdown: breakdown,
+            subagent_count: self.subagents.len(),
+            compaction_count: self.compactions,
+            compacted_tokens: self.compacted_tokens,
+            earliest_timestamp: earliest.map(str::to_string),
+            latest_timestamp: latest.map(str::to_string),
+            duration_display: compute_duration_display(earliest, latest),
+            ..SessionStats::default()
+        };
+        self.usage.finish(&mut stats);
+        stats
+    }
+}
+
+/// Decrement a count, dropping the key when it reaches zero.
+fn decrement(counts: &mut HashMap<String, usize>, key: &str) {
+    if let Some(n) = counts.get_mut(key) {
+        *n -= 1;
+        if *n == 0 {
+            counts.remove(key);
+        }
+    }
+}
+
+/// Context size before a compaction, for `compact_boundary` entries.
+fn compacted_tokens(entry: &LogEntry) -> u64 {
+    entry
+        .compact_metadata
+        .as_ref()
+        .and_then(|m| m.pre_tokens)
+        .unwrap_or(0)
+}
+
+/// Agent ID of a subagent entry (`is_sidechain == true`).
+fn subagent_id(entry: &LogEntry) -> Option<&str> {
+    if entry.is_sidechain == Some(true) {
+        entry.agent_id.as_deref()
+    } else {
+        None
+    }
+}
+
+/// Sum the estimated cost of per-model usage.
+///
+/// Models without a known price are skipped. Returns `None` if no model
+/// could be priced.
+pub fn estimate_cost(usage_by_model: &[(String, Usage)], prices: &PriceTable) -> Option<f64> {
+    let mut total: Option<f64> = None;
+    for (model, usage) in usage_by_model {
+        if let Some(cost) = prices.estimate(model, usage) {
+            *total.get_or_insert(0.0) += cost;
+        }
+    }
+    total
+}
+
+// ---------------------------------------------------------------------------
+// Usage accumulation
+// ---------------------------------------------------------------------------
+
+/// Usage attributed to one API message.
+#[derive(Debug)]
+struct UsageRecord {
+    session: Option<String>,
+    agent: String,
+    model: String,
+    usage: Usage,
+}
+
+impl UsageRecord {
+    fn from_entry(entry: &LogEntry) -> Option<Self> {
+        let message = entry.message.as_ref()?;
+        let usage = message.usage?;
+
+        let agent = if entry.is_sidechain == Some(true) {
+            entry
+                .slug
+                .clone()
+                .or_else(|| entry.agent_id.clone())
+                .unwrap_or_else(|| "unknown".to_string())
+        } else {
+            MAIN_AGENT_KEY.to_string()
+        };
+
+        Some(Self {
+            session: entry.session_id.clone(),
+            agent,
+            model: message
+                .model
+                .clone()
+                .unwrap_or_else(|| "unknown".to_string()),
+            usage,
+        })
+    }
+}
+
+/// Token usage per key, with the number of records behind it.
+type UsageMap<K> = HashMap<K, (Usage, usize)>;
 
-        // Track subagents.
-        if entry.is_sidechain == Some(true) {
-            if let Some(ref agent_id) = entry.agent_id {
-                subagent_ids.insert(agent_id.clone());
+/// Sums `message.usage` blocks as entries are recorded and forgotten.
+///
+/// Claude Code writes one JSONL line per content block of an API response,
+/// and every line repeats the message's `usage`. Records are therefore keyed
+/// by `message.id` and the last occurrence wins, so a response is counted
+/// once. Entries without an ID are counted individually.
+#[derive(Debug, Default)]
+struct UsageAccumulator {
+    /// Counted record of each message ID, and how many entries repeat it.
+    by_message_id: HashMap<String, (UsageRecord, usize)>,
+    total: Usage,
+    by_session: UsageMap<String>,
+    by_agent: UsageMap<String>,
+    by_model: UsageMap<String>,
+    by_session_model: UsageMap<(String, String)>,
+}
+
+impl UsageAccumulator {
+    fn record(&mut self, entry: &LogEntry) {
+        let Some(record) = UsageRecord::from_entry(entry) else {
+            return;
+        };
+        let message_id = entry.message.as_ref().and_then(|m| m.id.clone());
+        match message_id {
+            Some(id) => {
+                if let Some((counted, repeats)) = self.by_message_id.remove(&id) {
+                    self.subtract(&counted);
+                    self.add(&record);
+                    self.by_message_id.insert(id, (record, repeats + 1));
+                } else {
+                    self.add(&record);
+                    self.by_message_id.insert(id, (record, 1));
+                }
+            }
+            None => self.add(&record),
+        }
+    }
+
+    fn forget(&mut self, entry: &LogEntry) {
+        let Some(record) = UsageRecord::from_entry(entry) else {
+            return;
+        };
+        let message_id = entry.message.as_ref().and_then(|m| m.id.as_ref());
+        match message_id {
+            Some(id) => {
+                let Some((_, repeats)) = self.by_message_id.get_mut(id) else {
+                    return;
+                };
+                *repeats -= 1;
+                if *repeats == 0 {
+                    if let Some((counted, _)) = self.by_message_id.remove(id) {
+                        self.subtract(&counted);
+                    }
+                }
             }
+            None => self.subtract(&record),
         }
+    }
 
-        // Count tool_use blocks in message content.
-        count_tool_uses(entry, &mut tool_counts);
+    fn add(&mut self, record: &UsageRecord) {
+        self.total.accumulate(&record.usage);
+        if let Some(ref session) = record.session {
+            add_usage(&mut self.by_session, session.clone(), &record.usage);
+            add_usage(
+                &mut self.by_session_model,
+                (session.clone(), record.model.clone()),
+                &record.usage,
+            );
+        }
+        add_usage(&mut self.by_agent, record.agent.clone(), &record.usage);
+        add_usage(&mut self.by_model, record.model.clone(), &record.usage);
     }
 
-    stats.subagent_count = subagent_ids.len();
-    stats.tool_call_count = tool_counts.values().sum();
+    fn subtract(&mut self, record: &UsageRecord) {
+        self.total.subtract(&record.usage);
+        if let Some(ref session) = record.session {
+            subtract_usage(&mut self.by_session, session, &record.usage);
+            subtract_usage(
+                &mut self.by_session_model,
+                &(session.clone(), record.model.clone()),
+                &record.usage,
+            );
+        }
+        subtract_usage(&mut self.by_agent, &record.agent, &record.usage);
+        subtract_usage(&mut self.by_model, &record.model, &record.usage);
+    }
 
-    // Sort tool breakdown by count descending, then name ascending.
-    let mut breakdown: Vec<(String, usize)> = tool_counts.into_iter().collect();
-    breakdown.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
-    stats.tool_call_breakdown = breakdown;
+    fn finish(&self, stats: &mut SessionStats) {
+        stats.usage = self.total;
+        stats.usage_by_session = sorted_usage(&self.by_session);
+        stats.usage_by_agent = sorted_usage(&self.by_agent);
+        stats.usage_by_model = sorted_usage(&self.by_model);
+        stats.usage_by_session_model = self
+            .by_session_model
+            .iter()
+            .map(|((session, model), (usage, _))| (session.clone(), model.clone(), *usage))
+            .collect();
+    }
+}
 
-    // Store timestamps.
-    stats.earliest_timestamp = earliest.map(|s| s.to_string());
-    stats.latest_timestamp = latest.map(|s| s.to_string());
+fn add_usage<K: std::hash::Hash + Eq>(map: &mut UsageMap<K>, key: K, usage: &Usage) {
+    let (sum, records) = map.entry(key).or_default();
+    sum.accumulate(usage);
+    *records += 1;
+}
 
-    // Compute duration display.
-    stats.duration_display = compute_duration_display(earliest, latest);
+fn subtract_usage<K: std::hash::Hash + Eq>(map: &mut UsageMap<K>, key: &K, usage: &Usage) {
+    if let Some((sum, records)) = map.get_mut(key) {
+        sum.subtract(usage);
+        *records -= 1;
+        if *records == 0 {
+            map.remove(key);
+        }
+    }
+}
 
-    stats
+/// Sort a usage map by total tokens descending, then key ascending.
+fn sorted_usage(map: &UsageMap<String>) -> Vec<(String, Usage)> {
+    let mut v: Vec<(String, Usage)> = map
+        .iter()
+        .map(|(key, (usage, _))| (key.clone(), *usage))
+        .collect();
+    v.sort_by(|a, b| {
+        b.1.total_tokens()
+            .cmp(&a.1.total_tokens())
+            .then_with(|| a.0.cmp(&b.0))
+    });
+    v
 }
 
-/// Count `tool_use` content blocks in an entry's message content.
+/// Names of the `tool_use` content blocks in an entry's message content.
 ///
 /// Only blocks with `"type": "tool_use"` are counted. `tool_result` blocks
 /// are explicitly excluded.
-fn count_tool_uses(entry: &LogEntry, tool_counts: &mut HashMap<String, usize>) {
-    let message = match &entry.message {
-        Some(m) => m,
-        None => return,
-    };
-
-    let blocks = match message.content.as_array() {
-        Some(arr) => arr,
-        None => return,
-    };
-
-    for block in blocks {
-        if block.get("type").and_then(|t| t.as_str()) == Some("tool_use") {
-            let name = block
+fn tool_use_names(entry: &LogEntry) -> impl Iterator<Item = &str> {
+    entry
+        .message
+        .as_ref()
+        .and_then(|m| m.content.as_array())
+        .into_iter()
+        .flatten()
+        .filter(|block| block.get("type").and_then(|t| t.as_str()) == Some("tool_use"))
+        .map(|block| {
+            block
                 .get("name")
                 .and_then(|n| n.as_str())
-                .unwrap_or("unknown");
-            *tool_counts.entry(name.to_string()).or_insert(0) += 1;
-        }
-    }
+                .unwrap_or("unknown")
+        })
 }
 
 /// Compute a human-readable duration string from two ISO 8601 timestamps.
@@ -239,10 +569,30 @@ fn days_in_months_before(month: u64, leap: bool) -> u64 {
     total
 }
 
+/// Format a token count compactly.
+///
+/// Examples: "0", "999", "1.2k", "45.6k", "1.2M".
+pub fn format_token_count(n: u64) -> String {
+    if n < 1_000 {
+        n.to_string()
+    } else if n < 1_000_000 {
+        format!("{:.1}k", n as f64 / 1_000.0)
+    } else {
+        format!("{:.1}M", n as f64 / 1_000_000.0)
+    }
+}
+
+/// Format an estimated cost in USD.
+///
+/// Examples: "$0.00", "$0.42", "$12.34".
+pub fn format_cost(usd: f64) -> String {
+    format!("${:.2}", usd)
+}
+
 /// Format a duration in seconds as a human-readable string.
 ///
 /// Examples: "0s", "45s", "2m 30s", "1h 15m", "2h 0m", "25h 30m".
-fn format_duration_secs(secs: u64) -> String {
+pub(crate) fn format_duration_secs(secs: u64) -> String {
     if secs < 60 {
         return format!("{}s", secs);
     }
@@ -395,6 +745,33 @@ mod tests {
         assert_eq!(stats.subagent_count, 2);
     }
 
+    // -- compute_session_stats: compactions -----------------------------------
+
+    #[test]
+    fn test_compaction_counting() {
+        let mut buf = RingBuffer::new(100_000);
+        push_entry(
+            &mut buf,
+            r#"{"type": "system", "subtype": "compact_boundary", "compactMetadata": {"trigger": "auto", "preTokens": 150000}}"#,
+        );
+        push_entry(
+            &mut buf,
+            r#"{"type": "user", "isCompactSummary": true, "message": {"role": "user", "content": "This session is being continued..."}}"#,
+        );
+        push_entry(
+            &mut buf,
+            r#"{"type": "system", "subtype": "compact_boundary", "compactMetadata": {"trigger": "manual"}}"#,
+        );
+        push_entry(
+            &mut buf,
+            r#"{"type": "system", "subtype": "informational"}"#,
+        );
+
+        let stats = compute_session_stats(&buf);
+        assert_eq!(stats.compaction_count, 2);
+        assert_eq!(stats.compacted_tokens, 150_000);
+    }
+
     // -- compute_session_stats: timestamps and duration -----------------------
 
     #[test]
@@ -649,4 +1026,174 @@ mod tests {
         let stats = compute_session_stats(&buf);
         assert_eq!(stats.duration_display.as_deref(), Some("0s"));
     }
+
+    // -- compute_session_stats: token usage -----------------------------------
+
+    #[test]
+    fn test_usage_summed_per_session_agent_and_model() {
+        let mut buf = RingBuffer::new(100_000);
+        push_entry(
+            &mut buf,
+            r#"{"type": "assistant", "sessionId": "s1", "message": {"id": "m1", "model": "claude-opus-4-6", "content": [], "usage": {"input_tokens": 10, "output_tokens": 100}}}"#,
+        );
+        push_entry(
+            &mut buf,
+            r#"{"type": "assistant", "sessionId": "s1", "isSidechain": true, "agentId": "abc", "slug": "cool-agent", "message": {"id": "m2", "model": "claude-haiku-4-5", "content": [], "usage": {"input_tokens": 5, "output_tokens": 50, "cache_read_input_tokens": 1000}}}"#,
+        );
+        push_entry(
+            &mut buf,
+            r#"{"type": "assistant", "sessionId": "s2", "message": {"id": "m3", "model": "claude-opus-4-6", "content": [], "usage": {"input_tokens": 1, "output_tokens": 2}}}"#,
+        );
+
+        let stats = compute_session_stats(&buf);
+        assert_eq!(stats.usage.input_tokens, 16);
+        assert_eq!(stats.usage.output_tokens, 152);
+        assert_eq!(stats.usage.cache_read_input_tokens, 1000);
+
+        assert_eq!(stats.session_usage("s1").unwrap().total_tokens(), 1165);
+        assert_eq!(stats.session_usage("s2").unwrap().total_tokens(), 3);
+        assert!(stats.session_usage("s3").is_none());
+
+        assert_eq!(stats.usage_by_agent[0].0, "cool-agent");
+        assert_eq!(stats.usage_by_agent[1].0, MAIN_AGENT_KEY);
+        assert_eq!(stats.usage_by_agent[1].1.total_tokens(), 113);
+
+        assert_eq!(stats.usage_by_model.len(), 2);
+        assert_eq!(stats.usage_by_model[0].0, "claude-haiku-4-5");
+        assert_eq!(stats.usage_by_model[1].1.output_tokens, 102);
+
+        // s2: 1 input + 2 output tokens of claude-opus-4-6 ($5 / $25 per MTok).
+        let prices = PriceTable::builtin();
+        let s2_cost = stats.session_cost("s2", &prices).unwrap();
+        assert!((s2_cost - 0.000055).abs() < 1e-12);
+        assert!(stats.session_cost("s3", &prices).is_none());
+    }
+
+    #[test]
+    fn test_usage_deduplicated_by_message_id() {
+        let mut buf = RingBuffer::new(100_000);
+        // Two lines for the same API message (text block, then tool_use
+        // block), both carrying the message's usage.
+        push_entry(
+            &mut buf,
+            r#"{"type": "assistant", "message": {"id": "m1", "model": "claude-sonnet-4-5", "content": [{"type": "text", "text": "hi"}], "usage": {"input_tokens": 10, "output_tokens": 1}}}"#,
+        );
+        push_entry(
+            &mut buf,
+            r#"{"type": "assistant", "message": {"id": "m1", "model": "claude-sonnet-4-5", "content": [{"type": "tool_use", "id": "t1", "name": "Bash", "input": {}}], "usage": {"input_tokens": 10, "output_tokens": 40}}}"#,
+        );
+        // No message ID: counted as-is.
+        push_entry(
+            &mut buf,
+            r#"{"type": "assistant", "message": {"content": [], "usage": {"input_tokens": 1}}}"#,
+        );
+
+        let stats = compute_session_stats(&buf);
+        assert_eq!(stats.usage.input_tokens, 11);
+        assert_eq!(stats.usage.output_tokens, 40);
+        assert_eq!(stats.usage_by_model[0].0, "claude-sonnet-4-5");
+        assert_eq!(stats.usage_by_model[1].0, "unknown");
+    }
+
+    #[test]
+    fn test_estimated_cost() {
+        let mut buf = RingBuffer::new(100_000);
+        push_entry(
+            &mut buf,
+            r#"{"type": "assistant", "message": {"id": "m1", "model": "claude-sonnet-4-5", "content": [], "usage": {"input_tokens": 1000000, "output_tokens": 1000000}}}"#,
+        );
+        push_entry(
+            &mut buf,
+            r#"{"type": "assistant", "message": {"id": "m2", "model": "<synthetic>", "content": [], "usage": {"input_tokens": 5}}}"#,
+        );
+
+        let stats = compute_session_stats(&buf);
+        let cost = stats.estimated_cost(&PriceTable::builtin()).unwrap();
+        assert!((cost - 18.0).abs() < 1e-9);
+        assert!(stats.estimated_cost(&PriceTable::empty()).is_none());
+    }
+
+    #[test]
+    fn test_no_usage_no_cost() {
+        let mut buf = RingBuffer::new(100_000);
+        push_entry(
+            &mut buf,
+            r#"{"type": "user", "message": {"role": "user", "content": "hi"}}"#,
+        );
+
+        let stats = compute_session_stats(&buf);
+        assert_eq!(stats.usage.total_tokens(), 0);
+        assert!(stats.usage_by_model.is_empty());
+        assert!(stats.estimated_cost(&PriceTable::builtin()).is_none());
+    }
+
+    // -- StatsTracker -----------------------------------------------------------
+
+    #[test]
+    fn test_tracker_forget_matches_recompute_after_eviction() {
+        let lines = [
+            r#"{"type": "user", "timestamp": "2025-01-15T10:00:00Z", "message": {"role": "user", "content": "hi"}}"#,
+            r#"{"type": "assistant", "timestamp": "2025-01-15T10:05:00Z", "message": {"id": "m1", "model": "claude-opus-4", "role": "assistant", "content": [{"type": "tool_use", "id": "t1", "name": "Bash", "input": {}}], "usage": {"input_tokens": 100, "output_tokens": 10}}}"#,
+            r#"{"type": "assistant", "timestamp": "2025-01-15T10:06:00Z", "message": {"id": "m2", "model": "claude-opus-4", "role": "assistant", "content": [{"type": "tool_use", "id": "t2", "name": "Read", "input": {}}], "usage": {"input_tokens": 200, "output_tokens": 20}}}"#,
+        ];
+        let entries: Vec<LogEntry> = lines.iter().map(|l| parse_jsonl_line(l).unwrap()).collect();
+
+        let mut tracker = StatsTracker::default();
+        for entry in &entries {
+            tracker.record(entry);
+        }
+        tracker.forget(&entries[0]);
+        tracker.forget(&entries[1]);
+
+        let stats = tracker.stats();
+        assert_eq!(stats.entries_loaded, 1);
+        assert_eq!(stats.user_message_count, 0);
+        assert_eq!(stats.assistant_message_count, 1);
+        assert_eq!(stats.tool_call_breakdown, vec![("Read".to_string(), 1)]);
+        assert_eq!(stats.usage.input_tokens, 200);
+        assert_eq!(stats.usage.output_tokens, 20);
+        assert_eq!(
+            stats.earliest_timestamp.as_deref(),
+            Some("2025-01-15T10:06:00Z")
+        );
+        assert_eq!(stats.latest_timestamp, stats.earliest_timestamp);
+    }
+
+    #[test]
+    fn test_tracker_repeated_message_id_counts_until_last_repeat_forgotten() {
+        let line = r#"{"type": "assistant", "message": {"id": "m1", "model": "claude-opus-4", "role": "assistant", "content": "x", "usage": {"input_tokens": 100, "output_tokens": 10}}}"#;
+        let first = parse_jsonl_line(line).unwrap();
+        let second = parse_jsonl_line(line).unwrap();
+
+        let mut tracker = StatsTracker::default();
+        tracker.record(&first);
+        tracker.record(&second);
+        assert_eq!(tracker.stats().usage.input_tokens, 100);
+
+        tracker.forget(&first);
+        assert_eq!(tracker.stats().usage.input_tokens, 100);
+
+        tracker.forget(&second);
+        let stats = tracker.stats();
+        assert_eq!(stats.usage.total_tokens(), 0);
+        assert!(stats.usage_by_model.is_empty());
+    }
+
+    // -- format_token_count / format_cost -------------------------------------
+
+    #[test]
+    fn test_format_token_count() {
+        assert_eq!(format_token_count(0), "0");
+        assert_eq!(format_token_count(999), "999");
+        assert_eq!(format_token_count(1_234), "1.2k");
+        assert_eq!(format_token_count(45_600), "45.6k");
+        assert_eq!(format_token_count(1_250_000), "1.2M");
+    }
+
+    #[test]
+    fn test_format_cost() {
+        assert_eq!(format_cost(0.0), "$0.00");
+        assert_eq!(format_cost(0.4213), "$0.42");
+        assert_eq!(format_cost(12.346), "$12.35");
+    }
 }
@@ -0,0 +1,386 @@
+//! Session titles for the sidebar.
+//!
+//! A session's title is Claude's `summary` entry when the log has one,
+//! otherwise its first real human prompt with command tags and system
+//! reminders stripped. Claude Code writes `summary` entries at the top of
+//! a resumed session's log, so only the first [`TITLE_SCAN_LINES`] lines
+//! are read. [`TitleCache`] keeps each file's title, and where its scan
+//! stopped, until its modification time changes; it then reads only what
+//! was appended.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde_json::Value;
+
+use crate::log_entry::{parse_jsonl_line, EntryType, LogEntry};
+use crate::system_event::SystemEvent;
+
+/// Number of lines read from the top of a log when looking for a title.
+pub const TITLE_SCAN_LINES: usize = 200;
+
+/// Tags whose whole block is dropped from a prompt.
+const DROPPED_BLOCKS: &[&str] = &[
+    "system-reminder",
+    "command-message",
+    "local-command-stdout",
+    "local-command-stderr",
+];
+
+// ---------------------------------------------------------------------------
+// Extraction
+// ---------------------------------------------------------------------------
+
+/// Read the title of the session log at `path`: the first `summary` entry,
+/// or else the first human prompt, within the first [`TITLE_SCAN_LINES`]
+/// lines. Returns `None` if the file cannot be read or has neither.
+pub fn read_session_title(path: &Path) -> Option<String> {
+    let mut scan = TitleScan::default();
+    scan.resume(path).ok()?;
+    scan.title
+}
+
+/// The title `entry` gives its session, if it is the first one that does.
+fn entry_title(entry: &LogEntry) -> Option<String> {
+    match entry.entry_type {
+        EntryType::Summary => entry.summary.as_deref().and_then(clean_prompt),
+        _ => prompt_title(entry),
+    }
+}
+
+/// The prompt of a user entry typed by a human, cleaned with
+/// [`clean_prompt`]. `None` for tool results, meta entries, compaction
+/// summaries and interruption markers.
+pub fn prompt_title(entry: &LogEntry) -> Option<String> {
+    if entry.entry_type != EntryType::User
+        || entry.is_meta == Some(true)
+        || entry.is_compact_summary == Some(true)
+        || matches!(
+            SystemEvent::classify(entry),
+            Some(SystemEvent::Interrupted { .. })
+        )
+    {
+        return None;
+    }
+    let text = match &entry.message.as_ref()?.content {
+        Value::String(s) => s.clone(),
+        Value::Array(blocks) => blocks
+            .iter()
+            .filter(|b| b.get("type").and_then(Value::as_str) == Some("text"))
+            .filter_map(|b| b.get("text").and_then(Value::as_str))
+            .collect::<Vec<_>>()
+            .join(" "),
+        _ => return None,
+    };
+    clean_prompt(&text)
+}
+
+/// Strip injected markup from a prompt and collapse whitespace.
+///
+/// System reminders, command messages and local command output are
+/// dropped; a slash command becomes `/name args`. Returns `None` if
+/// nothing is left, or for a slash command without arguments (`/clear`
+/// says nothing about the session).
+pub fn clean_prompt(text: &str) -> Option<String> {
+    let mut text = text.to_string();
+    for tag in DROPPED_BLOCKS {
+        text = remove_blocks(&text, tag);
+    }
+    let text = match tag_content(&text, "command-name") {
+        Some(name) => match tag_content(&text, "command-args") {
+            Some(args) if !args.is_empty() => format!("{} {}", name, args),
+            _ => return None,
+        },
+        None => text,
+    };
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!text.is_empty()).then_some(text)
+}
+
+/// Remove every `<tag>...</tag>` block (an unclosed block runs to the end).
+fn remove_blocks(text: &str, tag: &str) -> String {
+    let open = format!("<{}>", tag);
+    let close = format!("</{}>", tag);
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(&open) {
+        out.push_str(&rest[..start]);
+        rest = match rest[start..].find(&close) {
+            Some(end) => &rest[start + end + close.len()..],
+            None => "",
+        };
+    }
+    out.push_str(rest);
+    out
+}
+
+/// The trimmed content of the first `<tag>...</tag>` block.
+fn tag_content(text: &str, tag: &str) -> Option<String> {
+    let open = format!("<{}>", tag);
+    let close = format!("</{}>", tag);
+    let start = text.find(&open)? + open.len();
+    let end = start + text[start..].find(&close)?;
+    Some(text[start..end].trim().to_string())
+}
+
+// ---------------------------------------------------------------------------
+// Cache
+// ---------------------------------------------------------------------------
+
+/// How far the title search through a log got.
+#[derive(Debug, Clone, Default)]
+struct TitleScan {
+    /// Byte offset just past the last complete line read.
+    offset: u64,
+    /// Number of complete lines read.
+    lines: usize,
+    title: Option<String>,
+}
+
+impl TitleScan {
+    /// Whether reading further can no longer change the title.
+    fn is_done(&self) -> bool {
+        self.title.is_some() || self.lines >= TITLE_SCAN_LINES
+    }
+
+    /// Continue the search in `path` from where it stopped. A trailing
+    /// line without a newline is still looked at, but left to be read
+    /// again, as it may be half written.
+    fn resume(&mut self, path: &Path) -> io::Result<()> {
+        let mut file = File::open(path)?;
+        file.seek(SeekFrom::Start(self.offset))?;
+        let mut reader = BufReader::new(file);
+        let mut line = Vec::new();
+        while !self.is_done() {
+            line.clear();
+            let read = reader.read_until(b'\n', &mut line)?;
+            if read == 0 {
+                break;
+            }
+            let complete = line.ends_with(b"\n");
+            self.title = std::str::from_utf8(&line)
+                .ok()
+                .and_then(|text| parse_jsonl_line(text.trim_end()).ok())
+                .and_then(|entry| entry_title(&entry));
+            if !complete {
+                break;
+            }
+            self.offset += read as u64;
+            self.lines += 1;
+        }
+        Ok(())
+    }
+}
+
+/// Titles of session logs, re-read only when a file's mtime changes.
+#[derive(Debug, Default)]
+pub struct TitleCache {
+    entries: HashMap<PathBuf, CachedTitle>,
+}
+
+#[derive(Debug)]
+struct CachedTitle {
+    modified: SystemTime,
+    scan: TitleScan,
+}
+
+impl TitleCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bring the title of `path` up to date, reading the file if it is new
+    /// to the cache or was modified since it was last read. Only lines
+    /// appended since then are read, unless the file shrank. Returns
+    /// whether the title changed.
+    pub fn refresh(&mut self, path: &Path) -> bool {
+        let Ok((modified, len)) = path.metadata().and_then(|m| Ok((m.modified()?, m.len()))) else {
+            return false;
+        };
+        let cached = self
+            .entries
+            .entry(path.to_path_buf())
+            .or_insert_with(|| CachedTitle {
+                modified: SystemTime::UNIX_EPOCH,
+                scan: TitleScan::default(),
+            });
+        if cached.modified == modified {
+            return false;
+        }
+        cached.modified = modified;
+        let previous = cached.scan.title.clone();
+        if len < cached.scan.offset {
+            // Truncated or rewritten: start over.
+            cached.scan = TitleScan::default();
+        }
+        if !cached.scan.is_done() && cached.scan.resume(path).is_err() {
+            cached.scan = TitleScan::default();
+        }
+        cached.scan.title != previous
+    }
+
+    /// The cached title of `path`, if any.
+    pub fn get(&self, path: &Path) -> Option<&str> {
+        self.entries.get(path)?.scan.title.as_deref()
+    }
+
+    /// Insert a title directly, bypassing the file.
+    #[cfg(test)]
+    pub fn insert(&mut self, path: PathBuf, title: &str) {
+        self.entries.insert(
+            path,
+            CachedTitle {
+                modified: SystemTime::UNIX_EPOCH,
+                scan: TitleScan {
+                    title: Some(title.to_string()),
+                    ..TitleScan::default()
+                },
+            },
+        );
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn user(content: &str) -> String {
+        serde_json::json!({"type": "user", "message": {"role": "user", "content": content}})
+            .to_string()
+    }
+
+    #[test]
+    fn test_clean_prompt() {
+        assert_eq!(
+            clean_prompt("<system-reminder>be nice</system-reminder>\n  Fix the   flaky test"),
+            Some("Fix the flaky test".to_string())
+        );
+        assert_eq!(
+            clean_prompt(
+                "<command-message>review is running</command-message>\n<command-name>/review</command-name>\n<command-args>src/main.rs</command-args>"
+            ),
+            Some("/review src/main.rs".to_string())
+        );
+        assert_eq!(
+            clean_prompt("<local-command-stdout>ok</local-command-stdout>"),
+            None
+        );
+        assert_eq!(clean_prompt("<command-name>/clear</command-name>"), None);
+        assert_eq!(clean_prompt("   "), None);
+    }
+
+    #[test]
+    fn test_title_prefers_summary() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("s.jsonl");
+        let lines = [
+            r#"{"type": "summary", "summary": "Flaky auth test", "leafUuid": "x"}"#.to_string(),
+            user("Fix the flaky test"),
+        ];
+        fs::write(&path, lines.join("\n")).unwrap();
+        assert_eq!(
+            read_session_title(&path).as_deref(),
+            Some("Flaky auth test")
+        );
+    }
+
+    #[test]
+    fn test_title_skips_injected_prompts() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("s.jsonl");
+        let lines = [
+            r#"{"type": "user", "isMeta": true, "message": {"role": "user", "content": "Caveat: local commands"}}"#.to_string(),
+            user("<local-command-stdout></local-command-stdout>"),
+            r#"{"type": "user", "message": {"role": "user", "content": [{"type": "tool_result", "tool_use_id": "t", "content": "ok"}]}}"#.to_string(),
+            user("[Request interrupted by user]"),
+            user("<system-reminder>ctx</system-reminder>Add a --json flag"),
+        ];
+        fs::write(&path, lines.join("\n")).unwrap();
+        assert_eq!(
+            read_session_title(&path).as_deref(),
+            Some("Add a --json flag")
+        );
+    }
+
+    #[test]
+    fn test_cache_rereads_on_mtime_change() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("s.jsonl");
+        let mut cache = TitleCache::new();
+
+        fs::write(&path, "").unwrap();
+        assert!(!cache.refresh(&path));
+        assert_eq!(cache.get(&path), None);
+
+        fs::write(&path, user("First prompt")).unwrap();
+        let later = SystemTime::now() + std::time::Duration::from_secs(5);
+        File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        assert!(cache.refresh(&path));
+        assert!(!cache.refresh(&path));
+        assert_eq!(cache.get(&path), Some("First prompt"));
+        assert_eq!(cache.get(&tmp.path().join("missing.jsonl")), None);
+    }
+
+    #[test]
+    fn test_cache_reads_only_appended_lines() {
+        use std::io::Write;
+
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("s.jsonl");
+        let mut cache = TitleCache::new();
+        let touch = |secs: u64| {
+            File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(secs))
+                .unwrap();
+        };
+        let append = |text: &str| {
+            let mut file = File::options().append(true).open(&path).unwrap();
+            file.write_all(text.as_bytes()).unwrap();
+        };
+        let offset = |cache: &TitleCache| cache.entries[&path].scan.offset;
+
+        let reply = r#"{"type": "assistant", "message": {"role": "assistant", "content": "hi"}}"#;
+        fs::write(&path, format!("{}\n", reply)).unwrap();
+        touch(1);
+        assert!(!cache.refresh(&path));
+        let first = offset(&cache);
+        assert_eq!(first, fs::metadata(&path).unwrap().len());
+
+        // A half-written prompt is not consumed...
+        let prompt = user("Add a --json flag");
+        let (head, tail) = prompt.split_at(10);
+        append(head);
+        touch(2);
+        assert!(!cache.refresh(&path));
+        assert_eq!(offset(&cache), first);
+
+        // ...and is read from its start once complete.
+        append(&format!("{}\n", tail));
+        touch(3);
+        assert!(cache.refresh(&path));
+        assert_eq!(cache.get(&path), Some("Add a --json flag"));
+
+        // A truncated file is scanned again from the top.
+        fs::write(&path, format!("{}\n", user("Start over"))).unwrap();
+        touch(4);
+        assert!(cache.refresh(&path));
+        assert_eq!(cache.get(&path), Some("Start over"));
+    }
+}
@@ -16,10 +16,18 @@ use notify::{EventKind, RecursiveMode, Watcher};
 use tokio::sync::mpsc;
 
 use crate::cli::{StreamArgs, Theme};
-use crate::content_render::{has_renderable_content, render_content_blocks, RenderedLine};
+use crate::content_render::{
+    collapsed_thinking, collect_tool_names, compact_boundary_label, has_renderable_content,
+    render_content_blocks_with_tools, RenderedLine, ToolNames, REDACTED_THINKING_LABEL,
+};
+use crate::filter::MessageFilter;
+use crate::filter_expr::FilterExpr;
 use crate::log_entry::{parse_jsonl_line, EntryType, LogEntry};
 use crate::replay::is_visible_type;
+use crate::symbols::Symbols;
+use crate::system_event::SystemEvent;
 use crate::watcher::{read_new_entries, FileWatchState};
+use crate::{log_debug, log_warn};
 
 // ---------------------------------------------------------------------------
 // Configuration
@@ -31,8 +39,10 @@ struct StreamConfig {
     path: PathBuf,
     /// Number of visible messages to replay before live tailing.
     replay_count: usize,
-    /// Show progress entries and parse errors.
-    verbose: bool,
+    /// Only entries matching this expression are printed (`--filter`).
+    filter: Option<FilterExpr>,
+    /// Print thinking blocks in full (`--thinking`).
+    show_thinking: bool,
     /// ANSI color codes (empty strings when piping).
     colors: AnsiColors,
     /// Whether stdout is a terminal (controls emoji vs ASCII indicators).
@@ -58,6 +68,10 @@ struct AnsiColors {
     role_system: &'static str,
     /// Tool use color.
     tool_use: &'static str,
+    /// Tool result color.
+    tool_result: &'static str,
+    /// Failed tool result color.
+    tool_error: &'static str,
     /// Default text color.
     text: &'static str,
     /// Reset all attributes.
@@ -74,6 +88,8 @@ impl AnsiColors {
                 role_assistant: "\x1b[32m", // green
                 role_system: "\x1b[33m",    // yellow
                 tool_use: "\x1b[33m",       // yellow
+                tool_result: "\x1b[36m",    // cyan
+                tool_error: "\x1b[31m",     // red
                 text: "\x1b[0m",            // default
                 reset: "\x1b[0m",
             },
@@ -83,6 +99,8 @@ impl AnsiColors {
                 role_assistant: "\x1b[32m", // green
                 role_system: "\x1b[35m",    // magenta
                 tool_use: "\x1b[35m",       // magenta
+                tool_result: "\x1b[90m",    // gray
+                tool_error: "\x1b[31m",     // red
                 text: "\x1b[0m",            // default
                 reset: "\x1b[0m",
             },
@@ -97,6 +115,8 @@ impl AnsiColors {
             role_assistant: "",
             role_system: "",
             tool_use: "",
+            tool_result: "",
+            tool_error: "",
             text: "",
             reset: "",
         }
@@ -122,7 +142,8 @@ pub async fn run_stream(args: &StreamArgs) -> Result<(), Box<dyn std::error::Err
     let config = StreamConfig {
         path: args.file.clone(),
         replay_count: args.replay,
-        verbose: args.verbose,
+        filter: args.filter.clone(),
+        show_thinking: args.thinking,
         colors,
         is_tty,
     };
@@ -134,10 +155,10 @@ pub async fn run_stream(args: &StreamArgs) -> Result<(), Box<dyn std::error::Err
     }
 
     // Phase 1: Replay
-    let eof_offset = replay_phase(&config)?;
+    let (eof_offset, tool_names) = replay_phase(&config)?;
 
     // Phase 2: Live tail
-    live_tail_phase(&config, eof_offset).await?;
+    live_tail_phase(&config, eof_offset, tool_names).await?;
 
     Ok(())
 }
@@ -148,21 +169,22 @@ pub async fn run_stream(args: &StreamArgs) -> Result<(), Box<dyn std::error::Err
 
 /// Read the entire file, filter to visible entries, print the last N.
 ///
-/// Returns the byte offset at EOF so that live tailing can start from there.
-fn replay_phase(config: &StreamConfig) -> Result<u64, Box<dyn std::error::Error>> {
+/// Returns the byte offset at EOF so that live tailing can start from there,
+/// along with the tool names seen so far (tool results printed during live
+/// tailing may refer to tool calls made before the replay window).
+fn replay_phase(config: &StreamConfig) -> Result<(u64, ToolNames), Box<dyn std::error::Error>> {
     let file = std::fs::File::open(&config.path)?;
     let file_len = file.metadata()?.len();
     let reader = BufReader::new(file);
 
     let mut all_visible: Vec<LogEntry> = Vec::new();
+    let mut tool_names = ToolNames::new();
 
     for line_result in reader.lines() {
         let line = match line_result {
             Ok(l) => l,
             Err(e) => {
-                if config.verbose {
-                    eprintln!("cc-tail: read error: {}", e);
-                }
+                log_warn!("read error: {}", e);
                 continue;
             }
         };
@@ -175,9 +197,7 @@ fn replay_phase(config: &StreamConfig) -> Result<u64, Box<dyn std::error::Error>
         let entry = match parse_jsonl_line(trimmed) {
             Ok(e) => e,
             Err(e) => {
-                if config.verbose {
-                    eprintln!("cc-tail: skipping malformed line: {}", e);
-                }
+                log_debug!("skipping malformed line: {}", e);
                 continue;
             }
         };
@@ -186,6 +206,15 @@ fn replay_phase(config: &StreamConfig) -> Result<u64, Box<dyn std::error::Error>
             continue;
         }
 
+        // Entries rejected by `--filter` still contribute tool names so
+        // that printed results can be attributed to their tool.
+        if !passes_filter(&entry, config) {
+            if let Some(ref msg) = entry.message {
+                collect_tool_names(&msg.content, &mut tool_names);
+            }
+            continue;
+        }
+
         all_visible.push(entry);
     }
 
@@ -193,6 +222,13 @@ fn replay_phase(config: &StreamConfig) -> Result<u64, Box<dyn std::error::Error>
     let start = all_visible.len().saturating_sub(config.replay_count);
     let replay_entries = &all_visible[start..];
 
+    // Record tool calls that precede the replay window.
+    for entry in &all_visible[..start] {
+        if let Some(ref msg) = entry.message {
+            collect_tool_names(&msg.content, &mut tool_names);
+        }
+    }
+
     let stdout = io::stdout();
     let mut out = stdout.lock();
 
@@ -200,13 +236,13 @@ fn replay_phase(config: &StreamConfig) -> Result<u64, Box<dyn std::error::Error>
         if should_skip_entry(entry) {
             continue;
         }
-        if print_entry(&mut out, entry, config).is_err() {
+        if print_entry(&mut out, entry, config, &mut tool_names).is_err() {
             // BrokenPipe — exit cleanly.
             std::process::exit(0);
         }
     }
 
-    Ok(file_len)
+    Ok((file_len, tool_names))
 }
 
 // ---------------------------------------------------------------------------
@@ -220,6 +256,7 @@ fn replay_phase(config: &StreamConfig) -> Result<u64, Box<dyn std::error::Error>
 async fn live_tail_phase(
     config: &StreamConfig,
     start_offset: u64,
+    mut tool_names: ToolNames,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let mut state = FileWatchState::new_with_offset(start_offset);
 
@@ -268,7 +305,7 @@ async fn live_tail_phase(
             maybe_event = rx.recv() => {
                 match maybe_event {
                     Some(()) => {
-                        let entries = read_new_entries(&config.path, &mut state, config.verbose);
+                        let entries = read_new_entries(&config.path, &mut state);
                         let mut out = stdout.lock();
                         for entry in &entries {
                             if !is_visible_type(entry) {
@@ -277,7 +314,13 @@ async fn live_tail_phase(
                             if should_skip_entry(entry) {
                                 continue;
                             }
-                            if print_entry(&mut out, entry, config).is_err() {
+                            if !passes_filter(entry, config) {
+                                if let Some(ref msg) = entry.message {
+                                    collect_tool_names(&msg.content, &mut tool_names);
+                                }
+                                continue;
+                            }
+                            if print_entry(&mut out, entry, config, &mut tool_names).is_err() {
                                 // BrokenPipe — exit cleanly.
                                 std::process::exit(0);
                             }
@@ -304,8 +347,8 @@ async fn live_tail_phase(
 
 /// Returns `true` if the entry should be skipped (not printed).
 ///
-/// User entries whose content consists entirely of `tool_result` blocks
-/// produce no visible output and would otherwise appear as empty lines.
+/// User entries whose content produces no visible output (e.g. an empty
+/// block array) would otherwise appear as empty lines.
 fn should_skip_entry(entry: &LogEntry) -> bool {
     if entry.entry_type != EntryType::User {
         return false;
@@ -316,15 +359,82 @@ fn should_skip_entry(entry: &LogEntry) -> bool {
     }
 }
 
+/// Returns `true` if the entry matches the `--filter` expression (or no
+/// expression was given).
+fn passes_filter(entry: &LogEntry, config: &StreamConfig) -> bool {
+    config.filter.as_ref().is_none_or(|f| f.matches(entry))
+}
+
 // ---------------------------------------------------------------------------
 // Formatting
 // ---------------------------------------------------------------------------
 
 /// Print a single log entry to the given writer.
 ///
+/// `tool_names` accumulates `tool_use` IDs across calls so that tool results
+/// can be summarized per tool.
+///
 /// Returns `Err` on I/O failure (typically `BrokenPipe`).
-fn print_entry<W: Write>(out: &mut W, entry: &LogEntry, config: &StreamConfig) -> io::Result<()> {
+fn print_entry<W: Write>(
+    out: &mut W,
+    entry: &LogEntry,
+    config: &StreamConfig,
+    tool_names: &mut ToolNames,
+) -> io::Result<()> {
     let ts = format_timestamp(entry.timestamp.as_deref());
+
+    // Compaction boundaries get a divider instead of a header and content.
+    if entry.is_compact_boundary() {
+        let rule = if config.is_tty {
+            "\u{2500}\u{2500}\u{2500}"
+        } else {
+            "---"
+        };
+        writeln!(
+            out,
+            "{}{}{} {}{} {} {}{}",
+            config.colors.timestamp,
+            ts,
+            config.colors.reset,
+            config.colors.role_system,
+            rule,
+            compact_boundary_label(entry.compact_metadata.as_ref()),
+            rule,
+            config.colors.reset,
+        )?;
+        return out.flush();
+    }
+
+    // API errors, hook output, interruptions and other system notices get
+    // a one-line summary.
+    if let Some(event) = SystemEvent::classify_notice(entry) {
+        let symbols = Symbols::new(!config.is_tty);
+        let symbol = match event {
+            SystemEvent::ApiError { .. } => symbols.api_error,
+            SystemEvent::Hook { .. } => symbols.hook,
+            SystemEvent::Interrupted { .. } => symbols.interrupted,
+            _ => symbols.system_info,
+        };
+        let color = match event {
+            _ if event.is_error() => config.colors.tool_error,
+            SystemEvent::Hook { .. } => config.colors.tool_use,
+            SystemEvent::Interrupted { .. } => config.colors.role_system,
+            _ => config.colors.timestamp,
+        };
+        writeln!(
+            out,
+            "{}{}{} {}{} {}{}",
+            config.colors.timestamp,
+            ts,
+            config.colors.reset,
+            color,
+            symbol,
+            event.label(),
+            config.colors.reset,
+        )?;
+        return out.flush();
+    }
+
     let role = entry
         .message
         .as_ref()
@@ -346,7 +456,8 @@ fn print_entry<W: Write>(out: &mut W, entry: &LogEntry, config: &StreamConfig) -
 
     // Print content lines
     if let Some(ref msg) = entry.message {
-        let rendered = render_content_blocks(&msg.content);
+        collect_tool_names(&msg.content, tool_names);
+        let rendered = render_content_blocks_with_tools(&msg.content, tool_names);
         for line in &rendered {
             match line {
                 RenderedLine::Text(text) => {
@@ -363,6 +474,39 @@ fn print_entry<W: Write>(out: &mut W, entry: &LogEntry, config: &StreamConfig) -
                         config.colors.tool_use, summary, config.colors.reset,
                     )?;
                 }
+                RenderedLine::ToolResult { summary, is_error } => {
+                    let color = if *is_error {
+                        config.colors.tool_error
+                    } else {
+                        config.colors.tool_result
+                    };
+                    writeln!(out, "  {}{}{}", color, summary, config.colors.reset)?;
+                }
+                RenderedLine::Thinking(text) if config.show_thinking => {
+                    for part in text.lines() {
+                        writeln!(
+                            out,
+                            "  {}{}{}",
+                            config.colors.timestamp, part, config.colors.reset,
+                        )?;
+                    }
+                }
+                RenderedLine::Thinking(text) => {
+                    writeln!(
+                        out,
+                        "  {}{}{}",
+                        config.colors.timestamp,
+                        collapsed_thinking(text),
+                        config.colors.reset,
+                    )?;
+                }
+                RenderedLine::RedactedThinking => {
+                    writeln!(
+                        out,
+                        "  {}{}{}",
+                        config.colors.timestamp, REDACTED_THINKING_LABEL, config.colors.reset,
+                    )?;
+                }
                 RenderedLine::Unknown(label) => {
                     writeln!(out, "  {}", label)?;
                 }
@@ -509,7 +653,8 @@ mod tests {
         StreamConfig {
             path: PathBuf::from("/dev/null"),
             replay_count: 0,
-            verbose: false,
+            filter: None,
+            show_thinking: false,
             colors: AnsiColors::for_tty(&Theme::Dark),
             is_tty: true,
         }
@@ -519,7 +664,8 @@ mod tests {
         StreamConfig {
             path: PathBuf::from("/dev/null"),
             replay_count: 0,
-            verbose: false,
+            filter: None,
+            show_thinking: false,
             colors: AnsiColors::for_pipe(),
             is_tty: false,
         }
@@ -594,7 +740,7 @@ mod tests {
         let entry = parse_jsonl_line(&json).unwrap();
 
         let mut buf = Vec::new();
-        print_entry(&mut buf, &entry, &config).unwrap();
+        print_entry(&mut buf, &entry, &config, &mut ToolNames::new()).unwrap();
         let output = String::from_utf8(buf).unwrap();
 
         assert!(output.contains(&expected_local_time(ts)));
@@ -613,7 +759,7 @@ mod tests {
         let entry = parse_jsonl_line(&json).unwrap();
 
         let mut buf = Vec::new();
-        print_entry(&mut buf, &entry, &config).unwrap();
+        print_entry(&mut buf, &entry, &config, &mut ToolNames::new()).unwrap();
         let output = String::from_utf8(buf).unwrap();
 
         assert!(output.contains(&expected_local_time(ts)));
@@ -630,13 +776,84 @@ mod tests {
         .unwrap();
 
         let mut buf = Vec::new();
-        print_entry(&mut buf, &entry, &config).unwrap();
+        print_entry(&mut buf, &entry, &config, &mut ToolNames::new()).unwrap();
         let output = String::from_utf8(buf).unwrap();
 
         assert!(output.contains("[Read]"));
         assert!(output.contains("src/auth/mod.rs"));
     }
 
+    #[test]
+    fn test_print_entry_thinking_collapsed_unless_flag() {
+        let mut config = make_config_pipe();
+        let entry = parse_jsonl_line(
+            r#"{"type": "assistant", "timestamp": "2025-01-15T10:30:16Z", "message": {"role": "assistant", "content": [{"type": "thinking", "thinking": "First idea.\nSecond idea."}, {"type": "redacted_thinking", "data": "xyz"}]}}"#,
+        )
+        .unwrap();
+
+        let mut buf = Vec::new();
+        print_entry(&mut buf, &entry, &config, &mut ToolNames::new()).unwrap();
+        let output = String::from_utf8(buf).unwrap();
+        assert!(output.contains("[thinking] First idea."));
+        assert!(!output.contains("Second idea."));
+        assert!(output.contains(REDACTED_THINKING_LABEL));
+
+        config.show_thinking = true;
+        let mut buf = Vec::new();
+        print_entry(&mut buf, &entry, &config, &mut ToolNames::new()).unwrap();
+        let output = String::from_utf8(buf).unwrap();
+        assert!(output.contains("  First idea.\n  Second idea.\n"));
+        assert!(!output.contains("[thinking]"));
+    }
+
+    #[test]
+    fn test_print_entry_compact_boundary_divider() {
+        let config = make_config_pipe();
+        let entry = parse_jsonl_line(
+            r#"{"type": "system", "subtype": "compact_boundary", "timestamp": "2025-01-15T10:30:16Z", "content": "Conversation compacted", "compactMetadata": {"trigger": "manual", "preTokens": 48200}}"#,
+        )
+        .unwrap();
+
+        let mut buf = Vec::new();
+        print_entry(&mut buf, &entry, &config, &mut ToolNames::new()).unwrap();
+        let output = String::from_utf8(buf).unwrap();
+        assert!(output.ends_with(" --- Context compacted (manual, 48.2k tokens) ---\n"));
+        assert!(!output.contains("[S]"));
+    }
+
+    #[test]
+    fn test_print_entry_system_events() {
+        let config = make_config_pipe();
+        let print = |json: &str| {
+            let mut buf = Vec::new();
+            print_entry(
+                &mut buf,
+                &parse_jsonl_line(json).unwrap(),
+                &config,
+                &mut ToolNames::new(),
+            )
+            .unwrap();
+            String::from_utf8(buf).unwrap()
+        };
+
+        let api_error = print(
+            r#"{"type": "system", "subtype": "api_error", "error": {"status": 529, "error": {"type": "overloaded_error", "message": "Overloaded"}}, "retryInMs": 2000, "retryAttempt": 4, "maxRetries": 10}"#,
+        );
+        assert!(api_error
+            .ends_with(" ! API error 529 overloaded_error: Overloaded (retry 4/10 in 2.0s)\n"));
+
+        let interrupted = print(
+            r#"{"type": "user", "message": {"role": "user", "content": [{"type": "text", "text": "[Request interrupted by user]"}]}}"#,
+        );
+        assert!(interrupted.ends_with(" # [Request interrupted by user]\n"));
+        assert!(!interrupted.contains("[H]"));
+
+        // Plain system messages keep the regular header and body.
+        let legacy =
+            print(r#"{"type": "system", "message": {"role": "user", "content": "sys prompt"}}"#);
+        assert!(legacy.contains("[S]"));
+    }
+
     #[test]
     fn test_print_entry_tty_has_ansi() {
         let config = make_config_tty();
@@ -646,7 +863,7 @@ mod tests {
         .unwrap();
 
         let mut buf = Vec::new();
-        print_entry(&mut buf, &entry, &config).unwrap();
+        print_entry(&mut buf, &entry, &config, &mut ToolNames::new()).unwrap();
         let output = String::from_utf8(buf).unwrap();
 
         // Should contain ANSI escape sequences
@@ -654,6 +871,52 @@ mod tests {
         assert!(output.contains("\u{1f9d1}")); // 🧑 emoji
     }
 
+    #[test]
+    fn test_print_entry_tool_result_uses_earlier_tool_name() {
+        let config = make_config_pipe();
+        let mut tool_names = ToolNames::new();
+        let call = parse_jsonl_line(
+            r#"{"type": "assistant", "message": {"role": "assistant", "content": [{"type": "tool_use", "id": "t1", "name": "Bash", "input": {"command": "cargo test"}}]}}"#,
+        )
+        .unwrap();
+        let result = parse_jsonl_line(
+            r#"{"type": "user", "message": {"role": "user", "content": [{"type": "tool_result", "tool_use_id": "t1", "content": "Exit code 101\ntest result: FAILED", "is_error": true}]}}"#,
+        )
+        .unwrap();
+
+        let mut buf = Vec::new();
+        print_entry(&mut buf, &call, &config, &mut tool_names).unwrap();
+        print_entry(&mut buf, &result, &config, &mut tool_names).unwrap();
+        let output = String::from_utf8(buf).unwrap();
+
+        assert!(output.contains("[Bash] cargo test"));
+        assert!(output.contains("[Bash] exit 101: test result: FAILED"));
+    }
+
+    #[test]
+    fn test_print_entry_tool_error_is_red_on_tty() {
+        let config = make_config_tty();
+        let entry = parse_jsonl_line(
+            r#"{"type": "user", "message": {"role": "user", "content": [{"type": "tool_result", "tool_use_id": "t9", "content": "boom", "is_error": true}]}}"#,
+        )
+        .unwrap();
+
+        let mut buf = Vec::new();
+        print_entry(&mut buf, &entry, &config, &mut ToolNames::new()).unwrap();
+        let output = String::from_utf8(buf).unwrap();
+
+        assert!(output.contains("\x1b[31m[result] error: boom"));
+    }
+
+    #[test]
+    fn test_tool_result_only_user_entry_not_skipped() {
+        let entry = parse_jsonl_line(
+            r#"{"type": "user", "message": {"role": "user", "content": [{"type": "tool_result", "tool_use_id": "t1", "content": "ok"}]}}"#,
+        )
+        .unwrap();
+        assert!(!should_skip_entry(&entry));
+    }
+
     #[test]
     fn test_print_entry_no_message() {
         let config = make_config_pipe();
@@ -662,7 +925,7 @@ mod tests {
         let entry = parse_jsonl_line(&json).unwrap();
 
         let mut buf = Vec::new();
-        print_entry(&mut buf, &entry, &config).unwrap();
+        print_entry(&mut buf, &entry, &config, &mut ToolNames::new()).unwrap();
         let output = String::from_utf8(buf).unwrap();
 
         // Should still print the header line
@@ -679,7 +942,7 @@ mod tests {
         .unwrap();
 
         let mut buf = Vec::new();
-        print_entry(&mut buf, &entry, &config).unwrap();
+        print_entry(&mut buf, &entry, &config, &mut ToolNames::new()).unwrap();
         let output = String::from_utf8(buf).unwrap();
 
         assert!(output.contains("System prompt text"));
@@ -728,12 +991,13 @@ mod tests {
         let config = StreamConfig {
             path,
             replay_count: 20,
-            verbose: false,
+            filter: None,
+            show_thinking: false,
             colors: AnsiColors::for_pipe(),
             is_tty: false,
         };
 
-        let offset = replay_phase(&config).unwrap();
+        let (offset, _) = replay_phase(&config).unwrap();
         assert_eq!(offset, 0);
     }
 
@@ -750,12 +1014,13 @@ mod tests {
         let config = StreamConfig {
             path,
             replay_count: 20,
-            verbose: false,
+            filter: None,
+            show_thinking: false,
             colors: AnsiColors::for_pipe(),
             is_tty: false,
         };
 
-        let offset = replay_phase(&config).unwrap();
+        let (offset, _) = replay_phase(&config).unwrap();
         assert_eq!(offset, expected_len);
     }
 
@@ -778,7 +1043,8 @@ mod tests {
         let config = StreamConfig {
             path,
             replay_count: 2,
-            verbose: false,
+            filter: None,
+            show_thinking: false,
             colors: AnsiColors::for_pipe(),
             is_tty: false,
         };
@@ -786,10 +1052,35 @@ mod tests {
         // This test verifies the function runs without error.
         // Actual output goes to stdout which we can't easily capture here,
         // but print_entry is tested separately.
-        let offset = replay_phase(&config).unwrap();
+        let (offset, _) = replay_phase(&config).unwrap();
         assert_eq!(offset, content.len() as u64);
     }
 
+    #[test]
+    fn test_replay_phase_filter_keeps_tool_names_of_skipped_entries() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("session.jsonl");
+        let content = concat!(
+            r#"{"type": "assistant", "message": {"role": "assistant", "content": [{"type": "tool_use", "id": "t1", "name": "Bash", "input": {}}]}}"#,
+            "\n",
+            r#"{"type": "user", "message": {"role": "user", "content": [{"type": "tool_result", "tool_use_id": "t1", "content": "ok"}]}}"#,
+            "\n",
+        );
+        std::fs::write(&path, content).unwrap();
+
+        let config = StreamConfig {
+            path,
+            replay_count: 20,
+            filter: Some(FilterExpr::parse("role:user").unwrap()),
+            show_thinking: false,
+            colors: AnsiColors::for_pipe(),
+            is_tty: false,
+        };
+
+        let (_, tool_names) = replay_phase(&config).unwrap();
+        assert_eq!(tool_names.get("t1").map(String::as_str), Some("Bash"));
+    }
+
     // -- replay_phase with interleaved valid/invalid JSON lines --------------
 
     #[test]
@@ -818,13 +1109,14 @@ mod tests {
         let config = StreamConfig {
             path,
             replay_count: 20,
-            verbose: false,
+            filter: None,
+            show_thinking: false,
             colors: AnsiColors::for_pipe(),
             is_tty: false,
         };
 
         // replay_phase should not panic or error — bad lines are silently skipped.
-        let offset = replay_phase(&config).unwrap();
+        let (offset, _) = replay_phase(&config).unwrap();
 
         // EOF offset should equal the total file size.
         assert_eq!(offset, expected_len);
@@ -14,12 +14,38 @@
 pub struct Symbols {
     /// Active session marker in sidebar (Unicode: `\u{25cf}` / ASCII: `*`)
     pub active_marker: &'static str,
+    /// Idle session marker in sidebar: process alive, log quiet
+    /// (Unicode: `\u{25cb}` / ASCII: `o`)
+    pub idle_marker: &'static str,
     /// Tree connector for child agents (Unicode: `\u{2514}` / ASCII: `` `- ``)
     pub tree_connector: &'static str,
     /// Progress/play indicator (Unicode: `\u{25b6}` / ASCII: `>`)
     pub progress_indicator: &'static str,
     /// Search cursor block (Unicode: `\u{2588}` / ASCII: `_`)
     pub search_cursor: &'static str,
+    /// Spinner frames for pending tool calls (Unicode: braille dots / ASCII: `|/-\\`)
+    pub spinner_frames: &'static [&'static str],
+    /// Finished tool call mark (Unicode: `\u{2713}` / ASCII: `ok`)
+    pub tool_ok: &'static str,
+    /// Failed tool call mark (Unicode: `\u{2717}` / ASCII: `x`)
+    pub tool_failed: &'static str,
+    /// Change arrow, e.g. old value to new value (Unicode: `\u{2192}` / ASCII: `->`)
+    pub arrow: &'static str,
+    /// Horizontal rule for dividers such as compaction boundaries
+    /// (Unicode: `\u{2500}` / ASCII: `-`)
+    pub divider: &'static str,
+    /// API error system entry (Unicode: `\u{26a0}` / ASCII: `!`)
+    pub api_error: &'static str,
+    /// Hook output system entry (Unicode: `\u{21aa}` / ASCII: `h`)
+    pub hook: &'static str,
+    /// Interrupted request (Unicode: `\u{25a0}` / ASCII: `#`)
+    pub interrupted: &'static str,
+    /// Other system entry (Unicode: `\u{2139}` / ASCII: `i`)
+    pub system_info: &'static str,
+    /// Start of a conversation branch in the tree view (Unicode: `\u{251c}` / ASCII: `+`)
+    pub branch_start: &'static str,
+    /// Continuation of a conversation branch in the tree view (Unicode: `\u{2502}` / ASCII: `|`)
+    pub branch_line: &'static str,
 }
 
 impl Symbols {
@@ -39,9 +65,24 @@ impl Symbols {
     pub fn unicode() -> Self {
         Self {
             active_marker: "\u{25cf}",      // ●
+            idle_marker: "\u{25cb}",        // ○
             tree_connector: "\u{2514}",     // └
             progress_indicator: "\u{25b6}", // ▶
             search_cursor: "\u{2588}",      // █
+            spinner_frames: &[
+                "\u{280b}", "\u{2819}", "\u{2839}", "\u{2838}", "\u{283c}", "\u{2834}", "\u{2826}",
+                "\u{2827}", "\u{2807}", "\u{280f}",
+            ], // ⠋⠙⠹⠸⠼⠴⠦⠧⠇⠏
+            tool_ok: "\u{2713}",            // ✓
+            tool_failed: "\u{2717}",        // ✗
+            arrow: "\u{2192}",              // →
+            divider: "\u{2500}",            // ─
+            api_error: "\u{26a0}",          // ⚠
+            hook: "\u{21aa}",               // ↪
+            interrupted: "\u{25a0}",        // ■
+            system_info: "\u{2139}",        // ℹ
+            branch_start: "\u{251c}",       // ├
+            branch_line: "\u{2502}",        // │
         }
     }
 
@@ -49,9 +90,21 @@ impl Symbols {
     pub fn ascii() -> Self {
         Self {
             active_marker: "*",
+            idle_marker: "o",
             tree_connector: "`-",
             progress_indicator: ">",
             search_cursor: "_",
+            spinner_frames: &["|", "/", "-", "\\"],
+            tool_ok: "ok",
+            tool_failed: "x",
+            arrow: "->",
+            divider: "-",
+            api_error: "!",
+            hook: "h",
+            interrupted: "#",
+            system_info: "i",
+            branch_start: "+",
+            branch_line: "|",
         }
     }
 }
@@ -68,17 +121,41 @@ mod tests {
     fn test_symbols_new_false_is_unicode() {
         let s = Symbols::new(false);
         assert_eq!(s.active_marker, "\u{25cf}");
+        assert_eq!(s.idle_marker, "\u{25cb}");
         assert_eq!(s.tree_connector, "\u{2514}");
         assert_eq!(s.progress_indicator, "\u{25b6}");
         assert_eq!(s.search_cursor, "\u{2588}");
+        assert_eq!(s.spinner_frames.len(), 10);
+        assert_eq!(s.tool_ok, "\u{2713}");
+        assert_eq!(s.tool_failed, "\u{2717}");
+        assert_eq!(s.arrow, "\u{2192}");
+        assert_eq!(s.divider, "\u{2500}");
+        assert_eq!(s.api_error, "\u{26a0}");
+        assert_eq!(s.hook, "\u{21aa}");
+        assert_eq!(s.interrupted, "\u{25a0}");
+        assert_eq!(s.system_info, "\u{2139}");
+        assert_eq!(s.branch_start, "\u{251c}");
+        assert_eq!(s.branch_line, "\u{2502}");
     }
 
     #[test]
     fn test_symbols_new_true_is_ascii() {
         let s = Symbols::new(true);
         assert_eq!(s.active_marker, "*");
+        assert_eq!(s.idle_marker, "o");
         assert_eq!(s.tree_connector, "`-");
         assert_eq!(s.progress_indicator, ">");
         assert_eq!(s.search_cursor, "_");
+        assert_eq!(s.spinner_frames, &["|", "/", "-", "\\"]);
+        assert_eq!(s.tool_ok, "ok");
+        assert_eq!(s.tool_failed, "x");
+        assert_eq!(s.arrow, "->");
+        assert_eq!(s.divider, "-");
+        assert_eq!(s.api_error, "!");
+        assert_eq!(s.hook, "h");
+        assert_eq!(s.interrupted, "#");
+        assert_eq!(s.system_info, "i");
+        assert_eq!(s.branch_start, "+");
+        assert_eq!(s.branch_line, "|");
     }
 }
@@ -0,0 +1,351 @@
+//! Classification of `system` entries and interruption markers.
+//!
+//! Claude Code writes `system` entries for several unrelated events: API
+//! errors it is about to retry, hook output, context compaction, and plain
+//! informational notices. When the user interrupts a request, a user entry
+//! with a bracketed `[Request interrupted by user]` marker is written
+//! instead. [`SystemEvent::classify`] turns both into a typed value so the
+//! log stream can give each its own symbol and color, and the "errors only"
+//! filter can find them.
+
+use serde_json::Value;
+
+use crate::log_entry::{EntryType, LogEntry};
+
+/// Prefix of the text Claude Code writes when the user interrupts a request
+/// (`[Request interrupted by user]`, `[Request interrupted by user for tool
+/// use]`).
+const INTERRUPTED_PREFIX: &str = "[Request interrupted by user";
+
+/// Hook events, as they prefix hook output (e.g. `PreToolUse:Bash ...`).
+const HOOK_EVENTS: &[&str] = &[
+    "PreToolUse",
+    "PostToolUse",
+    "Notification",
+    "UserPromptSubmit",
+    "Stop",
+    "SubagentStop",
+    "PreCompact",
+    "SessionStart",
+    "SessionEnd",
+];
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// A classified `system` entry or interruption marker.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SystemEvent {
+    /// A failed API request (`subtype: api_error`).
+    ApiError {
+        /// HTTP status, e.g. `529`.
+        status: Option<u64>,
+        /// Error type and message, e.g. `overloaded_error: Overloaded`.
+        message: String,
+        /// Delay before the next attempt.
+        retry_in_ms: Option<f64>,
+        attempt: Option<u32>,
+        max_retries: Option<u32>,
+    },
+    /// Output of a hook command.
+    Hook { text: String, is_error: bool },
+    /// The user interrupted a request.
+    Interrupted { text: String },
+    /// Context compaction boundary (drawn as a divider).
+    CompactBoundary,
+    /// Any other system entry.
+    Info { text: String, is_error: bool },
+}
+
+impl SystemEvent {
+    /// Classify `entry`. Returns `None` for entries that are neither
+    /// `system` entries nor interruption markers.
+    pub fn classify(entry: &LogEntry) -> Option<SystemEvent> {
+        match entry.entry_type {
+            EntryType::System => Some(classify_system(entry)),
+            EntryType::User => interruption_text(entry).map(|text| SystemEvent::Interrupted {
+                text: text.to_string(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Like [`classify`](Self::classify), but only for events rendered as a
+    /// one-line notice. `Info` events of entries carrying a `message` or no
+    /// text at all are left to the regular message rendering.
+    pub fn classify_notice(entry: &LogEntry) -> Option<SystemEvent> {
+        Self::classify(entry).filter(|event| match event {
+            SystemEvent::Info { text, .. } => entry.message.is_none() && !text.is_empty(),
+            _ => true,
+        })
+    }
+
+    /// Whether the event reports a failure.
+    pub fn is_error(&self) -> bool {
+        match self {
+            SystemEvent::ApiError { .. } => true,
+            SystemEvent::Hook { is_error, .. } | SystemEvent::Info { is_error, .. } => *is_error,
+            SystemEvent::Interrupted { .. } | SystemEvent::CompactBoundary => false,
+        }
+    }
+
+    /// One-line description for the log stream, e.g.
+    /// `"API error 529 overloaded_error: Overloaded (retry 2/10 in 1.2s)"`.
+    pub fn label(&self) -> String {
+        match self {
+            SystemEvent::ApiError {
+                status,
+                message,
+                retry_in_ms,
+                attempt,
+                max_retries,
+            } => {
+                let mut label = match status {
+                    Some(status) => format!("API error {} {}", status, message),
+                    None => format!("API error {}", message),
+                };
+                let attempt = match (attempt, max_retries) {
+                    (Some(a), Some(max)) => Some(format!("retry {}/{}", a, max)),
+                    (Some(a), None) => Some(format!("retry {}", a)),
+                    _ => None,
+                };
+                let delay = retry_in_ms.map(|ms| format!("in {:.1}s", ms / 1000.0));
+                let retry: Vec<String> = attempt.into_iter().chain(delay).collect();
+                if !retry.is_empty() {
+                    label.push_str(&format!(" ({})", retry.join(" ")));
+                }
+                label
+            }
+            SystemEvent::Hook { text, .. }
+            | SystemEvent::Interrupted { text }
+            | SystemEvent::Info { text, .. } => text.clone(),
+            SystemEvent::CompactBoundary => "Context compacted".to_string(),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Classification
+// ---------------------------------------------------------------------------
+
+fn classify_system(entry: &LogEntry) -> SystemEvent {
+    if entry.is_compact_boundary() {
+        return SystemEvent::CompactBoundary;
+    }
+    let subtype = entry.subtype.as_deref().unwrap_or("");
+    let text = system_text(entry);
+    let is_error_level = entry.level.as_deref() == Some("error");
+
+    if subtype == "api_error" {
+        let error = entry.error.as_ref();
+        return SystemEvent::ApiError {
+            status: error.and_then(|e| e.get("status")).and_then(Value::as_u64),
+            message: error
+                .and_then(api_error_message)
+                .unwrap_or_else(|| text.clone()),
+            retry_in_ms: entry.retry_in_ms,
+            attempt: entry.retry_attempt,
+            max_retries: entry.max_retries,
+        };
+    }
+    if text.starts_with(INTERRUPTED_PREFIX) {
+        return SystemEvent::Interrupted { text };
+    }
+    if subtype.contains("hook") || is_hook_output(&text) {
+        let has_hook_errors = entry
+            .hook_errors
+            .as_ref()
+            .and_then(Value::as_array)
+            .is_some_and(|errors| !errors.is_empty());
+        return SystemEvent::Hook {
+            text: if text.is_empty() {
+                subtype.replace('_', " ")
+            } else {
+                text
+            },
+            is_error: is_error_level || has_hook_errors,
+        };
+    }
+    SystemEvent::Info {
+        text,
+        is_error: is_error_level,
+    }
+}
+
+/// Text of a system entry: the top-level `content`, falling back to a
+/// string `message.content`.
+fn system_text(entry: &LogEntry) -> String {
+    entry
+        .content
+        .as_ref()
+        .and_then(Value::as_str)
+        .or_else(|| entry.message.as_ref().and_then(|m| m.content.as_str()))
+        .unwrap_or("")
+        .trim()
+        .to_string()
+}
+
+/// The interruption marker of a user entry, if that is all it contains.
+fn interruption_text(entry: &LogEntry) -> Option<&str> {
+    let content = &entry.message.as_ref()?.content;
+    let text = match content {
+        Value::String(s) => s.as_str(),
+        Value::Array(blocks) => match blocks.as_slice() {
+            [block] if block.get("type").and_then(Value::as_str) == Some("text") => {
+                block.get("text").and_then(Value::as_str)?
+            }
+            _ => return None,
+        },
+        _ => return None,
+    };
+    let text = text.trim();
+    text.starts_with(INTERRUPTED_PREFIX).then_some(text)
+}
+
+/// Whether `text` starts with a hook event name, e.g.
+/// `PostToolUse:Edit [cargo fmt] completed successfully`.
+fn is_hook_output(text: &str) -> bool {
+    HOOK_EVENTS.iter().any(|event| {
+        text.strip_prefix(event)
+            .is_some_and(|rest| rest.starts_with(':') || rest.starts_with(' '))
+    })
+}
+
+/// `type: message` of an API error, wherever it is nested. Claude Code
+/// wraps the API's `{"error": {"type", "message"}}` body in one or two
+/// more `error` objects depending on its version.
+fn api_error_message(error: &Value) -> Option<String> {
+    let mut current = error;
+    loop {
+        if let Some(message) = current.get("message").and_then(Value::as_str) {
+            return Some(match current.get("type").and_then(Value::as_str) {
+                Some(kind) if kind != "error" => format!("{}: {}", kind, message),
+                _ => message.to_string(),
+            });
+        }
+        current = current.get("error")?;
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log_entry::parse_jsonl_line;
+
+    fn classify(json: &str) -> Option<SystemEvent> {
+        SystemEvent::classify(&parse_jsonl_line(json).unwrap())
+    }
+
+    #[test]
+    fn test_api_error_with_retry() {
+        let event = classify(
+            r#"{"type": "system", "subtype": "api_error", "level": "error", "error": {"status": 529, "error": {"type": "error", "error": {"type": "overloaded_error", "message": "Overloaded"}}}, "retryInMs": 1153.8, "retryAttempt": 2, "maxRetries": 10}"#,
+        )
+        .unwrap();
+        assert!(event.is_error());
+        assert_eq!(
+            event.label(),
+            "API error 529 overloaded_error: Overloaded (retry 2/10 in 1.2s)"
+        );
+
+        // Without details the label still names the error.
+        let bare =
+            classify(r#"{"type": "system", "subtype": "api_error", "content": "Request failed"}"#)
+                .unwrap();
+        assert_eq!(bare.label(), "API error Request failed");
+    }
+
+    #[test]
+    fn test_hook_output() {
+        let ok = classify(
+            r#"{"type": "system", "level": "info", "content": "PostToolUse:Edit [cargo fmt] completed successfully"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            ok,
+            SystemEvent::Hook {
+                text: "PostToolUse:Edit [cargo fmt] completed successfully".to_string(),
+                is_error: false,
+            }
+        );
+
+        let failed = classify(
+            r#"{"type": "system", "subtype": "stop_hook_summary", "hookErrors": ["exit 1"]}"#,
+        )
+        .unwrap();
+        assert!(failed.is_error());
+        assert_eq!(failed.label(), "stop hook summary");
+    }
+
+    #[test]
+    fn test_interruptions() {
+        let user = classify(
+            r#"{"type": "user", "message": {"role": "user", "content": [{"type": "text", "text": "[Request interrupted by user for tool use]"}]}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            user,
+            SystemEvent::Interrupted {
+                text: "[Request interrupted by user for tool use]".to_string()
+            }
+        );
+        assert!(!user.is_error());
+
+        // Ordinary prompts are not system events.
+        assert_eq!(
+            classify(r#"{"type": "user", "message": {"role": "user", "content": "hello"}}"#),
+            None
+        );
+    }
+
+    #[test]
+    fn test_other_system_entries() {
+        assert_eq!(
+            classify(r#"{"type": "system", "subtype": "compact_boundary"}"#),
+            Some(SystemEvent::CompactBoundary)
+        );
+        let warning = classify(
+            r#"{"type": "system", "subtype": "informational", "level": "error", "content": "Model fallback failed"}"#,
+        )
+        .unwrap();
+        assert!(warning.is_error());
+        assert_eq!(warning.label(), "Model fallback failed");
+
+        // Old-style system entries keep their text in `message.content`.
+        let legacy = classify(
+            r#"{"type": "system", "message": {"role": "user", "content": "System prompt text"}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            legacy,
+            SystemEvent::Info {
+                text: "System prompt text".to_string(),
+                is_error: false
+            }
+        );
+    }
+
+    #[test]
+    fn test_classify_notice_skips_plain_messages() {
+        let entry = |json: &str| parse_jsonl_line(json).unwrap();
+        assert_eq!(
+            SystemEvent::classify_notice(&entry(
+                r#"{"type": "system", "message": {"role": "user", "content": "System prompt text"}}"#
+            )),
+            None
+        );
+        assert_eq!(
+            SystemEvent::classify_notice(&entry(r#"{"type": "system"}"#)),
+            None
+        );
+        assert!(SystemEvent::classify_notice(&entry(
+            r#"{"type": "system", "content": "Running PreCompact hooks..."}"#
+        ))
+        .is_some());
+    }
+}
@@ -0,0 +1,593 @@
+//! Correlation of `Task` tool calls with the subagents they spawned.
+//!
+//! The main log records a `Task` `tool_use` (with `description` and
+//! `prompt`) and later its `tool_result`; the subagent writes its own
+//! transcript to `agent-{agentId}.jsonl`, starting with the prompt it was
+//! given. [`TaskLinkIndex`] is fed entries in arrival order and links the
+//! two, in order of confidence:
+//!
+//! 1. the `agentId` Claude Code reports in the result's `toolUseResult`,
+//! 2. the subagent's first prompt equal to the `Task` prompt,
+//! 3. the nearest subagent starting within [`LINK_WINDOW_SECS`] after the
+//!    `Task` call in the same session.
+//!
+//! Each subagent is linked to at most one call; a more confident link
+//! takes a subagent over from a less confident one.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+
+use crate::log_entry::{EntryType, LogEntry};
+use crate::tool_calls::parse_timestamp;
+
+/// Name of the tool that spawns subagents.
+pub const TASK_TOOL: &str = "Task";
+
+/// How long after a `Task` call its subagent may start when linking by
+/// timing alone.
+pub const LINK_WINDOW_SECS: i64 = 60;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// A `Task` tool call and what it is linked to.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TaskCall {
+    /// The `tool_use` block ID.
+    pub tool_use_id: String,
+    /// `uuid` of the entry carrying the `tool_use` block.
+    pub entry_uuid: Option<String>,
+    pub session_id: Option<String>,
+    /// The `description` input, as shown on the log line.
+    pub description: String,
+    /// The linked subagent.
+    pub agent_id: Option<String>,
+    /// Text of the `tool_result` (the subagent's final report).
+    pub result: Option<String>,
+    prompt: String,
+    started_at: Option<DateTime<Utc>>,
+    /// `agentId` from the result's `toolUseResult`.
+    reported_agent: Option<String>,
+    /// How `agent_id` was linked.
+    link: Option<Link>,
+    /// Arrival order among calls.
+    seq: u64,
+}
+
+/// How a call was linked to its subagent, weakest first. A stronger link
+/// takes the subagent over from a weaker one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Link {
+    Timing,
+    Prompt,
+    Reported,
+}
+
+/// First entry seen from a subagent.
+#[derive(Debug, Clone)]
+struct SubagentStart {
+    session_id: Option<String>,
+    /// Text of its first prompt, if the first entry seen was that prompt.
+    prompt: Option<String>,
+    started_at: Option<DateTime<Utc>>,
+    /// Number of recorded entries of the subagent.
+    refs: usize,
+    /// Arrival order among subagents.
+    seq: u64,
+}
+
+/// Index of `Task` calls and subagent starts, linked to each other.
+///
+/// Links are updated incrementally as calls, results and subagents
+/// arrive, and both sides are dropped once all entries recording them
+/// are forgotten.
+#[derive(Debug, Clone, Default)]
+pub struct TaskLinkIndex {
+    /// `tool_use` ID -> call.
+    tasks: HashMap<String, TaskCall>,
+    /// `agentId` -> subagent.
+    agents: HashMap<String, SubagentStart>,
+    /// `agentId` -> `tool_use` ID of the call linked to it.
+    task_of_agent: HashMap<String, String>,
+    next_seq: u64,
+}
+
+// ---------------------------------------------------------------------------
+// TaskLinkIndex
+// ---------------------------------------------------------------------------
+
+impl TaskLinkIndex {
+    /// Create an empty index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the `Task` calls, `Task` results or subagent start in
+    /// `entry`, and link whatever it makes linkable.
+    pub fn record_entry(&mut self, entry: &LogEntry) {
+        if entry.is_sidechain == Some(true) {
+            self.record_subagent(entry);
+        } else {
+            self.record_main(entry);
+        }
+    }
+
+    fn record_main(&mut self, entry: &LogEntry) {
+        let Some(blocks) = entry.message.as_ref().and_then(|m| m.content.as_array()) else {
+            return;
+        };
+        for block in blocks {
+            match block.get("type").and_then(Value::as_str) {
+                Some("tool_use") => {
+                    if block.get("name").and_then(Value::as_str) != Some(TASK_TOOL) {
+                        continue;
+                    }
+                    let Some(id) = block.get("id").and_then(Value::as_str) else {
+                        continue;
+                    };
+                    if self.tasks.contains_key(id) {
+                        continue;
+                    }
+                    let input = |key| {
+                        block
+                            .get("input")
+                            .and_then(|i| i.get(key))
+                            .and_then(Value::as_str)
+                            .unwrap_or("")
+                    };
+                    let task = TaskCall {
+                        tool_use_id: id.to_string(),
+                        entry_uuid: entry.uuid.clone(),
+                        session_id: entry.session_id.clone(),
+                        description: input("description").to_string(),
+                        prompt: input("prompt").trim().to_string(),
+                        started_at: entry.timestamp.as_deref().and_then(parse_timestamp),
+                        seq: self.next_seq(),
+                        ..TaskCall::default()
+                    };
+                    self.tasks.insert(id.to_string(), task);
+                    self.place_task(id);
+                }
+                Some("tool_result") => {
+                    let Some(id) = block.get("tool_use_id").and_then(Value::as_str) else {
+                        continue;
+                    };
+                    let Some(task) = self.tasks.get_mut(id) else {
+                        continue;
+                    };
+                    task.result = Some(result_text(block.get("content")));
+                    let reported = entry
+                        .tool_use_result
+                        .as_ref()
+                        .and_then(|r| r.agent_id.as_deref());
+                    if let Some(agent_id) = reported {
+                        task.reported_agent = Some(agent_id.to_string());
+                        self.link(id, agent_id, Link::Reported);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn record_subagent(&mut self, entry: &LogEntry) {
+        let Some(agent_id) = entry.agent_id.as_deref() else {
+            return;
+        };
+        if let Some(agent) = self.agents.get_mut(agent_id) {
+            agent.refs += 1;
+            return;
+        }
+        let prompt = match (&entry.entry_type, &entry.message) {
+            (EntryType::User, Some(m)) if entry.parent_uuid.is_none() => {
+                Some(result_text(Some(&m.content)).trim().to_string())
+            }
+            _ => None,
+        };
+        let agent = SubagentStart {
+            session_id: entry.session_id.clone(),
+            prompt,
+            started_at: entry.timestamp.as_deref().and_then(parse_timestamp),
+            refs: 1,
+            seq: self.next_seq(),
+        };
+        self.agents.insert(agent_id.to_string(), agent);
+        self.place_agent(agent_id);
+    }
+
+    /// Drop what `entry` recorded, once it is evicted from the ring buffer.
+    ///
+    /// A call is dropped with the entry carrying its `tool_use`, a
+    /// subagent with the last of its entries. Whatever was linked to the
+    /// dropped side is relinked if possible.
+    pub fn forget_entry(&mut self, entry: &LogEntry) {
+        if entry.is_sidechain == Some(true) {
+            let Some(agent_id) = entry.agent_id.as_deref() else {
+                return;
+            };
+            let Some(agent) = self.agents.get_mut(agent_id) else {
+                return;
+            };
+            agent.refs -= 1;
+            if agent.refs > 0 {
+                return;
+            }
+            self.agents.remove(agent_id);
+            let Some(id) = self.task_of_agent.get(agent_id).cloned() else {
+                return;
+            };
+            // A reported link stays valid without the transcript.
+            if self.tasks[&id].link != Some(Link::Reported) {
+                self.unlink_task(&id);
+                self.place_task(&id);
+            }
+            return;
+        }
+
+        for block in entry
+            .message
+            .iter()
+            .filter_map(|m| m.content.as_array())
+            .flatten()
+        {
+            if block.get("type").and_then(Value::as_str) != Some("tool_use") {
+                continue;
+            }
+            let Some(id) = block.get("id").and_then(Value::as_str) else {
+                continue;
+            };
+            let Some(task) = self.tasks.remove(id) else {
+                continue;
+            };
+            if let Some(agent_id) = task.agent_id {
+                self.task_of_agent.remove(&agent_id);
+                if self.agents.contains_key(&agent_id) {
+                    self.place_agent(&agent_id);
+                }
+            }
+        }
+    }
+
+    fn next_seq(&mut self) -> u64 {
+        self.next_seq += 1;
+        self.next_seq
+    }
+
+    /// Link the unlinked call `id` to the best subagent available to it.
+    fn place_task(&mut self, id: &str) {
+        let task = &self.tasks[id];
+        if let Some(agent_id) = task.reported_agent.clone() {
+            // Two results reporting the same subagent: the newest keeps it.
+            if self.link_of_agent(&agent_id) < Some(Link::Reported) {
+                self.link(id, &agent_id, Link::Reported);
+            }
+            return;
+        }
+
+        // Same prompt, unless already linked as confidently.
+        let by_prompt = self
+            .agents
+            .iter()
+            .filter(|(agent_id, agent)| {
+                !task.prompt.is_empty()
+                    && agent.session_id == task.session_id
+                    && agent.prompt.as_deref() == Some(task.prompt.as_str())
+                    && self.link_of_agent(agent_id) < Some(Link::Prompt)
+            })
+            .min_by_key(|(_, agent)| agent.seq)
+            .map(|(agent_id, _)| agent_id.clone());
+        if let Some(agent_id) = by_prompt {
+            self.link(id, &agent_id, Link::Prompt);
+            return;
+        }
+
+        // Nearest unlinked start after the call.
+        let Some(called) = task.started_at else {
+            return;
+        };
+        let by_timing = self
+            .agents
+            .iter()
+            .filter(|(agent_id, agent)| {
+                agent.session_id == task.session_id && !self.task_of_agent.contains_key(*agent_id)
+            })
+            .filter_map(|(agent_id, agent)| {
+                let delay = (agent.started_at? - called).num_milliseconds();
+                (0..=LINK_WINDOW_SECS * 1000)
+                    .contains(&delay)
+                    .then_some((delay, agent.seq, agent_id))
+            })
+            .min()
+            .map(|(_, _, agent_id)| agent_id.clone());
+        if let Some(agent_id) = by_timing {
+            self.link(id, &agent_id, Link::Timing);
+        }
+    }
+
+    /// Link the unlinked subagent `agent_id` to the best call available to
+    /// it.
+    fn place_agent(&mut self, agent_id: &str) {
+        let agent = &self.agents[agent_id];
+
+        // Same prompt, unless already linked as confidently.
+        let by_prompt = self
+            .tasks
+            .values()
+            .filter(|task| {
+                !task.prompt.is_empty()
+                    && task.session_id == agent.session_id
+                    && agent.prompt.as_deref() == Some(task.prompt.as_str())
+                    && task.link < Some(Link::Prompt)
+            })
+            .min_by_key(|task| task.seq)
+            .map(|task| task.tool_use_id.clone());
+        if let Some(id) = by_prompt {
+            self.link(&id, agent_id, Link::Prompt);
+            return;
+        }
+
+        // Earliest unlinked call shortly before the start.
+        let Some(started) = agent.started_at else {
+            return;
+        };
+        let by_timing = self
+            .tasks
+            .values()
+            .filter(|task| task.session_id == agent.session_id && task.link.is_none())
+            .filter(|task| {
+                task.started_at.is_some_and(|called| {
+                    let delay = (started - called).num_milliseconds();
+                    (0..=LINK_WINDOW_SECS * 1000).contains(&delay)
+                })
+            })
+            .min_by_key(|task| task.seq)
+            .map(|task| task.tool_use_id.clone());
+        if let Some(id) = by_timing {
+            self.link(&id, agent_id, Link::Timing);
+        }
+    }
+
+    fn link_of_agent(&self, agent_id: &str) -> Option<Link> {
+        self.task_of_agent
+            .get(agent_id)
+            .and_then(|id| self.tasks[id].link)
+    }
+
+    /// Link call `id` to `agent_id`, taking the subagent over from the call
+    /// it was linked to, and relink whatever this leaves unlinked.
+    fn link(&mut self, id: &str, agent_id: &str, link: Link) {
+        if self.tasks[id].agent_id.as_deref() == Some(agent_id) {
+            self.tasks.get_mut(id).unwrap().link = Some(link);
+            return;
+        }
+        let previous_task = self.task_of_agent.get(agent_id).cloned();
+        if let Some(previous) = &previous_task {
+            self.unlink_task(previous);
+        }
+        let previous_agent = self.tasks[id].agent_id.clone();
+        self.unlink_task(id);
+
+        let task = self.tasks.get_mut(id).unwrap();
+        task.agent_id = Some(agent_id.to_string());
+        task.link = Some(link);
+        self.task_of_agent
+            .insert(agent_id.to_string(), id.to_string());
+
+        // Each move strengthens the link of the subagent taken over, so
+        // this terminates.
+        if let Some(previous) = previous_task {
+            self.place_task(&previous);
+        }
+        if let Some(previous) = previous_agent.filter(|a| self.agents.contains_key(a)) {
+            self.place_agent(&previous);
+        }
+    }
+
+    fn unlink_task(&mut self, id: &str) {
+        let task = self.tasks.get_mut(id).unwrap();
+        task.link = None;
+        if let Some(agent_id) = task.agent_id.take() {
+            self.task_of_agent.remove(&agent_id);
+        }
+    }
+
+    /// Look up a `Task` call by its `tool_use` ID.
+    pub fn get(&self, tool_use_id: &str) -> Option<&TaskCall> {
+        self.tasks.get(tool_use_id)
+    }
+
+    /// The `Task` call that spawned `agent_id`.
+    pub fn task_for_agent(&self, agent_id: &str) -> Option<&TaskCall> {
+        self.task_of_agent
+            .get(agent_id)
+            .and_then(|id| self.tasks.get(id))
+    }
+
+    /// The first linked `Task` call referenced by `entry`, through either
+    /// its `tool_use` or its `tool_result`.
+    pub fn linked_call_in(&self, entry: &LogEntry) -> Option<&TaskCall> {
+        let blocks = entry.message.as_ref()?.content.as_array()?;
+        blocks
+            .iter()
+            .filter_map(|b| {
+                b.get("id")
+                    .or_else(|| b.get("tool_use_id"))
+                    .and_then(Value::as_str)
+            })
+            .filter_map(|id| self.get(id))
+            .find(|t| t.agent_id.is_some())
+    }
+
+    /// Forget all calls and subagents.
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// Text of a string or an array of `text` blocks.
+fn result_text(content: Option<&Value>) -> String {
+    match content {
+        Some(Value::String(s)) => s.clone(),
+        Some(Value::Array(blocks)) => blocks
+            .iter()
+            .filter_map(|b| b.get("text").and_then(Value::as_str))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log_entry::parse_jsonl_line;
+
+    fn task_call(id: &str, ts: &str, prompt: &str) -> LogEntry {
+        parse_jsonl_line(&format!(
+            r#"{{"type": "assistant", "sessionId": "s1", "uuid": "u-{id}", "timestamp": "{ts}", "message": {{"role": "assistant", "content": [{{"type": "tool_use", "id": "{id}", "name": "Task", "input": {{"description": "Find {id}", "prompt": "{prompt}"}}}}]}}}}"#,
+        ))
+        .unwrap()
+    }
+
+    fn subagent_start(agent: &str, ts: &str, prompt: &str) -> LogEntry {
+        parse_jsonl_line(&format!(
+            r#"{{"type": "user", "sessionId": "s1", "isSidechain": true, "agentId": "{agent}", "parentUuid": null, "timestamp": "{ts}", "message": {{"role": "user", "content": "{prompt}"}}}}"#,
+        ))
+        .unwrap()
+    }
+
+    fn index(entries: &[LogEntry]) -> TaskLinkIndex {
+        let mut index = TaskLinkIndex::new();
+        for entry in entries {
+            index.record_entry(entry);
+        }
+        index
+    }
+
+    #[test]
+    fn test_links_by_prompt() {
+        // Two parallel calls whose subagents start in reverse order.
+        let index = index(&[
+            task_call("t1", "2025-01-15T10:00:00Z", "Search the docs"),
+            task_call("t2", "2025-01-15T10:00:00Z", "Search the code"),
+            subagent_start("a2", "2025-01-15T10:00:01Z", "Search the code"),
+            subagent_start("a1", "2025-01-15T10:00:02Z", "Search the docs"),
+        ]);
+        assert_eq!(index.get("t1").unwrap().agent_id.as_deref(), Some("a1"));
+        assert_eq!(index.get("t2").unwrap().agent_id.as_deref(), Some("a2"));
+        assert_eq!(
+            index.task_for_agent("a2").unwrap().entry_uuid.as_deref(),
+            Some("u-t2")
+        );
+    }
+
+    #[test]
+    fn test_links_by_timing_without_prompt() {
+        let mut late = subagent_start("late", "2025-01-15T10:05:00Z", "x");
+        late.parent_uuid = Some("p".to_string());
+        let mut near = subagent_start("near", "2025-01-15T10:00:03Z", "x");
+        near.parent_uuid = Some("p".to_string());
+        let index = index(&[task_call("t1", "2025-01-15T10:00:00Z", "Plan"), late, near]);
+        assert_eq!(index.get("t1").unwrap().agent_id.as_deref(), Some("near"));
+        assert!(index.task_for_agent("late").is_none());
+    }
+
+    #[test]
+    fn test_reported_agent_id_and_result_win() {
+        let result = parse_jsonl_line(
+            r#"{"type": "user", "sessionId": "s1", "toolUseResult": {"status": "completed", "agentId": "a9"}, "message": {"role": "user", "content": [{"type": "tool_result", "tool_use_id": "t1", "content": [{"type": "text", "text": "Found 3 call sites."}]}]}}"#,
+        )
+        .unwrap();
+        let index = index(&[
+            task_call("t1", "2025-01-15T10:00:00Z", "Plan"),
+            subagent_start("a1", "2025-01-15T10:00:01Z", "Plan"),
+            subagent_start("a9", "2025-01-15T10:00:02Z", "Other"),
+            result.clone(),
+        ]);
+        let task = index.get("t1").unwrap();
+        assert_eq!(task.agent_id.as_deref(), Some("a9"));
+        assert_eq!(task.result.as_deref(), Some("Found 3 call sites."));
+        assert_eq!(task.description, "Find t1");
+        // The result entry leads to the call as well.
+        assert_eq!(index.linked_call_in(&result).unwrap().tool_use_id, "t1");
+    }
+
+    #[test]
+    fn test_other_tools_and_sessions_are_ignored() {
+        let read = parse_jsonl_line(
+            r#"{"type": "assistant", "sessionId": "s1", "message": {"role": "assistant", "content": [{"type": "tool_use", "id": "r1", "name": "Read", "input": {}}]}}"#,
+        )
+        .unwrap();
+        let mut elsewhere = subagent_start("a1", "2025-01-15T10:00:01Z", "Plan");
+        elsewhere.session_id = Some("s2".to_string());
+        let index = index(&[
+            read,
+            task_call("t1", "2025-01-15T10:00:00Z", "Plan"),
+            elsewhere,
+        ]);
+        assert!(index.get("r1").is_none());
+        assert_eq!(index.get("t1").unwrap().agent_id, None);
+    }
+
+    #[test]
+    fn test_prompt_match_takes_agent_over_from_timing_link() {
+        // An agent seen without its prompt is linked to t1 by timing,
+        // until t1's own subagent arrives with the prompt; the first agent
+        // then moves on to t2.
+        let mut unprompted = subagent_start("a1", "2025-01-15T10:00:02Z", "x");
+        unprompted.parent_uuid = Some("p".to_string());
+        let index = index(&[
+            task_call("t1", "2025-01-15T10:00:00Z", "Plan"),
+            task_call("t2", "2025-01-15T10:00:01Z", "Review"),
+            unprompted,
+            subagent_start("a2", "2025-01-15T10:00:03Z", "Plan"),
+        ]);
+        assert_eq!(index.get("t1").unwrap().agent_id.as_deref(), Some("a2"));
+        assert_eq!(index.get("t2").unwrap().agent_id.as_deref(), Some("a1"));
+        assert_eq!(index.task_for_agent("a1").unwrap().tool_use_id, "t2");
+    }
+
+    #[test]
+    fn test_forget_drops_calls_and_agents_with_their_entries() {
+        let call = task_call("t1", "2025-01-15T10:00:00Z", "Plan");
+        let start = subagent_start("a1", "2025-01-15T10:00:01Z", "Plan");
+        let mut next = start.clone();
+        next.parent_uuid = Some("p".to_string());
+        let mut index = index(&[call.clone(), start.clone(), next.clone()]);
+        assert_eq!(index.get("t1").unwrap().agent_id.as_deref(), Some("a1"));
+
+        // The agent stays while any of its entries is left.
+        index.forget_entry(&start);
+        assert_eq!(index.task_for_agent("a1").unwrap().tool_use_id, "t1");
+        index.forget_entry(&next);
+        assert!(index.task_for_agent("a1").is_none());
+        assert_eq!(index.get("t1").unwrap().agent_id, None);
+
+        index.forget_entry(&call);
+        assert!(index.get("t1").is_none());
+    }
+
+    #[test]
+    fn test_forgotten_call_frees_its_agent() {
+        let first = task_call("t1", "2025-01-15T10:00:00Z", "Plan");
+        let mut index = index(&[
+            first.clone(),
+            task_call("t2", "2025-01-15T10:00:00Z", "Plan"),
+            subagent_start("a1", "2025-01-15T10:00:01Z", "Plan"),
+        ]);
+        assert_eq!(index.get("t1").unwrap().agent_id.as_deref(), Some("a1"));
+        index.forget_entry(&first);
+        assert_eq!(index.task_for_agent("a1").unwrap().tool_use_id, "t2");
+    }
+}
@@ -0,0 +1,40 @@
+//! Fixtures shared by the unit tests of modules that read session files.
+
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::session::{Agent, Session};
+
+/// Write `lines` as a JSONL file at `dir/name`, creating parent directories.
+pub fn write_file(dir: &Path, name: &str, lines: &[&str]) -> PathBuf {
+    let path = dir.join(name);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(&path, lines.join("\n") + "\n").unwrap();
+    path
+}
+
+/// A JSONL line for a user message with text `text`.
+pub fn user_line(uuid: &str, ts: &str, text: &str) -> String {
+    format!(
+        r#"{{"type": "user", "uuid": "{}", "timestamp": "{}", "message": {{"role": "user", "content": "{}"}}}}"#,
+        uuid, ts, text
+    )
+}
+
+/// A session modified now, with one agent per `(agent_id, log_path)` pair.
+/// A `None` agent ID is the main log.
+pub fn session(id: &str, files: Vec<(Option<&str>, PathBuf)>) -> Session {
+    Session {
+        id: id.to_string(),
+        agents: files
+            .into_iter()
+            .map(|(agent_id, log_path)| Agent {
+                agent_id: agent_id.map(str::to_string),
+                slug: None,
+                is_main: agent_id.is_none(),
+                log_path,
+            })
+            .collect(),
+        last_modified: SystemTime::now(),
+    }
+}
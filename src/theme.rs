@@ -52,6 +52,8 @@ pub struct ThemeColors {
     pub sidebar_unselected_child: Color,
     /// Tree connector prefix foreground.
     pub sidebar_child_prefix: Color,
+    /// Project group header foreground (`--all-projects`).
+    pub sidebar_project_header: Color,
 
     // -- Log stream --------------------------------------------------------
     /// Placeholder text (e.g. "Waiting for log entries...").
@@ -60,20 +62,42 @@ pub struct ThemeColors {
     pub logstream_timestamp: Color,
     /// Progress indicator foreground.
     pub logstream_progress: Color,
+    /// Divider lines (e.g. context compaction boundaries).
+    pub logstream_divider: Color,
+    /// API errors and failed hooks / system entries.
+    pub system_error: Color,
+    /// Hook output.
+    pub system_hook: Color,
+    /// Interrupted requests.
+    pub system_interrupted: Color,
+    /// Other system entries.
+    pub system_info: Color,
     /// User role indicator `>` color.
     pub role_user: Color,
     /// Assistant role indicator `<` color.
     pub role_assistant: Color,
     /// Unknown role indicator `?` color.
     pub role_unknown: Color,
+    /// Thinking block indicator `*` and text color.
+    pub role_thinking: Color,
     /// Tool use indicator `~` color.
     pub role_tool_use: Color,
+    /// Tool result indicator `=` color.
+    pub role_tool_result: Color,
+    /// Failed tool result (`is_error: true`) color.
+    pub role_tool_error: Color,
+    /// Finished tool call mark (`✓`) color.
+    pub tool_call_ok: Color,
     /// Default text foreground in the log stream.
     pub logstream_text: Color,
+    /// Background of the entry under the log stream cursor.
+    pub logstream_cursor_bg: Color,
     /// Main agent (no agent_id) color.
     pub agent_main: Color,
     /// 8-color palette for per-agent deterministic coloring.
     pub agent_palette: [Color; 8],
+    /// 8-color palette for the session tags of the all-sessions view.
+    pub session_palette: [Color; 8],
 
     // -- Status bar --------------------------------------------------------
     /// Status bar background.
@@ -84,6 +108,10 @@ pub struct ThemeColors {
     pub status_inactive_fg: Color,
     /// Inactive badge background.
     pub status_inactive_bg: Color,
+    /// Watcher error badge foreground.
+    pub status_error_fg: Color,
+    /// Watcher error badge background.
+    pub status_error_bg: Color,
     /// Filter display foreground.
     pub status_filter: Color,
     /// Separator foreground.
@@ -132,6 +160,16 @@ pub struct ThemeColors {
     pub search_input_fg: Color,
     /// Search prompt (`/`) foreground.
     pub search_prompt: Color,
+
+    // -- Detail pane -------------------------------------------------------
+    /// Section header (`Input`, `Diff`, `Result`) foreground.
+    pub detail_section: Color,
+    /// Added line (`+`) foreground in an Edit diff.
+    pub diff_added: Color,
+    /// Removed line (`-`) foreground in an Edit diff.
+    pub diff_removed: Color,
+    /// MultiEdit separator (`@@ edit 1/2 @@`) foreground.
+    pub diff_header: Color,
 }
 
 impl ThemeColors {
@@ -164,16 +202,27 @@ impl ThemeColors {
             sidebar_selected_child_bg: Color::DarkGray,
             sidebar_unselected_child: Color::DarkGray,
             sidebar_child_prefix: Color::DarkGray,
+            sidebar_project_header: Color::Magenta,
 
             // Log stream
             logstream_placeholder: Color::DarkGray,
             logstream_timestamp: Color::DarkGray,
             logstream_progress: Color::DarkGray,
+            logstream_divider: Color::Yellow,
+            system_error: Color::LightRed,
+            system_hook: Color::Cyan,
+            system_interrupted: Color::Yellow,
+            system_info: Color::DarkGray,
             role_user: Color::Blue,
             role_assistant: Color::Green,
             role_unknown: Color::Gray,
+            role_thinking: Color::Magenta,
             role_tool_use: Color::Yellow,
+            role_tool_result: Color::Cyan,
+            role_tool_error: Color::Red,
+            tool_call_ok: Color::Green,
             logstream_text: Color::White,
+            logstream_cursor_bg: Color::DarkGray,
             agent_main: Color::White,
             agent_palette: [
                 Color::Red,
@@ -185,12 +234,24 @@ impl ThemeColors {
                 Color::LightRed,
                 Color::LightGreen,
             ],
+            session_palette: [
+                Color::LightBlue,
+                Color::LightMagenta,
+                Color::LightCyan,
+                Color::LightYellow,
+                Color::Blue,
+                Color::Magenta,
+                Color::Cyan,
+                Color::Yellow,
+            ],
 
             // Status bar
             status_bar_bg: Color::DarkGray,
             status_bar_fg: Color::White,
             status_inactive_fg: Color::White,
             status_inactive_bg: Color::Red,
+            status_error_fg: Color::Black,
+            status_error_bg: Color::LightRed,
             status_filter: Color::Magenta,
             status_separator: Color::DarkGray,
             status_shortcut_key: Color::Yellow,
@@ -217,6 +278,12 @@ impl ThemeColors {
             search_current_fg: Color::White,
             search_input_fg: Color::White,
             search_prompt: Color::Yellow,
+
+            // Detail pane
+            detail_section: Color::Cyan,
+            diff_added: Color::Green,
+            diff_removed: Color::Red,
+            diff_header: Color::Magenta,
         }
     }
 
@@ -241,16 +308,27 @@ impl ThemeColors {
             sidebar_selected_child_bg: Color::Blue,
             sidebar_unselected_child: Color::Gray,
             sidebar_child_prefix: Color::Gray,
+            sidebar_project_header: Color::Magenta,
 
             // Log stream
             logstream_placeholder: Color::Gray,
             logstream_timestamp: Color::Gray,
             logstream_progress: Color::Gray,
+            logstream_divider: Color::Cyan,
+            system_error: Color::Red,
+            system_hook: Color::Blue,
+            system_interrupted: Color::Magenta,
+            system_info: Color::Gray,
             role_user: Color::Blue,
             role_assistant: Color::Green,
             role_unknown: Color::DarkGray,
+            role_thinking: Color::Magenta,
             role_tool_use: Color::Magenta,
+            role_tool_result: Color::DarkGray,
+            role_tool_error: Color::Red,
+            tool_call_ok: Color::Green,
             logstream_text: Color::Black,
+            logstream_cursor_bg: Color::Gray,
             agent_main: Color::Black,
             agent_palette: [
                 Color::Red,
@@ -262,12 +340,24 @@ impl ThemeColors {
                 Color::LightRed,
                 Color::LightBlue,
             ],
+            session_palette: [
+                Color::Blue,
+                Color::Magenta,
+                Color::Cyan,
+                Color::Red,
+                Color::Green,
+                Color::DarkGray,
+                Color::LightBlue,
+                Color::LightMagenta,
+            ],
 
             // Status bar
             status_bar_bg: Color::Gray,
             status_bar_fg: Color::Black,
             status_inactive_fg: Color::White,
             status_inactive_bg: Color::Red,
+            status_error_fg: Color::Black,
+            status_error_bg: Color::LightRed,
             status_filter: Color::Magenta,
             status_separator: Color::DarkGray,
             status_shortcut_key: Color::Blue,
@@ -294,6 +384,12 @@ impl ThemeColors {
             search_current_fg: Color::White,
             search_input_fg: Color::Black,
             search_prompt: Color::Blue,
+
+            // Detail pane
+            detail_section: Color::Blue,
+            diff_added: Color::Green,
+            diff_removed: Color::Red,
+            diff_header: Color::Magenta,
         }
     }
 }
@@ -347,7 +443,7 @@ mod tests {
         // Palette has exactly 8 entries (compile-time array, but verify at runtime)
         assert_eq!(t.agent_palette.len(), 8);
         // No palette entry uses Reset (which would indicate a missing assignment)
-        for color in &t.agent_palette {
+        for color in t.agent_palette.iter().chain(&t.session_palette) {
             assert_ne!(*color, Color::Reset);
         }
         // Dark and light are distinct themes (not accidentally identical)
@@ -0,0 +1,456 @@
+//! Correlation of `tool_use` blocks with their `tool_result` blocks.
+//!
+//! Provides [`ToolCallIndex`], which is fed entries in arrival order and
+//! tracks each tool call by its `tool_use` ID as pending, succeeded or
+//! failed, together with the timestamps needed to compute elapsed time.
+//! Entries evicted from the ring buffer are forgotten again, and calls that
+//! stay pending past a timeout are marked abandoned.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+
+use crate::log_entry::LogEntry;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// Lifecycle state of a single tool call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToolCallStatus {
+    /// The `tool_use` has been seen but no matching `tool_result` yet.
+    Pending,
+    /// A `tool_result` arrived without `is_error`.
+    Succeeded,
+    /// A `tool_result` arrived with `is_error: true`.
+    Failed,
+    /// No `tool_result` arrived before the call was expired, e.g. because
+    /// the session was interrupted mid-call.
+    Abandoned,
+}
+
+/// A tracked tool call.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolCall {
+    /// Tool name from the `tool_use` block (empty if the result arrived
+    /// before, or without, its `tool_use`).
+    pub name: String,
+    /// Current state.
+    pub status: ToolCallStatus,
+    /// Timestamp of the entry carrying the `tool_use` block.
+    pub started_at: Option<DateTime<Utc>>,
+    /// Timestamp of the entry carrying the `tool_result` block.
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl ToolCall {
+    /// Milliseconds between the `tool_use` and `tool_result` timestamps.
+    ///
+    /// For a pending call, measures up to `now`. Returns `None` when a
+    /// timestamp is missing, the call was abandoned, or the clock went
+    /// backwards.
+    pub fn elapsed_ms(&self, now: DateTime<Utc>) -> Option<u64> {
+        let start = self.started_at?;
+        let end = match self.status {
+            ToolCallStatus::Pending => now,
+            ToolCallStatus::Abandoned => return None,
+            _ => self.finished_at?,
+        };
+        u64::try_from((end - start).num_milliseconds()).ok()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// ToolCallIndex
+// ---------------------------------------------------------------------------
+
+/// Index from `tool_use` ID to [`ToolCall`].
+///
+/// Each call remembers how many recorded blocks refer to it, so it can be
+/// dropped once every entry mentioning it has been forgotten.
+#[derive(Debug, Clone, Default)]
+pub struct ToolCallIndex {
+    calls: HashMap<String, ToolCall>,
+    /// Number of recorded `tool_use` / `tool_result` blocks per call ID.
+    refs: HashMap<String, usize>,
+    /// Number of calls whose status is [`ToolCallStatus::Pending`].
+    pending: usize,
+}
+
+impl ToolCallIndex {
+    /// Create an empty index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Scan an entry's content blocks and record any `tool_use` or
+    /// `tool_result` blocks it contains.
+    ///
+    /// Entries may arrive out of order across files (e.g. a result that is
+    /// read before its call); the call's status and timestamps are merged
+    /// regardless of order.
+    pub fn record_entry(&mut self, entry: &LogEntry) {
+        let timestamp = entry.timestamp.as_deref().and_then(parse_timestamp);
+
+        for block in call_blocks(entry) {
+            match block {
+                CallBlock::Use { id, name } => {
+                    let call = self.entry(id);
+                    call.name = name.to_string();
+                    call.started_at = timestamp;
+                }
+                CallBlock::Result { id, is_error } => {
+                    let call = self.entry(id);
+                    let was_pending = call.status == ToolCallStatus::Pending;
+                    call.status = if is_error {
+                        ToolCallStatus::Failed
+                    } else {
+                        ToolCallStatus::Succeeded
+                    };
+                    call.finished_at = timestamp;
+                    if was_pending {
+                        self.pending -= 1;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Undo [`record_entry`](Self::record_entry) for an entry that left the
+    /// ring buffer.
+    ///
+    /// A call is dropped once no remaining entry refers to it.
+    pub fn forget_entry(&mut self, entry: &LogEntry) {
+        for block in call_blocks(entry) {
+            let id = match block {
+                CallBlock::Use { id, .. } | CallBlock::Result { id, .. } => id,
+            };
+            let Some(refs) = self.refs.get_mut(id) else {
+                continue;
+            };
+            *refs -= 1;
+            if *refs > 0 {
+                continue;
+            }
+            self.refs.remove(id);
+            if let Some(call) = self.calls.remove(id) {
+                if call.status == ToolCallStatus::Pending {
+                    self.pending -= 1;
+                }
+            }
+        }
+    }
+
+    /// Mark calls that started before `cutoff` and are still pending as
+    /// [`ToolCallStatus::Abandoned`]. Pending calls without a start
+    /// timestamp are abandoned as well, since they can never time out.
+    ///
+    /// Returns whether any call was expired.
+    pub fn expire_pending(&mut self, cutoff: DateTime<Utc>) -> bool {
+        if self.pending == 0 {
+            return false;
+        }
+        let mut expired = 0;
+        for call in self.calls.values_mut() {
+            let stale = call.started_at.is_none_or(|at| at < cutoff);
+            if call.status == ToolCallStatus::Pending && stale {
+                call.status = ToolCallStatus::Abandoned;
+                expired += 1;
+            }
+        }
+        self.pending -= expired;
+        expired > 0
+    }
+
+    /// Look up a tool call by its `tool_use` ID.
+    pub fn get(&self, id: &str) -> Option<&ToolCall> {
+        self.calls.get(id)
+    }
+
+    /// Whether any tracked call is still pending.
+    pub fn has_pending(&self) -> bool {
+        self.pending > 0
+    }
+
+    /// Number of tracked calls.
+    pub fn len(&self) -> usize {
+        self.calls.len()
+    }
+
+    /// Whether the index is empty.
+    pub fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+
+    /// Forget all tracked calls.
+    pub fn clear(&mut self) {
+        self.calls.clear();
+        self.refs.clear();
+        self.pending = 0;
+    }
+
+    /// Get the call for `id`, inserting a pending one if needed, and count
+    /// one more reference to it.
+    fn entry(&mut self, id: &str) -> &mut ToolCall {
+        *self.refs.entry(id.to_string()).or_default() += 1;
+        let pending = &mut self.pending;
+        self.calls.entry(id.to_string()).or_insert_with(|| {
+            *pending += 1;
+            ToolCall {
+                name: String::new(),
+                status: ToolCallStatus::Pending,
+                started_at: None,
+                finished_at: None,
+            }
+        })
+    }
+}
+
+/// A `tool_use` or `tool_result` content block, reduced to what the index
+/// tracks.
+pub(crate) enum CallBlock<'a> {
+    Use { id: &'a str, name: &'a str },
+    Result { id: &'a str, is_error: bool },
+}
+
+/// The `tool_use` and `tool_result` blocks of an entry that carry an ID.
+pub(crate) fn call_blocks(entry: &LogEntry) -> impl Iterator<Item = CallBlock<'_>> {
+    entry
+        .message
+        .as_ref()
+        .and_then(|m| m.content.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|block| match block.get("type").and_then(Value::as_str) {
+            Some("tool_use") => Some(CallBlock::Use {
+                id: block.get("id").and_then(Value::as_str)?,
+                name: block.get("name").and_then(Value::as_str).unwrap_or(""),
+            }),
+            Some("tool_result") => Some(CallBlock::Result {
+                id: block.get("tool_use_id").and_then(Value::as_str)?,
+                is_error: block.get("is_error").and_then(Value::as_bool) == Some(true),
+            }),
+            _ => None,
+        })
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// Parse an RFC 3339 timestamp into UTC.
+pub(crate) fn parse_timestamp(ts: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(ts)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Format an elapsed duration compactly.
+///
+/// Examples: `"0.4s"`, `"9.9s"`, `"12s"`, `"2m 05s"`, `"1h 03m"`.
+pub fn format_elapsed(ms: u64) -> String {
+    if ms < 10_000 {
+        return format!("{:.1}s", ms as f64 / 1000.0);
+    }
+    let secs = ms / 1000;
+    if secs < 60 {
+        format!("{}s", secs)
+    } else if secs < 3600 {
+        format!("{}m {:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{}h {:02}m", secs / 3600, (secs % 3600) / 60)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log_entry::parse_jsonl_line;
+
+    fn tool_use(ts: &str, id: &str, name: &str) -> LogEntry {
+        parse_jsonl_line(&format!(
+            r#"{{"type": "assistant", "timestamp": "{}", "message": {{"role": "assistant", "content": [{{"type": "tool_use", "id": "{}", "name": "{}", "input": {{}}}}]}}}}"#,
+            ts, id, name
+        ))
+        .unwrap()
+    }
+
+    fn tool_result(ts: &str, id: &str, is_error: bool) -> LogEntry {
+        parse_jsonl_line(&format!(
+            r#"{{"type": "user", "timestamp": "{}", "message": {{"role": "user", "content": [{{"type": "tool_result", "tool_use_id": "{}", "content": "x", "is_error": {}}}]}}}}"#,
+            ts, id, is_error
+        ))
+        .unwrap()
+    }
+
+    fn at(ts: &str) -> DateTime<Utc> {
+        parse_timestamp(ts).unwrap()
+    }
+
+    // -- 1. tool_use alone is pending -----------------------------------------
+
+    #[test]
+    fn test_tool_use_is_pending() {
+        let mut index = ToolCallIndex::new();
+        index.record_entry(&tool_use("2025-01-15T10:00:00Z", "t1", "Bash"));
+
+        let call = index.get("t1").unwrap();
+        assert_eq!(call.name, "Bash");
+        assert_eq!(call.status, ToolCallStatus::Pending);
+        assert!(index.has_pending());
+        assert_eq!(call.elapsed_ms(at("2025-01-15T10:00:42Z")), Some(42_000));
+    }
+
+    // -- 2. Result marks succeeded / failed ----------------------------------
+
+    #[test]
+    fn test_result_marks_succeeded() {
+        let mut index = ToolCallIndex::new();
+        index.record_entry(&tool_use("2025-01-15T10:00:00Z", "t1", "Read"));
+        index.record_entry(&tool_result("2025-01-15T10:00:01.500Z", "t1", false));
+
+        let call = index.get("t1").unwrap();
+        assert_eq!(call.status, ToolCallStatus::Succeeded);
+        assert!(!index.has_pending());
+        // Elapsed is fixed once finished, regardless of `now`.
+        assert_eq!(call.elapsed_ms(at("2025-01-15T11:00:00Z")), Some(1_500));
+    }
+
+    #[test]
+    fn test_result_marks_failed() {
+        let mut index = ToolCallIndex::new();
+        index.record_entry(&tool_use("2025-01-15T10:00:00Z", "t1", "Bash"));
+        index.record_entry(&tool_result("2025-01-15T10:00:03Z", "t1", true));
+
+        assert_eq!(index.get("t1").unwrap().status, ToolCallStatus::Failed);
+    }
+
+    // -- 3. Out-of-order arrival -----------------------------------------------
+
+    #[test]
+    fn test_result_before_use_is_merged() {
+        let mut index = ToolCallIndex::new();
+        index.record_entry(&tool_result("2025-01-15T10:00:05Z", "t1", false));
+        index.record_entry(&tool_use("2025-01-15T10:00:00Z", "t1", "Grep"));
+
+        let call = index.get("t1").unwrap();
+        assert_eq!(call.name, "Grep");
+        assert_eq!(call.status, ToolCallStatus::Succeeded);
+        assert_eq!(call.elapsed_ms(Utc::now()), Some(5_000));
+    }
+
+    // -- 4. Missing data -------------------------------------------------------
+
+    #[test]
+    fn test_missing_timestamps_no_elapsed() {
+        let mut index = ToolCallIndex::new();
+        let entry = parse_jsonl_line(
+            r#"{"type": "assistant", "message": {"content": [{"type": "tool_use", "id": "t1", "name": "Bash"}]}}"#,
+        )
+        .unwrap();
+        index.record_entry(&entry);
+        assert_eq!(index.get("t1").unwrap().elapsed_ms(Utc::now()), None);
+    }
+
+    #[test]
+    fn test_blocks_without_ids_ignored() {
+        let mut index = ToolCallIndex::new();
+        let entry = parse_jsonl_line(
+            r#"{"type": "assistant", "message": {"content": [{"type": "tool_use", "name": "Bash"}, {"type": "tool_result", "content": "x"}, {"type": "text", "text": "hi"}]}}"#,
+        )
+        .unwrap();
+        index.record_entry(&entry);
+        assert!(index.is_empty());
+
+        let string_content =
+            parse_jsonl_line(r#"{"type": "user", "message": {"content": "hello"}}"#).unwrap();
+        index.record_entry(&string_content);
+        assert!(index.is_empty());
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut index = ToolCallIndex::new();
+        index.record_entry(&tool_use("2025-01-15T10:00:00Z", "t1", "Bash"));
+        assert_eq!(index.len(), 1);
+        index.clear();
+        assert!(index.is_empty());
+        assert!(!index.has_pending());
+    }
+
+    // -- 5. Eviction and expiry -----------------------------------------------
+
+    #[test]
+    fn test_forget_drops_call_once_unreferenced() {
+        let mut index = ToolCallIndex::new();
+        let use_entry = tool_use("2025-01-15T10:00:00Z", "t1", "Bash");
+        let result_entry = tool_result("2025-01-15T10:00:01Z", "t1", false);
+        index.record_entry(&use_entry);
+        index.record_entry(&result_entry);
+
+        index.forget_entry(&use_entry);
+        assert_eq!(index.get("t1").unwrap().status, ToolCallStatus::Succeeded);
+
+        index.forget_entry(&result_entry);
+        assert!(index.is_empty());
+    }
+
+    #[test]
+    fn test_forget_pending_call_clears_pending() {
+        let mut index = ToolCallIndex::new();
+        let use_entry = tool_use("2025-01-15T10:00:00Z", "t1", "Bash");
+        index.record_entry(&use_entry);
+        assert!(index.has_pending());
+
+        index.forget_entry(&use_entry);
+        assert!(index.is_empty());
+        assert!(!index.has_pending());
+    }
+
+    #[test]
+    fn test_expire_pending_abandons_old_calls() {
+        let mut index = ToolCallIndex::new();
+        index.record_entry(&tool_use("2025-01-15T10:00:00Z", "old", "Bash"));
+        index.record_entry(&tool_use("2025-01-15T10:30:00Z", "new", "Bash"));
+
+        assert!(index.expire_pending(at("2025-01-15T10:10:00Z")));
+        let old = index.get("old").unwrap();
+        assert_eq!(old.status, ToolCallStatus::Abandoned);
+        assert_eq!(old.elapsed_ms(at("2025-01-15T11:00:00Z")), None);
+        assert_eq!(index.get("new").unwrap().status, ToolCallStatus::Pending);
+        assert!(index.has_pending());
+
+        assert!(index.expire_pending(at("2025-01-15T11:00:00Z")));
+        assert!(!index.has_pending());
+        assert!(!index.expire_pending(at("2025-01-15T12:00:00Z")));
+    }
+
+    #[test]
+    fn test_late_result_finishes_abandoned_call() {
+        let mut index = ToolCallIndex::new();
+        index.record_entry(&tool_use("2025-01-15T10:00:00Z", "t1", "Bash"));
+        index.expire_pending(at("2025-01-15T11:00:00Z"));
+        index.record_entry(&tool_result("2025-01-15T11:30:00Z", "t1", false));
+
+        assert_eq!(index.get("t1").unwrap().status, ToolCallStatus::Succeeded);
+        assert!(!index.has_pending());
+    }
+
+    // -- 6. format_elapsed -----------------------------------------------------
+
+    #[test]
+    fn test_format_elapsed() {
+        assert_eq!(format_elapsed(0), "0.0s");
+        assert_eq!(format_elapsed(420), "0.4s");
+        assert_eq!(format_elapsed(9_940), "9.9s");
+        assert_eq!(format_elapsed(12_000), "12s");
+        assert_eq!(format_elapsed(125_000), "2m 05s");
+        assert_eq!(format_elapsed(3_780_000), "1h 03m");
+    }
+}
@@ -0,0 +1,415 @@
+//! Full detail of tool calls for the expanded view.
+//!
+//! Where [`crate::tool_summary`] reduces each call to one line, this module
+//! gathers everything needed to show a call in full: the pretty-printed
+//! input, the matching `tool_result` output, and for `Edit` / `MultiEdit`
+//! calls a line diff of `old_string` against `new_string`. All text goes
+//! through [`clean_detail_text`] so redaction and control-character
+//! sanitizing still apply.
+
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use crate::log_entry::LogEntry;
+use crate::tool_summary::{clean_detail_text, tool_result_text};
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// One line of an `Edit` diff.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    /// Separator between the edits of a `MultiEdit` call.
+    Header(String),
+    /// Line present in both `old_string` and `new_string`.
+    Context(String),
+    /// Line only in `old_string`.
+    Removed(String),
+    /// Line only in `new_string`.
+    Added(String),
+}
+
+/// The output of a tool call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolResultDetail {
+    /// Cleaned result text.
+    pub text: String,
+    /// Mirrors the block's `is_error` flag.
+    pub is_error: bool,
+}
+
+/// Everything known about a single tool call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolDetail {
+    /// The `tool_use` block ID.
+    pub id: String,
+    /// Tool name (empty if the `tool_use` block was not found).
+    pub name: String,
+    /// Pretty-printed, cleaned input JSON (`None` if the `tool_use` block
+    /// was not found).
+    pub input: Option<String>,
+    /// Diff of `old_string` / `new_string` for `Edit` and `MultiEdit`.
+    pub diff: Vec<DiffLine>,
+    /// The matching result (`None` while pending or if not found).
+    pub result: Option<ToolResultDetail>,
+}
+
+// ---------------------------------------------------------------------------
+// Public API
+// ---------------------------------------------------------------------------
+
+/// Collect the details of every tool call referenced by `entry`.
+///
+/// Both `tool_use` blocks and `tool_result` blocks in `entry` are followed
+/// by ID, so selecting either half of a call yields the full call. The
+/// other half is looked up in `entries` (typically the ring buffer).
+/// Calls are returned in the order they are referenced by `entry`.
+pub fn collect_tool_details<'a, I>(entry: &LogEntry, entries: I) -> Vec<ToolDetail>
+where
+    I: IntoIterator<Item = &'a LogEntry>,
+{
+    let ids = referenced_tool_ids(entry);
+    if ids.is_empty() {
+        return Vec::new();
+    }
+
+    let mut uses: HashMap<&str, &Value> = HashMap::new();
+    let mut results: HashMap<&str, &Value> = HashMap::new();
+    let wanted = |id: &str| ids.iter().any(|w| w == id);
+
+    let mut scan = |e: &'a LogEntry| {
+        for block in content_blocks(e) {
+            match block.get("type").and_then(Value::as_str) {
+                Some("tool_use") => {
+                    if let Some(id) = block.get("id").and_then(Value::as_str) {
+                        if wanted(id) {
+                            uses.insert(id, block);
+                        }
+                    }
+                }
+                Some("tool_result") => {
+                    if let Some(id) = block.get("tool_use_id").and_then(Value::as_str) {
+                        if wanted(id) {
+                            results.insert(id, block);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    };
+    for e in entries {
+        scan(e);
+    }
+
+    let mut details: Vec<ToolDetail> = ids
+        .iter()
+        .map(|id| {
+            let use_block = uses.get(id.as_str()).copied();
+            let name = use_block
+                .and_then(|b| b.get("name"))
+                .and_then(Value::as_str)
+                .map(clean_detail_text)
+                .unwrap_or_default();
+            let input = use_block.map(|b| b.get("input").unwrap_or(&Value::Null));
+            ToolDetail {
+                id: id.clone(),
+                diff: input.map(|i| edit_diff(&name, i)).unwrap_or_default(),
+                input: input.map(pretty_input),
+                name,
+                result: results.get(id.as_str()).map(|b| result_detail(b)),
+            }
+        })
+        .collect();
+
+    // Fill in halves found only in `entry` itself (it may not be in
+    // `entries` yet).
+    for block in content_blocks(entry) {
+        let detail = match block
+            .get("id")
+            .or_else(|| block.get("tool_use_id"))
+            .and_then(Value::as_str)
+            .and_then(|id| details.iter_mut().find(|d| d.id == id))
+        {
+            Some(d) => d,
+            None => continue,
+        };
+        match block.get("type").and_then(Value::as_str) {
+            Some("tool_use") if detail.input.is_none() => {
+                let input = block.get("input").unwrap_or(&Value::Null);
+                detail.name = block
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .map(clean_detail_text)
+                    .unwrap_or_default();
+                detail.diff = edit_diff(&detail.name, input);
+                detail.input = Some(pretty_input(input));
+            }
+            Some("tool_result") if detail.result.is_none() => {
+                detail.result = Some(result_detail(block));
+            }
+            _ => {}
+        }
+    }
+
+    details
+}
+
+/// Line diff of `old` against `new`.
+///
+/// Lines shared at the start and end are kept as context; everything in
+/// between is shown as removed then added. This matches how `Edit` is
+/// used in practice (one contiguous replacement) without a full LCS diff.
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+
+    let prefix = old_lines
+        .iter()
+        .zip(&new_lines)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = old_lines[prefix..]
+        .iter()
+        .rev()
+        .zip(new_lines[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let clean = |s: &str| clean_detail_text(s);
+    let mut diff = Vec::new();
+    diff.extend(
+        old_lines[..prefix]
+            .iter()
+            .map(|l| DiffLine::Context(clean(l))),
+    );
+    diff.extend(
+        old_lines[prefix..old_lines.len() - suffix]
+            .iter()
+            .map(|l| DiffLine::Removed(clean(l))),
+    );
+    diff.extend(
+        new_lines[prefix..new_lines.len() - suffix]
+            .iter()
+            .map(|l| DiffLine::Added(clean(l))),
+    );
+    diff.extend(
+        old_lines[old_lines.len() - suffix..]
+            .iter()
+            .map(|l| DiffLine::Context(clean(l))),
+    );
+    diff
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// The content blocks of an entry's message (empty for string content).
+fn content_blocks(entry: &LogEntry) -> &[Value] {
+    entry
+        .message
+        .as_ref()
+        .and_then(|m| m.content.as_array())
+        .map(Vec::as_slice)
+        .unwrap_or(&[])
+}
+
+/// IDs of the tool calls an entry refers to, in order and without
+/// duplicates.
+fn referenced_tool_ids(entry: &LogEntry) -> Vec<String> {
+    let mut ids: Vec<String> = Vec::new();
+    for block in content_blocks(entry) {
+        let id = match block.get("type").and_then(Value::as_str) {
+            Some("tool_use") => block.get("id"),
+            Some("tool_result") => block.get("tool_use_id"),
+            _ => None,
+        };
+        if let Some(id) = id.and_then(Value::as_str) {
+            if !ids.iter().any(|existing| existing == id) {
+                ids.push(id.to_string());
+            }
+        }
+    }
+    ids
+}
+
+/// Pretty-print a tool input and clean it for display.
+fn pretty_input(input: &Value) -> String {
+    let json = serde_json::to_string_pretty(input).unwrap_or_default();
+    clean_detail_text(&json)
+}
+
+/// Build a [`ToolResultDetail`] from a `tool_result` block.
+fn result_detail(block: &Value) -> ToolResultDetail {
+    let content = block.get("content").unwrap_or(&Value::Null);
+    ToolResultDetail {
+        text: clean_detail_text(&tool_result_text(content)),
+        is_error: block.get("is_error").and_then(Value::as_bool) == Some(true),
+    }
+}
+
+/// Diff for `Edit` (`old_string` / `new_string`) and `MultiEdit` (`edits`
+/// array); empty for every other tool.
+fn edit_diff(name: &str, input: &Value) -> Vec<DiffLine> {
+    let single = |edit: &Value| -> Option<Vec<DiffLine>> {
+        let old = edit.get("old_string").and_then(Value::as_str)?;
+        let new = edit.get("new_string").and_then(Value::as_str)?;
+        Some(diff_lines(old, new))
+    };
+
+    match name {
+        "Edit" => single(input).unwrap_or_default(),
+        "MultiEdit" => {
+            let edits = match input.get("edits").and_then(Value::as_array) {
+                Some(e) => e,
+                None => return Vec::new(),
+            };
+            let mut diff = Vec::new();
+            for (i, edit) in edits.iter().enumerate() {
+                if let Some(lines) = single(edit) {
+                    diff.push(DiffLine::Header(format!("edit {}/{}", i + 1, edits.len())));
+                    diff.extend(lines);
+                }
+            }
+            diff
+        }
+        _ => Vec::new(),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log_entry::parse_jsonl_line;
+
+    fn entry(json: &str) -> LogEntry {
+        parse_jsonl_line(json).unwrap()
+    }
+
+    fn bash_use() -> LogEntry {
+        entry(
+            r#"{"type": "assistant", "message": {"role": "assistant", "content": [{"type": "tool_use", "id": "t1", "name": "Bash", "input": {"command": "curl -H 'Authorization: Bearer abc123' https://x", "description": "fetch"}}]}}"#,
+        )
+    }
+
+    fn bash_result() -> LogEntry {
+        entry(
+            r#"{"type": "user", "message": {"role": "user", "content": [{"type": "tool_result", "tool_use_id": "t1", "content": "Exit code 1\n\u001b[31mfailed\u001b[0m", "is_error": true}]}}"#,
+        )
+    }
+
+    // -- 1. Pairing ------------------------------------------------------------
+
+    #[test]
+    fn test_tool_use_entry_finds_result() {
+        let use_entry = bash_use();
+        let all = vec![use_entry.clone(), bash_result()];
+        let details = collect_tool_details(&use_entry, &all);
+
+        assert_eq!(details.len(), 1);
+        let d = &details[0];
+        assert_eq!(d.id, "t1");
+        assert_eq!(d.name, "Bash");
+        let input = d.input.as_deref().unwrap();
+        assert!(input.contains("\"description\": \"fetch\""));
+        let result = d.result.as_ref().unwrap();
+        assert!(result.is_error);
+        assert_eq!(result.text, "Exit code 1\nfailed");
+    }
+
+    #[test]
+    fn test_tool_result_entry_finds_use() {
+        let result_entry = bash_result();
+        let all = vec![bash_use(), result_entry.clone()];
+        let details = collect_tool_details(&result_entry, &all);
+
+        assert_eq!(details.len(), 1);
+        assert_eq!(details[0].name, "Bash");
+        assert!(details[0].input.is_some());
+        assert!(details[0].result.is_some());
+    }
+
+    #[test]
+    fn test_pending_call_has_no_result() {
+        let use_entry = bash_use();
+        // `entry` itself is not in the list: its own blocks are still used.
+        let details = collect_tool_details(&use_entry, &[]);
+        assert_eq!(details.len(), 1);
+        assert_eq!(details[0].name, "Bash");
+        assert!(details[0].result.is_none());
+    }
+
+    #[test]
+    fn test_entry_without_tools() {
+        let text = entry(
+            r#"{"type": "assistant", "message": {"content": [{"type": "text", "text": "hi"}]}}"#,
+        );
+        assert!(collect_tool_details(&text, std::slice::from_ref(&text)).is_empty());
+    }
+
+    // -- 2. Security -------------------------------------------------------------
+
+    #[test]
+    fn test_input_is_redacted() {
+        let use_entry = bash_use();
+        let details = collect_tool_details(&use_entry, &[]);
+        let input = details[0].input.as_deref().unwrap();
+        assert!(!input.contains("abc123"));
+        assert!(input.contains("Bearer [REDACTED]"));
+    }
+
+    // -- 3. Edit diff ------------------------------------------------------------
+
+    #[test]
+    fn test_edit_diff() {
+        let edit = entry(
+            r#"{"type": "assistant", "message": {"content": [{"type": "tool_use", "id": "e1", "name": "Edit", "input": {"file_path": "a.rs", "old_string": "fn a() {\n    1\n}", "new_string": "fn a() {\n    2\n    3\n}"}}]}}"#,
+        );
+        let details = collect_tool_details(&edit, &[]);
+        assert_eq!(
+            details[0].diff,
+            vec![
+                DiffLine::Context("fn a() {".into()),
+                DiffLine::Removed("    1".into()),
+                DiffLine::Added("    2".into()),
+                DiffLine::Added("    3".into()),
+                DiffLine::Context("}".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_multi_edit_diff_has_headers() {
+        let edit = entry(
+            r#"{"type": "assistant", "message": {"content": [{"type": "tool_use", "id": "e1", "name": "MultiEdit", "input": {"file_path": "a.rs", "edits": [{"old_string": "a", "new_string": "b"}, {"old_string": "c", "new_string": "d"}]}}]}}"#,
+        );
+        let diff = &collect_tool_details(&edit, &[])[0].diff;
+        assert_eq!(diff[0], DiffLine::Header("edit 1/2".into()));
+        assert_eq!(diff[1], DiffLine::Removed("a".into()));
+        assert_eq!(diff[2], DiffLine::Added("b".into()));
+        assert_eq!(diff[3], DiffLine::Header("edit 2/2".into()));
+    }
+
+    #[test]
+    fn test_non_edit_tools_have_no_diff() {
+        let details = collect_tool_details(&bash_use(), &[]);
+        assert!(details[0].diff.is_empty());
+    }
+
+    #[test]
+    fn test_diff_lines_identical_and_empty() {
+        assert_eq!(
+            diff_lines("same", "same"),
+            vec![DiffLine::Context("same".into())]
+        );
+        assert_eq!(diff_lines("", "new"), vec![DiffLine::Added("new".into())]);
+        assert_eq!(diff_lines("old", ""), vec![DiffLine::Removed("old".into())]);
+    }
+}
@@ -9,6 +9,10 @@ use std::sync::LazyLock;
 /// Maximum number of characters for Bash command summaries before truncation.
 const BASH_CMD_MAX_CHARS: usize = 80;
 
+/// Maximum number of characters of error / stderr text shown in a tool
+/// result summary before truncation.
+const RESULT_TEXT_MAX_CHARS: usize = 80;
+
 // ---------------------------------------------------------------------------
 // Compiled regex patterns (compiled once, reused across calls)
 // ---------------------------------------------------------------------------
@@ -21,6 +25,16 @@ static ANSI_RE: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(r"\x1b\[[0-9;]*[A-Za-z]|\x1b\][^\x07\x1b]*(?:\x07|\x1b\\)|\x1b[A-Za-z]").unwrap()
 });
 
+/// Matches the `Exit code N` line Claude Code prepends to failed Bash output.
+static EXIT_CODE_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^Exit code (-?\d+)").unwrap());
+
+/// Matches a numbered line in Read output (`     1→contents`).
+static READ_LINE_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\s*\d+→").unwrap());
+
+/// Matches the `Found N file(s)` / `Found N match(es)` header in Grep output.
+static FOUND_COUNT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^Found (\d+) (?:total )?(file|match|line|occurrence)").unwrap());
+
 /// Matches common secret patterns and redacts the sensitive portion.
 /// Each pattern captures a prefix group and a secret-value group.
 static SECRET_PATTERNS: LazyLock<Vec<Regex>> = LazyLock::new(|| {
@@ -81,6 +95,154 @@ pub fn summarize_tool_use(name: &str, input: &Value) -> String {
     redact_secrets(&raw)
 }
 
+/// Produce a one-line summary string for a `tool_result` content block.
+///
+/// `name` is the tool that produced the result, looked up by the caller from
+/// the matching `tool_use` block (results only carry a `tool_use_id`). When
+/// the name is unknown a generic summary is produced.
+///
+/// Per-tool outcomes:
+/// * `Bash` — exit code, plus the last output line (stderr tail) on failure
+/// * `Read` — number of lines returned
+/// * `Grep` — number of matches (or files, in files-with-matches mode)
+/// * `Glob` — number of files matched
+/// * `Edit` / `MultiEdit` / `Write` / `NotebookEdit` — `applied`
+///
+/// Any `is_error` result shows `error: <first line>` (Bash shows its tail).
+/// Output is sanitized and redacted like [`summarize_tool_use`].
+pub fn summarize_tool_result(name: Option<&str>, content: &Value, is_error: bool) -> String {
+    let text = sanitize_control_chars(&tool_result_text(content));
+    let label = name.map(sanitize_control_chars);
+    let label = match label.as_deref() {
+        Some(n) if !n.is_empty() => n,
+        _ => "result",
+    };
+
+    let outcome = match label {
+        "Bash" => summarize_bash_result(&text, is_error),
+        _ if is_error => format!("error: {}", first_line_excerpt(&text)),
+        "Read" => {
+            let n = text.lines().filter(|l| READ_LINE_RE.is_match(l)).count();
+            let n = if n == 0 { non_empty_lines(&text) } else { n };
+            plural(n, "line", "lines")
+        }
+        "Grep" => summarize_grep_result(&text),
+        "Glob" => {
+            let n = text
+                .lines()
+                .filter(|l| !l.trim().is_empty() && !l.starts_with('(') && *l != "No files found")
+                .count();
+            plural(n, "file", "files")
+        }
+        "Edit" | "MultiEdit" | "Write" | "NotebookEdit" => "applied".to_string(),
+        _ => plural(non_empty_lines(&text), "line", "lines"),
+    };
+
+    redact_secrets(&format!("[{}] {}", label, outcome))
+}
+
+/// Clean multi-line text for full display (e.g. the TUI detail pane).
+///
+/// Applies the same sanitizing and secret redaction as the one-line
+/// summaries, but keeps line breaks and expands tabs to four spaces.
+pub fn clean_detail_text(s: &str) -> String {
+    redact_secrets(&sanitize_control_chars(s)).replace('\t', "    ")
+}
+
+// ---------------------------------------------------------------------------
+// Tool result helpers
+// ---------------------------------------------------------------------------
+
+/// Flatten a `tool_result` `content` value into plain text.
+///
+/// `content` is either a string or an array of blocks; only `text` blocks
+/// contribute (images and other blocks are ignored).
+pub fn tool_result_text(content: &Value) -> String {
+    match content {
+        Value::String(s) => s.clone(),
+        Value::Array(blocks) => blocks
+            .iter()
+            .filter(|b| b.get("type").and_then(Value::as_str) == Some("text"))
+            .filter_map(|b| b.get("text").and_then(Value::as_str))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    }
+}
+
+/// Summarize Bash output: `exit 0 (N lines)` on success, or
+/// `exit N: <last output line>` on failure.
+fn summarize_bash_result(text: &str, is_error: bool) -> String {
+    let exit_code = text
+        .lines()
+        .next()
+        .and_then(|l| EXIT_CODE_RE.captures(l))
+        .and_then(|c| c[1].parse::<i64>().ok());
+
+    if !is_error && exit_code.is_none_or(|c| c == 0) {
+        return format!(
+            "exit 0 ({})",
+            plural(non_empty_lines(text), "line", "lines")
+        );
+    }
+
+    // The tail of the output is usually the most informative part of a
+    // failure (compiler errors, test summaries, stderr).
+    let body: Vec<&str> = text
+        .lines()
+        .skip(if exit_code.is_some() { 1 } else { 0 })
+        .collect();
+    let tail = body
+        .iter()
+        .rev()
+        .find(|l| !l.trim().is_empty())
+        .map(|l| truncate_chars(l.trim(), RESULT_TEXT_MAX_CHARS));
+
+    let head = match exit_code {
+        Some(code) => format!("exit {}", code),
+        None => "failed".to_string(),
+    };
+    match tail {
+        Some(t) => format!("{}: {}", head, t),
+        None => head,
+    }
+}
+
+/// Summarize Grep output as a match (or file) count.
+fn summarize_grep_result(text: &str) -> String {
+    let first = text.lines().next().unwrap_or("");
+    if let Some(c) = FOUND_COUNT_RE.captures(first) {
+        let n: usize = c[1].parse().unwrap_or(0);
+        return match &c[2] {
+            "file" => plural(n, "file", "files"),
+            _ => plural(n, "match", "matches"),
+        };
+    }
+    if first.starts_with("No matches found") || first.starts_with("No files found") {
+        return plural(0, "match", "matches");
+    }
+    plural(non_empty_lines(text), "match", "matches")
+}
+
+/// First non-empty line of `text`, trimmed and truncated.
+fn first_line_excerpt(text: &str) -> String {
+    text.lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty())
+        .map(|l| truncate_chars(l, RESULT_TEXT_MAX_CHARS))
+        .unwrap_or_default()
+}
+
+/// Count the non-blank lines in `text`.
+fn non_empty_lines(text: &str) -> usize {
+    text.lines().filter(|l| !l.trim().is_empty()).count()
+}
+
+/// Format a count with a singular or plural noun (e.g. `1 line`, `3 lines`).
+fn plural(n: usize, singular: &str, plural: &str) -> String {
+    format!("{} {}", n, if n == 1 { singular } else { plural })
+}
+
 // ---------------------------------------------------------------------------
 // Security helpers
 // ---------------------------------------------------------------------------
@@ -105,7 +267,7 @@ fn sanitize_control_chars(s: &str) -> String {
 ///
 /// Replaces the sensitive value portion with `[REDACTED]`, preserving the
 /// prefix so the user can see *what kind* of secret was present.
-fn redact_secrets(s: &str) -> String {
+pub fn redact_secrets(s: &str) -> String {
     let mut result = s.to_string();
     for pattern in SECRET_PATTERNS.iter() {
         result = pattern.replace_all(&result, "${1}[REDACTED]").to_string();
@@ -176,7 +338,7 @@ fn summarize_grep(input: &Value) -> String {
 /// Truncate a string to at most `max` characters, appending `…` if truncated.
 ///
 /// Uses `.chars()` iteration so that we never split a multi-byte codepoint.
-fn truncate_chars(s: &str, max: usize) -> String {
+pub(crate) fn truncate_chars(s: &str, max: usize) -> String {
     let char_count = s.chars().count();
     if char_count <= max {
         s.to_string()
@@ -864,4 +1026,154 @@ mod tests {
         let result = summarize_tool_use("Read", &input);
         assert_eq!(result, "[Read] src/config/settings.rs");
     }
+
+    // -----------------------------------------------------------------------
+    // Tool result summaries
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn test_result_bash_success() {
+        let content = json!("line one\nline two\n");
+        assert_eq!(
+            summarize_tool_result(Some("Bash"), &content, false),
+            "[Bash] exit 0 (2 lines)"
+        );
+    }
+
+    #[test]
+    fn test_result_bash_failure_with_exit_code_and_stderr_tail() {
+        let content = json!("Exit code 101\n   Compiling foo\nerror: could not compile `foo`\n");
+        assert_eq!(
+            summarize_tool_result(Some("Bash"), &content, true),
+            "[Bash] exit 101: error: could not compile `foo`"
+        );
+    }
+
+    #[test]
+    fn test_result_bash_error_without_exit_code() {
+        let content = json!("Command timed out after 2m 0.0s");
+        assert_eq!(
+            summarize_tool_result(Some("Bash"), &content, true),
+            "[Bash] failed: Command timed out after 2m 0.0s"
+        );
+    }
+
+    #[test]
+    fn test_result_bash_exit_code_only() {
+        let content = json!("Exit code 1");
+        assert_eq!(
+            summarize_tool_result(Some("Bash"), &content, true),
+            "[Bash] exit 1"
+        );
+    }
+
+    #[test]
+    fn test_result_read_counts_numbered_lines() {
+        let content =
+            json!("     1→fn main() {\n     2→}\n\n<system-reminder>\nnote\n</system-reminder>");
+        assert_eq!(
+            summarize_tool_result(Some("Read"), &content, false),
+            "[Read] 2 lines"
+        );
+    }
+
+    #[rstest]
+    #[case("Found 3 files\nsrc/a.rs\nsrc/b.rs\nsrc/c.rs", "[Grep] 3 files")]
+    #[case("Found 1 file\nsrc/a.rs", "[Grep] 1 file")]
+    #[case("src/a.rs:1:foo\nsrc/b.rs:7:foo", "[Grep] 2 matches")]
+    #[case("No matches found", "[Grep] 0 matches")]
+    #[case("Found 12 total occurrences across 3 files.", "[Grep] 12 matches")]
+    fn test_result_grep(#[case] text: &str, #[case] expected: &str) {
+        assert_eq!(
+            summarize_tool_result(Some("Grep"), &json!(text), false),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("src/a.rs\nsrc/b.rs", "[Glob] 2 files")]
+    #[case("No files found", "[Glob] 0 files")]
+    #[case(
+        "src/a.rs\n(Results are truncated. Consider using a more specific path or pattern.)",
+        "[Glob] 1 file"
+    )]
+    fn test_result_glob(#[case] text: &str, #[case] expected: &str) {
+        assert_eq!(
+            summarize_tool_result(Some("Glob"), &json!(text), false),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("Edit")]
+    #[case("MultiEdit")]
+    #[case("Write")]
+    fn test_result_edit_write_applied(#[case] tool: &str) {
+        let content = json!("The file /src/main.rs has been updated.");
+        assert_eq!(
+            summarize_tool_result(Some(tool), &content, false),
+            format!("[{}] applied", tool)
+        );
+    }
+
+    #[test]
+    fn test_result_edit_error_text() {
+        let content =
+            json!("<tool_use_error>String to replace not found in file.</tool_use_error>");
+        let result = summarize_tool_result(Some("Edit"), &content, true);
+        assert!(result.starts_with("[Edit] error: "));
+        assert!(result.contains("String to replace not found"));
+    }
+
+    #[test]
+    fn test_result_unknown_tool_name() {
+        let content = json!("a\nb\nc");
+        assert_eq!(
+            summarize_tool_result(None, &content, false),
+            "[result] 3 lines"
+        );
+        assert_eq!(
+            summarize_tool_result(Some("WebFetch"), &content, false),
+            "[WebFetch] 3 lines"
+        );
+    }
+
+    #[test]
+    fn test_result_array_content_text_blocks() {
+        let content = json!([
+            {"type": "text", "text": "     1→a\n     2→b"},
+            {"type": "image", "source": {}}
+        ]);
+        assert_eq!(
+            summarize_tool_result(Some("Read"), &content, false),
+            "[Read] 2 lines"
+        );
+    }
+
+    #[test]
+    fn test_result_error_is_sanitized_and_redacted() {
+        let content = json!("Exit code 1\n\x1b[31mAPI_KEY=supersecret123 rejected\x1b[0m");
+        let result = summarize_tool_result(Some("Bash"), &content, true);
+        assert!(!result.contains('\x1b'));
+        assert!(!result.contains("supersecret123"));
+        assert!(result.contains("API_KEY=[REDACTED]"));
+    }
+
+    #[test]
+    fn test_result_long_error_truncated() {
+        let long = "x".repeat(200);
+        let result = summarize_tool_result(Some("Write"), &json!(long), true);
+        assert!(result.ends_with('…'));
+        assert!(result.chars().count() < 120);
+    }
+
+    // -----------------------------------------------------------------------
+    // Detail text cleaning
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn test_clean_detail_text_keeps_newlines() {
+        let cleaned = clean_detail_text("line 1\n\tindented\x1b[31m red\x1b[0m\nBearer abc.def");
+        assert_eq!(cleaned, "line 1\n    indented red\nBearer [REDACTED]");
+    }
 }
@@ -4,22 +4,62 @@
 //! focus tracking, sidebar visibility, quit flag, sessions list,
 //! config, and the ring buffer of log entries.
 
+use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
-use std::time::SystemTime;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::mpsc;
+use std::time::{Duration, Instant, SystemTime};
 
 use crate::config::AppConfig;
+use crate::content_render::{has_non_tool_content, has_renderable_content};
+use crate::conversation_tree::{ConversationTree, TreeRow};
 use crate::filter::FilterState;
+use crate::filter_expr::FilterExpr;
+use crate::history_search::{search_sessions, HistoryMatch};
+use crate::log_entry::{EntryType, LogEntry};
+use crate::log_warn;
+use crate::process_detect::{ProcessDetector, ProcessScan, ProcessState};
+use crate::project_path::{project_label, read_session_cwd};
 use crate::replay::{load_full_session, replay_session, session_file_size, DEFAULT_REPLAY_COUNT};
 use crate::ring_buffer::RingBuffer;
 use crate::search::SearchState;
-use crate::session::{classify_new_file, Agent, NewFileKind, Session};
+use crate::search_index::LiveIndex;
+use crate::session::{
+    classify_new_file, classify_new_file_in_projects, discover_sessions, Agent, NewFileKind,
+    Session, SessionStatus,
+};
+use crate::session_info::{SessionInfo, SessionInfoCache};
+use crate::session_stats::StatsTracker;
+use crate::session_title::TitleCache;
 use crate::symbols::Symbols;
+use crate::task_links::TaskLinkIndex;
 use crate::theme::ThemeColors;
-use crate::tui::filter_overlay::{FilterMenuState, MenuAction};
+use crate::tool_calls::ToolCallIndex;
+use crate::tool_detail::collect_tool_details;
+use crate::tui::detail_view::{build_detail_lines, DetailAction, DetailViewState};
+use crate::tui::diagnostics::{Diagnostics, DiagnosticsAction};
+use crate::tui::filter_overlay::{FilterMenuState, FilterPromptState, MenuAction, PromptAction};
+use crate::tui::global_search::{
+    GlobalSearchAction, GlobalSearchMode, GlobalSearchState, MAX_MATCHES_PER_SESSION,
+};
+use crate::tui::session_filter::{SessionFilterAction, SessionFilterState};
+use crate::watcher::WatcherStatus;
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
 
+/// How often the process table is rescanned with `--detect-processes`.
+const PROCESS_SCAN_INTERVAL: Duration = Duration::from_secs(2);
+
+/// How often sidebar titles are checked for changed session logs.
+const TITLE_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How often new entries are added to the project's search index.
+const INDEX_UPDATE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How long a tool call may stay pending before it is considered abandoned.
+const TOOL_CALL_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
 // NOTE: ActiveFilters struct has been removed. Filter display is now handled
 // by FilterState::display() directly.
 
@@ -34,6 +74,47 @@ pub enum Focus {
     LogStream,
 }
 
+// ---------------------------------------------------------------------------
+// Task jumps
+// ---------------------------------------------------------------------------
+
+/// Log stream position saved by a jump into a subagent transcript.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskJump {
+    /// `uuid` of the entry holding the `Task` call.
+    pub entry_uuid: Option<String>,
+    /// Agent filter in effect before the jump.
+    pub selected_agent: Option<String>,
+}
+
+// ---------------------------------------------------------------------------
+// Log panes
+// ---------------------------------------------------------------------------
+
+/// Maximum number of panes the log area can be split into.
+pub const MAX_PANES: usize = 4;
+
+/// View state of one log pane: what it shows and where it is scrolled.
+///
+/// The focused pane's state lives in the matching `App` fields
+/// (`active_session_id`, `filter_state`, `scroll_mode`, ...), so key
+/// handling and rendering only ever deal with "the" log stream. The other
+/// panes are parked in [`App::panes`] and swapped in while they are drawn
+/// or when they receive focus.
+#[derive(Debug, Clone, Default)]
+pub struct LogPane {
+    /// Session shown in the pane.
+    pub session_id: Option<String>,
+    /// Filter of the pane; `selected_agent` narrows it to one subagent.
+    pub filter_state: FilterState,
+    pub scroll_mode: Option<ScrollMode>,
+    pub pending_scroll: Option<PendingScroll>,
+    pub search_state: SearchState,
+    pub log_cursor: Option<usize>,
+    pub full_history_loaded: bool,
+    pub task_jumps: Vec<TaskJump>,
+}
+
 // ---------------------------------------------------------------------------
 // Scroll types
 // ---------------------------------------------------------------------------
@@ -137,10 +218,21 @@ pub struct App {
     pub symbols: Symbols,
     /// Byte-budgeted ring buffer holding parsed log entries.
     pub ring_buffer: RingBuffer,
+    /// Status of each tool call seen in the ring buffer, keyed by
+    /// `tool_use` ID. Kept in step with `ring_buffer`.
+    pub tool_calls: ToolCallIndex,
+    /// `Task` tool calls linked to the subagents they spawned.
+    pub task_links: TaskLinkIndex,
+    /// Statistics of the entries in `ring_buffer`, for the status bar and
+    /// the help overlay.
+    pub session_stats: StatsTracker,
     /// Discovered sessions, sorted by last_modified descending.
     pub sessions: Vec<Session>,
     /// Index of the currently selected session in the sidebar.
     pub selected_session_index: usize,
+    /// Whether the sidebar's "All sessions" row is selected instead of the
+    /// session at `selected_session_index`.
+    pub all_sessions_selected: bool,
     /// Session IDs of newly appeared sessions (highlighted in the sidebar).
     /// Cleared when the user selects a session with Enter.
     pub new_session_ids: HashSet<String>,
@@ -153,9 +245,14 @@ pub struct App {
     pub filter_state: FilterState,
     /// State for the filter menu overlay (opened with `f`).
     pub filter_menu: FilterMenuState,
+    /// State for the filter expression prompt (opened with `F`).
+    pub filter_prompt: FilterPromptState,
     /// Per-file EOF offsets from the last replay, used to hand off to the
     /// watcher so it starts tailing from where replay left off.
     pub replay_offsets: HashMap<PathBuf, u64>,
+    /// Sessions whose file was renamed: the `sessionId` their entries
+    /// carry, mapped to the id of the session in the sidebar.
+    session_aliases: HashMap<String, String>,
     /// Transient status message shown in the status bar.
     /// Cleared after a few ticks or on the next key press.
     pub status_message: Option<String>,
@@ -163,22 +260,86 @@ pub struct App {
     pub project_path: Option<PathBuf>,
     /// Whether the help overlay is currently visible.
     pub help_overlay_visible: bool,
+    /// Working directory, branch and version of the active session.
+    pub session_info: SessionInfo,
+    /// Session info of the logs loaded so far, read incrementally.
+    session_infos: SessionInfoCache,
+    /// Whether the session info panel (opened with `i`) is visible.
+    pub session_info_visible: bool,
     /// Human-readable project name derived from the project path
     /// (e.g. last path component: `/Users/.../cc-tail` -> `"cc-tail"`).
     /// Shown in the status bar.
     pub project_display_name: Option<String>,
+    /// cc-tail's own log file, shown in the help overlay.
+    pub log_path: Option<PathBuf>,
+    /// Sidebar labels of project directories (`--all-projects`), keyed by
+    /// the directory under `~/.claude/projects/`.
+    pub project_labels: HashMap<PathBuf, String>,
     /// Active scroll (freeze) mode state, if the user has entered scroll mode.
     pub scroll_mode: Option<ScrollMode>,
     /// A pending scroll action waiting for the render phase to snapshot lines.
     pub pending_scroll: Option<PendingScroll>,
     /// Search state: mode, query, matches, current match index.
     pub search_state: SearchState,
+    /// Cross-session search input and results overlay (opened with `S`).
+    pub global_search: GlobalSearchState,
     /// Whether the full session history has been loaded (via `L` key).
     pub full_history_loaded: bool,
     /// Whether a full-load size confirmation prompt is pending.
     pub full_load_confirm_pending: bool,
     /// The file size (in MB) shown in the confirmation prompt.
     pub full_load_pending_size_mb: f64,
+    /// Entry cursor in the log stream: index into [`App::visible_entries`].
+    /// May exceed the last index (e.g. `usize::MAX` for "newest"); the
+    /// render phase clamps it.
+    pub log_cursor: Option<usize>,
+    /// Detail pane for the entry under the cursor (opened with Enter).
+    pub detail_view: DetailViewState,
+    /// Watcher diagnostics and their panel (opened with `D`).
+    pub diagnostics: Diagnostics,
+    /// Whether thinking blocks are expanded in the log stream (`T`).
+    pub show_thinking: bool,
+    /// UUIDs of entries whose thinking expansion is flipped relative to
+    /// `show_thinking` (`t` on the entry under the cursor).
+    pub toggled_thinking: HashSet<String>,
+    /// UUIDs of compaction summary entries shown in full (`t` on the
+    /// entry under the cursor); collapsed to one line otherwise.
+    pub expanded_summaries: HashSet<String>,
+    /// Where each `>` jump into a subagent transcript came from, newest
+    /// last; `<` returns to the top one.
+    pub task_jumps: Vec<TaskJump>,
+    /// Whether the log stream shows the conversation tree with abandoned
+    /// branches (`B`) instead of the current branch only.
+    pub show_tree: bool,
+    /// Conversation tree of `ring_buffer`, with the buffer generation it
+    /// was built from. Rebuilt lazily once the buffer changes.
+    tree_cache: RefCell<Option<(u64, Rc<ConversationTree>)>>,
+    /// Process detector, when `--detect-processes` is on and `/proc` exists.
+    pub process_detector: Option<ProcessDetector>,
+    /// Process state of each session from the last scan, keyed by id.
+    pub process_states: HashMap<String, ProcessState>,
+    /// When the scan behind `process_states` started.
+    pub process_scanned_at: SystemTime,
+    /// When the last process scan was started.
+    last_process_scan: Option<Instant>,
+    /// The process scan running in the background, if any.
+    process_scan: Option<mpsc::Receiver<ProcessScan>>,
+    /// Sidebar titles of session logs (summary or first prompt).
+    pub session_titles: TitleCache,
+    /// When the sidebar titles were last refreshed.
+    last_title_refresh: Option<Instant>,
+    /// Search indexes kept up to date with the entries the watcher reports.
+    pub search_index: LiveIndex,
+    /// When new entries were last added to the search index.
+    last_index_update: Option<Instant>,
+    /// Fuzzy filter over the sidebar (`/` with the sidebar focused).
+    pub session_filter: SessionFilterState,
+    /// Log panes, left to right. The entry at `focused_pane` is a
+    /// placeholder: that pane's state is held in the `App` fields (see
+    /// [`LogPane`]).
+    pub panes: Vec<LogPane>,
+    /// Index of the pane that log stream keys apply to.
+    pub focused_pane: usize,
     /// Dirty flag: when `true`, the next tick will redraw the terminal.
     /// Set to `true` on any state mutation; cleared after `terminal.draw()`.
     pub needs_redraw: bool,
@@ -196,6 +357,11 @@ impl App {
     pub fn new(config: AppConfig) -> Self {
         let theme_colors = ThemeColors::from_theme(&config.theme);
         let symbols = Symbols::new(config.ascii);
+        let process_detector = if config.detect_processes {
+            ProcessDetector::system()
+        } else {
+            None
+        };
         Self {
             focus: Focus::Sidebar,
             sidebar_visible: true,
@@ -204,24 +370,57 @@ impl App {
             theme_colors,
             symbols,
             ring_buffer: RingBuffer::with_default_budget(),
+            tool_calls: ToolCallIndex::new(),
+            task_links: TaskLinkIndex::new(),
+            session_stats: StatsTracker::default(),
             sessions: Vec::new(),
             selected_session_index: 0,
+            all_sessions_selected: false,
             new_session_ids: HashSet::new(),
             sidebar_scroll_offset: 0,
             active_session_id: None,
             filter_state: FilterState::default(),
             filter_menu: FilterMenuState::default(),
+            filter_prompt: FilterPromptState::default(),
             replay_offsets: HashMap::new(),
+            session_aliases: HashMap::new(),
             status_message: None,
             project_path: None,
             help_overlay_visible: false,
+            session_info: SessionInfo::default(),
+            session_infos: SessionInfoCache::new(),
+            session_info_visible: false,
             project_display_name: None,
+            log_path: None,
+            project_labels: HashMap::new(),
             scroll_mode: None,
             pending_scroll: None,
             search_state: SearchState::default(),
+            global_search: GlobalSearchState::default(),
             full_history_loaded: false,
             full_load_confirm_pending: false,
             full_load_pending_size_mb: 0.0,
+            log_cursor: None,
+            detail_view: DetailViewState::default(),
+            diagnostics: Diagnostics::default(),
+            show_thinking: false,
+            toggled_thinking: HashSet::new(),
+            expanded_summaries: HashSet::new(),
+            task_jumps: Vec::new(),
+            show_tree: false,
+            tree_cache: RefCell::new(None),
+            process_detector,
+            process_states: HashMap::new(),
+            process_scanned_at: SystemTime::UNIX_EPOCH,
+            last_process_scan: None,
+            process_scan: None,
+            session_titles: TitleCache::new(),
+            last_title_refresh: None,
+            search_index: LiveIndex::default(),
+            last_index_update: None,
+            session_filter: SessionFilterState::default(),
+            panes: vec![LogPane::default()],
+            focused_pane: 0,
             needs_redraw: true,
         }
     }
@@ -253,6 +452,33 @@ impl App {
             return;
         }
 
+        // The session info panel is modal like the help overlay.
+        if self.session_info_visible {
+            match key.code {
+                KeyCode::Char('i') | KeyCode::Esc => {
+                    self.session_info_visible = false;
+                }
+                _ => {} // consume the key
+            }
+            return;
+        }
+
+        // The diagnostics panel is modal and scrolls like the detail pane.
+        if self.diagnostics.visible {
+            if self.diagnostics.on_key(key) == DiagnosticsAction::Close {
+                self.diagnostics.close();
+            }
+            return;
+        }
+
+        // The detail pane is modal: it handles scrolling and closing.
+        if self.detail_view.visible {
+            if self.detail_view.on_key(key) == DetailAction::Close {
+                self.detail_view.close();
+            }
+            return;
+        }
+
         // Full-load confirmation prompt: intercept y/n/Esc before anything else.
         if self.full_load_confirm_pending {
             match key.code {
@@ -276,15 +502,52 @@ impl App {
                 self.filter_menu.visible = false;
                 return;
             }
+            // If the filter prompt is open, Ctrl+C cancels it instead of quitting.
+            if self.filter_prompt.visible {
+                self.filter_prompt.close();
+                return;
+            }
+            // If global search is open, Ctrl+C closes it instead of quitting.
+            if self.global_search.mode != GlobalSearchMode::Inactive {
+                self.global_search.close();
+                return;
+            }
             // If search input is active, cancel it instead of quitting.
             if self.search_state.is_input() {
                 self.search_state.cancel();
                 return;
             }
+            // If the session filter input is open, clear it instead of quitting.
+            if self.session_filter.is_input() {
+                self.session_filter.clear();
+                self.select_visible_session();
+                return;
+            }
             self.initiate_quit();
             return;
         }
 
+        // When the filter prompt is open, delegate all key events to it.
+        if self.filter_prompt.visible {
+            match self.filter_prompt.on_key(key) {
+                PromptAction::Close => self.filter_prompt.close(),
+                PromptAction::Submit => self.submit_filter_prompt(),
+                PromptAction::Consumed => {}
+            }
+            return;
+        }
+
+        // When global search input or results are shown, delegate to it.
+        if self.global_search.mode != GlobalSearchMode::Inactive {
+            match self.global_search.on_key(key) {
+                GlobalSearchAction::Close => self.global_search.close(),
+                GlobalSearchAction::Submit => self.submit_global_search(),
+                GlobalSearchAction::Pick(m) => self.jump_to_history_match(m),
+                GlobalSearchAction::Consumed => {}
+            }
+            return;
+        }
+
         // When search is in Input mode, delegate all key events to the search input handler.
         if self.search_state.is_input() {
             match key.code {
@@ -295,12 +558,15 @@ impl App {
                     self.search_state.confirm();
                     // If search became active, force scroll mode so highlights are stable.
                     if self.search_state.is_active() {
-                        self.force_scroll_mode_for_search();
+                        self.force_scroll_snapshot();
                     }
                 }
                 KeyCode::Backspace => {
                     self.search_state.on_backspace();
                 }
+                KeyCode::Tab => {
+                    self.search_state.cycle_match_mode();
+                }
                 KeyCode::Char(ch) => {
                     self.search_state.on_char(ch);
                 }
@@ -309,6 +575,17 @@ impl App {
             return;
         }
 
+        // When the session filter input is open, delegate to it.
+        if self.session_filter.is_input() {
+            match self.session_filter.on_key(key) {
+                SessionFilterAction::Changed | SessionFilterAction::Close => {
+                    self.select_visible_session();
+                }
+                SessionFilterAction::Submit | SessionFilterAction::Consumed => {}
+            }
+            return;
+        }
+
         // When the filter menu is visible, delegate ALL key events to it.
         if self.filter_menu.visible {
             let action = self.filter_menu.on_key(key);
@@ -334,18 +611,54 @@ impl App {
                 self.help_overlay_visible = true;
                 return;
             }
+            KeyCode::Char('i') => {
+                self.session_info_visible = true;
+                return;
+            }
+            KeyCode::Char('D') => {
+                self.diagnostics.open();
+                return;
+            }
             KeyCode::Char('f') => {
                 self.open_filter_menu();
                 return;
             }
+            KeyCode::Char('F') => {
+                let current = self
+                    .filter_state
+                    .expression
+                    .as_ref()
+                    .map(|e| e.source().to_string())
+                    .unwrap_or_default();
+                self.filter_prompt.open(&current);
+                return;
+            }
+            KeyCode::Char('/') if self.focus == Focus::Sidebar => {
+                self.session_filter.start_input();
+                return;
+            }
             KeyCode::Char('/') => {
                 self.search_state.start_input();
                 return;
             }
+            KeyCode::Char('S') => {
+                self.global_search.start_input();
+                return;
+            }
             KeyCode::Char('L') => {
                 self.handle_full_history_load();
                 return;
             }
+            KeyCode::Char('T') => {
+                self.show_thinking = !self.show_thinking;
+                self.toggled_thinking.clear();
+                self.invalidate_scroll_snapshot();
+                return;
+            }
+            KeyCode::Char('B') => {
+                self.toggle_tree_view();
+                return;
+            }
             KeyCode::Tab => {
                 self.toggle_focus();
                 return;
@@ -354,8 +667,36 @@ impl App {
                 self.toggle_sidebar();
                 return;
             }
+            KeyCode::Char('v') => {
+                self.split_pane();
+                return;
+            }
+            KeyCode::Char('x') => {
+                self.close_pane();
+                return;
+            }
+            KeyCode::Char('w') => {
+                self.focus_pane((self.focused_pane + 1) % self.panes.len());
+                return;
+            }
+            KeyCode::Char('W') => {
+                let count = self.panes.len();
+                self.focus_pane((self.focused_pane + count - 1) % count);
+                return;
+            }
+            KeyCode::Enter if self.focus == Focus::LogStream && self.log_cursor.is_some() => {
+                self.open_detail_view();
+                return;
+            }
             KeyCode::Enter => {
-                self.confirm_session_selection();
+                if self.all_sessions_selected {
+                    self.select_all_sessions();
+                } else if self
+                    .sidebar_sessions()
+                    .contains(&self.selected_session_index)
+                {
+                    self.confirm_session_selection();
+                }
                 return;
             }
             _ => {}
@@ -388,32 +729,30 @@ impl App {
             Focus::Sidebar => match key.code {
                 KeyCode::Up | KeyCode::Char('k') => self.select_prev_session(),
                 KeyCode::Down | KeyCode::Char('j') => self.select_next_session(),
+                KeyCode::Esc if self.session_filter.is_active() => {
+                    self.session_filter.clear();
+                    self.select_visible_session();
+                }
                 _ => {}
             },
             Focus::LogStream => match key.code {
                 KeyCode::Up | KeyCode::Char('k') => {
                     self.enter_scroll_mode(PendingScroll::Up(1));
                 }
-                KeyCode::Down | KeyCode::Char('j') => {
-                    if self.is_in_scroll_mode() {
-                        self.apply_scroll(PendingScroll::Down(1));
-                    }
+                KeyCode::Down | KeyCode::Char('j') if self.is_in_scroll_mode() => {
+                    self.apply_scroll(PendingScroll::Down(1));
                 }
                 KeyCode::PageUp => {
                     self.enter_scroll_mode(PendingScroll::Up(20));
                 }
-                KeyCode::PageDown => {
-                    if self.is_in_scroll_mode() {
-                        self.apply_scroll(PendingScroll::Down(20));
-                    }
+                KeyCode::PageDown if self.is_in_scroll_mode() => {
+                    self.apply_scroll(PendingScroll::Down(20));
                 }
                 KeyCode::Char('u') => {
                     self.enter_scroll_mode(PendingScroll::HalfPageUp);
                 }
-                KeyCode::Char('d') => {
-                    if self.is_in_scroll_mode() {
-                        self.apply_scroll(PendingScroll::HalfPageDown);
-                    }
+                KeyCode::Char('d') if self.is_in_scroll_mode() => {
+                    self.apply_scroll(PendingScroll::HalfPageDown);
                 }
                 KeyCode::Char('g') | KeyCode::Home => {
                     self.enter_scroll_mode(PendingScroll::ToTop);
@@ -421,10 +760,17 @@ impl App {
                 KeyCode::Char('G') | KeyCode::End => {
                     self.exit_scroll_mode();
                 }
-                KeyCode::Esc => {
-                    if self.is_in_scroll_mode() {
-                        self.exit_scroll_mode();
-                    }
+                KeyCode::Char('K') => self.move_log_cursor(-1),
+                KeyCode::Char('J') => self.move_log_cursor(1),
+                KeyCode::Char('t') => self.toggle_expansion_at_cursor(),
+                KeyCode::Char('>') => self.jump_to_subagent(),
+                KeyCode::Char('<') => self.jump_back_from_subagent(),
+                KeyCode::Esc if self.log_cursor.is_some() => {
+                    self.log_cursor = None;
+                    self.exit_scroll_mode();
+                }
+                KeyCode::Esc if self.is_in_scroll_mode() => {
+                    self.exit_scroll_mode();
                 }
                 _ => {}
             },
@@ -467,6 +813,127 @@ impl App {
         }
     }
 
+    // -- Log panes -----------------------------------------------------------
+
+    /// Split the log area: open a pane right of the focused one, showing
+    /// the same session with the same filter, and focus it.
+    pub fn split_pane(&mut self) {
+        if self.panes.len() >= MAX_PANES {
+            self.status_message = Some(format!("At most {} panes", MAX_PANES));
+            return;
+        }
+        let pane = LogPane {
+            session_id: self.active_session_id.clone(),
+            filter_state: self.filter_state.clone(),
+            full_history_loaded: self.full_history_loaded,
+            ..LogPane::default()
+        };
+        self.panes.insert(self.focused_pane + 1, pane);
+        self.focus_pane(self.focused_pane + 1);
+    }
+
+    /// Close the focused pane and focus its right neighbour (the left one
+    /// for the rightmost pane). The last pane cannot be closed.
+    pub fn close_pane(&mut self) {
+        if self.panes.len() == 1 {
+            self.status_message = Some("Cannot close the last pane".to_string());
+            return;
+        }
+        let closing = self.focused_pane;
+        let next = if closing + 1 < self.panes.len() {
+            closing + 1
+        } else {
+            closing - 1
+        };
+        // Load the neighbour; its slot then holds the closed pane's state,
+        // which becomes the new placeholder.
+        self.swap_pane(next);
+        self.panes[next] = LogPane::default();
+        self.panes.remove(closing);
+        self.focused_pane = if next > closing { next - 1 } else { next };
+        self.on_pane_focused();
+    }
+
+    /// Move keyboard focus to pane `index` (and to the log stream).
+    pub fn focus_pane(&mut self, index: usize) {
+        if index >= self.panes.len() {
+            return;
+        }
+        self.focus = Focus::LogStream;
+        if index == self.focused_pane {
+            return;
+        }
+        self.swap_pane(self.focused_pane);
+        self.swap_pane(index);
+        self.focused_pane = index;
+        self.on_pane_focused();
+    }
+
+    /// Run `f` with pane `index` swapped into the `App` fields, as if it
+    /// had focus. Used to draw the panes that do not.
+    pub fn with_pane<R>(&mut self, index: usize, f: impl FnOnce(&mut App) -> R) -> R {
+        if index == self.focused_pane {
+            return f(self);
+        }
+        self.swap_pane(index);
+        let result = f(self);
+        self.swap_pane(index);
+        result
+    }
+
+    /// Whether session `id` is shown in any pane.
+    pub fn is_shown_in_pane(&self, id: &str) -> bool {
+        self.active_session_id.as_deref() == Some(id)
+            || self
+                .panes
+                .iter()
+                .any(|p| p.session_id.as_deref() == Some(id))
+    }
+
+    /// Exchange the view state in the `App` fields with that of pane
+    /// `index`. Swapping the same pane twice restores both.
+    fn swap_pane(&mut self, index: usize) {
+        let pane = &mut self.panes[index];
+        std::mem::swap(&mut self.active_session_id, &mut pane.session_id);
+        std::mem::swap(&mut self.filter_state, &mut pane.filter_state);
+        std::mem::swap(&mut self.scroll_mode, &mut pane.scroll_mode);
+        std::mem::swap(&mut self.pending_scroll, &mut pane.pending_scroll);
+        std::mem::swap(&mut self.search_state, &mut pane.search_state);
+        std::mem::swap(&mut self.log_cursor, &mut pane.log_cursor);
+        std::mem::swap(&mut self.full_history_loaded, &mut pane.full_history_loaded);
+        std::mem::swap(&mut self.task_jumps, &mut pane.task_jumps);
+    }
+
+    /// Point the session info and the sidebar selection at the session of
+    /// the newly focused pane.
+    fn on_pane_focused(&mut self) {
+        let session = self.get_active_session();
+        self.session_info = session
+            .as_ref()
+            .map(|s| self.session_infos.load(s))
+            .unwrap_or_default();
+        if let Some(idx) = session.and_then(|s| self.sessions.iter().position(|o| o.id == s.id)) {
+            self.selected_session_index = idx;
+        }
+        self.all_sessions_selected = self.active_session_id.is_none() && !self.sessions.is_empty();
+    }
+
+    /// Whether the focused pane shows the all-sessions view: no session is
+    /// selected while sessions exist.
+    pub fn is_all_sessions_view(&self) -> bool {
+        self.active_session_id.is_none() && !self.sessions.is_empty()
+    }
+
+    /// Whether any pane shows the all-sessions view.
+    pub fn is_all_sessions_shown(&self) -> bool {
+        self.is_all_sessions_view()
+            || self
+                .panes
+                .iter()
+                .enumerate()
+                .any(|(i, p)| i != self.focused_pane && p.session_id.is_none())
+    }
+
     // -- Scroll mode ---------------------------------------------------------
 
     /// Returns `true` if scroll (freeze) mode is active.
@@ -549,7 +1016,11 @@ impl App {
         }
 
         // Ignore mouse events when overlays are active.
-        if self.help_overlay_visible || self.filter_menu.visible {
+        if self.help_overlay_visible
+            || self.session_info_visible
+            || self.diagnostics.visible
+            || self.filter_menu.visible
+        {
             return;
         }
 
@@ -557,11 +1028,9 @@ impl App {
             MouseEventKind::ScrollUp => {
                 self.enter_scroll_mode(PendingScroll::Up(3));
             }
-            MouseEventKind::ScrollDown => {
-                // Only scroll down when already in scroll mode.
-                if self.is_in_scroll_mode() {
-                    self.apply_scroll(PendingScroll::Down(3));
-                }
+            // Only scroll down when already in scroll mode.
+            MouseEventKind::ScrollDown if self.is_in_scroll_mode() => {
+                self.apply_scroll(PendingScroll::Down(3));
             }
             _ => {}
         }
@@ -569,20 +1038,84 @@ impl App {
 
     // -- Session selection ---------------------------------------------------
 
-    /// Move the session selection up by one.
+    /// Move the session selection up by one. Above the first listed
+    /// session is the "All sessions" row.
     pub fn select_prev_session(&mut self) {
-        if self.selected_session_index > 0 {
-            self.selected_session_index -= 1;
+        if self.all_sessions_selected {
+            return;
+        }
+        let shown = self.sidebar_sessions();
+        match shown
+            .iter()
+            .rev()
+            .find(|&&i| i < self.selected_session_index)
+        {
+            Some(&idx) => self.selected_session_index = idx,
+            None if !self.sessions.is_empty() => self.all_sessions_selected = true,
+            None => {}
         }
     }
 
     /// Move the session selection down by one.
     pub fn select_next_session(&mut self) {
-        if !self.sessions.is_empty() && self.selected_session_index < self.sessions.len() - 1 {
-            self.selected_session_index += 1;
+        let shown = self.sidebar_sessions();
+        if self.all_sessions_selected {
+            if let Some(&first) = shown.first() {
+                self.all_sessions_selected = false;
+                self.selected_session_index = first;
+            }
+            return;
+        }
+        if let Some(&idx) = shown.iter().find(|&&i| i > self.selected_session_index) {
+            self.selected_session_index = idx;
         }
     }
 
+    /// Indices of the sessions listed in the sidebar: all of them, or those
+    /// matching the session filter.
+    pub fn sidebar_sessions(&self) -> Vec<usize> {
+        self.sessions
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| {
+                let title = self.session_title(s).into_iter();
+                self.session_filter.matches(title.chain([s.id.as_str()]))
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// After the session filter changed, move the selection to the first
+    /// listed session if the selected one is no longer listed.
+    fn select_visible_session(&mut self) {
+        let shown = self.sidebar_sessions();
+        if !shown.contains(&self.selected_session_index) {
+            if let Some(&first) = shown.first() {
+                self.selected_session_index = first;
+            }
+        }
+        self.sidebar_scroll_offset = 0;
+    }
+
+    /// Sidebar title of `session` (its summary or first prompt), if known.
+    pub fn session_title(&self, session: &Session) -> Option<&str> {
+        let main = session.agents.iter().find(|a| a.is_main)?;
+        self.session_titles.get(&main.log_path)
+    }
+
+    /// Re-read the titles of sessions whose main log changed. Returns
+    /// whether any title changed.
+    pub fn refresh_session_titles(&mut self) -> bool {
+        let mut changed = false;
+        for session in &self.sessions {
+            if let Some(main) = session.agents.iter().find(|a| a.is_main) {
+                changed |= self.session_titles.refresh(&main.log_path);
+            }
+        }
+        self.last_title_refresh = Some(Instant::now());
+        changed
+    }
+
     /// Confirm the currently selected session (Enter key).
     ///
     /// Sets the active session ID, removes it from the new-session highlight
@@ -600,9 +1133,12 @@ impl App {
         self.new_session_ids.remove(&session.id);
 
         self.active_session_id = Some(session.id.clone());
+        // Hidden sessions only apply to the all-sessions view.
+        self.filter_state.hidden_sessions.clear();
 
-        // Exit scroll mode when switching sessions.
+        // Exit scroll mode and drop the entry cursor when switching sessions.
         self.exit_scroll_mode();
+        self.reset_entry_cursor();
 
         // Cancel search when switching sessions (matches would be stale).
         self.cancel_search();
@@ -615,31 +1151,287 @@ impl App {
         self.replay_session_entries(&session);
     }
 
+    /// Show the merged view of all active sessions in the focused pane
+    /// (Enter on the sidebar's "All sessions" row).
+    ///
+    /// Clears `active_session_id`: the log stream then shows the entries of
+    /// every session, each tagged with its session. The ring buffer is
+    /// refilled with the recent entries of the active sessions.
+    pub fn select_all_sessions(&mut self) {
+        self.active_session_id = None;
+        self.exit_scroll_mode();
+        self.reset_entry_cursor();
+        self.cancel_search();
+        self.full_history_loaded = false;
+        self.full_load_confirm_pending = false;
+        self.session_info = SessionInfo::default();
+        self.task_jumps.clear();
+        self.refill_ring_buffer(None);
+    }
+
     /// Perform session replay: read the last N visible messages from the
     /// given session's JSONL files and push them into the ring buffer.
     ///
-    /// Clears the ring buffer before replaying. Stores the resulting EOF
-    /// offsets in `self.replay_offsets` for watcher handoff.
+    /// Clears the ring buffer before replaying and reloads `session_info`.
+    /// The sessions of the other panes are replayed too, see
+    /// [`refill_ring_buffer`](Self::refill_ring_buffer).
     pub fn replay_session_entries(&mut self, session: &Session) {
-        self.ring_buffer.clear();
-        let (entries, offsets) = replay_session(
-            session,
-            &self.filter_state,
-            DEFAULT_REPLAY_COUNT,
-            self.config.verbose,
-        );
+        self.session_info = self.session_infos.load(session);
+        self.task_jumps.clear();
+        self.refill_ring_buffer(Some(session));
+    }
+
+    /// Clear the ring buffer and replay `session` (the focused pane's, or
+    /// `None` for the all-sessions view) and the sessions shown in the
+    /// other panes, each with its pane's filter: the recent entries, or the
+    /// whole history for panes that loaded it with `L`. Stores the EOF
+    /// offsets in `self.replay_offsets` for watcher handoff.
+    ///
+    /// Returns the number of entries replayed for the focused pane.
+    fn refill_ring_buffer(&mut self, session: Option<&Session>) -> usize {
+        let parked = self
+            .panes
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != self.focused_pane)
+            .filter_map(|(_, pane)| {
+                let session = match &pane.session_id {
+                    Some(id) => Some(self.sessions.iter().find(|s| &s.id == id)?),
+                    None => None,
+                };
+                Some((session, &pane.filter_state, pane.full_history_loaded))
+            });
+        let targets =
+            std::iter::once((session, &self.filter_state, self.full_history_loaded)).chain(parked);
+
+        // Replayed entries by session, so panes showing the same session
+        // share its entries.
+        let mut replays: Vec<(Option<String>, Vec<LogEntry>)> = Vec::new();
+        let mut offsets = HashMap::new();
+        let mut focused_count = 0;
+        for (i, (session, filter, full)) in targets.enumerate() {
+            let (replayed, eof_offsets) = match session {
+                Some(session) if full => load_full_session(session, filter),
+                Some(session) => replay_session(session, filter, DEFAULT_REPLAY_COUNT),
+                None => self.replay_active_sessions(filter),
+            };
+            if i == 0 {
+                focused_count = replayed.len();
+            }
+            offsets.extend(eof_offsets);
+            let mut by_session: Vec<(Option<String>, Vec<LogEntry>)> = Vec::new();
+            for entry in replayed {
+                match by_session
+                    .iter_mut()
+                    .find(|(id, _)| *id == entry.session_id)
+                {
+                    Some((_, group)) => group.push(entry),
+                    None => by_session.push((entry.session_id.clone(), vec![entry])),
+                }
+            }
+            for (session_id, more) in by_session {
+                match replays.iter_mut().find(|(id, _)| *id == session_id) {
+                    Some((_, merged)) => merge_replay(merged, more),
+                    None => replays.push((session_id, more)),
+                }
+            }
+        }
+        let mut entries: Vec<LogEntry> = replays.into_iter().flat_map(|(_, e)| e).collect();
+        sort_by_timestamp(&mut entries);
+
+        self.clear_ring_buffer();
         for entry in entries {
-            self.ring_buffer.push(entry);
+            self.push_entry(entry);
         }
         self.replay_offsets = offsets;
+        focused_count
+    }
+
+    /// Replay the recent entries of every active session not hidden by
+    /// `filter`, merged by timestamp, for the all-sessions view.
+    fn replay_active_sessions(
+        &self,
+        filter: &FilterState,
+    ) -> (Vec<LogEntry>, HashMap<PathBuf, u64>) {
+        let mut entries = Vec::new();
+        let mut offsets = HashMap::new();
+        for session in &self.sessions {
+            if self.session_status(session) == SessionStatus::Inactive
+                || filter.hidden_sessions.contains(&session.id)
+            {
+                continue;
+            }
+            let (replayed, eof_offsets) = replay_session(session, filter, DEFAULT_REPLAY_COUNT);
+            entries.extend(replayed);
+            offsets.extend(eof_offsets);
+        }
+        sort_by_timestamp(&mut entries);
+        let start = entries.len().saturating_sub(DEFAULT_REPLAY_COUNT);
+        (entries.split_off(start), offsets)
     }
 
     /// Push a single new log entry into the ring buffer.
     ///
     /// Called by the event loop when the watcher delivers a `NewLogEntry`.
-    pub fn on_new_log_entry(&mut self, entry: crate::log_entry::LogEntry) {
+    /// Any `tool_use` / `tool_result` blocks update `tool_calls`, and
+    /// entries of the active session update `session_info`.
+    pub fn on_new_log_entry(&mut self, mut entry: crate::log_entry::LogEntry) {
         self.needs_redraw = true;
-        self.ring_buffer.push(entry);
+        self.apply_session_alias(&mut entry);
+        if entry.session_id.is_some() && entry.session_id == self.active_session_id {
+            self.session_info.record(&entry);
+        }
+        if let Some(id) = &entry.session_id {
+            self.search_index.mark_stale(id);
+        }
+        self.push_entry(entry);
+    }
+
+    /// Push `entry` into the ring buffer and the indexes derived from it,
+    /// and drop the entries it evicts from those indexes.
+    fn push_entry(&mut self, mut entry: LogEntry) {
+        self.apply_session_alias(&mut entry);
+        self.tool_calls.record_entry(&entry);
+        self.task_links.record_entry(&entry);
+        self.session_stats.record(&entry);
+        for evicted in self.ring_buffer.push(entry) {
+            self.tool_calls.forget_entry(&evicted);
+            self.task_links.forget_entry(&evicted);
+            self.session_stats.forget(&evicted);
+        }
+    }
+
+    /// File `entry` under the current id of its session, if the session's
+    /// file was renamed.
+    fn apply_session_alias(&self, entry: &mut LogEntry) {
+        if let Some(id) = entry
+            .session_id
+            .as_ref()
+            .and_then(|id| self.session_aliases.get(id))
+        {
+            entry.session_id = Some(id.clone());
+        }
+    }
+
+    /// Empty the ring buffer and the indexes derived from it.
+    fn clear_ring_buffer(&mut self) {
+        self.ring_buffer.clear();
+        self.tool_calls.clear();
+        self.task_links.clear();
+        self.session_stats.clear();
+    }
+
+    /// Rebuild the indexes derived from the ring buffer, after entries
+    /// were removed from its middle.
+    fn reindex_ring_buffer(&mut self) {
+        self.tool_calls.clear();
+        self.task_links.clear();
+        self.session_stats.clear();
+        for entry in self.ring_buffer.iter() {
+            self.tool_calls.record_entry(entry);
+            self.task_links.record_entry(entry);
+            self.session_stats.record(entry);
+        }
+    }
+
+    /// Handle one iteration of the event loop (roughly every `TICK_RATE`
+    /// when idle).
+    ///
+    /// While any tool call is still pending, requests a redraw so its
+    /// spinner and elapsed time keep advancing; calls pending for longer
+    /// than `TOOL_CALL_TIMEOUT` are marked abandoned. Refreshes sidebar titles
+    /// every `TITLE_REFRESH_INTERVAL`, indexes new entries for global search
+    /// every `INDEX_UPDATE_INTERVAL` and, with process detection on,
+    /// rescans the process table in the background every
+    /// `PROCESS_SCAN_INTERVAL`.
+    pub fn on_tick(&mut self) {
+        if self.tool_calls.has_pending() {
+            let cutoff = chrono::Utc::now() - TOOL_CALL_TIMEOUT;
+            self.tool_calls.expire_pending(cutoff);
+            self.needs_redraw = true;
+        }
+        let scan_due = self
+            .last_process_scan
+            .is_none_or(|at| at.elapsed() >= PROCESS_SCAN_INTERVAL);
+        self.poll_process_scan();
+        if scan_due && self.process_scan.is_none() {
+            self.start_process_scan();
+        }
+        let titles_due = self
+            .last_title_refresh
+            .is_none_or(|at| at.elapsed() >= TITLE_REFRESH_INTERVAL);
+        if titles_due && self.refresh_session_titles() {
+            self.needs_redraw = true;
+        }
+        let index_due = self
+            .last_index_update
+            .is_none_or(|at| at.elapsed() >= INDEX_UPDATE_INTERVAL);
+        if index_due && self.search_index.has_stale() {
+            self.last_index_update = Some(Instant::now());
+            if let Err(e) = self.search_index.flush(&self.sessions) {
+                log_warn!("search index update failed: {}", e);
+            }
+        }
+    }
+
+    /// Start scanning the process table in the background; the result is
+    /// picked up by [`poll_process_scan`](Self::poll_process_scan).
+    fn start_process_scan(&mut self) {
+        let Some(detector) = &self.process_detector else {
+            return;
+        };
+        self.process_scan = Some(detector.scan_in_background(self.sessions.clone()));
+        self.last_process_scan = Some(Instant::now());
+    }
+
+    /// Apply the background process scan, if it has finished.
+    fn poll_process_scan(&mut self) {
+        let Some(rx) = &self.process_scan else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(scan) => {
+                self.process_scan = None;
+                self.process_scanned_at = scan.scanned_at;
+                if scan.states != self.process_states {
+                    self.process_states = scan.states;
+                    self.needs_redraw = true;
+                }
+            }
+            Err(mpsc::TryRecvError::Empty) => {}
+            Err(mpsc::TryRecvError::Disconnected) => self.process_scan = None,
+        }
+    }
+
+    /// Status of `session`: the mtime heuristic, combined with its process
+    /// state when process detection is on.
+    pub fn session_status(&self, session: &Session) -> SessionStatus {
+        // Sessions not scanned yet fall back to the mtime heuristic.
+        session.status_with_process(
+            self.process_states.get(&session.id).copied(),
+            self.process_scanned_at,
+        )
+    }
+
+    /// Record an error reported by the watcher.
+    pub fn on_watcher_error(&mut self, message: String) {
+        self.diagnostics.record_error(message);
+        self.needs_redraw = true;
+    }
+
+    /// Record a malformed line the watcher skipped in `path`.
+    pub fn on_parse_error(&mut self, path: PathBuf, error: String) {
+        self.diagnostics.record_parse_error(path, error);
+        self.needs_redraw = true;
+    }
+
+    /// Store the watcher's periodic status, redrawing only when the
+    /// diagnostics panel shows it.
+    pub fn on_watcher_status(&mut self, status: WatcherStatus) {
+        self.diagnostics.status = Some(status);
+        if self.diagnostics.visible {
+            self.needs_redraw = true;
+        }
     }
 
     /// Handle a newly detected JSONL file from the watcher.
@@ -649,18 +1441,11 @@ impl App {
     pub fn on_new_file_detected(&mut self, path: PathBuf) {
         self.needs_redraw = true;
 
-        // Need a project path to classify the file.
-        let project_path = match &self.project_path {
-            Some(p) => p.clone(),
-            None => return,
+        let Some(kind) = self.classify_watched_file(&path) else {
+            return;
         };
 
-        // Canonicalize project_path for comparison (watcher sends canonical paths).
-        let canonical_project_dir = project_path
-            .canonicalize()
-            .unwrap_or_else(|_| project_path.clone());
-
-        match classify_new_file(&path, &canonical_project_dir) {
+        match kind {
             NewFileKind::TopLevelSession { session_id } => {
                 // Check for duplicate session.
                 if self.sessions.iter().any(|s| s.id == session_id) {
@@ -686,6 +1471,10 @@ impl App {
                 if was_non_empty {
                     self.selected_session_index += 1;
                 }
+                if self.config.all_projects {
+                    self.group_sessions_by_project();
+                    self.refresh_project_labels();
+                }
             }
             NewFileKind::Subagent {
                 session_id,
@@ -715,24 +1504,188 @@ impl App {
         }
     }
 
+    /// Handle a JSONL file deleted or moved away, as seen by the watcher.
+    ///
+    /// A session's main file takes the session out of the sidebar, and a
+    /// subagent file takes the agent out of its session. Entries already
+    /// in the log stream stay.
+    pub fn on_file_removed(&mut self, path: PathBuf) {
+        if let Some(id) = self.forget_file(&path) {
+            if self.active_session_id.as_ref() == Some(&id) {
+                let short: String = id.chars().take(8).collect();
+                self.status_message = Some(format!("Session {} was deleted", short));
+            }
+        }
+    }
+
+    /// Handle a JSONL file renamed on disk: the session or agent under the
+    /// old name is replaced by one under the new name, which keeps the
+    /// sidebar selection and is not marked new, and panes showing the
+    /// session switch to the new id. The session's entries still carry the
+    /// old id in their `sessionId`; from now on they are filed under the
+    /// new one, so the panes keep showing them. The watcher keeps reading
+    /// the file where it left off.
+    pub fn on_file_renamed(&mut self, from: PathBuf, to: PathBuf) {
+        let selected_id = self
+            .sessions
+            .get(self.selected_session_index)
+            .map(|s| s.id.clone());
+        let removed_id = self.forget_file(&from);
+        self.on_new_file_detected(to.clone());
+
+        let Some(NewFileKind::TopLevelSession { session_id }) = self.classify_watched_file(&to)
+        else {
+            return;
+        };
+        let Some(removed_id) = removed_id else {
+            return;
+        };
+        self.new_session_ids.remove(&session_id);
+        if Some(&removed_id) == selected_id.as_ref() {
+            if let Some(index) = self.sessions.iter().position(|s| s.id == session_id) {
+                self.selected_session_index = index;
+            }
+        }
+        // Panes showing the session follow it to its new id.
+        let shown_ids = std::iter::once(&mut self.active_session_id)
+            .chain(self.panes.iter_mut().map(|pane| &mut pane.session_id));
+        for id in shown_ids.filter(|id| id.as_ref() == Some(&removed_id)) {
+            *id = Some(session_id.clone());
+        }
+
+        for target in self.session_aliases.values_mut() {
+            if *target == removed_id {
+                *target = session_id.clone();
+            }
+        }
+        self.session_aliases
+            .insert(removed_id.clone(), session_id.clone());
+        for entry in self.ring_buffer.iter_mut() {
+            if entry.session_id.as_ref() == Some(&removed_id) {
+                entry.session_id = Some(session_id.clone());
+            }
+        }
+        self.reindex_ring_buffer();
+    }
+
+    /// Handle a JSONL file that was truncated: drop its entries from the
+    /// log stream. The watcher then re-reads it from the start. Panes
+    /// showing the file's session leave scroll mode and drop their entry
+    /// cursor, which pointed into the entries removed.
+    pub fn on_file_truncated(&mut self, path: PathBuf) {
+        let (session_id, agent_id) = match self.classify_watched_file(&path) {
+            Some(NewFileKind::TopLevelSession { session_id }) => (session_id, None),
+            Some(NewFileKind::Subagent {
+                session_id,
+                agent_id,
+            }) => (session_id, Some(agent_id)),
+            _ => return,
+        };
+        self.needs_redraw = true;
+        self.ring_buffer.retain(|e| {
+            e.session_id.as_deref() != Some(session_id.as_str()) || e.agent_id != agent_id
+        });
+        self.reindex_ring_buffer();
+        let shows_session = |id: &Option<String>| id.as_ref().is_none_or(|id| *id == session_id);
+        if shows_session(&self.active_session_id) {
+            self.exit_scroll_mode();
+            self.reset_entry_cursor();
+        }
+        for (index, pane) in self.panes.iter_mut().enumerate() {
+            if index != self.focused_pane && shows_session(&pane.session_id) {
+                pane.scroll_mode = None;
+                pane.pending_scroll = None;
+                pane.log_cursor = None;
+            }
+        }
+        if agent_id.is_none() && self.active_session_id.as_ref() == Some(&session_id) {
+            self.session_info = SessionInfo::default();
+        }
+    }
+
+    /// Remove the session or agent whose JSONL file is `path` from
+    /// `sessions`. Returns the session's id if the whole session went.
+    fn forget_file(&mut self, path: &Path) -> Option<String> {
+        match self.classify_watched_file(path)? {
+            NewFileKind::TopLevelSession { session_id } => {
+                let index = self.sessions.iter().position(|s| s.id == session_id)?;
+                self.needs_redraw = true;
+                self.sessions.remove(index);
+                self.new_session_ids.remove(&session_id);
+                self.process_states.remove(&session_id);
+                // Keep the selection on the same session, or on its
+                // successor if it was the one removed.
+                if index < self.selected_session_index
+                    || self.selected_session_index >= self.sessions.len()
+                {
+                    self.selected_session_index = self.selected_session_index.saturating_sub(1);
+                }
+                Some(session_id)
+            }
+            NewFileKind::Subagent {
+                session_id,
+                agent_id,
+            } => {
+                let session = self.sessions.iter_mut().find(|s| s.id == session_id)?;
+                self.needs_redraw = true;
+                session
+                    .agents
+                    .retain(|a| a.is_main || a.agent_id.as_deref() != Some(agent_id.as_str()));
+                None
+            }
+            NewFileKind::Unknown => None,
+        }
+    }
+
+    /// Classify a JSONL file reported by the watcher against the project
+    /// directory (the projects directory with `--all-projects`). `None`
+    /// without a project, or for a file outside every project.
+    fn classify_watched_file(&self, path: &Path) -> Option<NewFileKind> {
+        let project_path = self.project_path.as_ref()?;
+
+        // Canonicalize project_path for comparison (watcher sends canonical paths).
+        let canonical_project_dir = project_path
+            .canonicalize()
+            .unwrap_or_else(|_| project_path.clone());
+
+        if self.config.all_projects {
+            classify_new_file_in_projects(path, &canonical_project_dir).map(|(_, kind)| kind)
+        } else {
+            Some(classify_new_file(path, &canonical_project_dir))
+        }
+    }
+
     /// Adjust the sidebar scroll offset so the selected session is visible.
     ///
     /// `visible_height` is the number of visual rows available in the sidebar
-    /// inner area (excluding borders). Each session occupies 1 header row
-    /// plus 1 row per agent child.
+    /// inner area (excluding borders). Each listed session occupies 1 header
+    /// row, 1 title row if it has a title, plus 1 row per agent child, and
+    /// in `--all-projects` mode each project group adds 1 row for its header.
     pub fn adjust_sidebar_scroll(&mut self, visible_height: usize) {
         if self.sessions.is_empty() || visible_height == 0 {
             self.sidebar_scroll_offset = 0;
             return;
         }
 
-        // Compute the visual row range for the selected session.
-        let mut row = 0usize;
+        // Compute the visual row range for the selected session. Row 0 is
+        // the "All sessions" row.
+        let mut row = 1usize;
         let mut selected_start = 0usize;
-        let mut selected_end = 0usize;
+        let mut selected_end = 1usize;
 
-        for (i, session) in self.sessions.iter().enumerate() {
-            let session_rows = 1 + session.agents.iter().filter(|a| !a.is_main).count();
+        let shown = if self.all_sessions_selected {
+            Vec::new()
+        } else {
+            self.sidebar_sessions()
+        };
+        for i in shown {
+            let session = &self.sessions[i];
+            if self.starts_project_group(i) {
+                row += 1;
+            }
+            let title_rows = usize::from(self.session_title(session).is_some());
+            let session_rows =
+                1 + title_rows + session.agents.iter().filter(|a| !a.is_main).count();
             if i == self.selected_session_index {
                 selected_start = row;
                 selected_end = row + session_rows; // exclusive
@@ -752,6 +1705,108 @@ impl App {
         }
     }
 
+    // -- Project groups (--all-projects) ------------------------------------
+
+    /// Reorder `sessions` so sessions of the same project are adjacent.
+    ///
+    /// Groups are ordered by their most recent session and keep their
+    /// sessions' order; the selected session stays selected.
+    pub fn group_sessions_by_project(&mut self) {
+        let selected_id = self
+            .sessions
+            .get(self.selected_session_index)
+            .map(|s| s.id.clone());
+
+        let mut groups: Vec<(Option<PathBuf>, Vec<Session>)> = Vec::new();
+        for session in self.sessions.drain(..) {
+            let dir = session.project_dir().map(Path::to_path_buf);
+            match groups.iter_mut().find(|(d, _)| *d == dir) {
+                Some((_, group)) => group.push(session),
+                None => groups.push((dir, vec![session])),
+            }
+        }
+        groups.sort_by_key(|(_, group)| {
+            std::cmp::Reverse(group.iter().map(|s| s.last_modified).max())
+        });
+        self.sessions = groups.into_iter().flat_map(|(_, group)| group).collect();
+
+        if let Some(id) = selected_id {
+            if let Some(idx) = self.sessions.iter().position(|s| s.id == id) {
+                self.selected_session_index = idx;
+            }
+        }
+    }
+
+    /// Compute sidebar labels for project directories not labelled yet,
+    /// using the `cwd` recorded in their sessions' logs when available.
+    pub fn refresh_project_labels(&mut self) {
+        for session in &self.sessions {
+            let Some(dir) = session.project_dir() else {
+                continue;
+            };
+            if self.project_labels.contains_key(dir) {
+                continue;
+            }
+            let cwd = self
+                .sessions
+                .iter()
+                .filter(|s| s.project_dir() == Some(dir))
+                .filter_map(|s| s.agents.iter().find(|a| a.is_main))
+                .find_map(|a| read_session_cwd(&a.log_path));
+            self.project_labels
+                .insert(dir.to_path_buf(), project_label(dir, cwd.as_deref()));
+        }
+    }
+
+    /// Whether the session at `index` is the first listed session of its
+    /// project group, i.e. the sidebar draws a project header above it.
+    /// Always `false` outside `--all-projects` mode.
+    pub fn starts_project_group(&self, index: usize) -> bool {
+        if !self.config.all_projects {
+            return false;
+        }
+        let Some(session) = self.sessions.get(index) else {
+            return false;
+        };
+        let shown = self.sidebar_sessions();
+        let prev = shown.iter().rev().find(|&&i| i < index);
+        match prev {
+            None => true,
+            Some(&prev) => session.project_dir() != self.sessions[prev].project_dir(),
+        }
+    }
+
+    /// Sidebar label of the project the session at `index` belongs to.
+    pub fn project_label_at(&self, index: usize) -> String {
+        let Some(dir) = self.sessions.get(index).and_then(|s| s.project_dir()) else {
+            return "unknown project".to_string();
+        };
+        self.project_labels
+            .get(dir)
+            .cloned()
+            .unwrap_or_else(|| project_label(dir, None))
+    }
+
+    /// Number of active sessions in the project of the session at `index`.
+    pub fn project_active_count(&self, index: usize) -> usize {
+        let dir = self.sessions.get(index).and_then(|s| s.project_dir());
+        self.sessions
+            .iter()
+            .filter(|s| s.project_dir() == dir && self.session_status(s) != SessionStatus::Inactive)
+            .count()
+    }
+
+    /// Project directory searched by global search: the watched project,
+    /// or in `--all-projects` mode the active session's project.
+    fn search_project_dir(&self) -> Option<PathBuf> {
+        if self.config.all_projects {
+            self.get_active_session()
+                .and_then(|s| s.project_dir().map(Path::to_path_buf))
+        } else {
+            self.project_path.clone()
+        }
+    }
+
     // -- Full history load ------------------------------------------------
 
     /// Size threshold (bytes) above which a confirmation prompt is shown.
@@ -804,16 +1859,12 @@ impl App {
         // Save the distance from bottom (so we can restore position after load).
         let distance_from_bottom = self.scroll_mode.as_ref().map(|sm| sm.offset).unwrap_or(0);
 
-        let (entries, offsets) =
-            load_full_session(&session, &self.filter_state, self.config.verbose);
-        let entry_count = entries.len();
-
         // Replace ring buffer contents.
-        self.ring_buffer.clear();
-        for entry in entries {
-            self.ring_buffer.push(entry);
-        }
-        self.replay_offsets = offsets;
+        self.full_history_loaded = true;
+        let entry_count = self.refill_ring_buffer(Some(&session));
+
+        // Drop the entry cursor (indices refer to the old contents).
+        self.reset_entry_cursor();
 
         // Cancel search (matches would be stale).
         self.cancel_search();
@@ -827,7 +1878,6 @@ impl App {
             self.exit_scroll_mode();
         }
 
-        self.full_history_loaded = true;
         self.status_message = Some(format!("Loaded full history ({} entries)", entry_count));
     }
 
@@ -840,14 +1890,23 @@ impl App {
     // -- Filter menu -----------------------------------------------------
 
     /// Open the filter menu, populating it with the current filter state
-    /// and known agents from the ring buffer.
+    /// and known agents from the ring buffer, plus the known sessions in
+    /// the all-sessions view.
     fn open_filter_menu(&mut self) {
         let known_agents = self.collect_known_agents();
         self.filter_menu.open(
             self.filter_state.hide_tool_calls,
+            self.filter_state.hide_meta,
+            self.filter_state.errors_only,
             self.filter_state.selected_agent.clone(),
             known_agents,
         );
+        if self.active_session_id.is_none() {
+            self.filter_menu.add_sessions(
+                self.collect_known_sessions(),
+                self.filter_state.hidden_sessions.clone(),
+            );
+        }
     }
 
     /// Apply the current filter menu selections to the app filter state.
@@ -855,10 +1914,47 @@ impl App {
     /// Called immediately on each menu selection (MenuAction::Selected).
     fn apply_filter_from_menu(&mut self) {
         self.filter_state.hide_tool_calls = self.filter_menu.hide_tool_calls;
+        self.filter_state.hide_meta = self.filter_menu.hide_meta;
+        self.filter_state.errors_only = self.filter_menu.errors_only;
         self.filter_state.selected_agent = self.filter_menu.selected_agent.clone();
+        self.filter_state.hidden_sessions = self.filter_menu.hidden_sessions.clone();
+        self.on_filter_changed();
+    }
 
+    /// Parse the filter prompt input and apply it as the filter expression.
+    ///
+    /// An empty input clears the expression. On a parse error the prompt
+    /// stays open and shows the error.
+    fn submit_filter_prompt(&mut self) {
+        let input = self.filter_prompt.input_buffer.trim();
+        if input.is_empty() {
+            self.filter_state.expression = None;
+        } else {
+            match FilterExpr::parse(input) {
+                Ok(expr) => self.filter_state.expression = Some(expr),
+                Err(e) => {
+                    self.filter_prompt.error = Some(e.to_string());
+                    return;
+                }
+            }
+        }
+        self.filter_prompt.close();
+        self.on_filter_changed();
+    }
+
+    /// Drop the entry cursor and the expansions toggled with `t`, when
+    /// the entries they refer to are replaced.
+    fn reset_entry_cursor(&mut self) {
+        self.log_cursor = None;
+        self.toggled_thinking.clear();
+        self.expanded_summaries.clear();
+    }
+
+    /// Reset view state that depends on the filtered content.
+    fn on_filter_changed(&mut self) {
         // Exit scroll mode when filters change (content snapshot is stale).
         self.exit_scroll_mode();
+        self.reset_entry_cursor();
 
         // Cancel search when filters change (matches would be stale).
         self.cancel_search();
@@ -871,11 +1967,11 @@ impl App {
         self.search_state.cancel();
     }
 
-    /// Force scroll mode when search is confirmed.
+    /// Force scroll mode when search is confirmed or the entry cursor moves.
     ///
     /// This ensures highlights are stable and navigable. If scroll mode
     /// is not already active, sets a pending scroll to the bottom.
-    fn force_scroll_mode_for_search(&mut self) {
+    fn force_scroll_snapshot(&mut self) {
         if self.is_in_scroll_mode() {
             // Already in scroll mode — invalidate the snapshot so the next render
             // rebuilds lines and recomputes search highlights.
@@ -898,32 +1994,452 @@ impl App {
         }
     }
 
-    /// Scroll the view so the current search match is visible.
-    ///
-    /// Converts the target logical line index to a visual (wrapped) line
-    /// position and adjusts the scroll offset to centre it in the viewport.
-    pub fn scroll_to_current_search_match(&mut self) {
-        let target_line = match self.search_state.current_match_line() {
-            Some(line) => line,
+    /// Scroll the view so the current search match is visible.
+    ///
+    /// Converts the target logical line index to a visual (wrapped) line
+    /// position and adjusts the scroll offset to centre it in the viewport.
+    pub fn scroll_to_current_search_match(&mut self) {
+        let target_line = match self.search_state.current_match_line() {
+            Some(line) => line,
+            None => return,
+        };
+
+        if let Some(ref mut sm) = self.scroll_mode {
+            let max_offset = sm.total_visual_lines.saturating_sub(sm.visible_height);
+            let half_visible = sm.visible_height / 2;
+
+            // Convert logical line index → visual line position.
+            let target_visual = visual_line_position(&sm.lines, target_line, sm.inner_width);
+
+            // Centre the target visual line in the viewport.
+            let desired_ratatui_top = target_visual.saturating_sub(half_visible);
+            // Convert to our offset system (0 = bottom):
+            //   ratatui_scroll = max_offset - offset
+            let new_offset = max_offset.saturating_sub(desired_ratatui_top);
+            sm.offset = new_offset.min(max_offset);
+        }
+    }
+
+    /// Scroll the view by the minimum amount needed to show a logical line.
+    ///
+    /// Used to keep the entry cursor on screen while it moves.
+    pub fn ensure_line_visible(&mut self, target_line: usize) {
+        if let Some(ref mut sm) = self.scroll_mode {
+            let max_offset = sm.total_visual_lines.saturating_sub(sm.visible_height);
+            let target_visual = visual_line_position(&sm.lines, target_line, sm.inner_width);
+
+            // Work in ratatui coordinates (visual lines from the top).
+            let mut top = max_offset.saturating_sub(sm.offset);
+            if target_visual < top {
+                top = target_visual;
+            } else if target_visual >= top + sm.visible_height {
+                top = target_visual + 1 - sm.visible_height.max(1);
+            }
+            sm.offset = max_offset.saturating_sub(top.min(max_offset));
+        }
+    }
+
+    // -- Global search -----------------------------------------------------
+
+    /// Search every session in the project for the typed query and show
+    /// the results overlay. An invalid regex keeps the input open.
+    fn submit_global_search(&mut self) {
+        let matcher = match self.global_search.matcher() {
+            Ok(m) => m,
+            Err(e) => {
+                self.global_search.error = Some(e.to_string());
+                return;
+            }
+        };
+
+        // Search all sessions on disk, not just the ones in the sidebar.
+        let project_dir = self.search_project_dir();
+        let sessions = match project_dir.as_deref() {
+            Some(dir) => {
+                discover_sessions(dir, usize::MAX).unwrap_or_else(|_| self.sessions.clone())
+            }
+            None => self.sessions.clone(),
+        };
+
+        // Use the project's index if one exists; otherwise scan every file.
+        let index = match project_dir.as_deref() {
+            Some(dir) => match self.search_index.updated(dir, &sessions) {
+                Ok(index) => index,
+                Err(e) => {
+                    self.status_message = Some(format!("Search index unavailable: {}", e));
+                    None
+                }
+            },
+            None => None,
+        };
+        let results = search_sessions(&sessions, &matcher, MAX_MATCHES_PER_SESSION, index);
+        self.global_search.show_results(results);
+    }
+
+    /// Switch to the session of a global search match and put the entry
+    /// cursor on the matching entry.
+    ///
+    /// Falls back to loading the full history when the entry is older than
+    /// the replayed tail.
+    fn jump_to_history_match(&mut self, m: HistoryMatch) {
+        let session = self
+            .global_search
+            .results
+            .iter()
+            .find(|g| g.session.id == m.session_id)
+            .map(|g| g.session.clone());
+        self.global_search.close();
+
+        let idx = match self.sessions.iter().position(|s| s.id == m.session_id) {
+            Some(idx) => idx,
+            None => match session {
+                Some(session) => {
+                    self.sessions.push(session);
+                    if self.config.all_projects {
+                        self.group_sessions_by_project();
+                        self.refresh_project_labels();
+                    }
+                    match self.sessions.iter().position(|s| s.id == m.session_id) {
+                        Some(idx) => idx,
+                        None => return,
+                    }
+                }
+                None => return,
+            },
+        };
+        self.selected_session_index = idx;
+        self.confirm_session_selection();
+
+        let mut position = self.find_history_match(&m);
+        if position.is_none() && !self.full_history_loaded {
+            self.perform_full_history_load();
+            position = self.find_history_match(&m);
+        }
+
+        match position {
+            Some(i) => {
+                self.log_cursor = Some(i);
+                self.focus = Focus::LogStream;
+                self.force_scroll_snapshot();
+            }
+            None => {
+                self.status_message = Some("Match is hidden by the current filter".to_string());
+            }
+        }
+    }
+
+    /// Index of the entry for `m` in [`App::visible_entries`], by uuid
+    /// (or timestamp for entries without one).
+    fn find_history_match(&self, m: &HistoryMatch) -> Option<usize> {
+        self.visible_entries().iter().position(|e| match &m.uuid {
+            Some(uuid) => e.uuid.as_deref() == Some(uuid.as_str()),
+            None => m.timestamp.is_some() && e.timestamp == m.timestamp,
+        })
+    }
+
+    // -- Entry cursor & detail pane ------------------------------------------
+
+    /// Move the entry cursor by `delta` entries (negative = older).
+    ///
+    /// The first move places the cursor on the newest entry. Moving freezes
+    /// the log stream (scroll mode) so the selection stays put while new
+    /// entries arrive.
+    pub fn move_log_cursor(&mut self, delta: isize) {
+        let count = self.visible_entries().len();
+        if count == 0 {
+            return;
+        }
+        let last = count - 1;
+        let next = match self.log_cursor {
+            None => last,
+            Some(c) => c.min(last).saturating_add_signed(delta).min(last),
+        };
+        self.log_cursor = Some(next);
+        self.force_scroll_snapshot();
+    }
+
+    /// Whether the thinking blocks of `entry` are shown in full.
+    pub fn is_thinking_expanded(&self, entry: &LogEntry) -> bool {
+        let toggled = entry
+            .uuid
+            .as_ref()
+            .is_some_and(|u| self.toggled_thinking.contains(u));
+        self.show_thinking != toggled
+    }
+
+    /// Whether `entry` is a compaction summary shown in full.
+    pub fn is_summary_expanded(&self, entry: &LogEntry) -> bool {
+        entry
+            .uuid
+            .as_ref()
+            .is_some_and(|u| self.expanded_summaries.contains(u))
+    }
+
+    /// Expand or collapse the entry under the cursor: the summary of a
+    /// compaction summary entry, the thinking blocks of any other entry.
+    fn toggle_expansion_at_cursor(&mut self) {
+        let entries = self.visible_entries();
+        let target = self
+            .log_cursor
+            .and_then(|c| entries.get(c.min(entries.len().saturating_sub(1))))
+            .and_then(|e| Some((e.uuid.clone()?, e.is_compact_summary == Some(true))));
+        match target {
+            Some((uuid, is_summary)) => {
+                let set = if is_summary {
+                    &mut self.expanded_summaries
+                } else {
+                    &mut self.toggled_thinking
+                };
+                if !set.remove(&uuid) {
+                    set.insert(uuid);
+                }
+                self.invalidate_scroll_snapshot();
+            }
+            None => {
+                self.status_message = Some("Select an entry with J/K to expand it".to_string());
+            }
+        }
+    }
+
+    /// Switch between the current branch and the conversation tree,
+    /// keeping the cursor on the same entry when it is still shown.
+    fn toggle_tree_view(&mut self) {
+        let entries = self.visible_entries();
+        let selected = self
+            .log_cursor
+            .and_then(|c| entries.get(c.min(entries.len().saturating_sub(1))))
+            .and_then(|e| e.uuid.clone());
+
+        self.show_tree = !self.show_tree;
+        let entries = self.visible_entries();
+        if let Some(uuid) = selected {
+            self.log_cursor = entries
+                .iter()
+                .position(|e| e.uuid.as_ref() == Some(&uuid))
+                .or(self.log_cursor);
+        }
+        let branches = self.conversation_tree().branch_count();
+        self.status_message = Some(match (self.show_tree, branches) {
+            (true, 1) => "Tree view: 1 abandoned branch".to_string(),
+            (true, n) => format!("Tree view: {} abandoned branches", n),
+            (false, 0) => "Current branch".to_string(),
+            (false, n) => format!("Current branch ({} abandoned hidden)", n),
+        });
+        self.invalidate_scroll_snapshot();
+    }
+
+    /// Show the transcript of the subagent spawned by the `Task` call under
+    /// the cursor (`>`), filtering the log stream to that agent.
+    fn jump_to_subagent(&mut self) {
+        let entries = self.visible_entries();
+        let Some(entry) = self
+            .log_cursor
+            .and_then(|c| entries.get(c.min(entries.len().saturating_sub(1))))
+        else {
+            self.status_message =
+                Some("Select a Task call with J/K to open its subagent".to_string());
+            return;
+        };
+        let Some(agent_id) = self
+            .task_links
+            .linked_call_in(entry)
+            .and_then(|task| task.agent_id.clone())
+        else {
+            self.status_message = Some("No subagent transcript linked to this entry".to_string());
+            return;
+        };
+        let jump = TaskJump {
+            entry_uuid: entry.uuid.clone(),
+            selected_agent: self.filter_state.selected_agent.clone(),
+        };
+
+        self.task_jumps.push(jump);
+        self.filter_state.selected_agent = Some(agent_id.clone());
+        self.on_filter_changed();
+        if self.visible_entries().is_empty() {
+            self.status_message = Some(format!(
+                "No entries of agent {} loaded (L loads the full history)",
+                agent_id
+            ));
+            return;
+        }
+        self.log_cursor = Some(0);
+        self.force_scroll_snapshot();
+    }
+
+    /// Return from a subagent transcript to the `Task` call that spawned
+    /// it (`<`).
+    ///
+    /// Undoes the last `>` jump; without one, uses the `Task` call linked
+    /// to the agent currently filtered to.
+    fn jump_back_from_subagent(&mut self) {
+        let jump = match self.task_jumps.pop() {
+            Some(jump) => jump,
+            None => match self
+                .filter_state
+                .selected_agent
+                .as_deref()
+                .and_then(|id| self.task_links.task_for_agent(id))
+            {
+                Some(task) => TaskJump {
+                    entry_uuid: task.entry_uuid.clone(),
+                    selected_agent: None,
+                },
+                None => {
+                    self.status_message = Some("Not in a subagent transcript".to_string());
+                    return;
+                }
+            },
+        };
+
+        self.filter_state.selected_agent = jump.selected_agent;
+        self.on_filter_changed();
+        let target = jump.entry_uuid.and_then(|uuid| {
+            self.visible_entries()
+                .iter()
+                .position(|e| e.uuid.as_ref() == Some(&uuid))
+        });
+        if let Some(index) = target {
+            self.log_cursor = Some(index);
+            self.force_scroll_snapshot();
+        }
+    }
+
+    /// Open the detail pane for the entry under the cursor.
+    pub fn open_detail_view(&mut self) {
+        let entries = self.visible_entries();
+        let entry = match self
+            .log_cursor
+            .and_then(|c| entries.get(c.min(entries.len().saturating_sub(1))))
+        {
+            Some(e) => *e,
             None => return,
         };
 
-        if let Some(ref mut sm) = self.scroll_mode {
-            let max_offset = sm.total_visual_lines.saturating_sub(sm.visible_height);
-            let half_visible = sm.visible_height / 2;
+        let details = collect_tool_details(entry, self.ring_buffer.iter());
+        let title = match details.first() {
+            Some(d) if !d.name.is_empty() => format!(" Detail: [{}] ", d.name),
+            _ => " Detail ".to_string(),
+        };
+        let lines = build_detail_lines(
+            entry,
+            &details,
+            &self.tool_calls,
+            chrono::Utc::now(),
+            &self.theme_colors,
+        );
+        self.detail_view.open(title, lines);
+    }
 
-            // Convert logical line index → visual line position.
-            let target_visual = visual_line_position(&sm.lines, target_line, sm.inner_width);
+    /// Entries shown in the log stream, in display order.
+    ///
+    /// Applies the active-session restriction, entry-type visibility and
+    /// the current filter. Indices into this list are what `log_cursor`
+    /// refers to.
+    pub fn visible_entries(&self) -> Vec<&LogEntry> {
+        self.visible_rows().into_iter().map(|r| r.entry).collect()
+    }
 
-            // Centre the target visual line in the viewport.
-            let desired_ratatui_top = target_visual.saturating_sub(half_visible);
-            // Convert to our offset system (0 = bottom):
-            //   ratatui_scroll = max_offset - offset
-            let new_offset = max_offset.saturating_sub(desired_ratatui_top);
-            sm.offset = new_offset.min(max_offset);
+    /// [`visible_entries`](Self::visible_entries) with their place in the
+    /// conversation tree.
+    ///
+    /// Abandoned branches are dropped unless the tree view is on (`B`), in
+    /// which case each branch is listed contiguously and indented.
+    pub fn visible_rows(&self) -> Vec<TreeRow<'_>> {
+        let tree = self.conversation_tree();
+        let shown = self.filtered_entries();
+        if self.show_tree {
+            return tree.layout(&shown);
+        }
+        shown
+            .into_iter()
+            .filter(|e| !tree.is_abandoned(e))
+            .map(|entry| TreeRow {
+                entry,
+                depth: 0,
+                branch_start: false,
+                abandoned: false,
+            })
+            .collect()
+    }
+
+    /// The conversation tree of the ring buffer, built at most once per
+    /// buffer change.
+    fn conversation_tree(&self) -> Rc<ConversationTree> {
+        let generation = self.ring_buffer.generation();
+        let mut cache = self.tree_cache.borrow_mut();
+        match cache.as_ref() {
+            Some((built, tree)) if *built == generation => Rc::clone(tree),
+            _ => {
+                let tree = Rc::new(ConversationTree::build(self.ring_buffer.iter()));
+                *cache = Some((generation, Rc::clone(&tree)));
+                tree
+            }
         }
     }
 
+    /// Entries passing the session restriction, type visibility and filter,
+    /// in timeline order.
+    fn filtered_entries(&self) -> Vec<&LogEntry> {
+        let filter_state = &self.filter_state;
+
+        // Entry-type visibility predicate: User, Assistant, System are always
+        // visible; Progress, FileHistorySnapshot and other types are always hidden.
+        let is_type_visible = |e: &LogEntry| -> bool {
+            match e.entry_type {
+                EntryType::User => {
+                    // Skip user entries that would produce no visible output (e.g.
+                    // only tool_result blocks while tool calls are hidden); they
+                    // would otherwise show as empty lines.
+                    e.message.as_ref().is_none_or(|msg| {
+                        if filter_state.is_tool_line_visible() {
+                            has_renderable_content(&msg.content)
+                        } else {
+                            has_non_tool_content(&msg.content)
+                        }
+                    })
+                }
+                EntryType::Assistant | EntryType::System => true,
+                _ => false,
+            }
+        };
+
+        let session_id = self.active_session_id.as_deref();
+        self.ring_buffer
+            .iter_filtered(move |e| {
+                session_id.is_none_or(|id| e.session_id.as_deref() == Some(id))
+                    && is_type_visible(e)
+                    && filter_state.matches(e)
+            })
+            .collect()
+    }
+
+    /// Sessions with entries in the ring buffer, or hidden by the filter,
+    /// for the filter menu of the all-sessions view.
+    ///
+    /// Returns `(session_id, display_name)` tuples in sidebar order; the
+    /// display name is the short id followed by the session's title.
+    pub fn collect_known_sessions(&self) -> Vec<(String, String)> {
+        let mut ids: HashSet<&str> = self
+            .ring_buffer
+            .iter()
+            .filter_map(|e| e.session_id.as_deref())
+            .collect();
+        ids.extend(self.filter_state.hidden_sessions.iter().map(String::as_str));
+        self.sessions
+            .iter()
+            .filter(|s| ids.contains(s.id.as_str()))
+            .map(|s| {
+                let short: String = s.id.chars().take(6).collect();
+                let name = match self.session_title(s) {
+                    Some(title) => format!("{} {}", short, title),
+                    None => short,
+                };
+                (s.id.clone(), name)
+            })
+            .collect()
+    }
+
     /// Collect unique agent identifiers from entries in the ring buffer.
     ///
     /// Returns a list of `(agent_id, display_name)` tuples, sorted by
@@ -955,6 +2471,27 @@ impl App {
     }
 }
 
+/// Sort entries by timestamp (ISO 8601 string comparison). The sort is
+/// stable; entries without a timestamp sort to the beginning.
+fn sort_by_timestamp(entries: &mut [LogEntry]) {
+    entries.sort_by(|a, b| {
+        let ts_a = a.timestamp.as_deref().unwrap_or("");
+        let ts_b = b.timestamp.as_deref().unwrap_or("");
+        ts_a.cmp(ts_b)
+    });
+}
+
+/// Add the entries of `more` that `merged` lacks (by uuid). Used when two
+/// panes show the same session; entries without a uuid were replayed with
+/// `merged` already.
+fn merge_replay(merged: &mut Vec<LogEntry>, more: Vec<LogEntry>) {
+    let known: HashSet<String> = merged.iter().filter_map(|e| e.uuid.clone()).collect();
+    merged.extend(
+        more.into_iter()
+            .filter(|e| e.uuid.as_ref().is_some_and(|u| !known.contains(u))),
+    );
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -1346,7 +2883,7 @@ mod tests {
     #[test]
     fn test_adjust_sidebar_scroll_selected_below_viewport() {
         let mut app = App::new(test_config());
-        // 5 sessions, each with 1 row (main only) = 5 rows total
+        // "All sessions" row + 5 sessions, each with 1 row (main only) = 6 rows
         app.sessions = vec![
             dummy_session("s1"),
             dummy_session("s2"),
@@ -1354,12 +2891,12 @@ mod tests {
             dummy_session("s4"),
             dummy_session("s5"),
         ];
-        app.selected_session_index = 4; // row 4
+        app.selected_session_index = 4; // row 5
 
         // Visible height = 3, so we need to scroll
         app.adjust_sidebar_scroll(3);
-        // selected_end = 5, visible_height = 3, so offset = 5 - 3 = 2
-        assert_eq!(app.sidebar_scroll_offset, 2);
+        // selected_end = 6, visible_height = 3, so offset = 6 - 3 = 3
+        assert_eq!(app.sidebar_scroll_offset, 3);
     }
 
     #[test]
@@ -1374,7 +2911,13 @@ mod tests {
         app.sidebar_scroll_offset = 2; // artificially scrolled down
 
         app.adjust_sidebar_scroll(3);
-        // Selected session starts at row 0, which is above offset 2
+        // Selected session starts at row 1 (below "All sessions"), which is
+        // above offset 2
+        assert_eq!(app.sidebar_scroll_offset, 1);
+
+        // The "All sessions" row is row 0.
+        app.all_sessions_selected = true;
+        app.adjust_sidebar_scroll(3);
         assert_eq!(app.sidebar_scroll_offset, 0);
     }
 
@@ -1389,13 +2932,13 @@ mod tests {
             dummy_session("s2"),
             session_with_agents("s3", 1),
         ];
-        app.selected_session_index = 2; // s3 starts at row 4
+        app.selected_session_index = 2; // s3 starts at row 5
 
         // Visible height = 3
         app.adjust_sidebar_scroll(3);
-        // s3 starts at row 4, ends at row 6
-        // offset = 6 - 3 = 3
-        assert_eq!(app.sidebar_scroll_offset, 3);
+        // s3 starts at row 5 (after the "All sessions" row), ends at row 7
+        // offset = 7 - 3 = 4
+        assert_eq!(app.sidebar_scroll_offset, 4);
     }
 
     #[test]
@@ -1571,6 +3114,51 @@ mod tests {
         assert_eq!(app.filter_state.display(), None);
     }
 
+    #[test]
+    fn test_shift_f_opens_filter_prompt_and_applies_expression() {
+        let mut app = App::new(test_config());
+        app.on_key(KeyEvent::new(KeyCode::Char('F'), KeyModifiers::SHIFT));
+        assert!(app.filter_prompt.visible);
+
+        for ch in "role:user".chars() {
+            app.on_key(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
+        }
+        app.on_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert!(!app.filter_prompt.visible);
+        assert_eq!(
+            app.filter_state.display(),
+            Some("[filter: role:user]".to_string())
+        );
+
+        // Reopening pre-fills the active expression; clearing it removes the filter.
+        app.on_key(KeyEvent::new(KeyCode::Char('F'), KeyModifiers::SHIFT));
+        assert_eq!(app.filter_prompt.input_buffer, "role:user");
+        app.filter_prompt.input_buffer.clear();
+        app.on_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(app.filter_state.expression.is_none());
+    }
+
+    #[test]
+    fn test_filter_prompt_parse_error_keeps_prompt_open() {
+        let mut app = App::new(test_config());
+        app.on_key(KeyEvent::new(KeyCode::Char('F'), KeyModifiers::SHIFT));
+        for ch in "colour:red".chars() {
+            app.on_key(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
+        }
+        app.on_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert!(app.filter_prompt.visible);
+        assert!(app.filter_prompt.error.is_some());
+        assert!(app.filter_state.expression.is_none());
+
+        // 'q' is typed into the prompt rather than quitting.
+        app.on_key(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE));
+        assert!(!app.should_quit);
+        app.on_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert!(!app.filter_prompt.visible);
+    }
+
     #[test]
     fn test_slash_key_does_not_open_filter() {
         let mut app = App::new(test_config());
@@ -1663,6 +3251,42 @@ mod tests {
         assert!(!app.should_quit);
     }
 
+    #[test]
+    fn test_i_toggles_session_info_panel() {
+        let mut app = App::new(test_config());
+        app.on_key(KeyEvent::new(KeyCode::Char('i'), KeyModifiers::NONE));
+        assert!(app.session_info_visible);
+
+        // Other keys are consumed while the panel is open.
+        app.on_key(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE));
+        assert!(app.session_info_visible);
+        assert!(!app.should_quit);
+
+        app.on_key(KeyEvent::new(KeyCode::Char('i'), KeyModifiers::NONE));
+        assert!(!app.session_info_visible);
+    }
+
+    #[test]
+    fn test_live_entries_update_session_info_of_active_session_only() {
+        let mut app = App::new(test_config());
+        app.active_session_id = Some("s1".to_string());
+        let entry = |session: &str, cwd: &str| {
+            crate::log_entry::parse_jsonl_line(&format!(
+                r#"{{"type":"user","sessionId":"{}","timestamp":"2025-01-15T10:00:00Z","cwd":"{}"}}"#,
+                session, cwd
+            ))
+            .unwrap()
+        };
+
+        app.on_new_log_entry(entry("s1", "/repo"));
+        app.on_new_log_entry(entry("s2", "/other"));
+        app.on_new_log_entry(entry("s1", "/repo/crates"));
+
+        assert_eq!(app.session_info.cwd.as_deref(), Some("/repo/crates"));
+        assert_eq!(app.session_info.changes.len(), 1);
+        assert_eq!(app.session_info.changes[0].from, "/repo");
+    }
+
     #[test]
     fn test_question_mark_does_not_open_help_when_filter_menu_active() {
         let mut app = App::new(test_config());
@@ -1723,6 +3347,300 @@ mod tests {
         assert_eq!(app.sessions.len(), 1);
     }
 
+    #[test]
+    fn test_panes_split_focus_and_close() {
+        let press = |app: &mut App, ch: char| {
+            app.on_key(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
+        };
+        let mut app = App::new(test_config());
+        app.sessions = vec![dummy_session("s1"), dummy_session("s2")];
+        app.active_session_id = Some("s1".to_string());
+        app.filter_state.hide_tool_calls = true;
+
+        // A new pane starts as a copy of the focused one and takes focus.
+        press(&mut app, 'v');
+        assert_eq!(app.panes.len(), 2);
+        assert_eq!(app.focused_pane, 1);
+        assert_eq!(app.focus, Focus::LogStream);
+        assert_eq!(app.active_session_id.as_deref(), Some("s1"));
+        assert!(app.filter_state.hide_tool_calls);
+
+        // It gets its own target and filter.
+        app.selected_session_index = 1;
+        app.confirm_session_selection();
+        app.filter_state.hide_tool_calls = false;
+        app.filter_state.selected_agent = Some("agent-1".to_string());
+
+        press(&mut app, 'w');
+        assert_eq!(app.focused_pane, 0);
+        assert_eq!(app.active_session_id.as_deref(), Some("s1"));
+        assert!(app.filter_state.hide_tool_calls);
+        assert_eq!(app.filter_state.selected_agent, None);
+        assert_eq!(app.selected_session_index, 0);
+        assert!(app.is_shown_in_pane("s2"));
+
+        // Panes: [s1, s1, s1, s2], focus on the third.
+        press(&mut app, 'v');
+        press(&mut app, 'v');
+        press(&mut app, 'v');
+        assert_eq!(app.panes.len(), MAX_PANES);
+        assert_eq!(app.focused_pane, 2);
+        assert!(app.status_message.as_deref().unwrap().contains("At most"));
+
+        // Closing focuses the right neighbour, or the left one at the end.
+        press(&mut app, 'x');
+        assert_eq!(app.panes.len(), 3);
+        assert_eq!(app.focused_pane, 2);
+        assert_eq!(app.active_session_id.as_deref(), Some("s2"));
+        assert_eq!(app.filter_state.selected_agent.as_deref(), Some("agent-1"));
+        press(&mut app, 'x');
+        assert_eq!(app.focused_pane, 1);
+        assert_eq!(app.active_session_id.as_deref(), Some("s1"));
+        assert!(!app.is_shown_in_pane("s2"));
+        press(&mut app, 'x');
+        press(&mut app, 'x');
+        assert_eq!(app.panes.len(), 1);
+        assert_eq!(app.focused_pane, 0);
+        assert!(app.status_message.as_deref().unwrap().contains("last pane"));
+    }
+
+    #[test]
+    fn test_panes_keep_entries_of_other_sessions() {
+        use tempfile::TempDir;
+
+        let tmp = TempDir::new().unwrap();
+        let session = |id: &str, count: usize| {
+            let log_path = tmp.path().join(format!("{}.jsonl", id));
+            let lines: Vec<String> = (0..count)
+                .map(|i| {
+                    format!(
+                        r#"{{"type":"user","sessionId":"{id}","uuid":"{id}-{i}","timestamp":"2025-01-15T10:00:0{i}Z","message":{{"role":"user","content":"hello {i}"}}}}"#
+                    )
+                })
+                .collect();
+            std::fs::write(&log_path, lines.join("\n")).unwrap();
+            let mut session = dummy_session(id);
+            session.agents[0].log_path = log_path;
+            session
+        };
+        let mut app = App::new(test_config());
+        app.sessions = vec![session("s1", 2), session("s2", 3)];
+        app.active_session_id = Some("s1".to_string());
+        let first = app.sessions[0].clone();
+        app.replay_session_entries(&first);
+
+        app.split_pane();
+        app.selected_session_index = 1;
+        app.confirm_session_selection();
+        assert_eq!(app.visible_entries().len(), 3);
+        assert_eq!(app.with_pane(0, |app| app.visible_entries().len()), 2);
+
+        // Loading the full history of one pane keeps the other's entries.
+        app.on_key(KeyEvent::new(KeyCode::Char('L'), KeyModifiers::NONE));
+        assert!(app.full_history_loaded);
+        assert_eq!(app.with_pane(0, |app| app.visible_entries().len()), 2);
+        assert!(!app.with_pane(0, |app| app.full_history_loaded));
+    }
+
+    #[test]
+    fn test_panes_on_the_same_session_share_its_entries() {
+        use crate::test_support::write_file;
+        use tempfile::TempDir;
+
+        let tmp = TempDir::new().unwrap();
+        // Two entries without a uuid share a timestamp; neither is a
+        // duplicate of the other.
+        let log_path = write_file(
+            tmp.path(),
+            "s1.jsonl",
+            &[
+                r#"{"type":"user","sessionId":"s1","uuid":"u1","timestamp":"2025-01-15T10:00:00Z","message":{"role":"user","content":"first"}}"#,
+                r#"{"type":"user","sessionId":"s1","timestamp":"2025-01-15T10:00:01Z","message":{"role":"user","content":"second"}}"#,
+                r#"{"type":"user","sessionId":"s1","timestamp":"2025-01-15T10:00:01Z","message":{"role":"user","content":"third"}}"#,
+            ],
+        );
+        let mut session = dummy_session("s1");
+        session.agents[0].log_path = log_path;
+        let mut app = App::new(test_config());
+        app.sessions = vec![session.clone()];
+        app.active_session_id = Some("s1".to_string());
+        app.replay_session_entries(&session);
+        assert_eq!(app.visible_entries().len(), 3);
+
+        // A second pane on the same session does not duplicate them.
+        app.split_pane();
+        app.replay_session_entries(&session);
+        assert_eq!(app.ring_buffer.len(), 3);
+        assert_eq!(app.visible_entries().len(), 3);
+    }
+
+    #[test]
+    fn test_all_sessions_view_merges_active_sessions() {
+        use crate::tui::filter_overlay::FilterMenuItem;
+        use tempfile::TempDir;
+
+        let press = |app: &mut App, code: KeyCode| {
+            app.on_key(KeyEvent::new(code, KeyModifiers::NONE));
+        };
+        let tmp = TempDir::new().unwrap();
+        let session = |id: &str, seconds: &[u32]| {
+            let log_path = tmp.path().join(format!("{}.jsonl", id));
+            let lines: Vec<String> = seconds
+                .iter()
+                .map(|s| {
+                    format!(
+                        r#"{{"type":"user","sessionId":"{id}","uuid":"{id}-{s}","timestamp":"2025-01-15T10:00:0{s}Z","message":{{"role":"user","content":"hello"}}}}"#
+                    )
+                })
+                .collect();
+            std::fs::write(&log_path, lines.join("\n")).unwrap();
+            let mut session = dummy_session(id);
+            session.agents[0].log_path = log_path;
+            session
+        };
+        let mut app = App::new(test_config());
+        app.sessions = vec![session("s1", &[1, 3]), session("s2", &[2])];
+        app.active_session_id = Some("s1".to_string());
+
+        // "All sessions" sits above the first session.
+        press(&mut app, KeyCode::Char('k'));
+        assert!(app.all_sessions_selected);
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.active_session_id, None);
+        let uuids: Vec<_> = app
+            .visible_entries()
+            .iter()
+            .filter_map(|e| e.uuid.clone())
+            .collect();
+        assert_eq!(uuids, ["s1-1", "s2-2", "s1-3"]);
+
+        // The filter menu lists the sessions after the toggles.
+        press(&mut app, KeyCode::Char('f'));
+        assert_eq!(
+            app.filter_menu.items[3],
+            FilterMenuItem::Session("s1".to_string(), "s1".to_string())
+        );
+        app.filter_menu.selected = 3;
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.visible_entries().len(), 1);
+        assert_eq!(
+            app.filter_state.display().unwrap(),
+            "[filter: 1 session hidden]"
+        );
+
+        // Picking a single session drops the session-level filter.
+        press(&mut app, KeyCode::Char('j'));
+        assert!(!app.all_sessions_selected);
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.active_session_id.as_deref(), Some("s1"));
+        assert!(app.filter_state.hidden_sessions.is_empty());
+        assert_eq!(app.visible_entries().len(), 2);
+    }
+
+    #[test]
+    fn test_sidebar_filter_by_title_and_id() {
+        let key = |c: char| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+        let mut app = App::new(test_config());
+        app.sessions = vec![
+            dummy_session("alpha-1"),
+            dummy_session("beta-2"),
+            dummy_session("gamma-3"),
+        ];
+        app.session_titles
+            .insert(PathBuf::from("/fake/beta-2.jsonl"), "Fix the flaky test");
+        app.selected_session_index = 2;
+
+        // `/` with the sidebar focused filters sessions, not the log stream.
+        app.on_key(key('/'));
+        assert!(app.session_filter.is_input());
+        assert!(!app.search_state.is_input());
+        for c in "flk".chars() {
+            app.on_key(key(c));
+        }
+        assert_eq!(app.sidebar_sessions(), [1]);
+        assert_eq!(app.selected_session_index, 1);
+
+        // Enter keeps the filter; navigation stays within matches.
+        app.on_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(!app.session_filter.is_input());
+        app.on_key(key('j'));
+        assert_eq!(app.selected_session_index, 1);
+
+        // Ids match too.
+        app.on_key(key('/'));
+        for _ in 0..3 {
+            app.on_key(KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE));
+        }
+        for c in "gm3".chars() {
+            app.on_key(key(c));
+        }
+        assert_eq!(app.sidebar_sessions(), [2]);
+        assert_eq!(app.selected_session_index, 2);
+
+        // Esc clears the filter.
+        app.on_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert_eq!(app.sidebar_sessions(), [0, 1, 2]);
+        assert_eq!(
+            app.session_title(&app.sessions[1]),
+            Some("Fix the flaky test")
+        );
+    }
+
+    #[test]
+    fn test_all_projects_groups_sessions_by_project() {
+        use std::time::{Duration, SystemTime};
+        use tempfile::TempDir;
+
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path().canonicalize().unwrap();
+        let mut app = App::new(AppConfig {
+            all_projects: true,
+            ..test_config()
+        });
+        app.project_path = Some(root.clone());
+        let session = |id: &str, project: &str, age_secs: u64| Session {
+            id: id.to_string(),
+            agents: vec![Agent {
+                agent_id: None,
+                slug: None,
+                log_path: root.join(project).join(format!("{}.jsonl", id)),
+                is_main: true,
+            }],
+            last_modified: SystemTime::now() - Duration::from_secs(age_secs),
+        };
+        app.sessions = vec![
+            session("a1", "-proj-a", 10),
+            session("b1", "-proj-b", 20),
+            session("a2", "-proj-a", 30),
+        ];
+        app.selected_session_index = 1;
+        app.group_sessions_by_project();
+
+        let ids = |app: &App| {
+            app.sessions
+                .iter()
+                .map(|s| s.id.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(&app), ["a1", "a2", "b1"]);
+        assert_eq!(app.selected_session_index, 2);
+        assert!(app.starts_project_group(0));
+        assert!(!app.starts_project_group(1));
+        assert!(app.starts_project_group(2));
+
+        // A new session moves its project's group to the top; the file sits
+        // one level deeper than in single-project mode.
+        app.on_new_file_detected(root.join("-proj-b").join("b2.jsonl"));
+        assert_eq!(ids(&app), ["b2", "b1", "a1", "a2"]);
+        assert_eq!(app.project_label_at(0), "/proj/b");
+
+        // Group headers count as sidebar rows: a2 is on row 6.
+        app.selected_session_index = 3;
+        app.adjust_sidebar_scroll(3);
+        assert_eq!(app.sidebar_scroll_offset, 4);
+    }
+
     #[test]
     fn test_on_new_file_detected_no_project_path() {
         let mut app = App::new(test_config());
@@ -1747,16 +3665,169 @@ mod tests {
         app.project_path = Some(project_dir);
         app.sessions = vec![dummy_session("parent-sess")];
 
-        let subagent_path = canonical_project_dir
-            .join("parent-sess")
-            .join("subagents")
-            .join("agent-sub123.jsonl");
-        app.on_new_file_detected(subagent_path);
+        let subagent_path = canonical_project_dir
+            .join("parent-sess")
+            .join("subagents")
+            .join("agent-sub123.jsonl");
+        app.on_new_file_detected(subagent_path);
+
+        // The parent session should now have 2 agents (1 main + 1 subagent).
+        assert_eq!(app.sessions[0].agents.len(), 2);
+        let sub = app.sessions[0].agents.iter().find(|a| !a.is_main).unwrap();
+        assert_eq!(sub.agent_id.as_deref(), Some("sub123"));
+    }
+
+    // -- Removed, renamed and truncated files --------------------------------
+
+    #[test]
+    fn test_on_file_removed_drops_session_and_agent() {
+        use tempfile::TempDir;
+
+        let tmp = TempDir::new().unwrap();
+        let project_dir = tmp.path().canonicalize().unwrap();
+
+        let mut app = App::new(test_config());
+        app.project_path = Some(project_dir.clone());
+        app.sessions = vec![
+            dummy_session("s1"),
+            dummy_session("s2"),
+            dummy_session("s3"),
+        ];
+        app.on_new_file_detected(project_dir.join("s2/subagents/agent-a1.jsonl"));
+        assert_eq!(app.sessions[1].agents.len(), 2);
+        app.selected_session_index = 2;
+        app.active_session_id = Some("s2".to_string());
+
+        // A subagent file takes only the agent.
+        app.on_file_removed(project_dir.join("s2/subagents/agent-a1.jsonl"));
+        assert_eq!(app.sessions[1].agents.len(), 1);
+        assert!(app.sessions[1].agents[0].is_main);
+
+        // A main file takes the session; the selection stays on s3.
+        app.on_file_removed(project_dir.join("s2.jsonl"));
+        let ids: Vec<_> = app.sessions.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, ["s1", "s3"]);
+        assert_eq!(app.selected_session_index, 1);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Session s2 was deleted")
+        );
+
+        // Removing the selected last session selects its predecessor.
+        app.on_file_removed(project_dir.join("s3.jsonl"));
+        assert_eq!(app.selected_session_index, 0);
+        app.on_file_removed(project_dir.join("s1.jsonl"));
+        assert!(app.sessions.is_empty());
+        assert_eq!(app.selected_session_index, 0);
+
+        // Unknown files are ignored.
+        app.on_file_removed(project_dir.join("missing.jsonl"));
+    }
+
+    #[test]
+    fn test_on_file_renamed_replaces_session() {
+        use crate::log_entry::parse_jsonl_line;
+        use tempfile::TempDir;
+
+        let tmp = TempDir::new().unwrap();
+        let project_dir = tmp.path().canonicalize().unwrap();
+
+        let mut app = App::new(test_config());
+        app.project_path = Some(project_dir.clone());
+        app.sessions = vec![dummy_session("s1"), dummy_session("s2")];
+        app.selected_session_index = 1;
+        app.active_session_id = Some("s2".to_string());
+        app.split_pane();
+        app.active_session_id = Some("s1".to_string());
+        app.focus_pane(0);
+        let entry = |uuid: &str| {
+            parse_jsonl_line(&format!(
+                r#"{{"type": "user", "sessionId": "s2", "uuid": "{}", "message": {{"role": "user", "content": "hi"}}}}"#,
+                uuid
+            ))
+            .unwrap()
+        };
+        let uuids = |app: &App| -> Vec<String> {
+            app.visible_entries()
+                .iter()
+                .filter_map(|e| e.uuid.clone())
+                .collect()
+        };
+        app.on_new_log_entry(entry("before"));
+        assert_eq!(uuids(&app), ["before"]);
+
+        app.on_file_renamed(project_dir.join("s2.jsonl"), project_dir.join("s9.jsonl"));
+        // Entries keep their `sessionId` but stay in the pane, and so do
+        // those read from the file later.
+        assert_eq!(uuids(&app), ["before"]);
+        app.on_new_log_entry(entry("after"));
+        assert_eq!(uuids(&app), ["before", "after"]);
+
+        let ids: Vec<_> = app.sessions.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, ["s9", "s1"]);
+        assert_eq!(
+            app.sessions[0].agents[0].log_path,
+            project_dir.join("s9.jsonl")
+        );
+        assert_eq!(app.selected_session_index, 0);
+        assert!(app.new_session_ids.is_empty());
+        assert!(app.status_message.is_none());
+        // The focused pane follows s2 to its new id; the pane on s1 stays.
+        assert_eq!(app.active_session_id.as_deref(), Some("s9"));
+        assert_eq!(app.panes[1].session_id.as_deref(), Some("s1"));
+        app.focus_pane(1);
+        assert_eq!(app.active_session_id.as_deref(), Some("s1"));
+        assert_eq!(app.panes[0].session_id.as_deref(), Some("s9"));
+    }
+
+    #[test]
+    fn test_on_file_truncated_purges_file_entries() {
+        use crate::log_entry::parse_jsonl_line;
+        use tempfile::TempDir;
+
+        let tmp = TempDir::new().unwrap();
+        let project_dir = tmp.path().canonicalize().unwrap();
+
+        let mut app = App::new(test_config());
+        app.project_path = Some(project_dir.clone());
+        app.sessions = vec![dummy_session("s1"), dummy_session("s2")];
+        for line in [
+            r#"{"type": "user", "sessionId": "s1", "uuid": "main"}"#,
+            r#"{"type": "user", "sessionId": "s1", "agentId": "a1", "uuid": "sub"}"#,
+            r#"{"type": "user", "sessionId": "s2", "uuid": "other"}"#,
+        ] {
+            app.on_new_log_entry(parse_jsonl_line(line).unwrap());
+        }
+        let uuids = |app: &App| -> Vec<String> {
+            app.ring_buffer
+                .iter()
+                .filter_map(|e| e.uuid.clone())
+                .collect()
+        };
+
+        // The focused pane shows s1 and a second one s2, both mid-scroll
+        // with an entry cursor.
+        app.active_session_id = Some("s2".to_string());
+        app.split_pane();
+        app.active_session_id = Some("s1".to_string());
+        for _ in 0..2 {
+            app.log_cursor = Some(0);
+            app.pending_scroll = Some(PendingScroll::ToTop);
+            app.focus_pane(1 - app.focused_pane);
+        }
 
-        // The parent session should now have 2 agents (1 main + 1 subagent).
-        assert_eq!(app.sessions[0].agents.len(), 2);
-        let sub = app.sessions[0].agents.iter().find(|a| !a.is_main).unwrap();
-        assert_eq!(sub.agent_id.as_deref(), Some("sub123"));
+        app.on_file_truncated(project_dir.join("s1/subagents/agent-a1.jsonl"));
+        assert_eq!(uuids(&app), ["main", "other"]);
+        assert_eq!(app.log_cursor, None);
+        assert_eq!(app.pending_scroll, None);
+        assert!(app.scroll_mode.is_none());
+        // The pane on s2 keeps its position.
+        assert_eq!(app.panes[0].log_cursor, Some(0));
+        assert_eq!(app.panes[0].pending_scroll, Some(PendingScroll::ToTop));
+
+        app.on_file_truncated(project_dir.join("s1.jsonl"));
+        assert_eq!(uuids(&app), ["other"]);
+        assert!(app.needs_redraw);
     }
 
     // -- Scroll mode tests ----------------------------------------------------
@@ -2201,6 +4272,7 @@ mod tests {
     #[test]
     fn test_slash_key_starts_search_input() {
         let mut app = App::new(test_config());
+        app.focus = Focus::LogStream;
         app.on_key(KeyEvent::new(KeyCode::Char('/'), KeyModifiers::NONE));
         assert!(app.search_state.is_input());
     }
@@ -2208,6 +4280,7 @@ mod tests {
     #[test]
     fn test_search_input_typing_and_confirm() {
         let mut app = App::new(test_config());
+        app.focus = Focus::LogStream;
         app.on_key(KeyEvent::new(KeyCode::Char('/'), KeyModifiers::NONE));
         assert!(app.search_state.is_input());
 
@@ -2237,6 +4310,7 @@ mod tests {
     #[test]
     fn test_search_input_backspace() {
         let mut app = App::new(test_config());
+        app.focus = Focus::LogStream;
         app.on_key(KeyEvent::new(KeyCode::Char('/'), KeyModifiers::NONE));
         app.on_key(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE));
         app.on_key(KeyEvent::new(KeyCode::Char('b'), KeyModifiers::NONE));
@@ -2247,6 +4321,7 @@ mod tests {
     #[test]
     fn test_search_input_ctrl_c_cancels_instead_of_quit() {
         let mut app = App::new(test_config());
+        app.focus = Focus::LogStream;
         app.on_key(KeyEvent::new(KeyCode::Char('/'), KeyModifiers::NONE));
         assert!(app.search_state.is_input());
 
@@ -2258,6 +4333,7 @@ mod tests {
     #[test]
     fn test_search_q_does_not_quit_in_input_mode() {
         let mut app = App::new(test_config());
+        app.focus = Focus::LogStream;
         app.on_key(KeyEvent::new(KeyCode::Char('/'), KeyModifiers::NONE));
         app.on_key(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE));
         // 'q' should be typed into the search buffer, not quit
@@ -2357,6 +4433,7 @@ mod tests {
     #[test]
     fn test_search_confirm_forces_scroll_mode() {
         let mut app = App::new(test_config());
+        app.focus = Focus::LogStream;
         app.on_key(KeyEvent::new(KeyCode::Char('/'), KeyModifiers::NONE));
         app.on_key(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE));
         app.on_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
@@ -2742,6 +4819,106 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_on_tick_redraws_only_while_tool_call_pending() {
+        use crate::log_entry::parse_jsonl_line;
+
+        let mut app = App::new(test_config());
+        app.needs_redraw = false;
+        app.on_tick();
+        assert!(!app.needs_redraw, "idle tick should not redraw");
+
+        app.on_new_log_entry(
+            parse_jsonl_line(
+                r#"{"type": "assistant", "message": {"role": "assistant", "content": [{"type": "tool_use", "id": "t1", "name": "Bash", "input": {}}]}}"#,
+            )
+            .unwrap(),
+        );
+        assert!(app.tool_calls.has_pending());
+        app.needs_redraw = false;
+        app.on_tick();
+        assert!(
+            app.needs_redraw,
+            "tick should redraw while a call is pending"
+        );
+
+        app.on_new_log_entry(
+            parse_jsonl_line(
+                r#"{"type": "user", "message": {"role": "user", "content": [{"type": "tool_result", "tool_use_id": "t1", "content": "ok"}]}}"#,
+            )
+            .unwrap(),
+        );
+        assert!(!app.tool_calls.has_pending());
+        app.needs_redraw = false;
+        app.on_tick();
+        assert!(!app.needs_redraw, "tick should not redraw once finished");
+    }
+
+    #[test]
+    fn test_evicted_pending_tool_call_stops_redraws() {
+        use crate::log_entry::parse_jsonl_line;
+
+        let mut app = App::new(test_config());
+        app.ring_buffer = RingBuffer::new(1);
+        app.on_new_log_entry(
+            parse_jsonl_line(
+                r#"{"type": "assistant", "message": {"role": "assistant", "content": [{"type": "tool_use", "id": "t1", "name": "Bash", "input": {}}]}}"#,
+            )
+            .unwrap(),
+        );
+        app.on_new_log_entry(
+            parse_jsonl_line(r#"{"type": "user", "message": {"role": "user", "content": "next"}}"#)
+                .unwrap(),
+        );
+
+        assert!(app.tool_calls.get("t1").is_none());
+        assert!(!app.tool_calls.has_pending());
+        app.needs_redraw = false;
+        app.on_tick();
+        assert!(!app.needs_redraw);
+    }
+
+    #[test]
+    fn test_on_tick_abandons_stale_tool_calls() {
+        use crate::log_entry::parse_jsonl_line;
+        use crate::tool_calls::ToolCallStatus;
+
+        let mut app = App::new(test_config());
+        app.on_new_log_entry(
+            parse_jsonl_line(
+                r#"{"type": "assistant", "timestamp": "2025-01-15T10:00:00Z", "message": {"role": "assistant", "content": [{"type": "tool_use", "id": "t1", "name": "Bash", "input": {}}]}}"#,
+            )
+            .unwrap(),
+        );
+        app.on_tick();
+
+        assert_eq!(
+            app.tool_calls.get("t1").unwrap().status,
+            ToolCallStatus::Abandoned
+        );
+        app.needs_redraw = false;
+        app.on_tick();
+        assert!(!app.needs_redraw);
+    }
+
+    #[test]
+    fn test_on_tick_scans_processes_in_background() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mut app = App::new(test_config());
+        app.process_detector = Some(ProcessDetector::new(tmp.path()));
+        app.sessions = vec![dummy_session("s1")];
+
+        // The result arrives on a later tick.
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while app.process_states.is_empty() && Instant::now() < deadline {
+            app.on_tick();
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        assert_eq!(app.process_states["s1"], ProcessState::Dead);
+        assert!(app.process_scan.is_none());
+        assert!(app.process_scanned_at > SystemTime::UNIX_EPOCH);
+    }
+
     #[test]
     fn test_on_new_file_detected_sets_needs_redraw() {
         let mut app = App::new(test_config());
@@ -2779,4 +4956,408 @@ mod tests {
             "mark_dirty() should set needs_redraw to true"
         );
     }
+
+    // -- Entry cursor & detail pane tests ---------------------------------------
+
+    fn app_with_tool_call() -> App {
+        use crate::log_entry::parse_jsonl_line;
+
+        let mut app = App::new(test_config());
+        app.focus = Focus::LogStream;
+        for json in [
+            r#"{"type": "user", "message": {"role": "user", "content": "run it"}}"#,
+            r#"{"type": "assistant", "message": {"role": "assistant", "content": [{"type": "tool_use", "id": "t1", "name": "Bash", "input": {"command": "make"}}]}}"#,
+            r#"{"type": "user", "message": {"role": "user", "content": [{"type": "tool_result", "tool_use_id": "t1", "content": "done"}]}}"#,
+        ] {
+            app.on_new_log_entry(parse_jsonl_line(json).unwrap());
+        }
+        app
+    }
+
+    #[test]
+    fn test_move_log_cursor_empty_buffer_is_noop() {
+        let mut app = App::new(test_config());
+        app.move_log_cursor(-1);
+        assert_eq!(app.log_cursor, None);
+        assert!(app.pending_scroll.is_none());
+    }
+
+    #[test]
+    fn test_cursor_keys_select_entries() {
+        let mut app = app_with_tool_call();
+        assert_eq!(app.visible_entries().len(), 3);
+
+        // First move selects the newest entry and freezes the stream.
+        app.on_key(KeyEvent::new(KeyCode::Char('K'), KeyModifiers::SHIFT));
+        assert_eq!(app.log_cursor, Some(2));
+        assert!(app.pending_scroll.is_some());
+
+        app.on_key(KeyEvent::new(KeyCode::Char('K'), KeyModifiers::SHIFT));
+        app.on_key(KeyEvent::new(KeyCode::Char('K'), KeyModifiers::SHIFT));
+        app.on_key(KeyEvent::new(KeyCode::Char('K'), KeyModifiers::SHIFT));
+        assert_eq!(app.log_cursor, Some(0), "cursor clamps at the oldest entry");
+
+        app.on_key(KeyEvent::new(KeyCode::Char('J'), KeyModifiers::SHIFT));
+        assert_eq!(app.log_cursor, Some(1));
+        for _ in 0..5 {
+            app.on_key(KeyEvent::new(KeyCode::Char('J'), KeyModifiers::SHIFT));
+        }
+        assert_eq!(app.log_cursor, Some(2), "cursor clamps at the newest entry");
+    }
+
+    #[test]
+    fn test_esc_clears_log_cursor() {
+        let mut app = app_with_tool_call();
+        app.move_log_cursor(-1);
+        app.on_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert_eq!(app.log_cursor, None);
+        assert!(!app.is_in_scroll_mode());
+        assert!(app.pending_scroll.is_none());
+    }
+
+    #[test]
+    fn test_enter_opens_detail_for_selected_entry() {
+        let mut app = app_with_tool_call();
+        app.log_cursor = Some(1);
+        app.on_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert!(app.detail_view.visible);
+        assert_eq!(app.detail_view.title, " Detail: [Bash] ");
+        let text: Vec<String> = app
+            .detail_view
+            .lines
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert!(text.iter().any(|l| l.contains("\"command\": \"make\"")));
+        assert!(text.iter().any(|l| l == "  done"));
+
+        // The pane is modal: `q` closes it instead of quitting.
+        app.on_key(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE));
+        assert!(!app.detail_view.visible);
+        assert!(!app.should_quit);
+    }
+
+    #[test]
+    fn test_thinking_toggles_globally_and_per_entry() {
+        use crate::log_entry::parse_jsonl_line;
+
+        let mut app = App::new(test_config());
+        app.focus = Focus::LogStream;
+        for uuid in ["a", "b"] {
+            app.on_new_log_entry(
+                parse_jsonl_line(&format!(
+                    r#"{{"type": "assistant", "uuid": "{}", "message": {{"role": "assistant", "content": [{{"type": "thinking", "thinking": "hmm"}}]}}}}"#,
+                    uuid
+                ))
+                .unwrap(),
+            );
+        }
+        let entries: Vec<LogEntry> = app.visible_entries().into_iter().cloned().collect();
+        assert!(!app.is_thinking_expanded(&entries[0]));
+
+        // Without a cursor `t` only explains itself.
+        app.on_key(KeyEvent::new(KeyCode::Char('t'), KeyModifiers::NONE));
+        assert!(app.toggled_thinking.is_empty());
+        assert!(app.status_message.is_some());
+
+        app.log_cursor = Some(0);
+        app.on_key(KeyEvent::new(KeyCode::Char('t'), KeyModifiers::NONE));
+        assert!(app.is_thinking_expanded(&entries[0]));
+        assert!(!app.is_thinking_expanded(&entries[1]));
+
+        // `T` expands everything and resets per-entry overrides.
+        app.on_key(KeyEvent::new(KeyCode::Char('T'), KeyModifiers::SHIFT));
+        assert!(app.is_thinking_expanded(&entries[0]));
+        assert!(app.is_thinking_expanded(&entries[1]));
+
+        // Per-entry toggle now collapses the selected entry only.
+        app.on_key(KeyEvent::new(KeyCode::Char('t'), KeyModifiers::NONE));
+        assert!(!app.is_thinking_expanded(&entries[0]));
+        assert!(app.is_thinking_expanded(&entries[1]));
+    }
+
+    #[test]
+    fn test_switching_sessions_clears_thinking_toggles() {
+        let mut app = App::new(test_config());
+        app.sessions = vec![dummy_session("s1")];
+        app.toggled_thinking.insert("a".to_string());
+        app.expanded_summaries.insert("b".to_string());
+        app.log_cursor = Some(0);
+
+        app.confirm_session_selection();
+        assert!(app.log_cursor.is_none());
+        assert!(app.toggled_thinking.is_empty());
+        assert!(app.expanded_summaries.is_empty());
+
+        app.toggled_thinking.insert("a".to_string());
+        app.select_all_sessions();
+        assert!(app.toggled_thinking.is_empty());
+    }
+
+    #[test]
+    fn test_tree_view_toggle_shows_abandoned_branches() {
+        use crate::log_entry::parse_jsonl_line;
+
+        let mut app = App::new(test_config());
+        // The prompt "c1" was rewound and replaced by "c2".
+        for (uuid, parent) in [("a", "null"), ("c1", r#""a""#), ("c2", r#""a""#)] {
+            app.on_new_log_entry(
+                parse_jsonl_line(&format!(
+                    r#"{{"type": "user", "sessionId": "s1", "uuid": "{}", "parentUuid": {}, "message": {{"role": "user", "content": "{}"}}}}"#,
+                    uuid, parent, uuid
+                ))
+                .unwrap(),
+            );
+        }
+        let uuids = |app: &App| -> Vec<String> {
+            app.visible_entries()
+                .iter()
+                .filter_map(|e| e.uuid.clone())
+                .collect()
+        };
+        assert_eq!(uuids(&app), vec!["a", "c2"]);
+
+        // The cursor stays on the same entry across the switch.
+        app.log_cursor = Some(1);
+        app.on_key(KeyEvent::new(KeyCode::Char('B'), KeyModifiers::SHIFT));
+        assert!(app.show_tree);
+        assert_eq!(uuids(&app), vec!["a", "c1", "c2"]);
+        assert_eq!(app.log_cursor, Some(2));
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Tree view: 1 abandoned branch")
+        );
+
+        app.on_key(KeyEvent::new(KeyCode::Char('B'), KeyModifiers::SHIFT));
+        assert!(!app.show_tree);
+        assert_eq!(uuids(&app), vec!["a", "c2"]);
+        assert_eq!(app.log_cursor, Some(1));
+    }
+
+    #[test]
+    fn test_jump_to_subagent_and_back() {
+        use crate::log_entry::parse_jsonl_line;
+
+        let mut app = App::new(test_config());
+        app.focus = Focus::LogStream;
+        for json in [
+            r#"{"type": "user", "sessionId": "s1", "uuid": "p", "message": {"role": "user", "content": "Audit the parser"}}"#,
+            r#"{"type": "assistant", "sessionId": "s1", "uuid": "task", "parentUuid": "p", "timestamp": "2025-01-15T10:00:00Z", "message": {"role": "assistant", "content": [{"type": "tool_use", "id": "t1", "name": "Task", "input": {"description": "Audit", "prompt": "Read src/parser.rs"}}]}}"#,
+            r#"{"type": "user", "sessionId": "s1", "isSidechain": true, "agentId": "a1", "uuid": "s-1", "timestamp": "2025-01-15T10:00:01Z", "message": {"role": "user", "content": "Read src/parser.rs"}}"#,
+            r#"{"type": "assistant", "sessionId": "s1", "isSidechain": true, "agentId": "a1", "uuid": "s-2", "parentUuid": "s-1", "message": {"role": "assistant", "content": "Done"}}"#,
+        ] {
+            app.on_new_log_entry(parse_jsonl_line(json).unwrap());
+        }
+        let selected = |app: &App| {
+            let entries = app.visible_entries();
+            app.log_cursor.and_then(|c| entries[c].uuid.clone())
+        };
+
+        // Not on a Task call: nothing happens.
+        app.log_cursor = Some(0);
+        app.on_key(KeyEvent::new(KeyCode::Char('>'), KeyModifiers::SHIFT));
+        assert_eq!(app.filter_state.selected_agent, None);
+        assert!(app.status_message.is_some());
+
+        app.log_cursor = Some(1);
+        app.on_key(KeyEvent::new(KeyCode::Char('>'), KeyModifiers::SHIFT));
+        assert_eq!(app.filter_state.selected_agent.as_deref(), Some("a1"));
+        assert_eq!(selected(&app).as_deref(), Some("s-1"));
+
+        app.on_key(KeyEvent::new(KeyCode::Char('<'), KeyModifiers::SHIFT));
+        assert_eq!(app.filter_state.selected_agent, None);
+        assert_eq!(selected(&app).as_deref(), Some("task"));
+        assert!(app.task_jumps.is_empty());
+
+        // Without a recorded jump, `<` uses the agent filtered to.
+        app.filter_state.selected_agent = Some("a1".to_string());
+        app.on_key(KeyEvent::new(KeyCode::Char('<'), KeyModifiers::SHIFT));
+        assert_eq!(app.filter_state.selected_agent, None);
+        assert_eq!(selected(&app).as_deref(), Some("task"));
+    }
+
+    #[test]
+    fn test_t_expands_compaction_summary() {
+        use crate::log_entry::parse_jsonl_line;
+
+        let mut app = App::new(test_config());
+        app.focus = Focus::LogStream;
+        app.on_new_log_entry(
+            parse_jsonl_line(
+                r#"{"type": "user", "uuid": "s1", "isCompactSummary": true, "message": {"role": "user", "content": "Summary"}}"#,
+            )
+            .unwrap(),
+        );
+        let entry = app.visible_entries()[0].clone();
+        assert!(!app.is_summary_expanded(&entry));
+
+        app.log_cursor = Some(0);
+        app.on_key(KeyEvent::new(KeyCode::Char('t'), KeyModifiers::NONE));
+        assert!(app.is_summary_expanded(&entry));
+        assert!(app.toggled_thinking.is_empty());
+
+        app.on_key(KeyEvent::new(KeyCode::Char('t'), KeyModifiers::NONE));
+        assert!(!app.is_summary_expanded(&entry));
+    }
+
+    #[test]
+    fn test_enter_without_cursor_confirms_session() {
+        let mut app = app_with_tool_call();
+        app.sessions = vec![dummy_session("s1")];
+        app.on_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(!app.detail_view.visible);
+        assert_eq!(app.active_session_id.as_deref(), Some("s1"));
+    }
+
+    #[test]
+    fn test_visible_entries_respects_active_session() {
+        use crate::log_entry::parse_jsonl_line;
+
+        let mut app = App::new(test_config());
+        app.ring_buffer.push(
+            parse_jsonl_line(r#"{"type": "user", "sessionId": "a", "message": {"content": "x"}}"#)
+                .unwrap(),
+        );
+        app.ring_buffer.push(
+            parse_jsonl_line(r#"{"type": "user", "sessionId": "b", "message": {"content": "y"}}"#)
+                .unwrap(),
+        );
+        app.ring_buffer
+            .push(parse_jsonl_line(r#"{"type": "progress", "sessionId": "a"}"#).unwrap());
+
+        assert_eq!(app.visible_entries().len(), 2);
+        app.active_session_id = Some("a".to_string());
+        assert_eq!(app.visible_entries().len(), 1);
+    }
+
+    // -- Global search --------------------------------------------------------
+
+    /// Write a session file with one user entry per `(uuid, text)` pair.
+    fn write_session_file(dir: &std::path::Path, id: &str, entries: &[(&str, &str)]) -> PathBuf {
+        use std::io::Write;
+
+        let path = dir.join(format!("{}.jsonl", id));
+        let mut file = std::fs::File::create(&path).unwrap();
+        for (uuid, text) in entries {
+            writeln!(
+                file,
+                r#"{{"type":"user","sessionId":"{}","uuid":"{}","timestamp":"2025-01-15T10:00:00Z","message":{{"role":"user","content":[{{"type":"text","text":"{}"}}]}}}}"#,
+                id, uuid, text
+            )
+            .unwrap();
+        }
+        path
+    }
+
+    fn type_keys(app: &mut App, text: &str) {
+        for ch in text.chars() {
+            app.on_key(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
+        }
+    }
+
+    #[test]
+    fn test_shift_s_opens_global_search_and_esc_closes() {
+        let mut app = App::new(test_config());
+        app.on_key(KeyEvent::new(KeyCode::Char('S'), KeyModifiers::NONE));
+        assert!(app.global_search.is_input());
+
+        // Keys go to the input, not the global handlers.
+        type_keys(&mut app, "q");
+        assert!(!app.should_quit);
+        assert_eq!(app.global_search.input_buffer, "q");
+
+        app.on_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert!(!app.global_search.is_input());
+    }
+
+    #[test]
+    fn test_global_search_invalid_regex_keeps_input_open() {
+        let mut app = App::new(test_config());
+        app.on_key(KeyEvent::new(KeyCode::Char('S'), KeyModifiers::NONE));
+        app.global_search.match_mode = crate::search::MatchMode::Regex;
+        type_keys(&mut app, "(");
+        app.on_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(app.global_search.is_input());
+        assert!(app.global_search.error.is_some());
+    }
+
+    #[test]
+    fn test_global_search_pick_switches_session_and_selects_entry() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let first = write_session_file(tmp.path(), "first", &[("f1", "hello")]);
+        write_session_file(
+            tmp.path(),
+            "second",
+            &[
+                ("s1", "unrelated"),
+                ("s2", "find the needle"),
+                ("s3", "later"),
+            ],
+        );
+
+        let mut app = App::new(test_config());
+        app.project_path = Some(tmp.path().to_path_buf());
+        // Only the first session is in the sidebar.
+        app.sessions = vec![Session {
+            id: "first".to_string(),
+            agents: vec![Agent {
+                agent_id: None,
+                slug: None,
+                log_path: first,
+                is_main: true,
+            }],
+            last_modified: SystemTime::now(),
+        }];
+        app.confirm_session_selection();
+
+        app.on_key(KeyEvent::new(KeyCode::Char('S'), KeyModifiers::NONE));
+        type_keys(&mut app, "needle");
+        app.on_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(app.global_search.is_results());
+        assert_eq!(app.global_search.match_count(), 1);
+
+        app.on_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(!app.global_search.is_results());
+        assert_eq!(app.active_session_id.as_deref(), Some("second"));
+        assert_eq!(app.sessions.len(), 2);
+        assert_eq!(app.selected_session_index, 1);
+        assert_eq!(app.focus, Focus::LogStream);
+        assert_eq!(app.log_cursor, Some(1));
+        assert_eq!(app.visible_entries()[1].uuid.as_deref(), Some("s2"));
+    }
+
+    #[test]
+    fn test_diagnostics_panel_and_watcher_events() {
+        let mut app = App::new(AppConfig::default());
+        app.on_watcher_error("watch failed".to_string());
+        app.on_parse_error(PathBuf::from("/p/s.jsonl"), "EOF".to_string());
+        assert_eq!(app.diagnostics.unseen_errors(), 2);
+
+        app.on_key(KeyEvent::new(KeyCode::Char('D'), KeyModifiers::SHIFT));
+        assert!(app.diagnostics.visible);
+        assert_eq!(app.diagnostics.unseen_errors(), 0);
+
+        // Keys are consumed by the panel while it is open.
+        app.on_key(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE));
+        app.on_key(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE));
+        assert_eq!(app.diagnostics.scroll, 1);
+        assert_eq!(app.focus, Focus::Sidebar);
+
+        app.needs_redraw = false;
+        app.on_watcher_status(WatcherStatus {
+            backend: crate::watcher::WatchBackend::Native,
+            offsets: Vec::new(),
+        });
+        assert!(app.needs_redraw, "an open panel follows status reports");
+
+        app.on_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert!(!app.diagnostics.visible);
+        app.needs_redraw = false;
+        app.on_watcher_status(WatcherStatus {
+            backend: crate::watcher::WatchBackend::Native,
+            offsets: Vec::new(),
+        });
+        assert!(!app.needs_redraw);
+    }
 }
@@ -0,0 +1,397 @@
+//! Detail pane for the entry under the log stream cursor.
+//!
+//! Opened with `Enter` while the log stream has focus and an entry is
+//! selected. Shows each tool call referenced by the entry in full: the
+//! pretty-printed input, an `Edit` diff, and the matching result output.
+//! Entries without tool calls show their full text content instead.
+
+use chrono::{DateTime, Utc};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+
+use crate::content_render::{render_content_blocks, RenderedLine, REDACTED_THINKING_LABEL};
+use crate::log_entry::LogEntry;
+use crate::theme::ThemeColors;
+use crate::tool_calls::{format_elapsed, ToolCallIndex, ToolCallStatus};
+use crate::tool_detail::{DiffLine, ToolDetail};
+
+/// Lines scrolled by PageUp / PageDown.
+const PAGE_SCROLL: usize = 20;
+
+// ---------------------------------------------------------------------------
+// DetailAction
+// ---------------------------------------------------------------------------
+
+/// Result of handling a key event in the detail pane.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DetailAction {
+    /// The pane consumed the event.
+    Consumed,
+    /// Close the pane.
+    Close,
+}
+
+// ---------------------------------------------------------------------------
+// DetailViewState
+// ---------------------------------------------------------------------------
+
+/// State for the detail pane overlay.
+#[derive(Debug, Clone, Default)]
+pub struct DetailViewState {
+    /// Whether the pane is currently visible.
+    pub visible: bool,
+    /// Title shown in the pane border.
+    pub title: String,
+    /// Pre-built content lines (snapshot taken when the pane was opened).
+    pub lines: Vec<Line<'static>>,
+    /// Scroll offset in visual lines from the top. May exceed the content
+    /// height; the render phase clamps it.
+    pub scroll: usize,
+}
+
+impl DetailViewState {
+    /// Show the pane with the given content, scrolled to the top.
+    pub fn open(&mut self, title: String, lines: Vec<Line<'static>>) {
+        self.visible = true;
+        self.title = title;
+        self.lines = lines;
+        self.scroll = 0;
+    }
+
+    /// Hide the pane and drop its content.
+    pub fn close(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Handle a key event while the pane is visible.
+    pub fn on_key(&mut self, key: KeyEvent) -> DetailAction {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return DetailAction::Close;
+        }
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => return DetailAction::Close,
+            KeyCode::Down | KeyCode::Char('j') => self.scroll = self.scroll.saturating_add(1),
+            KeyCode::Up | KeyCode::Char('k') => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::PageDown | KeyCode::Char('d') => {
+                self.scroll = self.scroll.saturating_add(PAGE_SCROLL)
+            }
+            KeyCode::PageUp | KeyCode::Char('u') => {
+                self.scroll = self.scroll.saturating_sub(PAGE_SCROLL)
+            }
+            KeyCode::Home | KeyCode::Char('g') => self.scroll = 0,
+            KeyCode::End | KeyCode::Char('G') => self.scroll = usize::MAX,
+            _ => {}
+        }
+        DetailAction::Consumed
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Content
+// ---------------------------------------------------------------------------
+
+/// Build the pane content for `entry`.
+///
+/// `details` are the tool calls referenced by the entry (see
+/// [`crate::tool_detail::collect_tool_details`]); `tool_calls` supplies
+/// their status and elapsed time relative to `now`.
+pub fn build_detail_lines(
+    entry: &LogEntry,
+    details: &[ToolDetail],
+    tool_calls: &ToolCallIndex,
+    now: DateTime<Utc>,
+    theme: &ThemeColors,
+) -> Vec<Line<'static>> {
+    let section = Style::default()
+        .fg(theme.detail_section)
+        .add_modifier(Modifier::BOLD);
+    let text = Style::default().fg(theme.logstream_text);
+    let dim = Style::default()
+        .fg(theme.logstream_timestamp)
+        .add_modifier(Modifier::DIM);
+
+    let mut lines: Vec<Line<'static>> = Vec::new();
+
+    if details.is_empty() {
+        lines.push(Line::from(Span::styled("Message", section)));
+        let rendered = entry
+            .message
+            .as_ref()
+            .map(|m| render_content_blocks(&m.content))
+            .unwrap_or_default();
+        if rendered.is_empty() {
+            lines.push(Line::from(Span::styled("  (no content)", dim)));
+        }
+        for line in rendered {
+            let (content, color) = match line {
+                RenderedLine::Text(t) => (t, theme.logstream_text),
+                RenderedLine::ToolUse(t) => (t, theme.role_tool_use),
+                RenderedLine::ToolResult { summary, .. } => (summary, theme.role_tool_result),
+                // The detail view always shows thinking in full.
+                RenderedLine::Thinking(t) => (t, theme.role_thinking),
+                RenderedLine::RedactedThinking => {
+                    (REDACTED_THINKING_LABEL.to_string(), theme.role_thinking)
+                }
+                RenderedLine::Unknown(t) => (t, theme.role_unknown),
+            };
+            for part in content.split('\n') {
+                lines.push(Line::from(Span::styled(
+                    format!("  {}", part),
+                    Style::default().fg(color),
+                )));
+            }
+        }
+        return lines;
+    }
+
+    for (i, detail) in details.iter().enumerate() {
+        if i > 0 {
+            lines.push(Line::from(""));
+        }
+
+        // Header: [Name] id  status
+        let name = if detail.name.is_empty() {
+            "tool"
+        } else {
+            detail.name.as_str()
+        };
+        let (status, status_color) = status_text(detail, tool_calls, now, theme);
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("[{}]", name),
+                Style::default()
+                    .fg(theme.role_tool_use)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(format!(" {} ", detail.id), dim),
+            Span::styled(status, Style::default().fg(status_color)),
+        ]));
+
+        // Input
+        lines.push(Line::from(Span::styled("Input", section)));
+        match detail.input {
+            Some(ref input) => {
+                lines.extend(input.lines().map(|l| indented(l, text)));
+            }
+            None => lines.push(Line::from(Span::styled("  (tool call not loaded)", dim))),
+        }
+
+        // Diff
+        if !detail.diff.is_empty() {
+            lines.push(Line::from(Span::styled("Diff", section)));
+            for diff_line in &detail.diff {
+                lines.push(match diff_line {
+                    DiffLine::Header(h) => Line::from(Span::styled(
+                        format!("  @@ {} @@", h),
+                        Style::default().fg(theme.diff_header),
+                    )),
+                    DiffLine::Context(l) => Line::from(Span::styled(format!("    {}", l), dim)),
+                    DiffLine::Removed(l) => Line::from(Span::styled(
+                        format!("  - {}", l),
+                        Style::default().fg(theme.diff_removed),
+                    )),
+                    DiffLine::Added(l) => Line::from(Span::styled(
+                        format!("  + {}", l),
+                        Style::default().fg(theme.diff_added),
+                    )),
+                });
+            }
+        }
+
+        // Result
+        lines.push(Line::from(Span::styled("Result", section)));
+        match detail.result {
+            Some(ref result) => {
+                let style = if result.is_error {
+                    Style::default().fg(theme.role_tool_error)
+                } else {
+                    text
+                };
+                if result.text.is_empty() {
+                    lines.push(Line::from(Span::styled("  (empty)", dim)));
+                }
+                lines.extend(result.text.lines().map(|l| indented(l, style)));
+            }
+            None => lines.push(Line::from(Span::styled("  (no result yet)", dim))),
+        }
+    }
+
+    lines
+}
+
+/// Human-readable status of a call, with its color.
+fn status_text(
+    detail: &ToolDetail,
+    tool_calls: &ToolCallIndex,
+    now: DateTime<Utc>,
+    theme: &ThemeColors,
+) -> (String, ratatui::style::Color) {
+    let call = match tool_calls.get(&detail.id) {
+        Some(c) => c,
+        None => return (String::new(), theme.logstream_text),
+    };
+    let elapsed = call.elapsed_ms(now).map(format_elapsed);
+    match (call.status, elapsed) {
+        (ToolCallStatus::Pending, Some(e)) => (format!("running for {}", e), theme.role_tool_use),
+        (ToolCallStatus::Pending, None) => ("running".to_string(), theme.role_tool_use),
+        (ToolCallStatus::Succeeded, Some(e)) => (format!("succeeded in {}", e), theme.tool_call_ok),
+        (ToolCallStatus::Succeeded, None) => ("succeeded".to_string(), theme.tool_call_ok),
+        (ToolCallStatus::Failed, Some(e)) => (format!("failed after {}", e), theme.role_tool_error),
+        (ToolCallStatus::Failed, None) => ("failed".to_string(), theme.role_tool_error),
+        (ToolCallStatus::Abandoned, _) => ("abandoned".to_string(), theme.system_interrupted),
+    }
+}
+
+/// A content line indented under its section header.
+fn indented(line: &str, style: Style) -> Line<'static> {
+    Line::from(Span::styled(format!("  {}", line), style))
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log_entry::parse_jsonl_line;
+    use crate::tool_detail::collect_tool_details;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn line_text(line: &Line) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    // -- 1. Key handling --------------------------------------------------------
+
+    #[test]
+    fn test_open_resets_scroll() {
+        let mut state = DetailViewState {
+            scroll: 7,
+            ..Default::default()
+        };
+        state.open("t".into(), vec![Line::from("a")]);
+        assert!(state.visible);
+        assert_eq!(state.scroll, 0);
+    }
+
+    #[test]
+    fn test_scroll_keys() {
+        let mut state = DetailViewState::default();
+        state.open("t".into(), Vec::new());
+
+        assert_eq!(
+            state.on_key(key(KeyCode::Char('j'))),
+            DetailAction::Consumed
+        );
+        assert_eq!(state.scroll, 1);
+        state.on_key(key(KeyCode::PageDown));
+        assert_eq!(state.scroll, 21);
+        state.on_key(key(KeyCode::Char('k')));
+        assert_eq!(state.scroll, 20);
+        state.on_key(key(KeyCode::Char('g')));
+        assert_eq!(state.scroll, 0);
+        state.on_key(key(KeyCode::Up));
+        assert_eq!(state.scroll, 0);
+        state.on_key(key(KeyCode::Char('G')));
+        assert_eq!(state.scroll, usize::MAX);
+    }
+
+    #[test]
+    fn test_close_keys() {
+        let mut state = DetailViewState::default();
+        for code in [KeyCode::Esc, KeyCode::Enter, KeyCode::Char('q')] {
+            assert_eq!(state.on_key(key(code)), DetailAction::Close);
+        }
+        assert_eq!(
+            state.on_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+            DetailAction::Close
+        );
+    }
+
+    #[test]
+    fn test_close_clears_content() {
+        let mut state = DetailViewState::default();
+        state.open("t".into(), vec![Line::from("a")]);
+        state.close();
+        assert!(!state.visible);
+        assert!(state.lines.is_empty());
+    }
+
+    // -- 2. Content ---------------------------------------------------------------
+
+    #[test]
+    fn test_build_lines_for_edit_call() {
+        let use_entry = parse_jsonl_line(
+            r#"{"type": "assistant", "timestamp": "2025-01-15T10:00:00Z", "message": {"content": [{"type": "tool_use", "id": "e1", "name": "Edit", "input": {"file_path": "a.rs", "old_string": "old", "new_string": "new"}}]}}"#,
+        )
+        .unwrap();
+        let result_entry = parse_jsonl_line(
+            r#"{"type": "user", "timestamp": "2025-01-15T10:00:02Z", "message": {"content": [{"type": "tool_result", "tool_use_id": "e1", "content": "The file a.rs has been updated."}]}}"#,
+        )
+        .unwrap();
+        let all = vec![use_entry.clone(), result_entry.clone()];
+        let mut tool_calls = ToolCallIndex::new();
+        for e in &all {
+            tool_calls.record_entry(e);
+        }
+        let theme = ThemeColors::dark();
+
+        let details = collect_tool_details(&use_entry, &all);
+        let lines = build_detail_lines(&use_entry, &details, &tool_calls, Utc::now(), &theme);
+        let texts: Vec<String> = lines.iter().map(line_text).collect();
+
+        assert_eq!(texts[0], "[Edit] e1 succeeded in 2.0s");
+        assert!(texts.contains(&"Input".to_string()));
+        assert!(texts.contains(&"    \"file_path\": \"a.rs\",".to_string()));
+        let removed = lines.iter().find(|l| line_text(l) == "  - old").unwrap();
+        assert_eq!(removed.spans[0].style.fg, Some(theme.diff_removed));
+        let added = lines.iter().find(|l| line_text(l) == "  + new").unwrap();
+        assert_eq!(added.spans[0].style.fg, Some(theme.diff_added));
+        assert_eq!(texts.last().unwrap(), "  The file a.rs has been updated.");
+    }
+
+    #[test]
+    fn test_build_lines_pending_call() {
+        let use_entry = parse_jsonl_line(
+            r#"{"type": "assistant", "message": {"content": [{"type": "tool_use", "id": "t1", "name": "Bash", "input": {"command": "sleep 60"}}]}}"#,
+        )
+        .unwrap();
+        let mut tool_calls = ToolCallIndex::new();
+        tool_calls.record_entry(&use_entry);
+
+        let details = collect_tool_details(&use_entry, std::slice::from_ref(&use_entry));
+        let lines = build_detail_lines(
+            &use_entry,
+            &details,
+            &tool_calls,
+            Utc::now(),
+            &ThemeColors::dark(),
+        );
+        let texts: Vec<String> = lines.iter().map(line_text).collect();
+        assert_eq!(texts[0], "[Bash] t1 running");
+        assert_eq!(texts.last().unwrap(), "  (no result yet)");
+    }
+
+    #[test]
+    fn test_build_lines_for_text_entry() {
+        let entry = parse_jsonl_line(
+            r#"{"type": "assistant", "message": {"content": [{"type": "text", "text": "line one\nline two"}]}}"#,
+        )
+        .unwrap();
+        let lines = build_detail_lines(
+            &entry,
+            &[],
+            &ToolCallIndex::new(),
+            Utc::now(),
+            &ThemeColors::dark(),
+        );
+        let texts: Vec<String> = lines.iter().map(line_text).collect();
+        assert_eq!(texts, vec!["Message", "  line one", "  line two"]);
+    }
+}
@@ -0,0 +1,383 @@
+//! Diagnostics panel for the file watcher.
+//!
+//! Opened with `D`. Lists watcher errors, malformed lines per file with
+//! the last parse error, the backend in use, the number of tracked files,
+//! the event rate, the channel backlog and every file's read offset. The
+//! status bar shows a badge while errors have arrived that the panel has
+//! not shown yet.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Local, Utc};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+
+use crate::theme::ThemeColors;
+use crate::watcher::{WatchBackend, WatcherStatus};
+
+/// Number of watcher errors kept; older ones are dropped.
+pub const MAX_WATCHER_ERRORS: usize = 50;
+
+/// Window over which the event rate is averaged.
+const RATE_WINDOW: Duration = Duration::from_secs(5);
+
+/// Lines scrolled by PageUp / PageDown.
+const PAGE_SCROLL: usize = 20;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// Result of handling a key event in the diagnostics panel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticsAction {
+    /// The panel consumed the event.
+    Consumed,
+    /// Close the panel.
+    Close,
+}
+
+/// A watcher error with the time it arrived.
+#[derive(Debug, Clone)]
+pub struct WatcherErrorRecord {
+    pub time: DateTime<Utc>,
+    pub message: String,
+}
+
+/// Malformed lines skipped in one file.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParseErrorStats {
+    pub count: usize,
+    pub last_error: String,
+}
+
+/// Watcher diagnostics collected by the TUI, and the panel state.
+#[derive(Debug, Default)]
+pub struct Diagnostics {
+    /// Whether the panel is currently visible.
+    pub visible: bool,
+    /// Scroll offset in lines from the top. May exceed the content height;
+    /// the render phase clamps it.
+    pub scroll: usize,
+    /// The most recent watcher errors, oldest first.
+    pub errors: VecDeque<WatcherErrorRecord>,
+    /// Malformed lines per file.
+    pub parse_errors: BTreeMap<PathBuf, ParseErrorStats>,
+    /// The last status reported by the watcher.
+    pub status: Option<WatcherStatus>,
+    /// Watcher events still queued in the channel after the last drain.
+    pub backlog: usize,
+    /// Errors and malformed lines not yet shown in the panel.
+    unseen: usize,
+    /// Arrival times of drained event batches within [`RATE_WINDOW`].
+    batches: VecDeque<(Instant, usize)>,
+}
+
+impl Diagnostics {
+    /// Show the panel, scrolled to the top. Marks all errors as seen.
+    pub fn open(&mut self) {
+        self.visible = true;
+        self.scroll = 0;
+        self.unseen = 0;
+    }
+
+    /// Hide the panel.
+    pub fn close(&mut self) {
+        self.visible = false;
+    }
+
+    /// Record a watcher error.
+    pub fn record_error(&mut self, message: String) {
+        if self.errors.len() == MAX_WATCHER_ERRORS {
+            self.errors.pop_front();
+        }
+        self.errors.push_back(WatcherErrorRecord {
+            time: Utc::now(),
+            message,
+        });
+        self.note_unseen();
+    }
+
+    /// Record a malformed line skipped in `path`.
+    pub fn record_parse_error(&mut self, path: PathBuf, error: String) {
+        let stats = self.parse_errors.entry(path).or_default();
+        stats.count += 1;
+        stats.last_error = error;
+        self.note_unseen();
+    }
+
+    /// Record `count` file events (entries and file changes) drained at
+    /// `now`.
+    pub fn record_events(&mut self, count: usize, now: Instant) {
+        if count > 0 {
+            self.batches.push_back((now, count));
+        }
+        while self
+            .batches
+            .front()
+            .is_some_and(|(t, _)| now.duration_since(*t) > RATE_WINDOW)
+        {
+            self.batches.pop_front();
+        }
+    }
+
+    /// Average watcher events per second over the last [`RATE_WINDOW`].
+    pub fn events_per_sec(&self, now: Instant) -> f64 {
+        let count: usize = self
+            .batches
+            .iter()
+            .filter(|(t, _)| now.duration_since(*t) <= RATE_WINDOW)
+            .map(|(_, n)| n)
+            .sum();
+        count as f64 / RATE_WINDOW.as_secs_f64()
+    }
+
+    /// Errors and malformed lines that arrived since the panel was last
+    /// opened.
+    pub fn unseen_errors(&self) -> usize {
+        self.unseen
+    }
+
+    /// Total malformed lines across all files.
+    pub fn total_parse_errors(&self) -> usize {
+        self.parse_errors.values().map(|s| s.count).sum()
+    }
+
+    fn note_unseen(&mut self) {
+        if !self.visible {
+            self.unseen += 1;
+        }
+    }
+
+    /// Handle a key event while the panel is visible.
+    pub fn on_key(&mut self, key: KeyEvent) -> DiagnosticsAction {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return DiagnosticsAction::Close;
+        }
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('D') | KeyCode::Char('q') => {
+                return DiagnosticsAction::Close
+            }
+            KeyCode::Down | KeyCode::Char('j') => self.scroll = self.scroll.saturating_add(1),
+            KeyCode::Up | KeyCode::Char('k') => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::PageDown | KeyCode::Char('d') => {
+                self.scroll = self.scroll.saturating_add(PAGE_SCROLL)
+            }
+            KeyCode::PageUp | KeyCode::Char('u') => {
+                self.scroll = self.scroll.saturating_sub(PAGE_SCROLL)
+            }
+            KeyCode::Home | KeyCode::Char('g') => self.scroll = 0,
+            KeyCode::End | KeyCode::Char('G') => self.scroll = usize::MAX,
+            _ => {}
+        }
+        DiagnosticsAction::Consumed
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Content
+// ---------------------------------------------------------------------------
+
+/// Build the panel content.
+///
+/// `configured` is the backend from the config, to tell a fallback to
+/// polling apart from configured polling. File paths are shown relative
+/// to `base` (the watched directory) when they are inside it.
+pub fn build_diagnostics_lines(
+    diag: &Diagnostics,
+    configured: WatchBackend,
+    base: Option<&Path>,
+    now: Instant,
+    theme: &ThemeColors,
+) -> Vec<Line<'static>> {
+    let section = Style::default()
+        .fg(theme.filter_overlay_fg)
+        .add_modifier(Modifier::BOLD);
+    let label = Style::default()
+        .fg(theme.filter_shortcut_key)
+        .add_modifier(Modifier::BOLD);
+    let text = Style::default().fg(theme.filter_overlay_fg);
+    let dim = text.add_modifier(Modifier::DIM);
+    let error = Style::default().fg(theme.system_error);
+
+    let canonical_base = base.and_then(|b| b.canonicalize().ok());
+    let display = |path: &Path| -> String {
+        base.into_iter()
+            .chain(canonical_base.as_deref())
+            .find_map(|b| path.strip_prefix(b).ok())
+            .unwrap_or(path)
+            .display()
+            .to_string()
+    };
+
+    let backend = match (&diag.status, configured) {
+        (None, _) => "not running".to_string(),
+        (Some(s), WatchBackend::Auto) if s.backend == WatchBackend::Poll => {
+            "polling (no native events, fell back)".to_string()
+        }
+        (Some(s), _) if s.backend == WatchBackend::Poll => "polling".to_string(),
+        (Some(_), _) => "native events".to_string(),
+    };
+    let files = diag.status.as_ref().map_or(0, |s| s.offsets.len());
+
+    let mut lines = vec![Line::from(Span::styled(" Watcher", section))];
+    for (name, value) in [
+        ("Backend", backend),
+        ("Files", format!("{} tracked", files)),
+        ("Events/s", format!("{:.1}", diag.events_per_sec(now))),
+        ("Backlog", format!("{} queued", diag.backlog)),
+    ] {
+        lines.push(Line::from(vec![
+            Span::styled(format!("   {:<10}", name), label),
+            Span::styled(value, text),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!(" Errors ({})", diag.errors.len()),
+        section,
+    )));
+    if diag.errors.is_empty() {
+        lines.push(Line::from(Span::styled("   none", dim)));
+    }
+    for record in &diag.errors {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(
+                    "   {}  ",
+                    record.time.with_timezone(&Local).format("%H:%M:%S")
+                ),
+                dim,
+            ),
+            Span::styled(record.message.clone(), error),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!(" Malformed lines ({})", diag.total_parse_errors()),
+        section,
+    )));
+    if diag.parse_errors.is_empty() {
+        lines.push(Line::from(Span::styled("   none", dim)));
+    }
+    for (path, stats) in &diag.parse_errors {
+        lines.push(Line::from(vec![
+            Span::styled(format!("   {}  ", display(path)), text),
+            Span::styled(format!("{} skipped", stats.count), error),
+        ]));
+        lines.push(Line::from(Span::styled(
+            format!("     last: {}", stats.last_error),
+            dim,
+        )));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(" Read offsets", section)));
+    match &diag.status {
+        Some(status) if !status.offsets.is_empty() => {
+            for (path, offset) in &status.offsets {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("   {:>12}  ", offset), label),
+                    Span::styled(display(path), text),
+                ]));
+            }
+        }
+        _ => lines.push(Line::from(Span::styled("   none", dim))),
+    }
+    lines
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line_text(line: &Line) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn test_unseen_errors_cleared_by_opening() {
+        let mut diag = Diagnostics::default();
+        diag.record_error("watch failed".to_string());
+        diag.record_parse_error(PathBuf::from("/p/a.jsonl"), "bad".to_string());
+        diag.record_parse_error(PathBuf::from("/p/a.jsonl"), "worse".to_string());
+        assert_eq!(diag.unseen_errors(), 3);
+        assert_eq!(diag.total_parse_errors(), 2);
+        assert_eq!(
+            diag.parse_errors[Path::new("/p/a.jsonl")].last_error,
+            "worse"
+        );
+
+        diag.open();
+        assert_eq!(diag.unseen_errors(), 0);
+        // Errors arriving while the panel is open are seen right away.
+        diag.record_error("again".to_string());
+        assert_eq!(diag.unseen_errors(), 0);
+        assert_eq!(
+            diag.on_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)),
+            DiagnosticsAction::Close
+        );
+    }
+
+    #[test]
+    fn test_error_list_is_capped() {
+        let mut diag = Diagnostics::default();
+        for i in 0..MAX_WATCHER_ERRORS + 5 {
+            diag.record_error(format!("error {}", i));
+        }
+        assert_eq!(diag.errors.len(), MAX_WATCHER_ERRORS);
+        assert_eq!(diag.errors.front().unwrap().message, "error 5");
+    }
+
+    #[test]
+    fn test_events_per_sec_uses_window() {
+        let mut diag = Diagnostics::default();
+        let start = Instant::now();
+        diag.record_events(10, start);
+        diag.record_events(15, start + Duration::from_secs(1));
+        assert_eq!(diag.events_per_sec(start + Duration::from_secs(1)), 5.0);
+        // The first batch falls out of the window.
+        diag.record_events(0, start + Duration::from_secs(6));
+        assert_eq!(diag.events_per_sec(start + Duration::from_secs(6)), 3.0);
+        assert_eq!(diag.batches.len(), 1);
+    }
+
+    #[test]
+    fn test_lines_show_fallback_offsets_and_relative_paths() {
+        let mut diag = Diagnostics {
+            status: Some(WatcherStatus {
+                backend: WatchBackend::Poll,
+                offsets: vec![(PathBuf::from("/proj/s1.jsonl"), 4096)],
+            }),
+            ..Default::default()
+        };
+        diag.record_parse_error(PathBuf::from("/proj/s1.jsonl"), "EOF".to_string());
+        let theme = ThemeColors::dark();
+        let text: Vec<String> = build_diagnostics_lines(
+            &diag,
+            WatchBackend::Auto,
+            Some(Path::new("/proj")),
+            Instant::now(),
+            &theme,
+        )
+        .iter()
+        .map(line_text)
+        .collect();
+
+        assert!(text.iter().any(|l| l.contains("fell back")));
+        assert!(text.iter().any(|l| l.contains("1 tracked")));
+        assert!(text.iter().any(|l| l == "   s1.jsonl  1 skipped"));
+        assert!(text.iter().any(|l| l == "     last: EOF"));
+        assert!(text.iter().any(|l| l.contains("4096  s1.jsonl")));
+    }
+}
@@ -10,6 +10,7 @@ use std::time::Duration;
 use tokio::sync::mpsc;
 
 use crate::log_entry::LogEntry;
+use crate::watcher::WatcherStatus;
 
 // ---------------------------------------------------------------------------
 // AppEvent
@@ -29,11 +30,39 @@ pub enum AppEvent {
     NewLogEntry(Box<LogEntry>),
     /// A new JSONL file was detected by the watcher.
     NewFileDetected(PathBuf),
+    /// A JSONL file was deleted or moved away.
+    FileRemoved(PathBuf),
+    /// A JSONL file was renamed.
+    FileRenamed { from: PathBuf, to: PathBuf },
+    /// A JSONL file was truncated and is being re-read.
+    FileTruncated(PathBuf),
+    /// The watcher skipped a malformed line of a file.
+    ParseError { path: PathBuf, error: String },
+    /// The watcher reported its state.
+    WatcherStatus(WatcherStatus),
+    /// The watcher reported an error.
+    WatcherError(String),
     /// A periodic tick (used for UI refresh, cursor blink, etc.).
     #[allow(dead_code)]
     Tick,
 }
 
+impl AppEvent {
+    /// Whether the event reports activity in the watched files: a new
+    /// entry, or a file appearing, going away, being renamed or truncated.
+    /// Watcher status heartbeats and errors are not.
+    pub fn is_file_activity(&self) -> bool {
+        matches!(
+            self,
+            AppEvent::NewLogEntry(_)
+                | AppEvent::NewFileDetected(_)
+                | AppEvent::FileRemoved(_)
+                | AppEvent::FileRenamed { .. }
+                | AppEvent::FileTruncated(_)
+        )
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Event polling
 // ---------------------------------------------------------------------------
@@ -73,8 +102,26 @@ pub fn drain_log_entries(
             Ok(crate::watcher::WatcherEvent::NewFileDetected { path }) => {
                 events.push(AppEvent::NewFileDetected(path));
             }
-            Ok(_) => {
-                // Error — skip for now
+            Ok(crate::watcher::WatcherEvent::FileRemoved { path }) => {
+                events.push(AppEvent::FileRemoved(path));
+            }
+            Ok(crate::watcher::WatcherEvent::FileRenamed { from, to }) => {
+                events.push(AppEvent::FileRenamed { from, to });
+            }
+            Ok(crate::watcher::WatcherEvent::FileTruncated { path }) => {
+                events.push(AppEvent::FileTruncated(path));
+            }
+            Ok(crate::watcher::WatcherEvent::ParseError { source, error }) => {
+                events.push(AppEvent::ParseError {
+                    path: source,
+                    error,
+                });
+            }
+            Ok(crate::watcher::WatcherEvent::Status(status)) => {
+                events.push(AppEvent::WatcherStatus(status));
+            }
+            Ok(crate::watcher::WatcherEvent::Error(message)) => {
+                events.push(AppEvent::WatcherError(message));
             }
             Err(_) => break,
         }
@@ -91,7 +138,7 @@ pub fn drain_log_entries(
 mod tests {
     use super::*;
     use crate::log_entry::{parse_jsonl_line, EntryType};
-    use crate::watcher::WatcherEvent;
+    use crate::watcher::{WatchBackend, WatcherEvent};
     use std::path::PathBuf;
 
     // -- drain_log_entries tests ---------------------------------------------
@@ -157,7 +204,7 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_drain_converts_new_file_detected_and_skips_errors() {
+    async fn test_drain_converts_file_events_and_errors() {
         let (tx, mut rx) = mpsc::channel::<WatcherEvent>(16);
 
         // Send a NewFileDetected event (should be converted)
@@ -167,10 +214,34 @@ mod tests {
         .await
         .unwrap();
 
-        // Send an Error event (should be skipped)
+        // Send an Error and a ParseError event (should be converted)
         tx.send(WatcherEvent::Error("test error".to_string()))
             .await
             .unwrap();
+        tx.send(WatcherEvent::ParseError {
+            source: PathBuf::from("/fake/s1.jsonl"),
+            error: "expected value".to_string(),
+        })
+        .await
+        .unwrap();
+
+        // Send file lifecycle events (should be converted)
+        tx.send(WatcherEvent::FileTruncated {
+            path: PathBuf::from("/fake/s1.jsonl"),
+        })
+        .await
+        .unwrap();
+        tx.send(WatcherEvent::FileRenamed {
+            from: PathBuf::from("/fake/s1.jsonl"),
+            to: PathBuf::from("/fake/s2.jsonl"),
+        })
+        .await
+        .unwrap();
+        tx.send(WatcherEvent::FileRemoved {
+            path: PathBuf::from("/fake/s2.jsonl"),
+        })
+        .await
+        .unwrap();
 
         // Send a real entry
         let entry = parse_jsonl_line(r#"{"type": "user", "sessionId": "s1"}"#).unwrap();
@@ -182,11 +253,18 @@ mod tests {
         .unwrap();
 
         let events = drain_log_entries(&mut rx, 100);
-        assert_eq!(events.len(), 2);
+        assert_eq!(events.len(), 7);
         assert!(
             matches!(&events[0], AppEvent::NewFileDetected(p) if p == &PathBuf::from("/fake/new.jsonl"))
         );
-        assert!(matches!(&events[1], AppEvent::NewLogEntry(e) if e.entry_type == EntryType::User));
+        assert!(matches!(&events[1], AppEvent::WatcherError(m) if m == "test error"));
+        assert!(
+            matches!(&events[2], AppEvent::ParseError { error, .. } if error == "expected value")
+        );
+        assert!(matches!(&events[3], AppEvent::FileTruncated(p) if p.ends_with("s1.jsonl")));
+        assert!(matches!(&events[4], AppEvent::FileRenamed { to, .. } if to.ends_with("s2.jsonl")));
+        assert!(matches!(&events[5], AppEvent::FileRemoved(p) if p.ends_with("s2.jsonl")));
+        assert!(matches!(&events[6], AppEvent::NewLogEntry(e) if e.entry_type == EntryType::User));
     }
 
     #[tokio::test]
@@ -197,4 +275,26 @@ mod tests {
         let events = drain_log_entries(&mut rx, 100);
         assert!(events.is_empty());
     }
+
+    #[test]
+    fn test_only_entries_and_file_events_are_file_activity() {
+        let path = PathBuf::from("/p/s.jsonl");
+        assert!(AppEvent::FileTruncated(path.clone()).is_file_activity());
+        assert!(AppEvent::FileRenamed {
+            from: path.clone(),
+            to: path.clone()
+        }
+        .is_file_activity());
+        assert!(!AppEvent::WatcherStatus(WatcherStatus {
+            backend: WatchBackend::Poll,
+            offsets: Vec::new(),
+        })
+        .is_file_activity());
+        assert!(!AppEvent::WatcherError("gone".to_string()).is_file_activity());
+        assert!(!AppEvent::ParseError {
+            path,
+            error: "bad".to_string()
+        }
+        .is_file_activity());
+    }
 }
@@ -1,8 +1,16 @@
 //! Filter menu overlay for the TUI.
 //!
 //! Provides a simple menu-style overlay activated by `f` that lets the
-//! user toggle tool call visibility and select an agent filter.
+//! user toggle tool call and meta entry visibility, show only errors,
+//! select an agent filter and, in the all-sessions view, pick the
+//! sessions to show.
 //! Changes are applied immediately on selection.
+//!
+//! Also provides the filter expression prompt activated by `F`, which
+//! replaces the status bar with an input line for a
+//! [`FilterExpr`](crate::filter_expr::FilterExpr).
+
+use std::collections::BTreeSet;
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
@@ -15,10 +23,17 @@ use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 pub enum FilterMenuItem {
     /// Toggle hide/show tool calls.
     ToolCallToggle,
+    /// Toggle hide/show meta entries (`isMeta`).
+    MetaToggle,
+    /// Toggle showing only errors (API errors, failed hooks and tools).
+    ErrorsToggle,
     /// Show all agents (clear agent filter).
     AgentAll,
     /// Filter to a specific agent (agent_id, display_name).
     Agent(String, String),
+    /// Show or hide a session in the all-sessions view
+    /// (session_id, display_name).
+    Session(String, String),
 }
 
 // ---------------------------------------------------------------------------
@@ -55,8 +70,14 @@ pub struct FilterMenuState {
     pub selected: usize,
     /// Current tool call hide state (toggled in-place).
     pub hide_tool_calls: bool,
+    /// Current meta entry hide state (toggled in-place).
+    pub hide_meta: bool,
+    /// Current errors-only state (toggled in-place).
+    pub errors_only: bool,
     /// Current selected agent filter (None = all agents).
     pub selected_agent: Option<String>,
+    /// Sessions hidden from the all-sessions view (toggled in-place).
+    pub hidden_sessions: BTreeSet<String>,
 }
 
 // Default is derived (all fields default to false/0/None/empty).
@@ -65,21 +86,30 @@ impl FilterMenuState {
     /// Open the filter menu with the given known agents.
     ///
     /// Restores the current filter state into the menu fields.
-    /// The menu always has at least one item (ToolCallToggle).
+    /// The menu always has the three toggles (ToolCallToggle, MetaToggle,
+    /// ErrorsToggle).
     /// Agent items are only shown when there are known subagents.
     pub fn open(
         &mut self,
         hide_tool_calls: bool,
+        hide_meta: bool,
+        errors_only: bool,
         selected_agent: Option<String>,
         known_agents: Vec<(String, String)>, // (agent_id, display_name)
     ) {
         self.visible = true;
         self.hide_tool_calls = hide_tool_calls;
+        self.hide_meta = hide_meta;
+        self.errors_only = errors_only;
         self.selected_agent = selected_agent;
         self.selected = 0;
 
         // Build menu items
-        self.items = vec![FilterMenuItem::ToolCallToggle];
+        self.items = vec![
+            FilterMenuItem::ToolCallToggle,
+            FilterMenuItem::MetaToggle,
+            FilterMenuItem::ErrorsToggle,
+        ];
 
         if !known_agents.is_empty() {
             self.items.push(FilterMenuItem::AgentAll);
@@ -90,6 +120,20 @@ impl FilterMenuState {
         }
     }
 
+    /// Add one show/hide item per session, after the toggles, for the
+    /// all-sessions view. Called right after [`open`](Self::open).
+    pub fn add_sessions(
+        &mut self,
+        known_sessions: Vec<(String, String)>, // (session_id, display_name)
+        hidden_sessions: BTreeSet<String>,
+    ) {
+        self.hidden_sessions = hidden_sessions;
+        let items = known_sessions
+            .into_iter()
+            .map(|(id, name)| FilterMenuItem::Session(id, name));
+        self.items.splice(3..3, items);
+    }
+
     /// Handle a key event while the menu is visible.
     pub fn on_key(&mut self, key: KeyEvent) -> MenuAction {
         // Ctrl+C always closes
@@ -139,12 +183,23 @@ impl FilterMenuState {
             FilterMenuItem::ToolCallToggle => {
                 self.hide_tool_calls = !self.hide_tool_calls;
             }
+            FilterMenuItem::MetaToggle => {
+                self.hide_meta = !self.hide_meta;
+            }
+            FilterMenuItem::ErrorsToggle => {
+                self.errors_only = !self.errors_only;
+            }
             FilterMenuItem::AgentAll => {
                 self.selected_agent = None;
             }
             FilterMenuItem::Agent(agent_id, _) => {
                 self.selected_agent = Some(agent_id.clone());
             }
+            FilterMenuItem::Session(session_id, _) => {
+                if !self.hidden_sessions.remove(session_id) {
+                    self.hidden_sessions.insert(session_id.clone());
+                }
+            }
         }
     }
 
@@ -155,6 +210,14 @@ impl FilterMenuState {
                 let checkbox = if self.hide_tool_calls { "[x]" } else { "[ ]" };
                 format!("{} Hide Tool Calls", checkbox)
             }
+            FilterMenuItem::MetaToggle => {
+                let checkbox = if self.hide_meta { "[x]" } else { "[ ]" };
+                format!("{} Hide Meta Entries", checkbox)
+            }
+            FilterMenuItem::ErrorsToggle => {
+                let checkbox = if self.errors_only { "[x]" } else { "[ ]" };
+                format!("{} Errors Only", checkbox)
+            }
             FilterMenuItem::AgentAll => {
                 let radio = if self.selected_agent.is_none() {
                     "(*)"
@@ -171,6 +234,83 @@ impl FilterMenuState {
                 };
                 format!("{} {}", radio, display_name)
             }
+            FilterMenuItem::Session(session_id, display_name) => {
+                let checkbox = if self.hidden_sessions.contains(session_id) {
+                    "[ ]"
+                } else {
+                    "[x]"
+                };
+                format!("{} Show {}", checkbox, display_name)
+            }
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// FilterPromptState
+// ---------------------------------------------------------------------------
+
+/// Result of handling a key event in the filter prompt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PromptAction {
+    /// The prompt consumed the event; no further handling needed.
+    Consumed,
+    /// Close the prompt without changes.
+    Close,
+    /// Enter was pressed; the caller should parse `input_buffer`.
+    Submit,
+}
+
+/// State for the filter expression prompt.
+///
+/// Opened with `F`, pre-filled with the current expression so it can be
+/// edited. A parse error keeps the prompt open and is shown next to the
+/// input until the next edit.
+#[derive(Debug, Clone, Default)]
+pub struct FilterPromptState {
+    /// Whether the prompt is currently visible.
+    pub visible: bool,
+    /// The expression being typed.
+    pub input_buffer: String,
+    /// Parse error from the last submit, if any.
+    pub error: Option<String>,
+}
+
+impl FilterPromptState {
+    /// Open the prompt with `current` (the active expression source) as input.
+    pub fn open(&mut self, current: &str) {
+        self.visible = true;
+        self.input_buffer = current.to_string();
+        self.error = None;
+    }
+
+    /// Close the prompt, discarding the input and any error.
+    pub fn close(&mut self) {
+        self.visible = false;
+        self.input_buffer.clear();
+        self.error = None;
+    }
+
+    /// Handle a key event while the prompt is visible.
+    pub fn on_key(&mut self, key: KeyEvent) -> PromptAction {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return PromptAction::Close;
+        }
+
+        match key.code {
+            KeyCode::Esc => PromptAction::Close,
+            KeyCode::Enter => PromptAction::Submit,
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+                self.error = None;
+                PromptAction::Consumed
+            }
+            KeyCode::Char(ch) => {
+                self.input_buffer.push(ch);
+                self.error = None;
+                PromptAction::Consumed
+            }
+            _ => PromptAction::Consumed,
         }
     }
 }
@@ -221,33 +361,37 @@ mod tests {
     #[test]
     fn test_open_sets_visible() {
         let mut menu = FilterMenuState::default();
-        menu.open(false, None, vec![]);
+        menu.open(false, false, false, None, vec![]);
         assert!(menu.visible);
     }
 
     #[test]
-    fn test_open_with_no_agents_has_one_item() {
+    fn test_open_with_no_agents_has_only_toggles() {
         let mut menu = FilterMenuState::default();
-        menu.open(false, None, vec![]);
-        // Only ToolCallToggle
-        assert_eq!(menu.items.len(), 1);
+        menu.open(false, false, false, None, vec![]);
+        // Only ToolCallToggle + MetaToggle + ErrorsToggle
+        assert_eq!(menu.items.len(), 3);
         assert_eq!(menu.items[0], FilterMenuItem::ToolCallToggle);
+        assert_eq!(menu.items[1], FilterMenuItem::MetaToggle);
+        assert_eq!(menu.items[2], FilterMenuItem::ErrorsToggle);
     }
 
     #[test]
     fn test_open_with_agents_has_all_items() {
         let mut menu = FilterMenuState::default();
-        menu.open(false, None, sample_agents());
-        // ToolCallToggle + AgentAll + 2 agents = 4
-        assert_eq!(menu.items.len(), 4);
+        menu.open(false, false, false, None, sample_agents());
+        // ToolCallToggle + MetaToggle + ErrorsToggle + AgentAll + 2 agents = 6
+        assert_eq!(menu.items.len(), 6);
         assert_eq!(menu.items[0], FilterMenuItem::ToolCallToggle);
-        assert_eq!(menu.items[1], FilterMenuItem::AgentAll);
+        assert_eq!(menu.items[1], FilterMenuItem::MetaToggle);
+        assert_eq!(menu.items[2], FilterMenuItem::ErrorsToggle);
+        assert_eq!(menu.items[3], FilterMenuItem::AgentAll);
         assert_eq!(
-            menu.items[2],
+            menu.items[4],
             FilterMenuItem::Agent("abc".to_string(), "cook".to_string())
         );
         assert_eq!(
-            menu.items[3],
+            menu.items[5],
             FilterMenuItem::Agent("xyz".to_string(), "baker".to_string())
         );
     }
@@ -255,8 +399,10 @@ mod tests {
     #[test]
     fn test_open_restores_filter_state() {
         let mut menu = FilterMenuState::default();
-        menu.open(true, Some("abc".to_string()), sample_agents());
+        menu.open(true, true, true, Some("abc".to_string()), sample_agents());
         assert!(menu.hide_tool_calls);
+        assert!(menu.hide_meta);
+        assert!(menu.errors_only);
         assert_eq!(menu.selected_agent, Some("abc".to_string()));
     }
 
@@ -264,7 +410,7 @@ mod tests {
     fn test_open_resets_selection_to_zero() {
         let mut menu = FilterMenuState::default();
         menu.selected = 5;
-        menu.open(false, None, sample_agents());
+        menu.open(false, false, false, None, sample_agents());
         assert_eq!(menu.selected, 0);
     }
 
@@ -273,7 +419,7 @@ mod tests {
     #[test]
     fn test_move_down() {
         let mut menu = FilterMenuState::default();
-        menu.open(false, None, sample_agents());
+        menu.open(false, false, false, None, sample_agents());
         assert_eq!(menu.selected, 0);
 
         assert_eq!(menu.on_key(key(KeyCode::Down)), MenuAction::Consumed);
@@ -285,16 +431,28 @@ mod tests {
         assert_eq!(menu.on_key(key(KeyCode::Down)), MenuAction::Consumed);
         assert_eq!(menu.selected, 3);
 
+        assert_eq!(menu.on_key(key(KeyCode::Down)), MenuAction::Consumed);
+        assert_eq!(menu.selected, 4);
+
+        assert_eq!(menu.on_key(key(KeyCode::Down)), MenuAction::Consumed);
+        assert_eq!(menu.selected, 5);
+
         // At end, stays
         assert_eq!(menu.on_key(key(KeyCode::Down)), MenuAction::Consumed);
-        assert_eq!(menu.selected, 3);
+        assert_eq!(menu.selected, 5);
     }
 
     #[test]
     fn test_move_up() {
         let mut menu = FilterMenuState::default();
-        menu.open(false, None, sample_agents());
-        menu.selected = 3;
+        menu.open(false, false, false, None, sample_agents());
+        menu.selected = 5;
+
+        assert_eq!(menu.on_key(key(KeyCode::Up)), MenuAction::Consumed);
+        assert_eq!(menu.selected, 4);
+
+        assert_eq!(menu.on_key(key(KeyCode::Up)), MenuAction::Consumed);
+        assert_eq!(menu.selected, 3);
 
         assert_eq!(menu.on_key(key(KeyCode::Up)), MenuAction::Consumed);
         assert_eq!(menu.selected, 2);
@@ -313,7 +471,7 @@ mod tests {
     #[test]
     fn test_j_moves_down() {
         let mut menu = FilterMenuState::default();
-        menu.open(false, None, sample_agents());
+        menu.open(false, false, false, None, sample_agents());
         assert_eq!(menu.on_key(char_key('j')), MenuAction::Consumed);
         assert_eq!(menu.selected, 1);
     }
@@ -321,7 +479,7 @@ mod tests {
     #[test]
     fn test_k_moves_up() {
         let mut menu = FilterMenuState::default();
-        menu.open(false, None, sample_agents());
+        menu.open(false, false, false, None, sample_agents());
         menu.selected = 2;
         assert_eq!(menu.on_key(char_key('k')), MenuAction::Consumed);
         assert_eq!(menu.selected, 1);
@@ -332,21 +490,21 @@ mod tests {
     #[test]
     fn test_esc_closes() {
         let mut menu = FilterMenuState::default();
-        menu.open(false, None, vec![]);
+        menu.open(false, false, false, None, vec![]);
         assert_eq!(menu.on_key(key(KeyCode::Esc)), MenuAction::Close);
     }
 
     #[test]
     fn test_f_closes() {
         let mut menu = FilterMenuState::default();
-        menu.open(false, None, vec![]);
+        menu.open(false, false, false, None, vec![]);
         assert_eq!(menu.on_key(char_key('f')), MenuAction::Close);
     }
 
     #[test]
     fn test_ctrl_c_closes() {
         let mut menu = FilterMenuState::default();
-        menu.open(false, None, vec![]);
+        menu.open(false, false, false, None, vec![]);
         assert_eq!(menu.on_key(ctrl_key('c')), MenuAction::Close);
     }
 
@@ -355,7 +513,7 @@ mod tests {
     #[test]
     fn test_enter_toggles_tool_calls() {
         let mut menu = FilterMenuState::default();
-        menu.open(false, None, vec![]);
+        menu.open(false, false, false, None, vec![]);
         assert!(!menu.hide_tool_calls);
 
         // selected=0 is ToolCallToggle
@@ -370,17 +528,48 @@ mod tests {
     #[test]
     fn test_space_toggles_tool_calls() {
         let mut menu = FilterMenuState::default();
-        menu.open(false, None, vec![]);
+        menu.open(false, false, false, None, vec![]);
 
         assert_eq!(menu.on_key(char_key(' ')), MenuAction::Selected);
         assert!(menu.hide_tool_calls);
     }
 
+    #[test]
+    fn test_enter_toggles_meta() {
+        let mut menu = FilterMenuState::default();
+        menu.open(false, false, false, None, vec![]);
+        menu.selected = 1; // MetaToggle
+
+        assert_eq!(menu.on_key(key(KeyCode::Enter)), MenuAction::Selected);
+        assert!(menu.hide_meta);
+        assert!(!menu.hide_tool_calls);
+        assert_eq!(menu.item_label(1), "[x] Hide Meta Entries");
+    }
+
+    #[test]
+    fn test_enter_toggles_errors_only() {
+        let mut menu = FilterMenuState::default();
+        menu.open(false, false, false, None, vec![]);
+        assert_eq!(menu.item_label(2), "[ ] Errors Only");
+        menu.selected = 2; // ErrorsToggle
+
+        assert_eq!(menu.on_key(key(KeyCode::Enter)), MenuAction::Selected);
+        assert!(menu.errors_only);
+        assert!(!menu.hide_meta);
+        assert_eq!(menu.item_label(2), "[x] Errors Only");
+    }
+
     #[test]
     fn test_enter_selects_agent_all() {
         let mut menu = FilterMenuState::default();
-        menu.open(false, Some("abc".to_string()), sample_agents());
-        menu.selected = 1; // AgentAll
+        menu.open(
+            false,
+            false,
+            false,
+            Some("abc".to_string()),
+            sample_agents(),
+        );
+        menu.selected = 3; // AgentAll
 
         assert_eq!(menu.on_key(key(KeyCode::Enter)), MenuAction::Selected);
         assert!(menu.selected_agent.is_none());
@@ -389,8 +578,8 @@ mod tests {
     #[test]
     fn test_enter_selects_specific_agent() {
         let mut menu = FilterMenuState::default();
-        menu.open(false, None, sample_agents());
-        menu.selected = 2; // Agent("abc", "cook")
+        menu.open(false, false, false, None, sample_agents());
+        menu.selected = 4; // Agent("abc", "cook")
 
         assert_eq!(menu.on_key(key(KeyCode::Enter)), MenuAction::Selected);
         assert_eq!(menu.selected_agent, Some("abc".to_string()));
@@ -399,20 +588,20 @@ mod tests {
     #[test]
     fn test_agent_selection_is_mutually_exclusive() {
         let mut menu = FilterMenuState::default();
-        menu.open(false, None, sample_agents());
+        menu.open(false, false, false, None, sample_agents());
 
         // Select agent "abc"
-        menu.selected = 2;
+        menu.selected = 4;
         menu.on_key(key(KeyCode::Enter));
         assert_eq!(menu.selected_agent, Some("abc".to_string()));
 
         // Select agent "xyz"
-        menu.selected = 3;
+        menu.selected = 5;
         menu.on_key(key(KeyCode::Enter));
         assert_eq!(menu.selected_agent, Some("xyz".to_string()));
 
         // Select "All Agents"
-        menu.selected = 1;
+        menu.selected = 3;
         menu.on_key(key(KeyCode::Enter));
         assert!(menu.selected_agent.is_none());
     }
@@ -422,43 +611,85 @@ mod tests {
     #[test]
     fn test_item_label_tool_call_toggle_off() {
         let mut menu = FilterMenuState::default();
-        menu.open(false, None, vec![]);
+        menu.open(false, false, false, None, vec![]);
         assert_eq!(menu.item_label(0), "[ ] Hide Tool Calls");
     }
 
     #[test]
     fn test_item_label_tool_call_toggle_on() {
         let mut menu = FilterMenuState::default();
-        menu.open(true, None, vec![]);
+        menu.open(true, false, false, None, vec![]);
         assert_eq!(menu.item_label(0), "[x] Hide Tool Calls");
     }
 
     #[test]
     fn test_item_label_agent_all_selected() {
         let mut menu = FilterMenuState::default();
-        menu.open(false, None, sample_agents());
-        assert_eq!(menu.item_label(1), "(*) All Agents");
+        menu.open(false, false, false, None, sample_agents());
+        assert_eq!(menu.item_label(3), "(*) All Agents");
     }
 
     #[test]
     fn test_item_label_agent_all_not_selected() {
         let mut menu = FilterMenuState::default();
-        menu.open(false, Some("abc".to_string()), sample_agents());
-        assert_eq!(menu.item_label(1), "( ) All Agents");
+        menu.open(
+            false,
+            false,
+            false,
+            Some("abc".to_string()),
+            sample_agents(),
+        );
+        assert_eq!(menu.item_label(3), "( ) All Agents");
     }
 
     #[test]
     fn test_item_label_agent_selected() {
         let mut menu = FilterMenuState::default();
-        menu.open(false, Some("abc".to_string()), sample_agents());
-        assert_eq!(menu.item_label(2), "(*) cook");
+        menu.open(
+            false,
+            false,
+            false,
+            Some("abc".to_string()),
+            sample_agents(),
+        );
+        assert_eq!(menu.item_label(4), "(*) cook");
     }
 
     #[test]
     fn test_item_label_agent_not_selected() {
         let mut menu = FilterMenuState::default();
-        menu.open(false, Some("abc".to_string()), sample_agents());
-        assert_eq!(menu.item_label(3), "( ) baker");
+        menu.open(
+            false,
+            false,
+            false,
+            Some("abc".to_string()),
+            sample_agents(),
+        );
+        assert_eq!(menu.item_label(5), "( ) baker");
+    }
+
+    #[test]
+    fn test_session_items_toggle_visibility() {
+        let mut menu = FilterMenuState::default();
+        menu.open(false, false, false, None, sample_agents());
+        menu.add_sessions(
+            vec![
+                ("s1".to_string(), "abc123".to_string()),
+                ("s2".to_string(), "def456".to_string()),
+            ],
+            BTreeSet::from(["s2".to_string()]),
+        );
+        // Toggles, then sessions, then agents.
+        assert_eq!(menu.items.len(), 8);
+        assert_eq!(menu.item_label(3), "[x] Show abc123");
+        assert_eq!(menu.item_label(4), "[ ] Show def456");
+        assert_eq!(menu.items[5], FilterMenuItem::AgentAll);
+
+        menu.selected = 3;
+        assert_eq!(menu.on_key(key(KeyCode::Enter)), MenuAction::Selected);
+        menu.selected = 4;
+        assert_eq!(menu.on_key(key(KeyCode::Enter)), MenuAction::Selected);
+        assert_eq!(menu.hidden_sessions, BTreeSet::from(["s1".to_string()]));
     }
 
     // -- Unknown key is consumed ------------------------------------------
@@ -466,7 +697,7 @@ mod tests {
     #[test]
     fn test_unknown_key_consumed() {
         let mut menu = FilterMenuState::default();
-        menu.open(false, None, vec![]);
+        menu.open(false, false, false, None, vec![]);
         assert_eq!(menu.on_key(char_key('z')), MenuAction::Consumed);
     }
 
@@ -486,4 +717,44 @@ mod tests {
         // items is empty - should not panic
         menu.activate_selected();
     }
+
+    // -- Filter prompt tests ----------------------------------------------
+
+    #[test]
+    fn test_prompt_open_prefills_current_expression() {
+        let mut prompt = FilterPromptState {
+            error: Some("stale".to_string()),
+            ..Default::default()
+        };
+        prompt.open("role:user");
+        assert!(prompt.visible);
+        assert_eq!(prompt.input_buffer, "role:user");
+        assert!(prompt.error.is_none());
+    }
+
+    #[test]
+    fn test_prompt_typing_and_backspace_clear_error() {
+        let mut prompt = FilterPromptState::default();
+        prompt.open("");
+        assert_eq!(prompt.on_key(char_key('a')), PromptAction::Consumed);
+        prompt.error = Some("bad".to_string());
+        assert_eq!(
+            prompt.on_key(key(KeyCode::Backspace)),
+            PromptAction::Consumed
+        );
+        assert_eq!(prompt.input_buffer, "");
+        assert!(prompt.error.is_none());
+    }
+
+    #[test]
+    fn test_prompt_enter_submits_and_esc_closes() {
+        let mut prompt = FilterPromptState::default();
+        prompt.open("tool:Bash");
+        assert_eq!(prompt.on_key(key(KeyCode::Enter)), PromptAction::Submit);
+        assert_eq!(prompt.on_key(key(KeyCode::Esc)), PromptAction::Close);
+        assert_eq!(prompt.on_key(ctrl_key('c')), PromptAction::Close);
+        prompt.close();
+        assert!(!prompt.visible);
+        assert!(prompt.input_buffer.is_empty());
+    }
 }
@@ -0,0 +1,352 @@
+//! Global (cross-session) search overlay state.
+//!
+//! Opened with `S`. The user types a query in the bottom bar; on Enter
+//! the caller runs [`crate::history_search::search_sessions`] over every
+//! discovered session and hands the results back via
+//! [`GlobalSearchState::show_results`]. Results are shown in an overlay
+//! grouped by session; picking one jumps to that entry.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::history_search::{HistoryMatch, SessionMatches};
+use crate::search::{MatchMode, Matcher};
+
+/// Maximum number of matches kept per session in the TUI.
+pub const MAX_MATCHES_PER_SESSION: usize = 50;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// Which part of the global search is currently shown.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GlobalSearchMode {
+    /// Not shown.
+    #[default]
+    Inactive,
+    /// Typing the query in the bottom bar.
+    Input,
+    /// Browsing the results overlay.
+    Results,
+}
+
+/// Result of handling a key event in the global search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GlobalSearchAction {
+    /// The event was consumed; no further handling needed.
+    Consumed,
+    /// Close the global search.
+    Close,
+    /// Enter was pressed in the input; the caller should run the search.
+    Submit,
+    /// Enter was pressed on a result; the caller should jump to it.
+    Pick(HistoryMatch),
+}
+
+/// State for the global search input and results overlay.
+#[derive(Debug, Clone, Default)]
+pub struct GlobalSearchState {
+    /// Current mode.
+    pub mode: GlobalSearchMode,
+    /// The query being typed.
+    pub input_buffer: String,
+    /// How the query is matched; `Tab` cycles while typing.
+    pub match_mode: MatchMode,
+    /// Error from the last submit (e.g. an invalid regex).
+    pub error: Option<String>,
+    /// The query the results were produced for.
+    pub query: String,
+    /// Results grouped by session.
+    pub results: Vec<SessionMatches>,
+    /// Index of the selected match across all groups.
+    pub selected: usize,
+}
+
+impl GlobalSearchState {
+    /// Open the input, keeping the previous query for editing.
+    pub fn start_input(&mut self) {
+        self.mode = GlobalSearchMode::Input;
+        self.input_buffer = self.query.clone();
+        self.error = None;
+    }
+
+    /// Close the input or overlay. Results are dropped.
+    pub fn close(&mut self) {
+        self.mode = GlobalSearchMode::Inactive;
+        self.input_buffer.clear();
+        self.error = None;
+        self.results.clear();
+        self.selected = 0;
+    }
+
+    /// Whether the query input is shown.
+    pub fn is_input(&self) -> bool {
+        self.mode == GlobalSearchMode::Input
+    }
+
+    /// Whether the results overlay is shown.
+    pub fn is_results(&self) -> bool {
+        self.mode == GlobalSearchMode::Results
+    }
+
+    /// Compile the typed query with the current match mode.
+    pub fn matcher(&self) -> Result<Matcher, regex::Error> {
+        Matcher::new(&self.input_buffer, self.match_mode)
+    }
+
+    /// Switch to the results overlay with `results` for the typed query.
+    pub fn show_results(&mut self, results: Vec<SessionMatches>) {
+        self.mode = GlobalSearchMode::Results;
+        self.query = std::mem::take(&mut self.input_buffer);
+        self.results = results;
+        self.selected = 0;
+    }
+
+    /// Total number of listed matches across all groups.
+    pub fn match_count(&self) -> usize {
+        self.results.iter().map(|g| g.matches.len()).sum()
+    }
+
+    /// The currently selected match, if any.
+    pub fn selected_match(&self) -> Option<&HistoryMatch> {
+        self.results
+            .iter()
+            .flat_map(|g| g.matches.iter())
+            .nth(self.selected)
+    }
+
+    /// Handle a key event while the input or overlay is shown.
+    pub fn on_key(&mut self, key: KeyEvent) -> GlobalSearchAction {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return GlobalSearchAction::Close;
+        }
+
+        match self.mode {
+            GlobalSearchMode::Inactive => GlobalSearchAction::Consumed,
+            GlobalSearchMode::Input => self.on_input_key(key),
+            GlobalSearchMode::Results => self.on_results_key(key),
+        }
+    }
+
+    fn on_input_key(&mut self, key: KeyEvent) -> GlobalSearchAction {
+        match key.code {
+            KeyCode::Esc => GlobalSearchAction::Close,
+            KeyCode::Enter => {
+                if self.input_buffer.is_empty() {
+                    GlobalSearchAction::Close
+                } else {
+                    GlobalSearchAction::Submit
+                }
+            }
+            KeyCode::Tab => {
+                self.match_mode = self.match_mode.next();
+                self.error = None;
+                GlobalSearchAction::Consumed
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+                self.error = None;
+                GlobalSearchAction::Consumed
+            }
+            KeyCode::Char(ch) => {
+                self.input_buffer.push(ch);
+                self.error = None;
+                GlobalSearchAction::Consumed
+            }
+            _ => GlobalSearchAction::Consumed,
+        }
+    }
+
+    fn on_results_key(&mut self, key: KeyEvent) -> GlobalSearchAction {
+        let count = self.match_count();
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => GlobalSearchAction::Close,
+            KeyCode::Char('j') | KeyCode::Down => {
+                if self.selected + 1 < count {
+                    self.selected += 1;
+                }
+                GlobalSearchAction::Consumed
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+                GlobalSearchAction::Consumed
+            }
+            KeyCode::Char('g') => {
+                self.selected = 0;
+                GlobalSearchAction::Consumed
+            }
+            KeyCode::Char('G') => {
+                self.selected = count.saturating_sub(1);
+                GlobalSearchAction::Consumed
+            }
+            KeyCode::Char('/') | KeyCode::Char('S') => {
+                self.start_input();
+                GlobalSearchAction::Consumed
+            }
+            KeyCode::Enter => match self.selected_match() {
+                Some(m) => GlobalSearchAction::Pick(m.clone()),
+                None => GlobalSearchAction::Consumed,
+            },
+            _ => GlobalSearchAction::Consumed,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::Session;
+    use std::path::PathBuf;
+    use std::time::SystemTime;
+
+    // -- Helpers ----------------------------------------------------------
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn char_key(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)
+    }
+
+    fn hit(session_id: &str, uuid: &str) -> HistoryMatch {
+        HistoryMatch {
+            session_id: session_id.to_string(),
+            agent_id: None,
+            path: PathBuf::from(format!("/tmp/{}.jsonl", session_id)),
+            byte_offset: 0,
+            uuid: Some(uuid.to_string()),
+            timestamp: None,
+            snippet: "needle".to_string(),
+        }
+    }
+
+    fn group(session_id: &str, uuids: &[&str]) -> SessionMatches {
+        SessionMatches {
+            session: Session {
+                id: session_id.to_string(),
+                agents: vec![],
+                last_modified: SystemTime::UNIX_EPOCH,
+            },
+            matches: uuids.iter().map(|u| hit(session_id, u)).collect(),
+            total: uuids.len(),
+        }
+    }
+
+    fn with_results() -> GlobalSearchState {
+        let mut state = GlobalSearchState::default();
+        state.start_input();
+        state.input_buffer = "needle".to_string();
+        state.show_results(vec![group("s1", &["a", "b"]), group("s2", &["c"])]);
+        state
+    }
+
+    // -- Input ------------------------------------------------------------
+
+    #[test]
+    fn test_typing_and_submit() {
+        let mut state = GlobalSearchState::default();
+        state.start_input();
+        assert!(state.is_input());
+        assert_eq!(state.on_key(char_key('h')), GlobalSearchAction::Consumed);
+        state.on_key(char_key('i'));
+        state.on_key(key(KeyCode::Backspace));
+        assert_eq!(state.input_buffer, "h");
+        assert_eq!(
+            state.on_key(key(KeyCode::Enter)),
+            GlobalSearchAction::Submit
+        );
+    }
+
+    #[test]
+    fn test_enter_on_empty_input_closes() {
+        let mut state = GlobalSearchState::default();
+        state.start_input();
+        assert_eq!(state.on_key(key(KeyCode::Enter)), GlobalSearchAction::Close);
+    }
+
+    #[test]
+    fn test_tab_cycles_match_mode_and_clears_error() {
+        let mut state = GlobalSearchState::default();
+        state.start_input();
+        state.error = Some("bad".to_string());
+        state.on_key(key(KeyCode::Tab));
+        assert_eq!(state.match_mode, MatchMode::SmartCase);
+        assert!(state.error.is_none());
+    }
+
+    #[test]
+    fn test_ctrl_c_closes() {
+        let mut state = GlobalSearchState::default();
+        state.start_input();
+        let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert_eq!(state.on_key(ctrl_c), GlobalSearchAction::Close);
+    }
+
+    #[test]
+    fn test_start_input_prefills_previous_query() {
+        let mut state = with_results();
+        state.start_input();
+        assert_eq!(state.input_buffer, "needle");
+    }
+
+    // -- Results ----------------------------------------------------------
+
+    #[test]
+    fn test_show_results_moves_query() {
+        let state = with_results();
+        assert!(state.is_results());
+        assert_eq!(state.query, "needle");
+        assert!(state.input_buffer.is_empty());
+        assert_eq!(state.match_count(), 3);
+    }
+
+    #[test]
+    fn test_navigation_crosses_groups_and_clamps() {
+        let mut state = with_results();
+        state.on_key(key(KeyCode::Up));
+        assert_eq!(state.selected, 0);
+        state.on_key(char_key('j'));
+        state.on_key(char_key('j'));
+        state.on_key(char_key('j'));
+        assert_eq!(state.selected, 2);
+        assert_eq!(state.selected_match().unwrap().session_id, "s2");
+        state.on_key(char_key('g'));
+        assert_eq!(state.selected, 0);
+        state.on_key(char_key('G'));
+        assert_eq!(state.selected, 2);
+    }
+
+    #[test]
+    fn test_enter_picks_selected_match() {
+        let mut state = with_results();
+        state.on_key(char_key('j'));
+        match state.on_key(key(KeyCode::Enter)) {
+            GlobalSearchAction::Pick(m) => assert_eq!(m.uuid.as_deref(), Some("b")),
+            other => panic!("expected Pick, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_enter_with_no_results_is_consumed() {
+        let mut state = GlobalSearchState::default();
+        state.show_results(vec![]);
+        assert_eq!(
+            state.on_key(key(KeyCode::Enter)),
+            GlobalSearchAction::Consumed
+        );
+    }
+
+    #[test]
+    fn test_close_drops_results() {
+        let mut state = with_results();
+        assert_eq!(state.on_key(key(KeyCode::Esc)), GlobalSearchAction::Close);
+        state.close();
+        assert_eq!(state.mode, GlobalSearchMode::Inactive);
+        assert!(state.results.is_empty());
+    }
+}
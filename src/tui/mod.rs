@@ -6,14 +6,20 @@
 //! (including panics).
 
 pub mod app;
+pub mod detail_view;
+pub mod diagnostics;
 pub mod event;
 pub mod filter_overlay;
+pub mod global_search;
+pub mod session_filter;
 pub mod ui;
 
+use std::collections::HashMap;
 use std::io;
+use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
 use crossterm::execute;
@@ -25,8 +31,10 @@ use ratatui::Terminal;
 use tokio::sync::mpsc;
 
 use crate::config::AppConfig;
-use crate::project_path::detect_project_path;
-use crate::session::{discover_sessions, resolve_session};
+use crate::log_error;
+use crate::logging;
+use crate::project_path::{claude_projects_dir, detect_project_path};
+use crate::session::{discover_all_sessions, discover_sessions, resolve_session, Session};
 use crate::watcher::{self, WatcherEvent};
 use app::App;
 use event::{drain_log_entries, poll_crossterm_event, AppEvent};
@@ -38,6 +46,9 @@ use event::{drain_log_entries, poll_crossterm_event, AppEvent};
 /// How long to wait for a crossterm event before emitting a Tick.
 const TICK_RATE: Duration = Duration::from_millis(100);
 
+/// Maximum number of sessions listed in the sidebar.
+const MAX_SIDEBAR_SESSIONS: usize = 50;
+
 // ---------------------------------------------------------------------------
 // Terminal setup / teardown
 // ---------------------------------------------------------------------------
@@ -147,6 +158,7 @@ pub fn run_tui(config: AppConfig) -> io::Result<()> {
 
     let mut terminal = setup_terminal()?;
     let mut app = App::new(config);
+    app.log_path = logging::log_file_path();
 
     // -- Session discovery and replay ----------------------------------------
 
@@ -154,18 +166,39 @@ pub fn run_tui(config: AppConfig) -> io::Result<()> {
     let mut _watcher_handle: Option<watcher::WatcherHandle> = None;
 
     let cwd = std::env::current_dir().unwrap_or_default();
-    match detect_project_path(&cwd, app.config.project.as_deref()) {
+    let all_projects = app.config.all_projects;
+    let detected = if all_projects {
+        claude_projects_dir()
+    } else {
+        detect_project_path(&cwd, app.config.project.as_deref())
+    };
+    match detected {
         Ok(project_dir) => {
             // Derive the display name from the project path.
-            app.project_display_name = project_dir
-                .file_name()
-                .and_then(|n| n.to_str())
-                .map(|s| s.to_string());
+            app.project_display_name = if all_projects {
+                Some("all projects".to_string())
+            } else {
+                project_dir
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .map(|s| s.to_string())
+            };
             app.project_path = Some(project_dir.clone());
 
-            // Discover sessions.
-            match discover_sessions(&project_dir, 50) {
-                Ok(sessions) => {
+            // Discover sessions. With --all-projects every session is
+            // discovered so the watcher can skip their existing content.
+            let discovered = if all_projects {
+                discover_all_sessions(&project_dir, usize::MAX)
+            } else {
+                discover_sessions(&project_dir, MAX_SIDEBAR_SESSIONS)
+            };
+            match discovered {
+                Ok(mut sessions) => {
+                    let mut offsets = HashMap::new();
+                    if all_projects {
+                        offsets = current_file_lengths(&sessions);
+                        sessions.truncate(MAX_SIDEBAR_SESSIONS);
+                    }
                     if !sessions.is_empty() {
                         // Auto-select the most recent session (index 0).
                         let selected = resolve_session(&sessions, app.config.session.as_deref())
@@ -174,38 +207,41 @@ pub fn run_tui(config: AppConfig) -> io::Result<()> {
                             .unwrap_or_else(|| sessions[0].clone());
 
                         app.sessions = sessions;
+                        if all_projects {
+                            app.group_sessions_by_project();
+                            app.refresh_project_labels();
+                        }
+                        app.refresh_session_titles();
                         app.active_session_id = Some(selected.id.clone());
 
                         // Replay recent messages from the selected session.
                         app.replay_session_entries(&selected);
 
                         // Start the file watcher from where replay left off.
-                        let offsets = app.replay_offsets.clone();
-                        match watcher::start_watching(project_dir, app.config.verbose, 256, offsets)
-                        {
+                        offsets.extend(app.replay_offsets.clone());
+                        match watcher::start_watching(
+                            project_dir,
+                            256,
+                            offsets,
+                            app.config.watcher.backend,
+                            Duration::from_millis(app.config.watcher.poll_interval_ms),
+                        ) {
                             Ok((rx, handle)) => {
                                 watcher_rx = Some(rx);
                                 _watcher_handle = Some(handle);
                             }
                             Err(e) => {
-                                if app.config.verbose {
-                                    eprintln!("cc-tail: watcher error: {}", e);
-                                }
+                                log_error!("watcher error: {}", e);
+                                app.on_watcher_error(e.to_string());
                             }
                         }
                     }
                 }
-                Err(e) => {
-                    if app.config.verbose {
-                        eprintln!("cc-tail: session discovery error: {}", e);
-                    }
-                }
+                Err(e) => log_error!("session discovery error: {}", e),
             }
         }
         Err(e) => {
-            if app.config.verbose {
-                eprintln!("cc-tail: project detection error: {}", e);
-            }
+            log_error!("project detection error: {}", e);
             app.status_message = Some(format!("No project detected: {}", e));
         }
     }
@@ -223,6 +259,23 @@ pub fn run_tui(config: AppConfig) -> io::Result<()> {
     result
 }
 
+/// Current length of every agent log of `sessions`, keyed by canonical
+/// path, so the watcher only reports what is appended from now on.
+fn current_file_lengths(sessions: &[Session]) -> HashMap<PathBuf, u64> {
+    sessions
+        .iter()
+        .flat_map(|s| &s.agents)
+        .filter_map(|agent| {
+            let len = std::fs::metadata(&agent.log_path).ok()?.len();
+            let path = agent
+                .log_path
+                .canonicalize()
+                .unwrap_or_else(|_| agent.log_path.clone());
+            Some((path, len))
+        })
+        .collect()
+}
+
 // ---------------------------------------------------------------------------
 // Event loop
 // ---------------------------------------------------------------------------
@@ -262,22 +315,37 @@ fn run_event_loop(
                     // ratatui handles resize automatically on next draw.
                     app.needs_redraw = true;
                 }
-                AppEvent::Tick | AppEvent::NewLogEntry(_) | AppEvent::NewFileDetected(_) => {}
+                _ => {}
             }
         }
 
         // Drain watcher events (non-blocking).
         if let Some(ref mut rx) = watcher_rx {
             let watcher_events = drain_log_entries(rx, MAX_DRAIN_PER_TICK);
+            let activity = watcher_events
+                .iter()
+                .filter(|e| e.is_file_activity())
+                .count();
+            app.diagnostics.record_events(activity, Instant::now());
+            app.diagnostics.backlog = rx.len();
             for evt in watcher_events {
                 match evt {
                     AppEvent::NewLogEntry(entry) => app.on_new_log_entry(*entry),
                     AppEvent::NewFileDetected(path) => app.on_new_file_detected(path),
+                    AppEvent::FileRemoved(path) => app.on_file_removed(path),
+                    AppEvent::FileRenamed { from, to } => app.on_file_renamed(from, to),
+                    AppEvent::FileTruncated(path) => app.on_file_truncated(path),
+                    AppEvent::ParseError { path, error } => app.on_parse_error(path, error),
+                    AppEvent::WatcherStatus(status) => app.on_watcher_status(status),
+                    AppEvent::WatcherError(message) => app.on_watcher_error(message),
                     _ => {}
                 }
             }
         }
 
+        // Keep pending tool-call spinners animating.
+        app.on_tick();
+
         // Check quit
         if app.should_quit {
             return Ok(());
@@ -0,0 +1,150 @@
+//! Fuzzy filter over the sidebar's session list.
+//!
+//! Opened with `/` while the sidebar has focus. The query is typed in the
+//! bottom bar and narrows the sidebar as it changes; a session is shown if
+//! the query is a case-insensitive subsequence of its title or id. Enter
+//! keeps the filter, Esc clears it.
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// Result of handling a key event in the session filter input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionFilterAction {
+    /// The event was consumed without changing the query.
+    Consumed,
+    /// The query changed; the caller should fix up the selection.
+    Changed,
+    /// Enter was pressed: keep the filter, close the input.
+    Submit,
+    /// Esc was pressed: the filter was cleared and the input closed.
+    Close,
+}
+
+/// State of the sidebar session filter.
+#[derive(Debug, Clone, Default)]
+pub struct SessionFilterState {
+    /// Whether the query is being typed.
+    pub input_active: bool,
+    /// The filter query; empty when no filter applies.
+    pub query: String,
+}
+
+impl SessionFilterState {
+    /// Open the input, keeping the current query for editing.
+    pub fn start_input(&mut self) {
+        self.input_active = true;
+    }
+
+    /// Whether the query is being typed.
+    pub fn is_input(&self) -> bool {
+        self.input_active
+    }
+
+    /// Whether the filter hides any sessions.
+    pub fn is_active(&self) -> bool {
+        !self.query.is_empty()
+    }
+
+    /// Close the input and drop the filter.
+    pub fn clear(&mut self) {
+        self.input_active = false;
+        self.query.clear();
+    }
+
+    /// Handle a key event while the input is open.
+    pub fn on_key(&mut self, key: KeyEvent) -> SessionFilterAction {
+        match key.code {
+            KeyCode::Esc => {
+                self.clear();
+                SessionFilterAction::Close
+            }
+            KeyCode::Enter => {
+                self.input_active = false;
+                SessionFilterAction::Submit
+            }
+            KeyCode::Backspace => {
+                self.query.pop();
+                SessionFilterAction::Changed
+            }
+            KeyCode::Char(ch) => {
+                self.query.push(ch);
+                SessionFilterAction::Changed
+            }
+            _ => SessionFilterAction::Consumed,
+        }
+    }
+
+    /// Whether any of `candidates` (a session's title and id) matches.
+    pub fn matches<'a>(&self, mut candidates: impl Iterator<Item = &'a str>) -> bool {
+        !self.is_active() || candidates.any(|c| fuzzy_match(&self.query, c))
+    }
+}
+
+/// Whether `query` is a case-insensitive subsequence of `text`, ignoring
+/// whitespace in the query (`"flk tst"` matches `"Fix flaky test"`).
+pub fn fuzzy_match(query: &str, text: &str) -> bool {
+    let mut text = text.chars().flat_map(char::to_lowercase);
+    query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .all(|q| text.any(|t| t == q))
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn test_fuzzy_match() {
+        assert!(fuzzy_match("flk tst", "Fix flaky test"));
+        assert!(fuzzy_match("FIX", "fix the build"));
+        assert!(fuzzy_match("", "anything"));
+        assert!(!fuzzy_match("tset", "test"));
+        assert!(!fuzzy_match("abc", "ab"));
+    }
+
+    #[test]
+    fn test_input_keys() {
+        let mut filter = SessionFilterState::default();
+        filter.start_input();
+        assert_eq!(
+            filter.on_key(key(KeyCode::Char('a'))),
+            SessionFilterAction::Changed
+        );
+        assert_eq!(
+            filter.on_key(key(KeyCode::Char('b'))),
+            SessionFilterAction::Changed
+        );
+        assert_eq!(
+            filter.on_key(key(KeyCode::Backspace)),
+            SessionFilterAction::Changed
+        );
+        assert_eq!(
+            filter.on_key(key(KeyCode::Enter)),
+            SessionFilterAction::Submit
+        );
+        assert!(!filter.is_input());
+        assert_eq!(filter.query, "a");
+        assert!(filter.matches(["xyz", "abc"].into_iter()));
+        assert!(!filter.matches(["xyz"].into_iter()));
+
+        filter.start_input();
+        assert_eq!(filter.on_key(key(KeyCode::Esc)), SessionFilterAction::Close);
+        assert!(!filter.is_active());
+        assert!(filter.matches(["xyz"].into_iter()));
+    }
+}
@@ -5,21 +5,32 @@
 //! - **Log stream** (right, fills remaining width): log entries
 //! - **Status bar** (bottom, height 1): key hints and status info
 
-use std::time::SystemTime;
+use std::time::{Instant, SystemTime};
 
+use chrono::{DateTime, Utc};
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap};
 use ratatui::Frame;
 
-use crate::content_render::{has_renderable_content, render_content_blocks, RenderedLine};
+use crate::content_render::{
+    collapsed_text, collapsed_thinking, collect_tool_names, compact_boundary_label,
+    render_content_blocks_with_tools, tool_use_ids, RenderedLine, ToolNames, COMPACT_SUMMARY_LABEL,
+    REDACTED_THINKING_LABEL,
+};
+use crate::conversation_tree::TreeRow;
+use crate::history_search::format_match_time;
 use crate::log_entry::{EntryType, LogEntry};
-use crate::search::{find_matches, SearchMatch};
+use crate::search::{MatchMode, Matcher, SearchMatch};
 use crate::session::SessionStatus;
-use crate::session_stats::compute_session_stats;
+use crate::session_stats::{format_cost, format_token_count};
+use crate::symbols::Symbols;
+use crate::system_event::SystemEvent;
 use crate::theme::ThemeColors;
+use crate::tool_calls::{format_elapsed, ToolCallIndex, ToolCallStatus};
 use crate::tui::app::{App, Focus, ScrollMode};
+use crate::tui::diagnostics::build_diagnostics_lines;
 
 // ---------------------------------------------------------------------------
 // Main draw function
@@ -30,6 +41,7 @@ use crate::tui::app::{App, Focus, ScrollMode};
 /// Splits the terminal into:
 /// 1. A vertical split: main area (fills) + status bar (1 row)
 /// 2. Within main area, a horizontal split: sidebar (30 cols) + log stream (rest)
+/// 3. Within the log stream, one column per log pane (`v` splits)
 ///
 /// When the sidebar is hidden, the log stream takes the full width.
 pub fn draw(frame: &mut Frame, app: &mut App) {
@@ -55,15 +67,21 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
         let logstream_area = horizontal_chunks[1];
 
         draw_sidebar(frame, app, sidebar_area);
-        draw_logstream(frame, app, logstream_area);
+        draw_panes(frame, app, logstream_area);
     } else {
         // No sidebar; log stream takes all width
-        draw_logstream(frame, app, main_area);
+        draw_panes(frame, app, main_area);
     }
 
-    // Show search input bar when in search input mode, otherwise status bar.
-    if app.search_state.is_input() {
+    // Show the filter prompt or search input bar when typing, otherwise status bar.
+    if app.filter_prompt.visible {
+        draw_filter_prompt_bar(frame, app, status_area);
+    } else if app.global_search.is_input() {
+        draw_global_search_input_bar(frame, app, status_area);
+    } else if app.search_state.is_input() {
         draw_search_input_bar(frame, app, status_area);
+    } else if app.session_filter.is_input() {
+        draw_session_filter_input_bar(frame, app, status_area);
     } else {
         draw_status_bar(frame, app, status_area);
     }
@@ -73,6 +91,26 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
         draw_filter_menu(frame, app, size);
     }
 
+    // Draw global search results on top of the main layout when shown.
+    if app.global_search.is_results() {
+        draw_global_search_results(frame, app, size);
+    }
+
+    // Draw the detail pane on top of the main layout when visible.
+    if app.detail_view.visible {
+        draw_detail_view(frame, app, size);
+    }
+
+    // Draw the session info panel on top of the main layout when visible.
+    if app.session_info_visible {
+        draw_session_info(frame, app, size);
+    }
+
+    // Draw the diagnostics panel on top of the main layout when visible.
+    if app.diagnostics.visible {
+        draw_diagnostics(frame, app, size);
+    }
+
     // Draw help overlay on top of everything when visible.
     if app.help_overlay_visible {
         draw_help_overlay(frame, app, size);
@@ -85,12 +123,52 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
 
 /// Draw the sidebar panel with the session list as a tree layout.
 ///
-/// Each session renders as:
+/// The list starts with an `All sessions (N active)` row selecting the
+/// merged view of every active session. Each session renders as:
 ///   `● abc123  5m`     (header row: active marker + 6-char ID prefix + relative time)
+///   `  Fix flaky test` (title row: summary or first prompt, when known)
 ///   `  └ slug-name`    (one row per non-main agent, indented)
 ///
+/// With `--all-projects`, each project group starts with a header row
+/// showing the project path and its number of active sessions.
+///
 /// Navigation operates at session level; j/k skip over agent child rows.
 /// New sessions (in `app.new_session_ids`) are highlighted in bold yellow.
+/// Header row of a project group: `"~/code/app (2 active)"`. Long paths
+/// keep their end, which names the project: `"...code/app (2 active)"`.
+fn project_header_line(
+    label: &str,
+    active: usize,
+    max_width: usize,
+    theme: &ThemeColors,
+) -> Line<'static> {
+    let suffix = if active > 0 {
+        format!(" ({} active)", active)
+    } else {
+        String::new()
+    };
+    let available = max_width.saturating_sub(suffix.len());
+    let chars: Vec<char> = label.chars().collect();
+    let label = if chars.len() > available {
+        let keep = available.saturating_sub(3);
+        format!(
+            "...{}",
+            chars[chars.len() - keep..].iter().collect::<String>()
+        )
+    } else {
+        label.to_string()
+    };
+    Line::from(vec![
+        Span::styled(
+            label,
+            Style::default()
+                .fg(theme.sidebar_project_header)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(suffix, Style::default().fg(theme.sidebar_active_marker)),
+    ])
+}
+
 fn draw_sidebar(frame: &mut Frame, app: &mut App, area: Rect) {
     let focused = app.focus == Focus::Sidebar;
     let border_style = if focused {
@@ -99,13 +177,24 @@ fn draw_sidebar(frame: &mut Frame, app: &mut App, area: Rect) {
         Style::default().fg(app.theme_colors.border_unfocused)
     };
 
+    let title = if app.session_filter.is_active() {
+        format!(" Sessions /{} ", app.session_filter.query)
+    } else {
+        " Sessions ".to_string()
+    };
     let block = Block::default()
-        .title(" Sessions ")
+        .title(title)
         .borders(Borders::ALL)
         .border_style(border_style);
 
-    if app.sessions.is_empty() {
-        let placeholder = Paragraph::new("No sessions found")
+    let shown = app.sidebar_sessions();
+    if shown.is_empty() {
+        let text = if app.sessions.is_empty() {
+            "No sessions found"
+        } else {
+            "No matching sessions"
+        };
+        let placeholder = Paragraph::new(text)
             .style(Style::default().fg(app.theme_colors.sidebar_placeholder))
             .block(block);
         frame.render_widget(placeholder, area);
@@ -128,21 +217,57 @@ fn draw_sidebar(frame: &mut Frame, app: &mut App, area: Rect) {
     // Max width available inside the block (inner width).
     let max_width = inner.width as usize;
 
-    for (i, session) in app.sessions.iter().enumerate() {
-        let is_selected = i == app.selected_session_index;
+    // -- "All sessions" row (merged view of every active session) --
+    let active_count = app
+        .sessions
+        .iter()
+        .filter(|s| app.session_status(s) != SessionStatus::Inactive)
+        .count();
+    let all_style = if app.all_sessions_selected {
+        Style::default()
+            .fg(theme.sidebar_selected_fg)
+            .bg(theme.sidebar_selected_bg)
+            .add_modifier(Modifier::BOLD)
+    } else if app.is_all_sessions_shown() {
+        Style::default().fg(theme.sidebar_active_target)
+    } else {
+        Style::default().fg(theme.sidebar_default_session)
+    };
+    all_rows.push(ListItem::new(Line::from(vec![
+        Span::raw("  "),
+        Span::styled(format!("All sessions ({} active)", active_count), all_style),
+    ])));
+
+    for i in shown {
+        let session = &app.sessions[i];
+        let is_selected = i == app.selected_session_index && !app.all_sessions_selected;
         let is_new = app.new_session_ids.contains(&session.id);
-        let is_active_target = app.active_session_id.as_ref() == Some(&session.id);
+        let is_active_target = app.is_shown_in_pane(&session.id);
+
+        // -- Project group header row (--all-projects) --
+        if app.starts_project_group(i) {
+            all_rows.push(ListItem::new(project_header_line(
+                &app.project_label_at(i),
+                app.project_active_count(i),
+                max_width,
+                theme,
+            )));
+        }
 
         // -- Session header row --
 
         // Active marker: "● " (or "* " in ASCII mode) for active sessions, "  " for inactive.
-        let marker = match session.status() {
+        let marker = match app.session_status(session) {
             SessionStatus::Active => Span::styled(
                 format!("{} ", app.symbols.active_marker),
                 Style::default()
                     .fg(theme.sidebar_active_marker)
                     .add_modifier(Modifier::BOLD),
             ),
+            SessionStatus::Idle => Span::styled(
+                format!("{} ", app.symbols.idle_marker),
+                Style::default().fg(theme.sidebar_active_marker),
+            ),
             SessionStatus::Inactive => {
                 Span::styled("  ", Style::default().fg(theme.sidebar_inactive_marker))
             }
@@ -176,6 +301,28 @@ fn draw_sidebar(frame: &mut Frame, app: &mut App, area: Rect) {
         let header_line = Line::from(vec![marker, Span::styled(header_text, header_style)]);
         all_rows.push(ListItem::new(header_line));
 
+        // -- Title row (summary or first prompt) --
+        if let Some(title) = app.session_title(session) {
+            let available = max_width.saturating_sub(2);
+            let title: String = if title.chars().count() > available {
+                let kept: String = title.chars().take(available.saturating_sub(3)).collect();
+                format!("{}...", kept)
+            } else {
+                title.to_string()
+            };
+            let title_style = if is_selected {
+                Style::default()
+                    .fg(theme.sidebar_selected_child_fg)
+                    .bg(theme.sidebar_selected_child_bg)
+            } else {
+                Style::default().fg(theme.sidebar_unselected_child)
+            };
+            all_rows.push(ListItem::new(Line::from(vec![
+                Span::raw("  "),
+                Span::styled(title, title_style),
+            ])));
+        }
+
         // -- Agent child rows (non-main agents) --
         let sub_agents: Vec<_> = session.agents.iter().filter(|a| !a.is_main).collect();
         for agent in &sub_agents {
@@ -254,7 +401,57 @@ fn format_relative_time(time: SystemTime) -> String {
 // Log stream
 // ---------------------------------------------------------------------------
 
-/// Draw the log stream panel.
+/// Split the log area into side-by-side columns, one per log pane, and
+/// draw each with its own session, filter and scroll state.
+fn draw_panes(frame: &mut Frame, app: &mut App, area: Rect) {
+    let count = app.panes.len();
+    if count == 1 {
+        let focused = app.focus == Focus::LogStream;
+        let title = if app.is_all_sessions_view() {
+            " Log Stream: all sessions "
+        } else {
+            " Log Stream "
+        };
+        draw_logstream(frame, app, area, focused, title.to_string());
+        return;
+    }
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![Constraint::Ratio(1, count as u32); count])
+        .split(area);
+    for (index, column) in columns.iter().enumerate() {
+        let focused = app.focus == Focus::LogStream && index == app.focused_pane;
+        app.with_pane(index, |app| {
+            let title = format!(" {}: {} ", index + 1, pane_target_label(app));
+            draw_logstream(frame, app, *column, focused, title);
+        });
+    }
+}
+
+/// What a log pane shows: the session's short id, plus the subagent it is
+/// narrowed to, e.g. `"abc123 > swift-fox"`.
+fn pane_target_label(app: &App) -> String {
+    let session = match app.active_session_id.as_deref() {
+        Some(id) => id.chars().take(6).collect(),
+        None => "all sessions".to_string(),
+    };
+    match app.filter_state.selected_agent.as_deref() {
+        Some(agent_id) => {
+            let slug = app
+                .sessions
+                .iter()
+                .flat_map(|s| &s.agents)
+                .find(|a| a.agent_id.as_deref() == Some(agent_id))
+                .and_then(|a| a.slug.as_deref())
+                .unwrap_or(agent_id);
+            format!("{} > {}", session, slug)
+        }
+        None => session,
+    }
+}
+
+/// Draw the log stream panel of one pane, titled `title` (followed by
+/// the tree and scroll markers).
 ///
 /// Filters entries by `active_session_id` (if set), renders each entry
 /// as styled lines with timestamps, role indicators, optional agent
@@ -266,21 +463,26 @@ fn format_relative_time(time: SystemTime) -> String {
 /// - **Branch B**: `pending_scroll` is set -- build lines, create snapshot,
 ///   apply pending action, render from new snapshot.
 /// - **Branch C**: normal -- existing auto-scroll behavior.
-fn draw_logstream(frame: &mut Frame, app: &mut App, area: Rect) {
+fn draw_logstream(frame: &mut Frame, app: &mut App, area: Rect, focused: bool, title: String) {
     let theme = &app.theme_colors;
-    let focused = app.focus == Focus::LogStream;
     let border_style = if focused {
         Style::default().fg(theme.border_focused)
     } else {
         Style::default().fg(theme.border_unfocused)
     };
 
-    // Dynamic title: show scroll indicator when in scroll mode or pending scroll.
-    let title = if app.scroll_mode.is_some() || app.pending_scroll.is_some() {
-        " Log Stream [SCROLL mode - Esc:exit] "
-    } else {
-        " Log Stream "
-    };
+    // Dynamic title: show the tree view marker, and the scroll indicator
+    // when in scroll mode or pending scroll.
+    let title = format!(
+        "{}{}{}",
+        title,
+        if app.show_tree { "[tree] " } else { "" },
+        if app.scroll_mode.is_some() || app.pending_scroll.is_some() {
+            "[SCROLL mode - Esc:exit] "
+        } else {
+            ""
+        }
+    );
 
     let block = Block::default()
         .title(title)
@@ -310,38 +512,9 @@ fn draw_logstream(frame: &mut Frame, app: &mut App, area: Rect) {
 
     // -- Build lines from the ring buffer (used by both Branch B and C). --
     let filter_state = &app.filter_state;
+    let rows = app.visible_rows();
 
-    // Entry-type visibility predicate: User, Assistant, System are always
-    // visible; Progress, FileHistorySnapshot and other types are always hidden.
-    let is_type_visible = |e: &LogEntry| -> bool {
-        match e.entry_type {
-            EntryType::User => {
-                // Skip user entries that only contain tool_result blocks
-                // (they produce no visible output and would show as empty lines).
-                e.message
-                    .as_ref()
-                    .is_none_or(|msg| has_renderable_content(&msg.content))
-            }
-            EntryType::Assistant | EntryType::System => true,
-            _ => false,
-        }
-    };
-
-    let entries: Vec<&LogEntry> = if let Some(ref session_id) = app.active_session_id {
-        app.ring_buffer
-            .iter_filtered(|e| {
-                e.session_id.as_deref() == Some(session_id.as_str())
-                    && is_type_visible(e)
-                    && filter_state.matches(e)
-            })
-            .collect()
-    } else {
-        app.ring_buffer
-            .iter_filtered(|e| is_type_visible(e) && filter_state.matches(e))
-            .collect()
-    };
-
-    if entries.is_empty() {
+    if rows.is_empty() {
         // Clear pending scroll if there are no entries to snapshot.
         app.pending_scroll = None;
         let paragraph = Paragraph::new("Waiting for log entries...")
@@ -353,8 +526,26 @@ fn draw_logstream(frame: &mut Frame, app: &mut App, area: Rect) {
 
     // Build styled lines for all entries.
     let mut lines: Vec<Line<'static>> = Vec::new();
+    // tool_use id -> tool name, so tool_result lines can be summarized per tool.
+    let mut tool_names = ToolNames::new();
+    // Reference time for pending tool calls' spinner frame and elapsed time.
+    let now = Utc::now();
+
+    // In the all-sessions view each line gets a 7-column session tag, and
+    // in the tree view a branch gutter; both are inserted after the lines
+    // are built, but take up width all the same.
+    let all_sessions = app.is_all_sessions_view();
+    let tag_width = if all_sessions { 7 } else { 0 };
+    let lead_width = |row: &TreeRow<'_>| {
+        tag_width + Span::raw(branch_gutter(row.depth, false, &app.symbols)).width()
+    };
+
+    // Index of the first line of each entry, for the entry cursor.
+    let mut entry_starts: Vec<usize> = Vec::with_capacity(rows.len());
 
-    for entry in &entries {
+    for row in &rows {
+        let entry = row.entry;
+        entry_starts.push(lines.len());
         let ts = format_timestamp(&entry.timestamp);
         let ts_span = Span::styled(
             ts,
@@ -378,6 +569,25 @@ fn draw_logstream(frame: &mut Frame, app: &mut App, area: Rect) {
             continue;
         }
 
+        // Compaction boundaries are drawn as a divider across the stream.
+        if entry.is_compact_boundary() {
+            let label = compact_boundary_label(entry.compact_metadata.as_ref());
+            let used = ts_span.width() + lead_width(row) + 1 + 3 + label.chars().count() + 2;
+            let fill = (inner_width as usize).saturating_sub(used).max(3);
+            let rule = |n: usize| app.symbols.divider.repeat(n);
+            lines.push(Line::from(vec![
+                ts_span,
+                Span::raw(" "),
+                Span::styled(
+                    format!("{} {} {}", rule(3), label, rule(fill)),
+                    Style::default()
+                        .fg(theme.logstream_divider)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            ]));
+            continue;
+        }
+
         // Determine the entry-level role from the message.
         let entry_role = entry
             .message
@@ -393,12 +603,65 @@ fn draw_logstream(frame: &mut Frame, app: &mut App, area: Rect) {
             )
         });
 
+        // Classified system entries (API errors, hooks, interruptions) get
+        // their own symbol and color.
+        if let Some(event) = SystemEvent::classify_notice(entry) {
+            let (symbol, color) = system_event_style(&event, &app.symbols, theme);
+            for (i, text) in event.label().split('\n').enumerate() {
+                let mut spans = vec![
+                    ts_span.clone(),
+                    Span::raw(" "),
+                    Span::styled(symbol.to_string(), Style::default().fg(color)),
+                ];
+                if i == 0 {
+                    if let Some(ref ps) = prefix_span {
+                        spans.push(ps.clone());
+                    }
+                }
+                spans.push(Span::raw(" "));
+                spans.push(Span::styled(text.to_string(), Style::default().fg(color)));
+                lines.push(Line::from(spans));
+            }
+            continue;
+        }
+
         // Render content blocks from the message.
         let rendered = entry
             .message
             .as_ref()
-            .map(|m| render_content_blocks(&m.content))
+            .map(|m| {
+                collect_tool_names(&m.content, &mut tool_names);
+                render_content_blocks_with_tools(&m.content, &tool_names)
+            })
             .unwrap_or_default();
+        // Compaction summaries are long; collapse them to one line unless
+        // expanded with `t`.
+        let summary_collapsed =
+            entry.is_compact_summary == Some(true) && !app.is_summary_expanded(entry);
+        let rendered = if summary_collapsed {
+            let text: Vec<&str> = rendered
+                .iter()
+                .filter_map(|l| match l {
+                    RenderedLine::Text(t) => Some(t.as_str()),
+                    _ => None,
+                })
+                .collect();
+            vec![RenderedLine::Text(collapsed_text(
+                COMPACT_SUMMARY_LABEL,
+                &text.join("\n"),
+            ))]
+        } else {
+            rendered
+        };
+        // One ID per ToolUse line, in the same order.
+        let mut call_ids = entry
+            .message
+            .as_ref()
+            .map(|m| tool_use_ids(&m.content))
+            .unwrap_or_default()
+            .into_iter();
+
+        let thinking_expanded = app.is_thinking_expanded(entry);
 
         if rendered.is_empty() {
             // Even with no content, show the timestamp + role indicator line.
@@ -417,59 +680,161 @@ fn draw_logstream(frame: &mut Frame, app: &mut App, area: Rect) {
             // so we know when to attach the agent prefix.
             let mut first_visible = true;
             for rendered_line in rendered.iter() {
+                let call_id = match rendered_line {
+                    RenderedLine::ToolUse(_) => call_ids.next().flatten(),
+                    _ => None,
+                };
+
                 // Skip tool call lines when tool call hiding is active.
-                if !filter_state.is_tool_line_visible()
-                    && matches!(rendered_line, RenderedLine::ToolUse(_))
-                {
+                if !filter_state.is_tool_line_visible() && rendered_line.is_tool_line() {
                     continue;
                 }
 
-                let (indicator, color, text) = match rendered_line {
+                // Thinking blocks expand to one line per text line.
+                let collapsed;
+                let (indicator, color, texts): (char, Color, Vec<&str>) = match rendered_line {
                     RenderedLine::Text(t) => {
                         let (ind, col) = role_indicator(entry_role, theme);
-                        (ind, col, t.as_str())
+                        (ind, col, vec![t.as_str()])
+                    }
+                    RenderedLine::ToolUse(t) => ('~', theme.role_tool_use, vec![t.as_str()]),
+                    RenderedLine::ToolResult { summary, is_error } => {
+                        let col = if *is_error {
+                            theme.role_tool_error
+                        } else {
+                            theme.role_tool_result
+                        };
+                        ('=', col, vec![summary.as_str()])
+                    }
+                    RenderedLine::Thinking(t) if thinking_expanded => {
+                        ('*', theme.role_thinking, t.split('\n').collect())
+                    }
+                    RenderedLine::Thinking(t) => {
+                        collapsed = collapsed_thinking(t);
+                        ('*', theme.role_thinking, vec![collapsed.as_str()])
+                    }
+                    RenderedLine::RedactedThinking => {
+                        ('*', theme.role_thinking, vec![REDACTED_THINKING_LABEL])
+                    }
+                    RenderedLine::Unknown(t) => ('?', theme.role_unknown, vec![t.as_str()]),
+                };
+                let text_style = match rendered_line {
+                    RenderedLine::Thinking(_) if thinking_expanded => {
+                        Style::default().fg(color).add_modifier(Modifier::ITALIC)
+                    }
+                    RenderedLine::Thinking(_) | RenderedLine::RedactedThinking => {
+                        Style::default().fg(color).add_modifier(Modifier::DIM)
+                    }
+                    _ if summary_collapsed => {
+                        Style::default().fg(color).add_modifier(Modifier::DIM)
                     }
-                    RenderedLine::ToolUse(t) => ('~', theme.role_tool_use, t.as_str()),
-                    RenderedLine::Unknown(t) => ('?', theme.role_unknown, t.as_str()),
+                    _ => Style::default().fg(color),
                 };
 
-                let mut spans = vec![
-                    ts_span.clone(),
-                    Span::raw(" "),
-                    Span::styled(String::from(indicator), Style::default().fg(color)),
-                ];
+                for text in texts {
+                    let mut spans = vec![
+                        ts_span.clone(),
+                        Span::raw(" "),
+                        Span::styled(String::from(indicator), Style::default().fg(color)),
+                    ];
+
+                    // Only show agent prefix on the first visible line of each entry.
+                    if first_visible {
+                        if let Some(ref ps) = prefix_span {
+                            spans.push(ps.clone());
+                        }
+                        first_visible = false;
+                    }
 
-                // Only show agent prefix on the first visible line of each entry.
-                if first_visible {
-                    if let Some(ref ps) = prefix_span {
-                        spans.push(ps.clone());
+                    spans.push(Span::raw(" "));
+                    spans.push(Span::styled(text.to_string(), text_style));
+
+                    if let Some(status) = call_id.and_then(|id| {
+                        tool_call_status_span(&app.tool_calls, id, now, &app.symbols, theme)
+                    }) {
+                        spans.push(Span::raw(" "));
+                        spans.push(status);
+                    }
+
+                    // Task calls name the subagent `>` jumps into.
+                    if let Some(agent_id) = call_id
+                        .and_then(|id| app.task_links.get(id))
+                        .and_then(|t| t.agent_id.as_deref())
+                    {
+                        spans.push(Span::styled(
+                            format!(" {} agent {}", app.symbols.arrow, agent_id),
+                            Style::default()
+                                .fg(theme.logstream_timestamp)
+                                .add_modifier(Modifier::DIM),
+                        ));
                     }
-                    first_visible = false;
+
+                    lines.push(Line::from(spans));
                 }
+            }
+        }
+    }
 
-                spans.push(Span::raw(" "));
-                spans.push(Span::styled(text.to_string(), Style::default().fg(color)));
+    // -- Tree view: indent branches after the timestamp, dim abandoned ones. --
+    for (i, row) in rows.iter().enumerate() {
+        if row.depth == 0 && !row.abandoned {
+            continue;
+        }
+        let end = entry_starts.get(i + 1).copied().unwrap_or(lines.len());
+        for (n, line) in lines[entry_starts[i]..end].iter_mut().enumerate() {
+            if row.abandoned {
+                for span in line.spans.iter_mut() {
+                    span.style = span.style.add_modifier(Modifier::DIM);
+                }
+            }
+            if row.depth > 0 && line.spans.len() >= 2 {
+                let gutter = branch_gutter(row.depth, row.branch_start && n == 0, &app.symbols);
+                line.spans.insert(
+                    2,
+                    Span::styled(gutter, Style::default().fg(theme.logstream_divider)),
+                );
+            }
+        }
+    }
 
-                lines.push(Line::from(spans));
+    // -- All-sessions view: tag each line with its entry's session. --
+    if all_sessions {
+        for (i, row) in rows.iter().enumerate() {
+            let end = entry_starts.get(i + 1).copied().unwrap_or(lines.len());
+            let tag = session_tag_span(row.entry, theme);
+            for line in &mut lines[entry_starts[i]..end] {
+                line.spans.insert(1.min(line.spans.len()), tag.clone());
             }
         }
     }
 
+    // -- Entry cursor: clamp to the visible entries and find its lines. --
+    let cursor_bg = theme.logstream_cursor_bg;
+    let cursor_range = app.log_cursor.map(|c| {
+        let c = c.min(entry_starts.len() - 1);
+        let end = entry_starts.get(c + 1).copied().unwrap_or(lines.len());
+        (c, entry_starts[c], end)
+    });
+    if let Some((c, _, _)) = cursor_range {
+        app.log_cursor = Some(c);
+    }
+
     // -- Search highlights: compute matches and apply highlights to lines. --
     if app.search_state.is_active() && !app.search_state.query.is_empty() {
-        let query = &app.search_state.query;
         let mut all_matches: Vec<SearchMatch> = Vec::new();
 
-        // Compute matches for each line.
-        for (line_idx, line) in lines.iter().enumerate() {
-            let line_text = line_to_text(line);
-            let matches = find_matches(&line_text, query);
-            for (byte_start, byte_len) in matches {
-                all_matches.push(SearchMatch {
-                    line_index: line_idx,
-                    byte_start,
-                    byte_len,
-                });
+        // Compute matches for each line. The query was validated on confirm,
+        // so a compile failure here just leaves no matches.
+        if let Ok(matcher) = app.search_state.matcher() {
+            for (line_idx, line) in lines.iter().enumerate() {
+                let line_text = line_to_text(line);
+                for (byte_start, byte_len) in matcher.find_all(&line_text) {
+                    all_matches.push(SearchMatch {
+                        line_index: line_idx,
+                        byte_start,
+                        byte_len,
+                    });
+                }
             }
         }
 
@@ -514,6 +879,13 @@ fn draw_logstream(frame: &mut Frame, app: &mut App, area: Rect) {
         app.search_state.current_match_index = None;
     }
 
+    // Highlight the selected entry (after search highlights, which rebuild lines).
+    if let Some((_, start, end)) = cursor_range {
+        for line in &mut lines[start..end] {
+            line.style = line.style.bg(cursor_bg);
+        }
+    }
+
     // -- Branch B: pending_scroll -- create snapshot and apply pending action.
     if let Some(pending_action) = app.pending_scroll.take() {
         let total_lines = lines.len();
@@ -561,6 +933,11 @@ fn draw_logstream(frame: &mut Frame, app: &mut App, area: Rect) {
             app.scroll_to_current_search_match();
         }
 
+        // Keep the entry cursor on screen.
+        if let Some((_, start, _)) = cursor_range {
+            app.ensure_line_visible(start);
+        }
+
         // Convert scroll.offset (visual lines from bottom) to ratatui scroll (visual lines from top).
         let scroll_ref = app.scroll_mode.as_ref().unwrap();
         let max_visual = scroll_ref
@@ -623,6 +1000,40 @@ fn role_indicator(role: &str, theme: &ThemeColors) -> (char, Color) {
     }
 }
 
+/// Tree view indentation for an entry `depth` forks deep, e.g. `"│ ├ "`.
+///
+/// Every level draws a branch line, except that the first entry of a
+/// branch ends in a branch-start marker.
+fn branch_gutter(depth: usize, branch_start: bool, symbols: &Symbols) -> String {
+    (1..=depth)
+        .map(|level| {
+            let symbol = if branch_start && level == depth {
+                symbols.branch_start
+            } else {
+                symbols.branch_line
+            };
+            format!("{} ", symbol)
+        })
+        .collect()
+}
+
+/// Symbol and color of a classified system entry.
+fn system_event_style(
+    event: &SystemEvent,
+    symbols: &Symbols,
+    theme: &ThemeColors,
+) -> (&'static str, Color) {
+    match event {
+        SystemEvent::ApiError { .. } => (symbols.api_error, theme.system_error),
+        SystemEvent::Hook { is_error: true, .. } => (symbols.hook, theme.system_error),
+        SystemEvent::Hook { .. } => (symbols.hook, theme.system_hook),
+        SystemEvent::Interrupted { .. } => (symbols.interrupted, theme.system_interrupted),
+        SystemEvent::CompactBoundary => (symbols.divider, theme.logstream_divider),
+        SystemEvent::Info { is_error: true, .. } => (symbols.system_info, theme.system_error),
+        SystemEvent::Info { .. } => (symbols.system_info, theme.system_info),
+    }
+}
+
 /// Return an agent prefix string for subagent entries.
 ///
 /// For subagent entries (`is_sidechain == Some(true)`), returns the last
@@ -658,15 +1069,64 @@ fn agent_color(entry: &LogEntry, theme: &ThemeColors) -> Color {
     let key = entry.slug.as_deref().or(entry.agent_id.as_deref());
 
     match key {
-        Some(k) => {
-            // Simple hash: sum of bytes mod palette size.
-            let hash: usize = k.bytes().map(|b| b as usize).sum();
-            theme.agent_palette[hash % theme.agent_palette.len()]
-        }
+        Some(k) => palette_color(k, &theme.agent_palette),
         None => theme.agent_main,
     }
 }
 
+/// The color of `palette` assigned to `key`.
+///
+/// Simple hash: sum of bytes mod palette size, so a key keeps its color
+/// across runs.
+fn palette_color(key: &str, palette: &[Color]) -> Color {
+    let hash: usize = key.bytes().map(|b| b as usize).sum();
+    palette[hash % palette.len()]
+}
+
+/// Session tag of `entry` in the all-sessions view: `" abc123"` (the
+/// first 6 characters of the session id), colored deterministically from
+/// the session palette.
+fn session_tag_span(entry: &LogEntry, theme: &ThemeColors) -> Span<'static> {
+    let id = entry.session_id.as_deref().unwrap_or("?");
+    let color = palette_color(id, &theme.session_palette);
+    Span::styled(
+        format!(" {}", id.chars().take(6).collect::<String>()),
+        Style::default().fg(color).add_modifier(Modifier::BOLD),
+    )
+}
+
+/// Build the status suffix for a `tool_use` line.
+///
+/// Pending calls show a spinner frame (advancing with wall-clock time) and
+/// the time elapsed so far; finished calls show a check or cross mark and
+/// the time between the `tool_use` and `tool_result` entries; abandoned
+/// calls show the interrupted mark. Returns
+/// `None` for calls that are not tracked.
+fn tool_call_status_span(
+    tool_calls: &ToolCallIndex,
+    id: &str,
+    now: DateTime<Utc>,
+    symbols: &Symbols,
+    theme: &ThemeColors,
+) -> Option<Span<'static>> {
+    let call = tool_calls.get(id)?;
+    let (mark, color) = match call.status {
+        ToolCallStatus::Pending => {
+            let frames = symbols.spinner_frames;
+            let frame = (now.timestamp_millis() / 100).rem_euclid(frames.len() as i64);
+            (frames[frame as usize], theme.role_tool_use)
+        }
+        ToolCallStatus::Succeeded => (symbols.tool_ok, theme.tool_call_ok),
+        ToolCallStatus::Failed => (symbols.tool_failed, theme.role_tool_error),
+        ToolCallStatus::Abandoned => (symbols.interrupted, theme.system_interrupted),
+    };
+    let text = match call.elapsed_ms(now) {
+        Some(ms) => format!("{} {}", mark, format_elapsed(ms)),
+        None => mark.to_string(),
+    };
+    Some(Span::styled(text, Style::default().fg(color)))
+}
+
 /// Extract a human-readable description from a progress entry's `data` field.
 ///
 /// Priority:
@@ -852,9 +1312,61 @@ fn highlight_line(
 /// Draw the search input bar at the bottom of the screen.
 ///
 /// Replaces the status bar when search input mode is active.
-/// Shows `/ ` prompt followed by the current input buffer and a cursor.
+/// Shows `/ ` prompt followed by the current input buffer and a cursor,
+/// then the match mode and key hints (or an invalid-regex error) right-aligned.
+/// Draw the sidebar session filter input in the status bar area.
+fn draw_session_filter_input_bar(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme_colors;
+    let query = &app.session_filter.query;
+    let mut spans: Vec<Span> = vec![
+        Span::styled(
+            "/",
+            Style::default()
+                .fg(theme.search_prompt)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(query.clone(), Style::default().fg(theme.search_input_fg)),
+        Span::styled(
+            app.symbols.search_cursor.to_string(),
+            Style::default()
+                .fg(theme.search_input_fg)
+                .add_modifier(Modifier::SLOW_BLINK),
+        ),
+    ];
+
+    let hint = format!(
+        " [{}/{} sessions] Enter:keep  Esc:clear",
+        app.sidebar_sessions().len(),
+        app.sessions.len()
+    );
+    let content_len = 1 + query.chars().count() + 1; // "/" + query + cursor
+    let remaining = (area.width as usize).saturating_sub(content_len + hint.chars().count());
+    if remaining > 0 {
+        spans.push(Span::raw(" ".repeat(remaining)));
+        spans.push(Span::styled(
+            hint,
+            Style::default()
+                .fg(theme.status_bar_fg)
+                .add_modifier(Modifier::DIM),
+        ));
+    }
+
+    let paragraph = Paragraph::new(Line::from(spans)).style(
+        Style::default()
+            .bg(theme.status_bar_bg)
+            .fg(theme.status_bar_fg),
+    );
+    frame.render_widget(paragraph, area);
+}
+
 fn draw_search_input_bar(frame: &mut Frame, app: &App, area: Rect) {
     let theme = &app.theme_colors;
+    let search = &app.search_state;
+    let input_color = if search.error.is_some() {
+        theme.filter_invalid
+    } else {
+        theme.search_input_fg
+    };
 
     let mut spans: Vec<Span> = vec![
         Span::styled(
@@ -864,8 +1376,8 @@ fn draw_search_input_bar(frame: &mut Frame, app: &App, area: Rect) {
                 .add_modifier(Modifier::BOLD),
         ),
         Span::styled(
-            app.search_state.input_buffer.clone(),
-            Style::default().fg(theme.search_input_fg),
+            search.input_buffer.clone(),
+            Style::default().fg(input_color),
         ),
         // Cursor indicator.
         Span::styled(
@@ -876,18 +1388,148 @@ fn draw_search_input_bar(frame: &mut Frame, app: &App, area: Rect) {
         ),
     ];
 
-    // Right-aligned hint.
-    let hint = " Enter:search  Esc:cancel";
-    let content_len = 1 + app.search_state.input_buffer.len() + 1; // "/" + input + cursor
-    let remaining = (area.width as usize).saturating_sub(content_len + hint.len());
+    // Right-aligned hint, replaced by the error while the regex is invalid.
+    let (hint, hint_style) = match search.error {
+        Some(ref err) => (
+            format!(" [{}] {}", search.match_mode.label(), err),
+            Style::default().fg(theme.filter_invalid),
+        ),
+        None => (
+            format!(
+                " [{}] Tab:mode  Enter:search  Esc:cancel",
+                search.match_mode.label()
+            ),
+            Style::default()
+                .fg(theme.status_bar_fg)
+                .add_modifier(Modifier::DIM),
+        ),
+    };
+    let content_len = 1 + search.input_buffer.chars().count() + 1; // "/" + input + cursor
+    let remaining = (area.width as usize).saturating_sub(content_len + hint.chars().count());
     if remaining > 0 {
         spans.push(Span::raw(" ".repeat(remaining)));
-        spans.push(Span::styled(
-            hint.to_string(),
+        spans.push(Span::styled(hint, hint_style));
+    }
+
+    let line = Line::from(spans);
+    let paragraph = Paragraph::new(line).style(
+        Style::default()
+            .bg(theme.status_bar_bg)
+            .fg(theme.status_bar_fg),
+    );
+
+    frame.render_widget(paragraph, area);
+}
+
+/// Draw the filter expression prompt at the bottom of the screen.
+///
+/// Replaces the status bar while the prompt is open. Shows `filter: `
+/// followed by the input and a cursor, then either the last parse error or
+/// a key hint right-aligned.
+fn draw_filter_prompt_bar(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme_colors;
+    let prompt = &app.filter_prompt;
+
+    let mut spans: Vec<Span> = vec![
+        Span::styled(
+            "filter: ",
+            Style::default()
+                .fg(theme.search_prompt)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            prompt.input_buffer.clone(),
+            Style::default().fg(theme.search_input_fg),
+        ),
+        Span::styled(
+            app.symbols.search_cursor.to_string(),
+            Style::default()
+                .fg(theme.search_input_fg)
+                .add_modifier(Modifier::SLOW_BLINK),
+        ),
+    ];
+
+    let (hint, hint_style) = match prompt.error {
+        Some(ref err) => (
+            format!(" {}", err),
+            Style::default().fg(theme.role_tool_error),
+        ),
+        None => (
+            " Enter:apply  Esc:cancel".to_string(),
             Style::default()
                 .fg(theme.status_bar_fg)
                 .add_modifier(Modifier::DIM),
-        ));
+        ),
+    };
+    let content_len = "filter: ".len() + prompt.input_buffer.chars().count() + 1;
+    let remaining = (area.width as usize).saturating_sub(content_len + hint.chars().count());
+    if remaining > 0 {
+        spans.push(Span::raw(" ".repeat(remaining)));
+        spans.push(Span::styled(hint, hint_style));
+    }
+
+    let line = Line::from(spans);
+    let paragraph = Paragraph::new(line).style(
+        Style::default()
+            .bg(theme.status_bar_bg)
+            .fg(theme.status_bar_fg),
+    );
+
+    frame.render_widget(paragraph, area);
+}
+
+/// Draw the global search input bar at the bottom of the screen.
+///
+/// Same layout as [`draw_search_input_bar`] with an `all sessions: ` prompt.
+fn draw_global_search_input_bar(frame: &mut Frame, app: &App, area: Rect) {
+    const PROMPT: &str = "all sessions: ";
+    let theme = &app.theme_colors;
+    let search = &app.global_search;
+    let input_color = if search.error.is_some() {
+        theme.filter_invalid
+    } else {
+        theme.search_input_fg
+    };
+
+    let mut spans: Vec<Span> = vec![
+        Span::styled(
+            PROMPT,
+            Style::default()
+                .fg(theme.search_prompt)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            search.input_buffer.clone(),
+            Style::default().fg(input_color),
+        ),
+        Span::styled(
+            app.symbols.search_cursor.to_string(),
+            Style::default()
+                .fg(theme.search_input_fg)
+                .add_modifier(Modifier::SLOW_BLINK),
+        ),
+    ];
+
+    let (hint, hint_style) = match search.error {
+        Some(ref err) => (
+            format!(" [{}] {}", search.match_mode.label(), err),
+            Style::default().fg(theme.filter_invalid),
+        ),
+        None => (
+            format!(
+                " [{}] Tab:mode  Enter:search  Esc:cancel",
+                search.match_mode.label()
+            ),
+            Style::default()
+                .fg(theme.status_bar_fg)
+                .add_modifier(Modifier::DIM),
+        ),
+    };
+    let content_len = PROMPT.len() + search.input_buffer.chars().count() + 1;
+    let remaining = (area.width as usize).saturating_sub(content_len + hint.chars().count());
+    if remaining > 0 {
+        spans.push(Span::raw(" ".repeat(remaining)));
+        spans.push(Span::styled(hint, hint_style));
     }
 
     let line = Line::from(spans);
@@ -900,6 +1542,149 @@ fn draw_search_input_bar(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(paragraph, area);
 }
 
+// ---------------------------------------------------------------------------
+// Global search results
+// ---------------------------------------------------------------------------
+
+/// Build the lines of the global search results overlay.
+///
+/// Returns the lines and the line index of the selected match. Each
+/// session gets a header row followed by one row per match:
+///   `3f2a9c1e  (2 matches)`
+///   `  2025-01-15 10:30:00  [a0d0bbc] ...snippet...`
+fn build_global_search_lines(app: &App) -> (Vec<Line<'static>>, usize) {
+    let theme = &app.theme_colors;
+    let search = &app.global_search;
+    let matcher = Matcher::new(&search.query, search.match_mode).ok();
+
+    let mut lines: Vec<Line<'static>> = Vec::new();
+    let mut selected_line = 0;
+    let mut match_index = 0;
+
+    for group in &search.results {
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
+        }
+        let noun = if group.total == 1 { "match" } else { "matches" };
+        let mut header = format!("{}  ({} {}", group.session.id, group.total, noun);
+        if group.total > group.matches.len() {
+            header.push_str(&format!(", showing {}", group.matches.len()));
+        }
+        header.push(')');
+        lines.push(Line::from(Span::styled(
+            header,
+            Style::default()
+                .fg(theme.detail_section)
+                .add_modifier(Modifier::BOLD),
+        )));
+
+        for m in &group.matches {
+            let is_selected = match_index == search.selected;
+            if is_selected {
+                selected_line = lines.len();
+            }
+
+            let mut spans = vec![
+                Span::raw("  "),
+                Span::styled(
+                    format_match_time(m.timestamp.as_deref()),
+                    Style::default().fg(theme.logstream_timestamp),
+                ),
+                Span::raw("  "),
+            ];
+            if let Some(ref agent_id) = m.agent_id {
+                spans.push(Span::styled(
+                    format!("[{}] ", agent_id),
+                    Style::default().fg(theme.agent_main),
+                ));
+            }
+            let ranges = matcher
+                .as_ref()
+                .map(|mt| mt.find_all(&m.snippet))
+                .unwrap_or_default();
+            let mut pos = 0;
+            for (start, len) in ranges {
+                let end = start + len;
+                if start > pos {
+                    spans.push(Span::raw(m.snippet[pos..start].to_string()));
+                }
+                spans.push(Span::styled(
+                    m.snippet[start..end].to_string(),
+                    Style::default()
+                        .fg(theme.search_match_fg)
+                        .bg(theme.search_match_bg),
+                ));
+                pos = end;
+            }
+            if pos < m.snippet.len() {
+                spans.push(Span::raw(m.snippet[pos..].to_string()));
+            }
+
+            let mut line = Line::from(spans);
+            if is_selected {
+                line = line.style(Style::default().bg(theme.logstream_cursor_bg));
+            }
+            lines.push(line);
+            match_index += 1;
+        }
+    }
+
+    if lines.is_empty() {
+        lines.push(Line::from(Span::styled(
+            format!("No matches for \"{}\"", search.query),
+            Style::default().fg(theme.logstream_placeholder),
+        )));
+    }
+
+    (lines, selected_line)
+}
+
+/// Draw the global search results overlay.
+///
+/// Fills most of the screen like the detail pane. Lines are not wrapped
+/// so the selected match can be kept on screen by line index.
+fn draw_global_search_results(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme_colors;
+    let overlay_area = Rect::new(
+        area.x + 1.min(area.width),
+        area.y + 1.min(area.height),
+        area.width.saturating_sub(2),
+        area.height.saturating_sub(2),
+    );
+
+    frame.render_widget(Clear, overlay_area);
+
+    let title = format!(
+        " Search all sessions: {} ({} in {} sessions) ",
+        app.global_search.query,
+        app.global_search.match_count(),
+        app.global_search.results.len()
+    );
+    let block = Block::default()
+        .title(title)
+        .title_bottom(Line::from(Span::styled(
+            " j/k:select  Enter:jump  /:edit query  Esc:close ",
+            Style::default().fg(theme.status_shortcut_key),
+        )))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.border_focused));
+
+    let inner = block.inner(overlay_area);
+    let (lines, selected_line) = build_global_search_lines(app);
+    let height = inner.height as usize;
+    let scroll = if height == 0 {
+        0
+    } else {
+        (selected_line + 1).saturating_sub(height)
+    };
+
+    let paragraph = Paragraph::new(lines)
+        .style(Style::default().fg(theme.logstream_text))
+        .block(block)
+        .scroll((scroll as u16, 0));
+    frame.render_widget(paragraph, overlay_area);
+}
+
 // ---------------------------------------------------------------------------
 // Filter overlay
 // ---------------------------------------------------------------------------
@@ -1002,9 +1787,198 @@ fn draw_filter_menu(frame: &mut Frame, app: &App, area: Rect) {
                 .bg(theme.filter_overlay_bg)
                 .fg(theme.filter_overlay_fg),
         )
-        .wrap(Wrap { trim: false });
-
-    frame.render_widget(paragraph, inner);
+        .wrap(Wrap { trim: false });
+
+    frame.render_widget(paragraph, inner);
+}
+
+// ---------------------------------------------------------------------------
+// Detail pane
+// ---------------------------------------------------------------------------
+
+/// Render the detail pane for the entry under the cursor.
+///
+/// Fills most of the screen, leaving a one-cell margin. The scroll offset
+/// is clamped here, once the wrapped content height is known.
+fn draw_detail_view(frame: &mut Frame, app: &mut App, area: Rect) {
+    let theme = &app.theme_colors;
+    let overlay_area = Rect::new(
+        area.x + 1.min(area.width),
+        area.y + 1.min(area.height),
+        area.width.saturating_sub(2),
+        area.height.saturating_sub(2),
+    );
+
+    frame.render_widget(Clear, overlay_area);
+
+    let block = Block::default()
+        .title(app.detail_view.title.clone())
+        .title_bottom(Line::from(Span::styled(
+            " j/k:scroll  PgUp/PgDn:page  g/G:top/bottom  Esc:close ",
+            Style::default().fg(theme.status_shortcut_key),
+        )))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.border_focused));
+
+    let inner = block.inner(overlay_area);
+    let max_scroll = crate::tui::app::total_visual_lines(&app.detail_view.lines, inner.width)
+        .saturating_sub(inner.height as usize);
+    app.detail_view.scroll = app.detail_view.scroll.min(max_scroll);
+
+    let paragraph = Paragraph::new(app.detail_view.lines.clone())
+        .style(Style::default().fg(app.theme_colors.logstream_text))
+        .block(block)
+        .wrap(Wrap { trim: false })
+        .scroll((app.detail_view.scroll as u16, 0));
+    frame.render_widget(paragraph, overlay_area);
+}
+
+// ---------------------------------------------------------------------------
+// Session info panel
+// ---------------------------------------------------------------------------
+
+/// Build the lines of the session info panel: current working directory,
+/// git branch and Claude Code version, followed by every mid-session change.
+fn build_session_info_lines(app: &App) -> Vec<Line<'static>> {
+    let theme = &app.theme_colors;
+    let info = &app.session_info;
+    let section_style = Style::default()
+        .fg(theme.filter_overlay_fg)
+        .add_modifier(Modifier::BOLD);
+    let label_style = Style::default()
+        .fg(theme.filter_shortcut_key)
+        .add_modifier(Modifier::BOLD);
+    let text_style = Style::default().fg(theme.filter_overlay_fg);
+    let dim_style = text_style.add_modifier(Modifier::DIM);
+
+    let value = |v: Option<&str>| match v {
+        None => "unknown".to_string(),
+        Some("") => "(none)".to_string(),
+        Some(s) => s.to_string(),
+    };
+
+    let mut lines = vec![Line::from(Span::styled(" Current", section_style))];
+    for (label, v) in [
+        ("Directory", &info.cwd),
+        ("Branch", &info.git_branch),
+        ("Version", &info.version),
+    ] {
+        lines.push(Line::from(vec![
+            Span::styled(format!("   {:<10}", label), label_style),
+            Span::styled(value(v.as_deref()), text_style),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(" Changes", section_style)));
+    if info.changes.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "   none during this session",
+            dim_style,
+        )));
+    }
+    for change in &info.changes {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("   {}  ", format_timestamp(&change.timestamp)),
+                dim_style,
+            ),
+            Span::styled(format!("{:<8}", change.field.label()), label_style),
+            Span::styled(
+                format!(
+                    "{} {} {}",
+                    value(Some(&change.from)),
+                    app.symbols.arrow,
+                    value(Some(&change.to))
+                ),
+                text_style,
+            ),
+        ]));
+    }
+    lines
+}
+
+/// Draw the session info panel as a centered overlay.
+fn draw_session_info(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme_colors;
+    let lines = build_session_info_lines(app);
+
+    let overlay_width = 80u16.min(area.width.saturating_sub(2));
+    let overlay_height = (lines.len() as u16 + 2).min(area.height);
+    let x = area.x + (area.width.saturating_sub(overlay_width)) / 2;
+    let y = area.y + (area.height.saturating_sub(overlay_height)) / 2;
+    let overlay_area = Rect::new(x, y, overlay_width, overlay_height);
+
+    frame.render_widget(Clear, overlay_area);
+
+    let block = Block::default()
+        .title(" Session Info ")
+        .title_bottom(Line::from(Span::styled(
+            " i/Esc:close ",
+            Style::default().fg(theme.status_shortcut_key),
+        )))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.border_focused));
+
+    let paragraph = Paragraph::new(lines)
+        .style(
+            Style::default()
+                .bg(theme.filter_overlay_bg)
+                .fg(theme.filter_overlay_fg),
+        )
+        .block(block)
+        .wrap(Wrap { trim: false });
+    frame.render_widget(paragraph, overlay_area);
+}
+
+// ---------------------------------------------------------------------------
+// Diagnostics panel
+// ---------------------------------------------------------------------------
+
+/// Draw the watcher diagnostics panel as a centered overlay. The content
+/// is rebuilt on every draw, so it follows the watcher's status reports.
+fn draw_diagnostics(frame: &mut Frame, app: &mut App, area: Rect) {
+    let theme = &app.theme_colors;
+    let lines = build_diagnostics_lines(
+        &app.diagnostics,
+        app.config.watcher.backend,
+        app.project_path.as_deref(),
+        Instant::now(),
+        theme,
+    );
+
+    let overlay_width = 100u16.min(area.width.saturating_sub(2));
+    let overlay_height = (lines.len() as u16 + 2).min(area.height.saturating_sub(2));
+    let x = area.x + (area.width.saturating_sub(overlay_width)) / 2;
+    let y = area.y + (area.height.saturating_sub(overlay_height)) / 2;
+    let overlay_area = Rect::new(x, y, overlay_width, overlay_height);
+
+    frame.render_widget(Clear, overlay_area);
+
+    let block = Block::default()
+        .title(" Diagnostics ")
+        .title_bottom(Line::from(Span::styled(
+            " j/k:scroll  g/G:top/bottom  D/Esc:close ",
+            Style::default().fg(theme.status_shortcut_key),
+        )))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.border_focused));
+
+    let inner = block.inner(overlay_area);
+    let max_scroll = crate::tui::app::total_visual_lines(&lines, inner.width)
+        .saturating_sub(inner.height as usize);
+    app.diagnostics.scroll = app.diagnostics.scroll.min(max_scroll);
+
+    let paragraph = Paragraph::new(lines)
+        .style(
+            Style::default()
+                .bg(theme.filter_overlay_bg)
+                .fg(theme.filter_overlay_fg),
+        )
+        .block(block)
+        .wrap(Wrap { trim: false })
+        .scroll((app.diagnostics.scroll as u16, 0));
+    frame.render_widget(paragraph, overlay_area);
 }
 
 // ---------------------------------------------------------------------------
@@ -1023,8 +1997,7 @@ fn draw_help_overlay(frame: &mut Frame, app: &App, area: Rect) {
         return;
     }
 
-    // Compute session stats from the ring buffer.
-    let stats = compute_session_stats(&app.ring_buffer);
+    let stats = app.session_stats.stats();
 
     // ----- Section 1: Symbol & Color Legend ---------------------------------
     // (symbol, color, description)
@@ -1032,6 +2005,36 @@ fn draw_help_overlay(frame: &mut Frame, app: &App, area: Rect) {
         (">", theme.role_user, "User message"),
         ("<", theme.role_assistant, "Assistant message"),
         ("~", theme.role_tool_use, "Tool call"),
+        ("=", theme.role_tool_result, "Tool result"),
+        ("=", theme.role_tool_error, "Failed tool result"),
+        (
+            app.symbols.spinner_frames[0],
+            theme.role_tool_use,
+            "Tool call running (elapsed)",
+        ),
+        (
+            app.symbols.tool_ok,
+            theme.tool_call_ok,
+            "Tool call finished",
+        ),
+        (
+            app.symbols.tool_failed,
+            theme.role_tool_error,
+            "Tool call failed",
+        ),
+        ("*", theme.role_thinking, "Thinking (T / t to expand)"),
+        (
+            app.symbols.api_error,
+            theme.system_error,
+            "API error (with retry attempt)",
+        ),
+        (app.symbols.hook, theme.system_hook, "Hook output"),
+        (
+            app.symbols.interrupted,
+            theme.system_interrupted,
+            "Request interrupted",
+        ),
+        (app.symbols.system_info, theme.system_info, "System notice"),
         ("?", theme.role_unknown, "Unknown role"),
         (
             app.symbols.progress_indicator,
@@ -1048,30 +2051,52 @@ fn draw_help_overlay(frame: &mut Frame, app: &App, area: Rect) {
         ("Ctrl+C", "Quit (force)"),
         ("Tab", "Toggle focus between panels"),
         ("b", "Toggle sidebar"),
+        ("v / x", "Split off a new log pane / close the focused pane"),
+        ("w / W", "Focus next / previous log pane"),
         ("Enter", "Confirm session selection"),
         ("f", "Open filter menu"),
+        ("F", "Filter expression (e.g. tool:Bash)"),
         ("/", "Search (type query, Enter to confirm)"),
+        ("/ (sidebar)", "Fuzzy filter sessions by title or id"),
+        ("Tab", "Cycle search mode: plain/smart/regex"),
         ("n / N", "Next / previous search match"),
+        ("S", "Search all sessions in the project"),
         ("L", "Load full session history"),
+        ("T", "Expand / collapse all thinking"),
+        ("t", "Expand / collapse selected entry (thinking, summary)"),
+        ("B", "Toggle conversation tree (abandoned branches)"),
+        ("> / <", "Open subagent of selected Task call / go back"),
+        ("i", "Session info: cwd, branch, version"),
+        ("D", "Watcher diagnostics: errors, offsets"),
         ("j / Down", "Navigate / scroll down"),
         ("k / Up", "Navigate / scroll up"),
         ("u / d", "Half-page up / down"),
         ("PgUp/PgDn", "Page up / down"),
         ("g / Home", "Scroll to top"),
         ("G / End", "Scroll to bottom (exit scroll)"),
+        ("J / K", "Select next / previous entry"),
+        ("Enter", "Open detail for selected entry"),
     ];
 
     // ----- Compute overlay dimensions ---------------------------------------
     // Target: ~70 wide, ~45 tall. Degrade gracefully on small terminals.
     let overlay_width = 70u16.min(area.width.saturating_sub(2));
     // Estimate content height:
-    //   title(1) + blank(1) + legend_header(1) + legend rows(5) + note(1) + note(1)
-    //   + blank(1) + keybind_header(1) + keybind rows(17)
+    //   title(1) + blank(1) + legend_header(1) + legend rows(10) + note(1) + note(1)
+    //   + blank(1) + keybind_header(1) + keybind rows(19)
     //   + blank(1) + stats_header(1) + stats rows(~8)
     //   + blank(1) + footer(1) + borders(2)
     // Roughly: 6 + 2 + 18 + 2 + 10 + 4 = ~42
-    let stats_lines_count =
-        4 + stats.tool_call_breakdown.len().min(5) + if stats.subagent_count > 0 { 1 } else { 0 };
+    let usage_lines_count = if stats.usage.total_tokens() > 0 {
+        2 + stats.usage_by_model.len().min(3)
+    } else {
+        0
+    };
+    let stats_lines_count = 4
+        + stats.tool_call_breakdown.len().min(5)
+        + if stats.subagent_count > 0 { 1 } else { 0 }
+        + if stats.compaction_count > 0 { 1 } else { 0 }
+        + usage_lines_count;
     let content_height = 3   // title + blank + legend header
         + legend.len()       // legend rows
         + 2                  // agent prefix note + timestamp note
@@ -1079,6 +2104,7 @@ fn draw_help_overlay(frame: &mut Frame, app: &App, area: Rect) {
         + keybindings.len()  // keybind rows
         + 2                  // blank + stats header
         + stats_lines_count  // stats rows
+        + usize::from(app.log_path.is_some()) // log file
         + 2; // blank + footer
     let overlay_height = (content_height as u16 + 2).min(area.height); // +2 for borders
 
@@ -1199,12 +2225,88 @@ fn draw_help_overlay(frame: &mut Frame, app: &App, area: Rect) {
         ]));
     }
 
+    // Context compactions
+    if stats.compaction_count > 0 {
+        lines.push(Line::from(vec![
+            Span::styled("   Compacted:  ", label_style),
+            Span::styled(
+                format!(
+                    "{} {} ({} tokens)",
+                    stats.compaction_count,
+                    if stats.compaction_count == 1 {
+                        "time"
+                    } else {
+                        "times"
+                    },
+                    format_token_count(stats.compacted_tokens)
+                ),
+                text_style,
+            ),
+        ]));
+    }
+
+    // Token usage and estimated cost
+    if stats.usage.total_tokens() > 0 {
+        lines.push(Line::from(vec![
+            Span::styled("   Tokens:     ", label_style),
+            Span::styled(
+                format!(
+                    "{} in, {} out, {} cache read, {} cache write",
+                    format_token_count(stats.usage.input_tokens),
+                    format_token_count(stats.usage.output_tokens),
+                    format_token_count(stats.usage.cache_read_input_tokens),
+                    format_token_count(stats.usage.cache_creation_input_tokens),
+                ),
+                text_style,
+            ),
+        ]));
+
+        let cost_text = match stats.estimated_cost(&app.config.pricing) {
+            Some(cost) => format!("~{} (estimated)", format_cost(cost)),
+            None => "-- (no pricing for these models)".to_string(),
+        };
+        lines.push(Line::from(vec![
+            Span::styled("   Cost:       ", label_style),
+            Span::styled(cost_text, text_style),
+        ]));
+
+        // Per-model breakdown (top 3)
+        for (model, usage) in stats.usage_by_model.iter().take(3) {
+            let model_cost = app
+                .config
+                .pricing
+                .estimate(model, usage)
+                .map(|c| format!(" ~{}", format_cost(c)))
+                .unwrap_or_default();
+            lines.push(Line::from(vec![
+                Span::styled("     ", text_style),
+                Span::styled(
+                    format!(
+                        "{}: {} tok{}",
+                        model,
+                        format_token_count(usage.total_tokens()),
+                        model_cost
+                    ),
+                    dim_style,
+                ),
+            ]));
+        }
+    }
+
     // Entries loaded
     lines.push(Line::from(vec![
         Span::styled("   Entries:    ", label_style),
         Span::styled(format!("{} loaded", stats.entries_loaded), text_style),
     ]));
 
+    // Where cc-tail's own log goes, for bug reports.
+    if let Some(ref path) = app.log_path {
+        lines.push(Line::from(vec![
+            Span::styled("   Log file:   ", label_style),
+            Span::styled(path.display().to_string(), text_style),
+        ]));
+    }
+
     // Footer
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
@@ -1264,22 +2366,49 @@ fn session_info_text(app: &App) -> String {
 /// Build the inactive badge text if the active session is inactive.
 ///
 /// Returns `Some(" INACTIVE ")` if the active session exists and is inactive,
+/// `Some(" IDLE ")` if its process is alive but quiet (process detection),
 /// `None` otherwise (no active session or session is active).
 fn inactive_badge_text(app: &App) -> Option<String> {
     let session_id = app.active_session_id.as_ref()?;
     let session = app.sessions.iter().find(|s| &s.id == session_id)?;
-    match session.status() {
+    match app.session_status(session) {
         SessionStatus::Inactive => Some(" INACTIVE ".to_string()),
+        SessionStatus::Idle => Some(" IDLE ".to_string()),
         SessionStatus::Active => None,
     }
 }
 
+/// Build the token usage segment text for the active session.
+///
+/// Returns e.g. `"45.6k tok ~$1.23"`, or `"45.6k tok"` when no model has
+/// a known price. Falls back to all loaded entries when no session is
+/// active. Returns `None` when no usage has been recorded.
+fn usage_text(app: &App) -> Option<String> {
+    let stats = app.session_stats.stats();
+    let (usage, cost) = match app.active_session_id {
+        Some(ref id) => {
+            let usage = *stats.session_usage(id)?;
+            (usage, stats.session_cost(id, &app.config.pricing))
+        }
+        None => (stats.usage, stats.estimated_cost(&app.config.pricing)),
+    };
+    if usage.total_tokens() == 0 {
+        return None;
+    }
+    let tokens = format_token_count(usage.total_tokens());
+    Some(match cost {
+        Some(c) => format!("{} tok ~{}", tokens, format_cost(c)),
+        None => format!("{} tok", tokens),
+    })
+}
+
 /// Compute the status bar layout and return the composed `Line`.
 ///
 /// Priority layout algorithm:
 /// 1. **Inactive badge** -- always visible (highest priority)
 /// 2. **Active filters** -- shown if space permits, truncated if needed
-/// 3. **Session info** -- shown if space permits
+/// 3. **Session info** -- shown if space permits, followed by the project
+///    name and token usage / estimated cost
 /// 4. **Keyboard shortcuts** -- hidden first when space is tight (lowest priority)
 ///
 /// Each segment is separated by ` | `. The algorithm greedily allocates
@@ -1326,6 +2455,38 @@ fn build_status_bar_line(app: &App, width: usize) -> Line<'static> {
         }
     }
 
+    // -- Priority 1.05: Unseen watcher errors --
+    let unseen = app.diagnostics.unseen_errors();
+    if unseen > 0 {
+        let error_badge = format!(
+            " {} watcher error{} (D) ",
+            unseen,
+            if unseen == 1 { "" } else { "s" }
+        );
+        let eb_width = error_badge.len();
+        let sep_cost = if used > 0 { SEPARATOR_WIDTH } else { 1 };
+        if used + sep_cost + eb_width <= width {
+            if used > 0 {
+                spans.push(Span::styled(
+                    SEPARATOR.to_string(),
+                    Style::default().fg(theme.status_separator),
+                ));
+                used += SEPARATOR_WIDTH;
+            } else {
+                spans.push(Span::raw(" ".to_string()));
+                used += 1;
+            }
+            spans.push(Span::styled(
+                error_badge,
+                Style::default()
+                    .fg(theme.status_error_fg)
+                    .bg(theme.status_error_bg)
+                    .add_modifier(Modifier::BOLD),
+            ));
+            used += eb_width;
+        }
+    }
+
     // -- Priority 1.1: Full history loaded badge --
     if app.full_history_loaded {
         let full_badge = " FULL ";
@@ -1384,7 +2545,15 @@ fn build_status_bar_line(app: &App, width: usize) -> Line<'static> {
 
     // -- Priority 1.5: Search match counter --
     if let Some(ref counter_text) = app.search_state.match_counter_display() {
-        let search_display = format!(" /{} {} ", app.search_state.query, counter_text);
+        let search_display = match app.search_state.match_mode {
+            MatchMode::Plain => format!(" /{} {} ", app.search_state.query, counter_text),
+            mode => format!(
+                " /{} ({}) {} ",
+                app.search_state.query,
+                mode.label(),
+                counter_text
+            ),
+        };
         let sw = search_display.len();
         let sep_cost = if used > 0 { SEPARATOR_WIDTH } else { 1 };
 
@@ -1481,6 +2650,29 @@ fn build_status_bar_line(app: &App, width: usize) -> Line<'static> {
         }
     }
 
+    // -- Priority 3.7: Token usage and estimated cost --
+    if let Some(usage) = usage_text(app) {
+        let uw = usage.len();
+        let sep_cost = if used > 0 { SEPARATOR_WIDTH } else { 1 };
+        if used + sep_cost + uw <= width {
+            if used > 0 {
+                spans.push(Span::styled(
+                    SEPARATOR.to_string(),
+                    Style::default().fg(theme.status_separator),
+                ));
+                used += SEPARATOR_WIDTH;
+            } else {
+                spans.push(Span::raw(" ".to_string()));
+                used += 1;
+            }
+            spans.push(Span::styled(
+                usage,
+                Style::default().fg(theme.status_bar_fg),
+            ));
+            used += uw;
+        }
+    }
+
     // -- Priority 4: Keyboard shortcuts (lowest priority, hidden first) --
     let shortcuts = shortcuts_text();
     let sc_width = shortcuts.len();
@@ -1582,8 +2774,10 @@ mod tests {
 
     /// Helper: create a test App with the light theme.
     fn test_app_light() -> App {
-        let mut config = AppConfig::default();
-        config.theme = Theme::Light;
+        let config = AppConfig {
+            theme: Theme::Light,
+            ..Default::default()
+        };
         App::new(config)
     }
 
@@ -1593,6 +2787,15 @@ mod tests {
         Terminal::new(backend).unwrap()
     }
 
+    /// Helper: draw `app` on a fresh terminal and return the screen
+    /// contents as one string, row after row.
+    fn render_screen(app: &mut App, width: u16, height: u16) -> String {
+        let mut terminal = test_terminal(width, height);
+        terminal.draw(|frame| draw(frame, app)).unwrap();
+        let buffer = terminal.backend().buffer();
+        buffer.content().iter().map(|c| c.symbol()).collect()
+    }
+
     // -- Smoke tests: draw does not panic ------------------------------------
 
     #[test]
@@ -2069,79 +3272,389 @@ mod tests {
     }
 
     #[test]
-    fn test_agent_color_different_agents_can_differ() {
-        // This is a probabilistic test -- with 8 colors, different strings
-        // are likely to produce different colors. We just check it doesn't panic.
+    fn test_agent_color_different_agents_can_differ() {
+        // This is a probabilistic test -- with 8 colors, different strings
+        // are likely to produce different colors. We just check it doesn't panic.
+        let theme = ThemeColors::dark();
+        let entry_a = LogEntry {
+            slug: Some("alpha-agent".to_string()),
+            ..LogEntry::default()
+        };
+        let entry_b = LogEntry {
+            slug: Some("beta-agent".to_string()),
+            ..LogEntry::default()
+        };
+        // Both should return valid colors (not panic).
+        let _color_a = agent_color(&entry_a, &theme);
+        let _color_b = agent_color(&entry_b, &theme);
+    }
+
+    #[test]
+    fn test_agent_color_light_theme_main_agent() {
+        let theme = ThemeColors::light();
+        let entry = LogEntry::default();
+        assert_eq!(agent_color(&entry, &theme), theme.agent_main);
+        assert_eq!(agent_color(&entry, &theme), Color::Black);
+    }
+
+    #[test]
+    fn test_agent_color_light_theme_subagent() {
+        let theme = ThemeColors::light();
+        let entry = LogEntry {
+            slug: Some("test-agent".to_string()),
+            ..LogEntry::default()
+        };
+        let color = agent_color(&entry, &theme);
+        // Should be one of the light theme palette colors
+        assert!(theme.agent_palette.contains(&color));
+    }
+
+    // -- draw_logstream smoke tests with entries ------------------------------
+
+    #[test]
+    fn test_draw_logstream_with_entries_no_panic() {
+        use crate::log_entry::parse_jsonl_line;
+
+        let mut app = test_app();
+
+        // Push some entries into the ring buffer.
+        let user_json = r#"{
+            "type": "user",
+            "sessionId": "sess-001",
+            "timestamp": "2025-01-15T10:30:00Z",
+            "message": {"role": "user", "content": [{"type": "text", "text": "Hello!"}]}
+        }"#;
+        let assistant_json = r#"{
+            "type": "assistant",
+            "sessionId": "sess-001",
+            "timestamp": "2025-01-15T10:30:05Z",
+            "message": {
+                "role": "assistant",
+                "content": [
+                    {"type": "text", "text": "Here is the response."},
+                    {"type": "tool_use", "id": "t1", "name": "Read", "input": {"file_path": "src/main.rs"}}
+                ]
+            }
+        }"#;
+
+        app.ring_buffer.push(parse_jsonl_line(user_json).unwrap());
+        app.ring_buffer
+            .push(parse_jsonl_line(assistant_json).unwrap());
+
+        let mut terminal = test_terminal(80, 24);
+        terminal
+            .draw(|frame| draw(frame, &mut app))
+            .expect("draw should not fail with log entries");
+    }
+
+    #[test]
+    fn test_draw_logstream_tool_result_summary_and_filter() {
+        use crate::log_entry::parse_jsonl_line;
+
+        let mut app = test_app();
+        app.ring_buffer.push(
+            parse_jsonl_line(
+                r#"{"type": "assistant", "message": {"role": "assistant", "content": [{"type": "tool_use", "id": "t1", "name": "Read", "input": {"file_path": "src/main.rs"}}]}}"#,
+            )
+            .unwrap(),
+        );
+        app.ring_buffer.push(
+            parse_jsonl_line(
+                r#"{"type": "user", "message": {"role": "user", "content": [{"type": "tool_result", "tool_use_id": "t1", "content": "     1→fn main() {\n     2→}"}]}}"#,
+            )
+            .unwrap(),
+        );
+
+        assert!(render_screen(&mut app, 80, 24).contains("[Read] 2 lines"));
+
+        // Hiding tool calls hides the result line as well.
+        app.filter_state.hide_tool_calls = true;
+        assert!(!render_screen(&mut app, 80, 24).contains("[Read] 2 lines"));
+    }
+
+    #[test]
+    fn test_draw_logstream_thinking_collapsed_and_expanded() {
+        use crate::log_entry::parse_jsonl_line;
+
+        let mut app = test_app();
+        app.ring_buffer.push(
+            parse_jsonl_line(
+                r#"{"type": "assistant", "uuid": "u1", "message": {"role": "assistant", "content": [{"type": "thinking", "thinking": "Plan the fix.\nCheck the tests."}, {"type": "redacted_thinking", "data": "x"}]}}"#,
+            )
+            .unwrap(),
+        );
+
+        let collapsed = render_screen(&mut app, 80, 24);
+        assert!(collapsed.contains("[thinking] Plan the fix."));
+        assert!(!collapsed.contains("Check the tests."));
+        assert!(collapsed.contains(REDACTED_THINKING_LABEL));
+
+        app.show_thinking = true;
+        let expanded = render_screen(&mut app, 80, 24);
+        assert!(expanded.contains("Plan the fix."));
+        assert!(expanded.contains("Check the tests."));
+        assert!(!expanded.contains("[thinking]"));
+    }
+
+    #[test]
+    fn test_draw_logstream_compaction_divider_and_summary() {
+        use crate::log_entry::parse_jsonl_line;
+
+        let mut app = test_app();
+        for json in [
+            r#"{"type": "system", "subtype": "compact_boundary", "uuid": "b1", "compactMetadata": {"trigger": "auto", "preTokens": 155432}}"#,
+            r#"{"type": "user", "uuid": "s1", "isCompactSummary": true, "message": {"role": "user", "content": "This session is being continued.\nAnalysis: fixed the parser"}}"#,
+        ] {
+            app.ring_buffer.push(parse_jsonl_line(json).unwrap());
+        }
+
+        let collapsed = render_screen(&mut app, 100, 24);
+        assert!(collapsed
+            .contains("\u{2500}\u{2500}\u{2500} Context compacted (auto, 155.4k tokens) \u{2500}"));
+        assert!(collapsed.contains("[compact summary] This session is being continued."));
+        assert!(!collapsed.contains("Analysis: fixed the parser"));
+
+        app.expanded_summaries.insert("s1".to_string());
+        let expanded = render_screen(&mut app, 100, 24);
+        assert!(expanded.contains("Analysis: fixed the parser"));
+        assert!(!expanded.contains("[compact summary]"));
+    }
+
+    #[test]
+    fn test_draw_logstream_system_events() {
+        use crate::log_entry::parse_jsonl_line;
+
+        let mut app = test_app();
+        for json in [
+            r#"{"type": "system", "subtype": "api_error", "error": {"status": 529, "error": {"type": "overloaded_error", "message": "Overloaded"}}, "retryInMs": 8000, "retryAttempt": 5, "maxRetries": 10}"#,
+            r#"{"type": "system", "content": "PostToolUse:Edit [cargo fmt] completed successfully"}"#,
+            r#"{"type": "user", "message": {"role": "user", "content": "[Request interrupted by user]"}}"#,
+        ] {
+            app.ring_buffer.push(parse_jsonl_line(json).unwrap());
+        }
+
+        let screen = render_screen(&mut app, 160, 24);
+
+        assert!(screen
+            .contains("\u{26a0} API error 529 overloaded_error: Overloaded (retry 5/10 in 8.0s)"));
+        assert!(screen.contains("\u{21aa} PostToolUse:Edit [cargo fmt] completed successfully"));
+        assert!(screen.contains("\u{25a0} [Request interrupted by user]"));
+    }
+
+    #[test]
+    fn test_draw_logstream_tree_view() {
+        use crate::log_entry::parse_jsonl_line;
+
+        let mut app = test_app();
+        for (uuid, parent, text) in [
+            ("a", "null", "first prompt"),
+            ("b1", r#""a""#, "old reply"),
+            ("b2", r#""a""#, "new reply"),
+        ] {
+            app.ring_buffer.push(
+                parse_jsonl_line(&format!(
+                    r#"{{"type": "assistant", "uuid": "{}", "parentUuid": {}, "message": {{"role": "assistant", "content": "{}"}}}}"#,
+                    uuid, parent, text
+                ))
+                .unwrap(),
+            );
+        }
+
+        let branch = render_screen(&mut app, 100, 24);
+        assert!(branch.contains("new reply"));
+        assert!(!branch.contains("old reply"));
+
+        app.show_tree = true;
+        let tree = render_screen(&mut app, 100, 24);
+        assert!(tree.contains("Log Stream [tree]"));
+        assert!(tree.contains("\u{251c} < old reply"));
+        assert!(tree.contains("new reply"));
+    }
+
+    #[test]
+    fn test_draw_sidebar_project_groups() {
+        use crate::session::{Agent, Session};
+        use std::path::PathBuf;
+
+        let mut app = App::new(AppConfig {
+            all_projects: true,
+            ..Default::default()
+        });
+        let session = |id: &str, project: &str| Session {
+            id: id.to_string(),
+            agents: vec![Agent {
+                agent_id: None,
+                slug: None,
+                log_path: PathBuf::from(format!("/projects/{}/{}.jsonl", project, id)),
+                is_main: true,
+            }],
+            last_modified: SystemTime::now(),
+        };
+        app.sessions = vec![
+            session("aaaaaa", "-srv-api"),
+            session("bbbbbb", "-srv-api"),
+            session("cccccc", "-srv-web"),
+        ];
+
+        let mut terminal = test_terminal(100, 24);
+        terminal.draw(|frame| draw(frame, &mut app)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let rows: Vec<String> = (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect()
+            })
+            .collect();
+        let row_of = |needle: &str| rows.iter().position(|r| r.contains(needle)).unwrap();
+
+        assert!(rows[row_of("/srv/api")].contains("(2 active)"));
+        assert!(rows[row_of("/srv/web")].contains("(1 active)"));
+        assert!(row_of("/srv/api") < row_of("aaaaaa"));
+        assert!(row_of("bbbbbb") < row_of("/srv/web"));
+        assert!(row_of("/srv/web") < row_of("cccccc"));
+    }
+
+    #[test]
+    fn test_draw_sidebar_titles_and_filter() {
+        use crate::session::{Agent, Session};
+        use std::path::PathBuf;
+
+        let mut app = test_app();
+        for id in ["aaaaaa", "bbbbbb"] {
+            app.sessions.push(Session {
+                id: id.to_string(),
+                agents: vec![Agent {
+                    agent_id: None,
+                    slug: None,
+                    log_path: PathBuf::from(format!("/fake/{}.jsonl", id)),
+                    is_main: true,
+                }],
+                last_modified: SystemTime::now(),
+            });
+        }
+        app.session_titles
+            .insert(PathBuf::from("/fake/aaaaaa.jsonl"), "Fix the flaky test");
+
+        let all = render_screen(&mut app, 100, 24);
+        assert!(all.contains("Fix the flaky test"));
+        assert!(all.contains("bbbbbb"));
+
+        app.session_filter.query = "zzz".to_string();
+        app.session_filter.start_input();
+        let none = render_screen(&mut app, 100, 24);
+        assert!(none.contains("Sessions /zzz"));
+        assert!(none.contains("No matching sessions"));
+        assert!(none.contains("[0/2 sessions]"));
+    }
+
+    #[test]
+    fn test_project_header_line_keeps_path_end() {
         let theme = ThemeColors::dark();
-        let entry_a = LogEntry {
-            slug: Some("alpha-agent".to_string()),
-            ..LogEntry::default()
-        };
-        let entry_b = LogEntry {
-            slug: Some("beta-agent".to_string()),
-            ..LogEntry::default()
-        };
-        // Both should return valid colors (not panic).
-        let _color_a = agent_color(&entry_a, &theme);
-        let _color_b = agent_color(&entry_b, &theme);
+        let line = project_header_line("/home/user/code/project", 1, 20, &theme);
+        let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(text, "...roject (1 active)");
+        assert_eq!(text.chars().count(), 20);
     }
 
     #[test]
-    fn test_agent_color_light_theme_main_agent() {
-        let theme = ThemeColors::light();
-        let entry = LogEntry::default();
-        assert_eq!(agent_color(&entry, &theme), theme.agent_main);
-        assert_eq!(agent_color(&entry, &theme), Color::Black);
-    }
+    fn test_draw_logstream_tool_call_status() {
+        use crate::log_entry::parse_jsonl_line;
 
-    #[test]
-    fn test_agent_color_light_theme_subagent() {
-        let theme = ThemeColors::light();
-        let entry = LogEntry {
-            slug: Some("test-agent".to_string()),
-            ..LogEntry::default()
+        let mut app = test_app();
+        let entries = [
+            r#"{"type": "assistant", "timestamp": "2025-01-15T10:00:00Z", "message": {"role": "assistant", "content": [{"type": "tool_use", "id": "t1", "name": "Read", "input": {"file_path": "a.rs"}}]}}"#,
+            r#"{"type": "user", "timestamp": "2025-01-15T10:00:01.200Z", "message": {"role": "user", "content": [{"type": "tool_result", "tool_use_id": "t1", "content": "x"}]}}"#,
+            r#"{"type": "assistant", "timestamp": "2025-01-15T10:00:02Z", "message": {"role": "assistant", "content": [{"type": "tool_use", "id": "t2", "name": "Bash", "input": {"command": "false"}}]}}"#,
+            r#"{"type": "user", "timestamp": "2025-01-15T10:00:14Z", "message": {"role": "user", "content": [{"type": "tool_result", "tool_use_id": "t2", "content": "boom", "is_error": true}]}}"#,
+            r#"{"type": "assistant", "timestamp": "2025-01-15T10:00:15Z", "message": {"role": "assistant", "content": [{"type": "tool_use", "id": "t3", "name": "Bash", "input": {"command": "cargo build"}}]}}"#,
+        ];
+        for json in entries {
+            app.on_new_log_entry(parse_jsonl_line(json).unwrap());
+        }
+
+        let mut terminal = test_terminal(100, 24);
+        terminal.draw(|frame| draw(frame, &mut app)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let rows: Vec<String> = (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect()
+            })
+            .collect();
+        let row_with = |needle: &str| -> String {
+            rows.iter()
+                .find(|r| r.contains(needle))
+                .cloned()
+                .unwrap_or_default()
         };
-        let color = agent_color(&entry, &theme);
-        // Should be one of the light theme palette colors
-        assert!(theme.agent_palette.contains(&color));
-    }
 
-    // -- draw_logstream smoke tests with entries ------------------------------
+        assert!(row_with("a.rs").contains("\u{2713} 1.2s"));
+        assert!(row_with("false").contains("\u{2717} 12s"));
+        // Still running: a spinner frame instead of a result mark.
+        let pending = row_with("cargo build");
+        assert!(
+            app.symbols
+                .spinner_frames
+                .iter()
+                .any(|f| pending.contains(f)),
+            "pending row should show a spinner: {}",
+            pending
+        );
+        assert!(!pending.contains("\u{2713}"));
+    }
 
     #[test]
-    fn test_draw_logstream_with_entries_no_panic() {
+    fn test_draw_logstream_highlights_cursor_entry() {
         use crate::log_entry::parse_jsonl_line;
 
         let mut app = test_app();
+        app.sidebar_visible = false;
+        for text in ["first", "second", "third"] {
+            app.ring_buffer.push(
+                parse_jsonl_line(&format!(
+                    r#"{{"type": "user", "message": {{"role": "user", "content": "{}"}}}}"#,
+                    text
+                ))
+                .unwrap(),
+            );
+        }
+        app.log_cursor = Some(usize::MAX);
+
+        let mut terminal = test_terminal(60, 10);
+        terminal.draw(|frame| draw(frame, &mut app)).unwrap();
+
+        // The out-of-range cursor is clamped to the newest entry.
+        assert_eq!(app.log_cursor, Some(2));
+        let buffer = terminal.backend().buffer();
+        let row_bg = |needle: &str| {
+            (0..buffer.area.height).find_map(|y| {
+                let row: String = (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect();
+                let x = row.find(needle)?;
+                // Byte offset == column here: the rows are ASCII up to the needle.
+                Some(buffer[(x as u16, y)].bg)
+            })
+        };
+        assert_eq!(row_bg("third"), Some(app.theme_colors.logstream_cursor_bg));
+        assert_ne!(row_bg("second"), Some(app.theme_colors.logstream_cursor_bg));
+    }
 
-        // Push some entries into the ring buffer.
-        let user_json = r#"{
-            "type": "user",
-            "sessionId": "sess-001",
-            "timestamp": "2025-01-15T10:30:00Z",
-            "message": {"role": "user", "content": [{"type": "text", "text": "Hello!"}]}
-        }"#;
-        let assistant_json = r#"{
-            "type": "assistant",
-            "sessionId": "sess-001",
-            "timestamp": "2025-01-15T10:30:05Z",
-            "message": {
-                "role": "assistant",
-                "content": [
-                    {"type": "text", "text": "Here is the response."},
-                    {"type": "tool_use", "id": "t1", "name": "Read", "input": {"file_path": "src/main.rs"}}
-                ]
-            }
-        }"#;
+    #[test]
+    fn test_draw_detail_view_clamps_scroll() {
+        let mut app = test_app();
+        let lines: Vec<Line<'static>> = (0..5).map(|i| Line::from(format!("line {}", i))).collect();
+        app.detail_view.open(" Detail ".to_string(), lines);
+        app.detail_view.scroll = usize::MAX;
 
-        app.ring_buffer.push(parse_jsonl_line(user_json).unwrap());
-        app.ring_buffer
-            .push(parse_jsonl_line(assistant_json).unwrap());
+        let screen = render_screen(&mut app, 40, 10);
 
-        let mut terminal = test_terminal(80, 24);
-        terminal
-            .draw(|frame| draw(frame, &mut app))
-            .expect("draw should not fail with log entries");
+        // 5 lines fit in the pane, so there is nothing to scroll.
+        assert_eq!(app.detail_view.scroll, 0);
+        assert!(screen.contains("Detail"));
+        assert!(screen.contains("line 4"));
     }
 
     #[test]
@@ -2173,6 +3686,154 @@ mod tests {
             .expect("draw should not fail with session filter");
     }
 
+    #[test]
+    fn test_draw_watcher_error_badge_and_diagnostics() {
+        let mut app = test_app();
+        app.on_watcher_error("filesystem watcher error: gone".to_string());
+        app.on_parse_error(
+            std::path::PathBuf::from("/proj/s1.jsonl"),
+            "EOF".to_string(),
+        );
+
+        let screen = render_screen(&mut app, 120, 30);
+        assert!(screen.contains("2 watcher errors (D)"));
+
+        app.diagnostics.open();
+        let screen = render_screen(&mut app, 120, 30);
+        assert!(!screen.contains("watcher errors (D)"));
+        assert!(screen.contains("Diagnostics"));
+        assert!(screen.contains("filesystem watcher error: gone"));
+        assert!(screen.contains("Malformed lines (1)"));
+    }
+
+    #[test]
+    fn test_draw_all_sessions_view_tags_entries() {
+        use crate::log_entry::parse_jsonl_line;
+        use crate::session::{Agent, Session};
+        use std::path::PathBuf;
+
+        let mut app = test_app();
+        for (id, text) in [("aaaaaa-1", "First session"), ("bbbbbb-2", "Second one")] {
+            app.sessions.push(Session {
+                id: id.to_string(),
+                agents: vec![Agent {
+                    agent_id: None,
+                    slug: None,
+                    log_path: PathBuf::from(format!("/fake/{}.jsonl", id)),
+                    is_main: true,
+                }],
+                last_modified: SystemTime::now(),
+            });
+            let json = format!(
+                r#"{{"type": "user", "sessionId": "{}", "timestamp": "2025-01-15T10:30:00Z", "message": {{"role": "user", "content": "{}"}}}}"#,
+                id, text
+            );
+            app.ring_buffer.push(parse_jsonl_line(&json).unwrap());
+        }
+        app.all_sessions_selected = true;
+
+        let screen = render_screen(&mut app, 100, 12);
+
+        assert!(screen.contains("All sessions (2 active)"));
+        assert!(screen.contains("Log Stream: all sessions"));
+        assert!(screen.contains(" aaaaaa"));
+        assert!(screen.contains("First session"));
+        assert!(screen.contains(" bbbbbb"));
+        assert!(screen.contains("Second one"));
+    }
+
+    #[test]
+    fn test_all_sessions_view_tagged_lines_fit_the_pane() {
+        use crate::log_entry::parse_jsonl_line;
+        use crate::session::{Agent, Session};
+        use std::path::PathBuf;
+
+        let mut app = test_app();
+        app.sidebar_visible = false;
+        for id in ["aaaaaa-1", "bbbbbb-2"] {
+            app.sessions.push(Session {
+                id: id.to_string(),
+                agents: vec![Agent {
+                    agent_id: None,
+                    slug: None,
+                    log_path: PathBuf::from(format!("/fake/{}.jsonl", id)),
+                    is_main: true,
+                }],
+                last_modified: SystemTime::now(),
+            });
+        }
+        app.ring_buffer.push(
+            parse_jsonl_line(
+                r#"{"type": "system", "sessionId": "aaaaaa-1", "subtype": "compact_boundary", "uuid": "b1", "timestamp": "2025-01-15T10:30:00Z", "compactMetadata": {"trigger": "auto", "preTokens": 1000}}"#,
+            )
+            .unwrap(),
+        );
+        for i in 1..10 {
+            let json = format!(
+                r#"{{"type": "user", "sessionId": "{}", "timestamp": "2025-01-15T10:30:0{}Z", "message": {{"role": "user", "content": "{} end{}"}}}}"#,
+                if i % 2 == 0 { "aaaaaa-1" } else { "bbbbbb-2" },
+                i,
+                "wrapped ".repeat(i * 2),
+                i
+            );
+            app.ring_buffer.push(parse_jsonl_line(&json).unwrap());
+        }
+        app.all_sessions_selected = true;
+
+        // Wrapping counts the tag: the newest line is not cut off.
+        let screen = render_screen(&mut app, 50, 12);
+        assert!(screen.contains("end9"), "{}", screen);
+
+        // The divider leaves room for the tag and stays on one row.
+        let screen = render_screen(&mut app, 70, 40);
+        let rows: Vec<String> = screen
+            .chars()
+            .collect::<Vec<_>>()
+            .chunks(70)
+            .map(|row| row.iter().collect())
+            .collect();
+        let divider = rows
+            .iter()
+            .position(|r| r.contains("Context compacted"))
+            .unwrap();
+        assert!(rows[divider].contains(" aaaaaa "));
+        assert!(rows[divider].ends_with("\u{2500}\u{2502}"));
+        assert!(!rows[divider + 1].contains('\u{2500}'));
+    }
+
+    #[test]
+    fn test_draw_panes_side_by_side() {
+        use crate::log_entry::parse_jsonl_line;
+
+        let mut app = test_app();
+        app.sidebar_visible = false;
+        for (session, text) in [("aaaaaa-1", "Left pane text"), ("bbbbbb-2", "Right pane")] {
+            let json = format!(
+                r#"{{"type": "user", "sessionId": "{}", "timestamp": "2025-01-15T10:30:00Z", "message": {{"role": "user", "content": "{}"}}}}"#,
+                session, text
+            );
+            app.ring_buffer.push(parse_jsonl_line(&json).unwrap());
+        }
+        app.active_session_id = Some("aaaaaa-1".to_string());
+        app.split_pane();
+        app.active_session_id = Some("bbbbbb-2".to_string());
+        app.filter_state.selected_agent = Some("a1".to_string());
+
+        let mut terminal = test_terminal(100, 10);
+        terminal.draw(|frame| draw(frame, &mut app)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let row = |y: u16| -> String { (0..100).map(|x| buffer[(x, y)].symbol()).collect() };
+
+        let top = row(0);
+        assert!(top.find(" 1: aaaaaa ").unwrap() < 50);
+        assert!(top.find(" 2: bbbbbb > a1 ").unwrap() >= 50);
+        let body: String = (1..9).map(row).collect();
+        assert!(body.contains("Left pane text"));
+        // The right pane is narrowed to an agent with no entries.
+        assert!(!body.contains("Right pane"));
+        assert!(body.contains("Waiting for log entries"));
+    }
+
     #[test]
     fn test_draw_logstream_with_subagent_entry_no_panic() {
         use crate::log_entry::parse_jsonl_line;
@@ -2337,6 +3998,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_status_bar_badge_uses_process_state() {
+        use crate::process_detect::ProcessState;
+
+        let mut app = test_app();
+        app.sessions = vec![inactive_session("sess-old")];
+        app.active_session_id = Some("sess-old".to_string());
+
+        // A live but quiet process: idle rather than inactive.
+        app.process_states
+            .insert("sess-old".to_string(), ProcessState::Idle);
+        let text = line_text(&build_status_bar_line(&app, 120));
+        assert!(text.contains(" IDLE "), "expected IDLE badge in: {}", text);
+        assert!(!text.contains("INACTIVE"));
+
+        // A running process: no badge.
+        app.process_states
+            .insert("sess-old".to_string(), ProcessState::Running);
+        let text = line_text(&build_status_bar_line(&app, 120));
+        assert!(!text.contains("IDLE") && !text.contains("INACTIVE"));
+    }
+
     #[test]
     fn test_status_bar_no_active_session_no_badge() {
         let mut app = test_app();
@@ -2482,6 +4165,20 @@ mod tests {
             .expect("draw should not fail with active filters");
     }
 
+    #[test]
+    fn test_draw_filter_prompt_with_error_no_panic() {
+        let mut app = test_app();
+        app.filter_prompt.open("colour:red");
+        app.filter_prompt.error = Some("unknown filter key 'colour'".to_string());
+
+        for width in [80, 10] {
+            let mut terminal = test_terminal(width, 24);
+            terminal
+                .draw(|frame| draw(frame, &mut app))
+                .expect("draw should not fail with the filter prompt open");
+        }
+    }
+
     #[test]
     fn test_draw_narrow_terminal_with_all_features_no_panic() {
         let mut app = test_app();
@@ -2781,6 +4478,54 @@ mod tests {
             .expect("draw should not fail with help overlay");
     }
 
+    // -- Session info panel ------------------------------------------------
+
+    #[test]
+    fn test_session_info_lines_show_current_values_and_changes() {
+        use crate::session_info::{InfoChange, InfoField};
+
+        let mut app = test_app();
+        app.session_info.cwd = Some("/repo/sub".to_string());
+        app.session_info.git_branch = Some(String::new());
+        app.session_info.changes.push(InfoChange {
+            timestamp: None,
+            field: InfoField::Cwd,
+            from: "/repo".to_string(),
+            to: "/repo/sub".to_string(),
+        });
+
+        let text: Vec<String> = build_session_info_lines(&app)
+            .iter()
+            .map(line_to_text)
+            .collect();
+        assert!(text.contains(&"   Directory /repo/sub".to_string()));
+        assert!(text.contains(&"   Branch    (none)".to_string()));
+        assert!(text.contains(&"   Version   unknown".to_string()));
+        assert!(text.contains(&"   --:--:--  cwd     /repo \u{2192} /repo/sub".to_string()));
+    }
+
+    #[test]
+    fn test_session_info_lines_without_changes() {
+        let app = test_app();
+        let text: Vec<String> = build_session_info_lines(&app)
+            .iter()
+            .map(line_to_text)
+            .collect();
+        assert!(text.contains(&"   none during this session".to_string()));
+    }
+
+    #[test]
+    fn test_draw_session_info_panel_no_panic() {
+        let mut app = test_app();
+        app.session_info_visible = true;
+        for (w, h) in [(80, 24), (10, 4)] {
+            let mut terminal = test_terminal(w, h);
+            terminal
+                .draw(|frame| draw(frame, &mut app))
+                .expect("draw should not fail with session info panel");
+        }
+    }
+
     #[test]
     fn test_draw_help_overlay_small_terminal_no_panic() {
         let mut app = test_app();
@@ -2893,6 +4638,57 @@ mod tests {
             .expect("draw should not fail with active search");
     }
 
+    #[test]
+    fn test_draw_with_regex_search_highlights_matches() {
+        use crate::log_entry::parse_jsonl_line;
+
+        let mut app = test_app();
+        let user_json = r#"{
+            "type": "user",
+            "sessionId": "sess-001",
+            "timestamp": "2025-01-15T10:30:00Z",
+            "message": {"role": "user", "content": [{"type": "text", "text": "error[E0308] and error[E0599]"}]}
+        }"#;
+        app.ring_buffer.push(parse_jsonl_line(user_json).unwrap());
+
+        app.search_state.start_input();
+        app.search_state.cycle_match_mode();
+        app.search_state.cycle_match_mode();
+        for ch in r"error\[E\d+\]".chars() {
+            app.search_state.on_char(ch);
+        }
+        app.search_state.confirm();
+
+        let mut terminal = test_terminal(80, 24);
+        terminal
+            .draw(|frame| draw(frame, &mut app))
+            .expect("draw should not fail with regex search");
+
+        assert_eq!(app.search_state.matches.len(), 2);
+        assert!(app
+            .search_state
+            .matches
+            .iter()
+            .all(|m| m.byte_len == "error[E0308]".len()));
+    }
+
+    #[test]
+    fn test_draw_search_input_bar_with_regex_error_no_panic() {
+        let mut app = test_app();
+        app.search_state.start_input();
+        app.search_state.cycle_match_mode();
+        app.search_state.cycle_match_mode();
+        app.search_state.on_char('(');
+        assert!(app.search_state.error.is_some());
+
+        for width in [80, 10] {
+            let mut terminal = test_terminal(width, 3);
+            terminal
+                .draw(|frame| draw(frame, &mut app))
+                .expect("draw should not fail with an invalid regex");
+        }
+    }
+
     #[test]
     fn test_draw_with_search_active_no_entries_no_panic() {
         let mut app = test_app();
@@ -2953,6 +4749,82 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_status_bar_shows_session_usage_and_cost() {
+        use crate::log_entry::parse_jsonl_line;
+
+        let mut app = test_app();
+        app.sessions = vec![active_session("sess-001")];
+        app.active_session_id = Some("sess-001".to_string());
+        app.on_new_log_entry(
+            parse_jsonl_line(
+                r#"{"type": "assistant", "sessionId": "sess-001", "message": {"id": "m1", "model": "claude-sonnet-4-5", "content": [], "usage": {"input_tokens": 1000000, "output_tokens": 2000}}}"#,
+            )
+            .unwrap(),
+        );
+        // Usage from another session is not attributed to the active one.
+        app.on_new_log_entry(
+            parse_jsonl_line(
+                r#"{"type": "assistant", "sessionId": "sess-002", "message": {"id": "m2", "model": "claude-sonnet-4-5", "content": [], "usage": {"input_tokens": 5000000}}}"#,
+            )
+            .unwrap(),
+        );
+
+        let line = build_status_bar_line(&app, 160);
+        let text = line_text(&line);
+
+        assert!(
+            text.contains("1.0M tok ~$3.03"),
+            "expected usage segment in: {}",
+            text
+        );
+    }
+
+    #[test]
+    fn test_status_bar_no_usage_no_segment() {
+        let mut app = test_app();
+        app.sessions = vec![active_session("sess-001")];
+        app.active_session_id = Some("sess-001".to_string());
+
+        let line = build_status_bar_line(&app, 160);
+        let text = line_text(&line);
+
+        assert!(
+            !text.contains("tok"),
+            "unexpected usage segment in: {}",
+            text
+        );
+    }
+
+    #[test]
+    fn test_draw_help_overlay_with_usage_no_panic() {
+        use crate::log_entry::parse_jsonl_line;
+
+        let mut app = test_app();
+        app.help_overlay_visible = true;
+        app.on_new_log_entry(
+            parse_jsonl_line(
+                r#"{"type": "assistant", "message": {"id": "m1", "model": "claude-opus-4-6", "content": [], "usage": {"input_tokens": 10, "output_tokens": 20}}}"#,
+            )
+            .unwrap(),
+        );
+
+        let mut terminal = test_terminal(80, 60);
+        terminal
+            .draw(|frame| draw(frame, &mut app))
+            .expect("draw should not fail with usage stats");
+    }
+
+    #[test]
+    fn test_draw_help_overlay_shows_log_file() {
+        let mut app = test_app();
+        app.help_overlay_visible = true;
+        assert!(!render_screen(&mut app, 100, 120).contains("Log file:"));
+
+        app.log_path = Some(std::path::PathBuf::from("/tmp/cctail.log"));
+        assert!(render_screen(&mut app, 100, 120).contains("Log file:   /tmp/cctail.log"));
+    }
+
     #[test]
     fn test_line_to_text_concatenates_spans() {
         let line = Line::from(vec![Span::raw("hello "), Span::raw("world")]);
@@ -3016,4 +4888,71 @@ mod tests {
             .draw(|frame| draw(frame, &mut app))
             .expect("draw should not fail with search input bar on narrow terminal");
     }
+
+    // -- Global search ------------------------------------------------------
+
+    fn app_with_global_results() -> App {
+        use crate::history_search::{HistoryMatch, SessionMatches};
+
+        let mut app = test_app();
+        app.global_search.start_input();
+        app.global_search.input_buffer = "needle".to_string();
+        let matches = ["a", "b"]
+            .iter()
+            .map(|uuid| HistoryMatch {
+                session_id: "sess-1".to_string(),
+                agent_id: Some("a0d0bbc".to_string()),
+                path: std::path::PathBuf::from("/tmp/sess-1.jsonl"),
+                byte_offset: 0,
+                uuid: Some(uuid.to_string()),
+                timestamp: None,
+                snippet: "the needle here".to_string(),
+            })
+            .collect();
+        app.global_search.show_results(vec![SessionMatches {
+            session: inactive_session("sess-1"),
+            matches,
+            total: 3,
+        }]);
+        app
+    }
+
+    #[test]
+    fn test_build_global_search_lines_groups_and_selects() {
+        let mut app = app_with_global_results();
+        app.global_search.selected = 1;
+        let (lines, selected_line) = build_global_search_lines(&app);
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(line_text(&lines[0]), "sess-1  (3 matches, showing 2)");
+        assert_eq!(
+            line_text(&lines[1]),
+            "  ---------- --:--:--  [a0d0bbc] the needle here"
+        );
+        assert_eq!(selected_line, 2);
+        // The query is highlighted in the snippet.
+        assert!(lines[1].spans.iter().any(|s| s.content == "needle"));
+    }
+
+    #[test]
+    fn test_build_global_search_lines_no_results() {
+        let mut app = test_app();
+        app.global_search.input_buffer = "zzz".to_string();
+        app.global_search.show_results(vec![]);
+        let (lines, _) = build_global_search_lines(&app);
+        assert_eq!(line_text(&lines[0]), "No matches for \"zzz\"");
+    }
+
+    #[test]
+    fn test_draw_global_search_no_panic() {
+        let mut app = app_with_global_results();
+        for (w, h) in [(80, 24), (10, 3), (1, 1)] {
+            let mut terminal = test_terminal(w, h);
+            terminal.draw(|frame| draw(frame, &mut app)).unwrap();
+        }
+
+        app.global_search.start_input();
+        let mut terminal = test_terminal(80, 24);
+        terminal.draw(|frame| draw(frame, &mut app)).unwrap();
+    }
 }
@@ -9,7 +9,12 @@
 //! - Per-file byte-offset tracking for efficient incremental reads
 //! - Incomplete line buffering across multiple read events
 //! - File truncation detection (resets offset when file shrinks)
+//! - Removal and rename reporting, keeping the read offset of a renamed
+//!   file so its content is not read twice
 //! - Recursive directory watching for subagent log files
+//! - A polling backend for filesystems that deliver no native events
+//!   (NFS, sshfs, some container bind mounts), chosen by config or
+//!   automatically when files change without events
 
 use std::collections::HashMap;
 use std::fmt;
@@ -17,7 +22,7 @@ use std::io::{Read, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 // ---------------------------------------------------------------------------
 // Security constants
@@ -31,11 +36,30 @@ const MAX_READ_BYTES: u64 = 64 * 1024 * 1024;
 /// exceeds this size the buffer is discarded to prevent unbounded memory growth.
 const MAX_INCOMPLETE_LINE_BUF: usize = 10 * 1024 * 1024;
 
+/// Default interval between scans of the polling backend.
+pub const DEFAULT_POLL_INTERVAL_MS: u64 = 1000;
+
+/// How often the watcher reports a [`WatcherStatus`].
+const STATUS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long a one-sided rename event (`From` or `To`) waits for the
+/// matching side before it is taken as a removal or a creation.
+const RENAME_SETTLE: Duration = Duration::from_millis(100);
+
+/// How often [`WatchBackend::Auto`] rescans the directory while waiting
+/// for the first native event.
+const PROBE_INTERVAL: Duration = Duration::from_secs(3);
+
+/// How long [`WatchBackend::Auto`] probes for missed native events. A
+/// watcher that has not been caught missing one by then is kept.
+const PROBE_DURATION: Duration = Duration::from_secs(60);
+
 use notify::{Event, EventKind, RecursiveMode, Watcher};
 use tokio::sync::mpsc;
 use tokio::task::JoinHandle;
 
 use crate::log_entry::{parse_jsonl_line, LogEntry};
+use crate::{log_debug, log_info, log_warn};
 
 // ---------------------------------------------------------------------------
 // Per-file watch state
@@ -71,6 +95,13 @@ impl FileWatchState {
             incomplete_line_buf: String::new(),
         }
     }
+
+    /// Offset just past the last complete line read, i.e. `byte_offset`
+    /// minus any buffered incomplete line.
+    pub fn complete_offset(&self) -> u64 {
+        self.byte_offset
+            .saturating_sub(self.incomplete_line_buf.len() as u64)
+    }
 }
 
 impl Default for FileWatchState {
@@ -97,8 +128,40 @@ pub enum WatcherEvent {
         #[allow(dead_code)]
         path: PathBuf,
     },
+    /// A watched `.jsonl` file was deleted or moved out of the directory.
+    FileRemoved { path: PathBuf },
+    /// A watched `.jsonl` file was renamed. Its read offset moves with it,
+    /// so entries already sent are not sent again.
+    FileRenamed { from: PathBuf, to: PathBuf },
+    /// A watched file shrank. Its entries sent so far are stale; the file
+    /// is re-read from the start, with the new entries following this event.
+    FileTruncated { path: PathBuf },
+    /// A line of a watched file could not be parsed and was skipped.
+    ParseError { source: PathBuf, error: String },
+    /// Periodic snapshot of the watcher's state.
+    Status(WatcherStatus),
     /// An error occurred during watching or reading.
-    Error(#[allow(dead_code)] String),
+    Error(String),
+}
+
+/// Snapshot of the watcher's state, sent every [`STATUS_INTERVAL`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct WatcherStatus {
+    /// Backend in use: `Native` or `Poll`, never `Auto`.
+    pub backend: WatchBackend,
+    /// Read offset of every tracked file, sorted by path.
+    pub offsets: Vec<(PathBuf, u64)>,
+}
+
+impl WatcherStatus {
+    fn new(backend: WatchBackend, file_states: &HashMap<PathBuf, FileWatchState>) -> Self {
+        let mut offsets: Vec<(PathBuf, u64)> = file_states
+            .iter()
+            .map(|(path, state)| (path.clone(), state.byte_offset))
+            .collect();
+        offsets.sort();
+        Self { backend, offsets }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -140,6 +203,35 @@ impl From<notify::Error> for WatcherError {
     }
 }
 
+// ---------------------------------------------------------------------------
+// WatchBackend
+// ---------------------------------------------------------------------------
+
+/// How the watcher learns about file changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WatchBackend {
+    /// Native events, switching to polling if `.jsonl` files change while
+    /// no event arrives.
+    #[default]
+    Auto,
+    /// Native filesystem events only (inotify, FSEvents, ...).
+    Native,
+    /// Stat the watched files at a fixed interval.
+    Poll,
+}
+
+impl WatchBackend {
+    /// Parse a config value (`auto`, `native` or `poll`, case-insensitive).
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "auto" => Some(WatchBackend::Auto),
+            "native" => Some(WatchBackend::Native),
+            "poll" => Some(WatchBackend::Poll),
+            _ => None,
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // WatcherHandle
 // ---------------------------------------------------------------------------
@@ -179,22 +271,46 @@ pub fn is_watched_jsonl(path: &Path) -> bool {
 /// Read new entries from `path` starting at the byte offset recorded in `state`.
 ///
 /// Returns a vector of successfully parsed `LogEntry` values. Lines that
-/// fail to parse are silently skipped (with an optional verbose warning).
+/// fail to parse are skipped and logged at debug level.
 /// If the file has been truncated (its size is less than the recorded
 /// offset), the offset is reset to 0 and the entire file is re-read.
 ///
 /// Any trailing bytes that do not end with a newline are buffered in
 /// `state.incomplete_line_buf` for the next call.
-pub fn read_new_entries(path: &Path, state: &mut FileWatchState, verbose: bool) -> Vec<LogEntry> {
+pub fn read_new_entries(path: &Path, state: &mut FileWatchState) -> Vec<LogEntry> {
     let mut entries = Vec::new();
 
+    for (_, line) in read_new_lines(path, state) {
+        match parse_jsonl_line(line.trim()) {
+            Ok(entry) => entries.push(entry),
+            Err(e) => {
+                log_debug!("skipping malformed line in {}: {}", path.display(), e);
+            }
+        }
+    }
+
+    entries
+}
+
+/// Read new complete lines from `path` starting at the byte offset
+/// recorded in `state`, without parsing them.
+///
+/// Returns `(byte_offset, line)` pairs, where `byte_offset` is the start of
+/// the line within the file. Blank lines are skipped. Truncation and
+/// incomplete-line handling are the same as for [`read_new_entries`].
+pub fn read_new_lines(path: &Path, state: &mut FileWatchState) -> Vec<(u64, String)> {
+    let mut lines_out = Vec::new();
+
     let mut file = match std::fs::File::open(path) {
         Ok(f) => f,
+        // A file removed between the event and this read is routine.
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            log_debug!("could not open {}: {}", path.display(), e);
+            return lines_out;
+        }
         Err(e) => {
-            if verbose {
-                eprintln!("cc-tail: warning: could not open {}: {}", path.display(), e);
-            }
-            return entries;
+            log_warn!("could not open {}: {}", path.display(), e);
+            return lines_out;
         }
     };
 
@@ -203,41 +319,31 @@ pub fn read_new_entries(path: &Path, state: &mut FileWatchState, verbose: bool)
     let file_len = match file.metadata() {
         Ok(m) => m.len(),
         Err(e) => {
-            if verbose {
-                eprintln!("cc-tail: warning: could not stat {}: {}", path.display(), e);
-            }
-            return entries;
+            log_warn!("could not stat {}: {}", path.display(), e);
+            return lines_out;
         }
     };
 
     if file_len < state.byte_offset {
-        if verbose {
-            eprintln!(
-                "cc-tail: file truncated, resetting offset: {}",
-                path.display()
-            );
-        }
+        log_info!("file truncated, resetting offset: {}", path.display());
         state.byte_offset = 0;
         state.incomplete_line_buf.clear();
     }
 
     // Nothing new to read
     if file_len == state.byte_offset {
-        return entries;
+        return lines_out;
     }
 
     // Seek to our last read position
     if let Err(e) = file.seek(SeekFrom::Start(state.byte_offset)) {
-        if verbose {
-            eprintln!(
-                "cc-tail: warning: could not seek in {}: {}",
-                path.display(),
-                e
-            );
-        }
-        return entries;
+        log_warn!("could not seek in {}: {}", path.display(), e);
+        return lines_out;
     }
 
+    // File offset of the first byte of `full_text` below.
+    let text_start = state.complete_offset();
+
     // Read new bytes, capped at MAX_READ_BYTES to prevent OOM
     let bytes_available = file_len - state.byte_offset;
     let read_limit = bytes_available.min(MAX_READ_BYTES);
@@ -245,10 +351,8 @@ pub fn read_new_entries(path: &Path, state: &mut FileWatchState, verbose: bool)
     let bytes_read = match file.take(read_limit).read_to_string(&mut buf) {
         Ok(n) => n,
         Err(e) => {
-            if verbose {
-                eprintln!("cc-tail: warning: could not read {}: {}", path.display(), e);
-            }
-            return entries;
+            log_warn!("could not read {}: {}", path.display(), e);
+            return lines_out;
         }
     };
 
@@ -275,24 +379,20 @@ pub fn read_new_entries(path: &Path, state: &mut FileWatchState, verbose: bool)
             if !last.is_empty() {
                 if last.len() > MAX_INCOMPLETE_LINE_BUF {
                     // Discard oversized incomplete lines to prevent OOM
-                    if verbose {
-                        eprintln!(
-                            "cc-tail: warning: discarding oversized incomplete line ({} bytes) in {}",
-                            last.len(),
-                            path.display()
-                        );
-                    }
+                    log_warn!(
+                        "discarding oversized incomplete line ({} bytes) in {}",
+                        last.len(),
+                        path.display()
+                    );
                 } else {
                     state.incomplete_line_buf = last.to_string();
                     // Check if buffer has grown too large after prepending
                     if state.incomplete_line_buf.len() > MAX_INCOMPLETE_LINE_BUF {
-                        if verbose {
-                            eprintln!(
-                                "cc-tail: warning: incomplete line buffer exceeded {} bytes, resetting for {}",
-                                MAX_INCOMPLETE_LINE_BUF,
-                                path.display()
-                            );
-                        }
+                        log_warn!(
+                            "incomplete line buffer exceeded {} bytes, resetting for {}",
+                            MAX_INCOMPLETE_LINE_BUF,
+                            path.display()
+                        );
                         state.incomplete_line_buf.clear();
                     }
                 }
@@ -300,26 +400,17 @@ pub fn read_new_entries(path: &Path, state: &mut FileWatchState, verbose: bool)
         }
     }
 
+    let mut offset = text_start;
     for line in lines {
-        let trimmed = line.trim();
-        if trimmed.is_empty() {
+        let line_offset = offset;
+        offset += line.len() as u64 + 1;
+        if line.trim().is_empty() {
             continue;
         }
-        match parse_jsonl_line(trimmed) {
-            Ok(entry) => entries.push(entry),
-            Err(e) => {
-                if verbose {
-                    eprintln!(
-                        "cc-tail: warning: skipping malformed line in {}: {}",
-                        path.display(),
-                        e
-                    );
-                }
-            }
-        }
+        lines_out.push((line_offset, line.to_string()));
     }
 
-    entries
+    lines_out
 }
 
 // ---------------------------------------------------------------------------
@@ -328,18 +419,28 @@ pub fn read_new_entries(path: &Path, state: &mut FileWatchState, verbose: bool)
 
 /// Start watching a project directory for `.jsonl` file changes.
 ///
-/// Sets up a `notify::RecommendedWatcher` in recursive mode and bridges
-/// events from the synchronous `notify` channel to a tokio `mpsc` channel.
-/// Returns a receiver for `WatcherEvent` values and a `JoinHandle` for
-/// the background task.
+/// With a native backend, sets up a `notify::RecommendedWatcher` in
+/// recursive mode and bridges events from the synchronous `notify` channel
+/// to a tokio `mpsc` channel. With [`WatchBackend::Poll`], the directory is
+/// instead rescanned every `poll_interval` and size changes of `.jsonl`
+/// files are turned into the same events. Returns a receiver for
+/// `WatcherEvent` values and a `WatcherHandle` for the background task.
+///
+/// With [`WatchBackend::Auto`] the directory is also rescanned every few
+/// seconds until the first native event arrives, for at most
+/// [`PROBE_DURATION`]. If a `.jsonl` file has changed on two scans in a
+/// row without any event, native events are assumed not to work on this
+/// filesystem: the native watcher is dropped, polling takes over and the
+/// changes seen so far are read.
 ///
 /// # Arguments
 ///
 /// * `project_dir` - Path to the Claude Code project `.claude/projects/...` directory.
-/// * `verbose` - Whether to emit verbose diagnostic messages to stderr.
 /// * `channel_capacity` - Capacity of the tokio mpsc channel.
 /// * `initial_offsets` - Per-file byte offsets from replay. The watcher will
 ///   start reading each file from the given offset instead of byte 0.
+/// * `backend` - How file changes are detected.
+/// * `poll_interval` - Interval between scans of the polling backend.
 ///
 /// # Errors
 ///
@@ -347,9 +448,10 @@ pub fn read_new_entries(path: &Path, state: &mut FileWatchState, verbose: bool)
 /// or `WatcherError::Notify` if the watcher cannot be created.
 pub fn start_watching(
     project_dir: PathBuf,
-    verbose: bool,
     channel_capacity: usize,
     initial_offsets: HashMap<PathBuf, u64>,
+    backend: WatchBackend,
+    poll_interval: Duration,
 ) -> Result<(mpsc::Receiver<WatcherEvent>, WatcherHandle), WatcherError> {
     // Validate the project directory exists
     if !project_dir.is_dir() {
@@ -366,16 +468,27 @@ pub fn start_watching(
     // Create the synchronous channel for notify
     let (notify_tx, notify_rx) = std::sync::mpsc::channel::<Result<Event, notify::Error>>();
 
-    // Create the watcher
-    let mut watcher = notify::RecommendedWatcher::new(
-        move |res: Result<Event, notify::Error>| {
-            let _ = notify_tx.send(res);
-        },
-        notify::Config::default(),
-    )?;
-
-    // Start watching the directory recursively
-    watcher.watch(project_dir.as_ref(), RecursiveMode::Recursive)?;
+    // Snapshot the directory before the native watcher starts, so that
+    // changes it misses are measured from here.
+    let mut probe = (backend == WatchBackend::Auto).then(|| NativeProbe::new(&canonical_dir));
+    let mut poller = (backend == WatchBackend::Poll).then(|| DirScan::new(&canonical_dir));
+
+    let native = match backend {
+        WatchBackend::Poll => None,
+        WatchBackend::Auto | WatchBackend::Native => {
+            let notify_tx = notify_tx.clone();
+            let mut watcher = notify::RecommendedWatcher::new(
+                move |res: Result<Event, notify::Error>| {
+                    let _ = notify_tx.send(res);
+                },
+                notify::Config::default(),
+            )?;
+
+            // Start watching the directory recursively
+            watcher.watch(project_dir.as_ref(), RecursiveMode::Recursive)?;
+            Some(watcher)
+        }
+    };
 
     // Shutdown flag checked by the watcher loop on each timeout.
     let shutdown = Arc::new(AtomicBool::new(false));
@@ -383,17 +496,36 @@ pub fn start_watching(
 
     // Spawn a blocking task to bridge notify events to the async world
     let handle = tokio::task::spawn_blocking(move || {
-        // Keep the watcher alive for the lifetime of this task
-        let _watcher = watcher;
+        // Keep the watcher alive for the lifetime of this task, and the
+        // channel open once polling has replaced it.
+        let mut _native = native;
+        let _notify_tx = notify_tx;
         let mut file_states: HashMap<PathBuf, FileWatchState> = initial_offsets
             .into_iter()
             .map(|(path, offset)| (path, FileWatchState::new_with_offset(offset)))
             .collect();
+        let mut active_backend = match backend {
+            WatchBackend::Poll => WatchBackend::Poll,
+            WatchBackend::Auto | WatchBackend::Native => WatchBackend::Native,
+        };
+        let mut last_status: Option<Instant> = None;
+        let mut renames = PendingRenames::default();
 
         loop {
+            if last_status.is_none_or(|t| t.elapsed() >= STATUS_INTERVAL) {
+                // Dropped when the channel is full; the next one follows soon.
+                let status = WatcherStatus::new(active_backend, &file_states);
+                let _ = tx.try_send(WatcherEvent::Status(status));
+                last_status = Some(Instant::now());
+            }
+
             match notify_rx.recv_timeout(Duration::from_millis(200)) {
                 Ok(Ok(event)) => {
-                    process_notify_event(&event, &mut file_states, &tx, verbose, &canonical_dir);
+                    // Native events work; stop probing.
+                    probe = None;
+                    if !renames.defer(&event) {
+                        process_notify_event(&event, &mut file_states, &tx, &canonical_dir);
+                    }
                 }
                 Ok(Err(e)) => {
                     let _ = tx.blocking_send(WatcherEvent::Error(format!(
@@ -411,55 +543,318 @@ pub fn start_watching(
                     break;
                 }
             }
+
+            if let Some(scan) = poller.as_mut() {
+                if scan.last_scan.elapsed() >= poll_interval {
+                    for event in scan.rescan() {
+                        process_notify_event(&event, &mut file_states, &tx, &canonical_dir);
+                    }
+                }
+            }
+
+            if probe.as_ref().is_some_and(NativeProbe::expired) {
+                probe = None;
+            }
+            if let Some(missed) = probe.as_mut().and_then(NativeProbe::check) {
+                // Expected on some filesystems; the status reports the backend.
+                log_info!(
+                    "no filesystem events for {}, falling back to polling",
+                    canonical_dir.display()
+                );
+                poller = probe.take().map(|p| p.scan);
+                _native = None;
+                active_backend = WatchBackend::Poll;
+                for event in &missed {
+                    process_notify_event(event, &mut file_states, &tx, &canonical_dir);
+                }
+            }
+
+            for event in renames.take_settled() {
+                process_notify_event(&event, &mut file_states, &tx, &canonical_dir);
+            }
         }
     });
 
     Ok((rx, WatcherHandle { shutdown, handle }))
 }
 
+// ---------------------------------------------------------------------------
+// Renames
+// ---------------------------------------------------------------------------
+
+/// One-sided rename events waiting for their other side.
+///
+/// Backends report a rename as a single `Name(Both)` event with both
+/// paths, as `From` and `To` halves followed by `Both` (inotify), or as
+/// halves only. Halves are held for [`RENAME_SETTLE`]: a `Both` event
+/// covering them settles them as a rename, and the rest become a `Remove`
+/// or a `Create` depending on whether the path still exists.
+#[derive(Default)]
+struct PendingRenames {
+    paths: Vec<(PathBuf, Instant)>,
+}
+
+impl PendingRenames {
+    /// Hold `event` if it is one half of a rename. Returns `false` for
+    /// events the caller should process now, including `Both` events,
+    /// whose halves are dropped.
+    fn defer(&mut self, event: &Event) -> bool {
+        match event.kind {
+            EventKind::Modify(notify::event::ModifyKind::Name(notify::event::RenameMode::Both)) => {
+                self.paths.retain(|(p, _)| !event.paths.contains(p));
+                false
+            }
+            EventKind::Modify(notify::event::ModifyKind::Name(_)) => {
+                let now = Instant::now();
+                for path in &event.paths {
+                    if !self.paths.iter().any(|(p, _)| p == path) {
+                        self.paths.push((path.clone(), now));
+                    }
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Halves held for at least [`RENAME_SETTLE`], as synthetic `Create`
+    /// events for paths that exist and `Remove` events for the others.
+    fn take_settled(&mut self) -> Vec<Event> {
+        let (settled, waiting) = std::mem::take(&mut self.paths)
+            .into_iter()
+            .partition::<Vec<_>, _>(|(_, at)| at.elapsed() >= RENAME_SETTLE);
+        self.paths = waiting;
+        settled
+            .into_iter()
+            .map(|(path, _)| {
+                let kind = if path.exists() {
+                    EventKind::Create(notify::event::CreateKind::File)
+                } else {
+                    EventKind::Remove(notify::event::RemoveKind::File)
+                };
+                Event::new(kind).add_path(path)
+            })
+            .collect()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Polling
+// ---------------------------------------------------------------------------
+
+/// Sizes of the `.jsonl` files under a directory, for detecting changes
+/// by rescanning it. Logs are only appended to (or truncated), so a size
+/// change is taken as a write.
+struct DirScan {
+    dir: PathBuf,
+    sizes: HashMap<PathBuf, u64>,
+    last_scan: Instant,
+}
+
+impl DirScan {
+    fn new(dir: &Path) -> Self {
+        Self {
+            dir: dir.to_path_buf(),
+            sizes: jsonl_file_sizes(dir),
+            last_scan: Instant::now(),
+        }
+    }
+
+    /// Rescan the directory. Returns the changes since the previous scan
+    /// as synthetic `notify` events.
+    fn rescan(&mut self) -> Vec<Event> {
+        let sizes = jsonl_file_sizes(&self.dir);
+        let events = diff_file_sizes(&self.sizes, &sizes);
+        self.sizes = sizes;
+        self.last_scan = Instant::now();
+        events
+    }
+}
+
+/// Detects native watchers that never deliver events.
+///
+/// Rescans every [`PROBE_INTERVAL`] and compares with the snapshot taken
+/// before the watcher started. A change seen on one scan could still have
+/// its event in flight, so only a change still unannounced on the next
+/// scan counts.
+struct NativeProbe {
+    scan: DirScan,
+    baseline: HashMap<PathBuf, u64>,
+    changed: bool,
+    started: Instant,
+}
+
+impl NativeProbe {
+    fn new(dir: &Path) -> Self {
+        let scan = DirScan::new(dir);
+        Self {
+            baseline: scan.sizes.clone(),
+            scan,
+            changed: false,
+            started: Instant::now(),
+        }
+    }
+
+    /// Whether the probe ran for [`PROBE_DURATION`] without a verdict.
+    fn expired(&self) -> bool {
+        self.started.elapsed() >= PROBE_DURATION
+    }
+
+    /// Rescan if due. Returns the changes since the baseline, as synthetic
+    /// events, once native events are judged not to work.
+    fn check(&mut self) -> Option<Vec<Event>> {
+        if self.scan.last_scan.elapsed() < PROBE_INTERVAL {
+            return None;
+        }
+        self.scan.rescan();
+        let events = diff_file_sizes(&self.baseline, &self.scan.sizes);
+        if events.is_empty() {
+            return None;
+        }
+        if !self.changed {
+            self.changed = true;
+            return None;
+        }
+        Some(events)
+    }
+}
+
+/// Size of every `.jsonl` file under `dir`, recursively. Symlinks are not
+/// followed; unreadable entries are skipped.
+fn jsonl_file_sizes(dir: &Path) -> HashMap<PathBuf, u64> {
+    let mut sizes = HashMap::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(read_dir) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in read_dir.flatten() {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            let path = entry.path();
+            if file_type.is_dir() {
+                pending.push(path);
+            } else if file_type.is_file() && is_watched_jsonl(&path) {
+                if let Ok(meta) = entry.metadata() {
+                    sizes.insert(path, meta.len());
+                }
+            }
+        }
+    }
+    sizes
+}
+
+/// The events that turn `before` into `after`: `Create` for new files,
+/// `Modify` for resized ones and `Remove` for deleted ones. A non-empty
+/// file that appeared with the size of one that disappeared is taken as
+/// renamed and reported with a `Name(Both)` event.
+fn diff_file_sizes(before: &HashMap<PathBuf, u64>, after: &HashMap<PathBuf, u64>) -> Vec<Event> {
+    let mut events = Vec::new();
+    let mut removed: Vec<_> = before
+        .iter()
+        .filter(|(p, _)| !after.contains_key(*p))
+        .collect();
+    for (path, size) in after {
+        let event = match before.get(path) {
+            None => match removed.iter().position(|(_, s)| *s == size && *size > 0) {
+                Some(i) => {
+                    let (from, _) = removed.swap_remove(i);
+                    Event::new(EventKind::Modify(notify::event::ModifyKind::Name(
+                        notify::event::RenameMode::Both,
+                    )))
+                    .add_path(from.clone())
+                }
+                None => Event::new(EventKind::Create(notify::event::CreateKind::File)),
+            },
+            Some(old) if old != size => Event::new(EventKind::Modify(
+                notify::event::ModifyKind::Data(notify::event::DataChange::Size),
+            )),
+            Some(_) => continue,
+        };
+        events.push(event.add_path(path.clone()));
+    }
+    for (path, _) in removed {
+        events.push(
+            Event::new(EventKind::Remove(notify::event::RemoveKind::File)).add_path(path.clone()),
+        );
+    }
+    events
+}
+
 /// Validate that a path, after resolving symlinks, is still within the
 /// watched directory. Returns `Some(canonical)` if valid, `None` otherwise.
-fn validate_path_within_dir(path: &Path, watched_dir: &Path, verbose: bool) -> Option<PathBuf> {
+fn validate_path_within_dir(path: &Path, watched_dir: &Path) -> Option<PathBuf> {
     match path.canonicalize() {
         Ok(canonical) => {
             if canonical.starts_with(watched_dir) {
                 Some(canonical)
-            } else if verbose {
-                eprintln!(
-                    "cc-tail: warning: path {} resolves to {} which is outside watched directory {}",
+            } else {
+                log_warn!(
+                    "path {} resolves to {} which is outside watched directory {}",
                     path.display(),
                     canonical.display(),
                     watched_dir.display()
                 );
                 None
-            } else {
-                None
             }
         }
         Err(e) => {
             // File may have been deleted between the event and validation;
-            // this is expected for Remove events, so only warn in verbose mode.
-            if verbose {
-                eprintln!(
-                    "cc-tail: warning: could not canonicalize {}: {}",
-                    path.display(),
-                    e
-                );
-            }
+            // this is expected for Remove events, so it is only debug output.
+            log_debug!("could not canonicalize {}: {}", path.display(), e);
             None
         }
     }
 }
 
+/// Read new lines from `path` and send them through the channel: a
+/// `NewEntry` for each parsed line, a `ParseError` for each malformed one.
+/// A `FileTruncated` goes first if the file shrank.
+fn send_new_entries(path: &Path, state: &mut FileWatchState, tx: &mpsc::Sender<WatcherEvent>) {
+    let offset = state.byte_offset;
+    let lines = read_new_lines(path, state);
+    // Reads only move the offset forward unless the file was truncated.
+    if state.byte_offset < offset {
+        let _ = tx.blocking_send(WatcherEvent::FileTruncated {
+            path: path.to_path_buf(),
+        });
+    }
+    for (_, line) in lines {
+        let event = match parse_jsonl_line(line.trim()) {
+            Ok(entry) => WatcherEvent::NewEntry {
+                source: path.to_path_buf(),
+                entry: Box::new(entry),
+            },
+            Err(e) => {
+                log_debug!("skipping malformed line in {}: {}", path.display(), e);
+                WatcherEvent::ParseError {
+                    source: path.to_path_buf(),
+                    error: e.to_string(),
+                }
+            }
+        };
+        let _ = tx.blocking_send(event);
+    }
+}
+
 /// Process a single notify event, reading new entries and sending them
 /// through the channel.
 fn process_notify_event(
     event: &Event,
     file_states: &mut HashMap<PathBuf, FileWatchState>,
     tx: &mpsc::Sender<WatcherEvent>,
-    verbose: bool,
     watched_dir: &Path,
 ) {
+    if let EventKind::Modify(notify::event::ModifyKind::Name(notify::event::RenameMode::Both)) =
+        event.kind
+    {
+        if let [from, to] = event.paths.as_slice() {
+            process_rename(from, to, file_states, tx, watched_dir);
+        }
+        return;
+    }
+
     for path in &event.paths {
         if !is_watched_jsonl(path) {
             continue;
@@ -468,49 +863,27 @@ fn process_notify_event(
         match event.kind {
             EventKind::Create(_) => {
                 // Validate path is within the watched directory (symlink check)
-                let validated_path = match validate_path_within_dir(path, watched_dir, verbose) {
+                let validated_path = match validate_path_within_dir(path, watched_dir) {
                     Some(p) => p,
                     None => continue,
                 };
-
-                // New file detected
-                let _ = tx.blocking_send(WatcherEvent::NewFileDetected {
-                    path: validated_path.clone(),
-                });
-                // Also try to read any content that was written at creation time
-                // (handles race condition where data is written before the watcher
-                // sees the Modify event).
-                let state = file_states.entry(validated_path.clone()).or_default();
-                let entries = read_new_entries(&validated_path, state, verbose);
-                for entry in entries {
-                    let _ = tx.blocking_send(WatcherEvent::NewEntry {
-                        source: validated_path.clone(),
-                        entry: Box::new(entry),
-                    });
-                }
+                track_new_file(validated_path, file_states, tx);
             }
             EventKind::Modify(_) => {
                 // Validate path is within the watched directory (symlink check)
-                let validated_path = match validate_path_within_dir(path, watched_dir, verbose) {
+                let validated_path = match validate_path_within_dir(path, watched_dir) {
                     Some(p) => p,
                     None => continue,
                 };
 
                 let state = file_states.entry(validated_path.clone()).or_default();
-                let entries = read_new_entries(&validated_path, state, verbose);
-                for entry in entries {
-                    let _ = tx.blocking_send(WatcherEvent::NewEntry {
-                        source: validated_path.clone(),
-                        entry: Box::new(entry),
-                    });
-                }
+                send_new_entries(&validated_path, state, tx);
             }
             EventKind::Remove(_) => {
                 // Prune deleted files from file_states to prevent unbounded growth.
-                // Try to resolve symlink first; if that fails (file already gone),
-                // fall back to the original path for removal.
-                let key = path.canonicalize().unwrap_or_else(|_| path.clone());
+                let key = removed_path_key(path);
                 file_states.remove(&key);
+                let _ = tx.blocking_send(WatcherEvent::FileRemoved { path: key });
             }
             _ => {
                 // Ignore Access, etc.
@@ -519,6 +892,73 @@ fn process_notify_event(
     }
 }
 
+/// Report a new file at `path` (already validated) and send its content.
+fn track_new_file(
+    path: PathBuf,
+    file_states: &mut HashMap<PathBuf, FileWatchState>,
+    tx: &mpsc::Sender<WatcherEvent>,
+) {
+    let _ = tx.blocking_send(WatcherEvent::NewFileDetected { path: path.clone() });
+    // Also try to read any content that was written at creation time
+    // (handles race condition where data is written before the watcher
+    // sees the Modify event).
+    let state = file_states.entry(path.clone()).or_default();
+    send_new_entries(&path, state, tx);
+}
+
+/// Process the rename of `from` to `to`. Either side may not be a watched
+/// `.jsonl` file: renaming one away counts as a removal, and renaming a
+/// file into one (an atomic save) as a creation.
+fn process_rename(
+    from: &Path,
+    to: &Path,
+    file_states: &mut HashMap<PathBuf, FileWatchState>,
+    tx: &mpsc::Sender<WatcherEvent>,
+    watched_dir: &Path,
+) {
+    let from = is_watched_jsonl(from).then(|| removed_path_key(from));
+    let to = if is_watched_jsonl(to) {
+        validate_path_within_dir(to, watched_dir)
+    } else {
+        None
+    };
+    match (from, to) {
+        (Some(from), Some(to)) => {
+            // A file never read is tailed from its current end, like the
+            // files that existed when watching started.
+            let state = file_states.remove(&from).unwrap_or_else(|| {
+                let len = std::fs::metadata(&to).map(|m| m.len()).unwrap_or(0);
+                FileWatchState::new_with_offset(len)
+            });
+            let _ = tx.blocking_send(WatcherEvent::FileRenamed {
+                from,
+                to: to.clone(),
+            });
+            let state = file_states.entry(to.clone()).insert_entry(state).into_mut();
+            send_new_entries(&to, state, tx);
+        }
+        (Some(from), None) => {
+            file_states.remove(&from);
+            let _ = tx.blocking_send(WatcherEvent::FileRemoved { path: from });
+        }
+        (None, Some(to)) => {
+            // Whatever was tracked under `to` has been replaced.
+            file_states.remove(&to);
+            track_new_file(to, file_states, tx);
+        }
+        (None, None) => {}
+    }
+}
+
+/// Key of a file in `file_states` that may no longer exist: its canonical
+/// path, or else its canonical parent directory joined with its name.
+fn removed_path_key(path: &Path) -> PathBuf {
+    path.canonicalize()
+        .ok()
+        .or_else(|| Some(path.parent()?.canonicalize().ok()?.join(path.file_name()?)))
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -554,7 +994,7 @@ mod tests {
         let path = create_temp_jsonl(tmp.path(), "test.jsonl", content);
         let mut state = FileWatchState::new();
 
-        let entries = read_new_entries(&path, &mut state, false);
+        let entries = read_new_entries(&path, &mut state);
 
         assert_eq!(entries.len(), 2);
         assert_eq!(entries[0].entry_type, crate::log_entry::EntryType::User);
@@ -567,6 +1007,27 @@ mod tests {
         assert!(state.incomplete_line_buf.is_empty());
     }
 
+    // -- read_new_lines offsets ------------------------------------------------
+
+    #[test]
+    fn test_read_new_lines_reports_line_offsets() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("offsets.jsonl");
+        std::fs::write(&path, "aaa\n\nbb\ncc").unwrap();
+
+        let mut state = FileWatchState::new();
+        let lines = read_new_lines(&path, &mut state);
+        assert_eq!(lines, vec![(0, "aaa".to_string()), (5, "bb".to_string())]);
+        // The incomplete "cc" is not counted as read.
+        assert_eq!(state.complete_offset(), 8);
+
+        // Completing the line reports it at its original start offset.
+        std::fs::write(&path, "aaa\n\nbb\ncccc\n").unwrap();
+        let lines = read_new_lines(&path, &mut state);
+        assert_eq!(lines, vec![(8, "cccc".to_string())]);
+        assert_eq!(state.complete_offset(), 13);
+    }
+
     // -- 2. Incomplete line buffering across multiple reads --------------------
 
     #[test]
@@ -580,7 +1041,7 @@ mod tests {
         std::fs::write(&path, part1).unwrap();
 
         let mut state = FileWatchState::new();
-        let entries1 = read_new_entries(&path, &mut state, false);
+        let entries1 = read_new_entries(&path, &mut state);
 
         assert_eq!(entries1.len(), 1);
         assert_eq!(entries1[0].entry_type, crate::log_entry::EntryType::User);
@@ -596,7 +1057,7 @@ mod tests {
         );
         std::fs::write(&path, part2).unwrap();
 
-        let entries2 = read_new_entries(&path, &mut state, false);
+        let entries2 = read_new_entries(&path, &mut state);
 
         assert_eq!(entries2.len(), 1);
         assert_eq!(
@@ -620,7 +1081,7 @@ mod tests {
         let mut state = FileWatchState::new();
 
         // First read
-        let entries1 = read_new_entries(&path, &mut state, false);
+        let entries1 = read_new_entries(&path, &mut state);
         assert_eq!(entries1.len(), 1);
 
         // Append more content
@@ -633,7 +1094,7 @@ mod tests {
         drop(file);
 
         // Second read should only get the new line
-        let entries2 = read_new_entries(&path, &mut state, false);
+        let entries2 = read_new_entries(&path, &mut state);
         assert_eq!(entries2.len(), 1);
         assert_eq!(
             entries2[0].entry_type,
@@ -652,7 +1113,7 @@ mod tests {
         drop(file);
 
         // Third read should get both new lines
-        let entries3 = read_new_entries(&path, &mut state, false);
+        let entries3 = read_new_entries(&path, &mut state);
         assert_eq!(entries3.len(), 2);
         assert_eq!(
             entries3[0].entry_type,
@@ -677,7 +1138,7 @@ mod tests {
         let path = create_temp_jsonl(tmp.path(), "malformed.jsonl", content);
         let mut state = FileWatchState::new();
 
-        let entries = read_new_entries(&path, &mut state, false);
+        let entries = read_new_entries(&path, &mut state);
 
         // Only the two valid entries should be returned
         assert_eq!(entries.len(), 2);
@@ -707,7 +1168,7 @@ mod tests {
         let mut state = FileWatchState::new();
 
         // First read: consume everything
-        let entries1 = read_new_entries(&path, &mut state, false);
+        let entries1 = read_new_entries(&path, &mut state);
         assert_eq!(entries1.len(), 2);
         assert_eq!(state.byte_offset, initial.len() as u64);
 
@@ -716,7 +1177,7 @@ mod tests {
         std::fs::write(&path, new_content).unwrap();
 
         // The file is now shorter than byte_offset, so truncation should be detected
-        let entries2 = read_new_entries(&path, &mut state, true);
+        let entries2 = read_new_entries(&path, &mut state);
         assert_eq!(entries2.len(), 1);
         assert_eq!(
             entries2[0].entry_type,
@@ -752,7 +1213,7 @@ mod tests {
         let path = create_temp_jsonl(tmp.path(), "empty.jsonl", "");
         let mut state = FileWatchState::new();
 
-        let entries = read_new_entries(&path, &mut state, false);
+        let entries = read_new_entries(&path, &mut state);
         assert!(entries.is_empty());
         assert_eq!(state.byte_offset, 0);
         assert!(state.incomplete_line_buf.is_empty());
@@ -778,11 +1239,11 @@ mod tests {
         let mut state = FileWatchState::new();
 
         // First read
-        let entries1 = read_new_entries(&path, &mut state, false);
+        let entries1 = read_new_entries(&path, &mut state);
         assert_eq!(entries1.len(), 1);
 
         // Second read with no new content should return empty
-        let entries2 = read_new_entries(&path, &mut state, false);
+        let entries2 = read_new_entries(&path, &mut state);
         assert!(entries2.is_empty());
     }
 
@@ -819,7 +1280,7 @@ mod tests {
         let path = create_temp_jsonl(tmp.path(), "whitespace.jsonl", content);
         let mut state = FileWatchState::new();
 
-        let entries = read_new_entries(&path, &mut state, false);
+        let entries = read_new_entries(&path, &mut state);
         assert_eq!(entries.len(), 2);
     }
 
@@ -840,9 +1301,10 @@ mod tests {
     async fn test_start_watching_nonexistent_dir() {
         let result = start_watching(
             PathBuf::from("/nonexistent/path/12345"),
-            false,
             16,
             HashMap::new(),
+            WatchBackend::Auto,
+            Duration::from_millis(DEFAULT_POLL_INTERVAL_MS),
         );
         assert!(result.is_err());
         match result.unwrap_err() {
@@ -858,7 +1320,13 @@ mod tests {
     #[tokio::test]
     async fn test_start_watching_valid_dir() {
         let tmp = TempDir::new().unwrap();
-        let result = start_watching(tmp.path().to_path_buf(), false, 16, HashMap::new());
+        let result = start_watching(
+            tmp.path().to_path_buf(),
+            16,
+            HashMap::new(),
+            WatchBackend::Auto,
+            Duration::from_millis(DEFAULT_POLL_INTERVAL_MS),
+        );
         assert!(result.is_ok());
 
         let (_rx, handle) = result.unwrap();
@@ -884,7 +1352,7 @@ mod tests {
             attrs: Default::default(),
         };
 
-        process_notify_event(&event, &mut file_states, &tx, false, &watched_dir);
+        process_notify_event(&event, &mut file_states, &tx, &watched_dir);
 
         // No events should have been sent
         assert!(rx.try_recv().is_err());
@@ -908,7 +1376,7 @@ mod tests {
             attrs: Default::default(),
         };
 
-        process_notify_event(&event, &mut file_states, &tx, false, &watched_dir);
+        process_notify_event(&event, &mut file_states, &tx, &watched_dir);
 
         // Should receive NewFileDetected (with canonical path)
         match rx.try_recv() {
@@ -941,7 +1409,7 @@ mod tests {
             attrs: Default::default(),
         };
 
-        process_notify_event(&event, &mut file_states, &tx, false, &watched_dir);
+        process_notify_event(&event, &mut file_states, &tx, &watched_dir);
 
         // Should receive NewEntry (with canonical path)
         match rx.try_recv() {
@@ -958,7 +1426,7 @@ mod tests {
     #[test]
     fn test_read_new_entries_nonexistent_file() {
         let mut state = FileWatchState::new();
-        let entries = read_new_entries(Path::new("/nonexistent/file.jsonl"), &mut state, false);
+        let entries = read_new_entries(Path::new("/nonexistent/file.jsonl"), &mut state);
         assert!(entries.is_empty());
     }
 
@@ -997,7 +1465,7 @@ mod tests {
 "#;
         std::fs::write(&path, content).unwrap();
 
-        let entries = read_new_entries(&path, &mut state, false);
+        let entries = read_new_entries(&path, &mut state);
 
         // The oversized buffer gets prepended to the first line, making it
         // unparseable, but subsequent complete lines should still parse.
@@ -1025,7 +1493,7 @@ mod tests {
         // Create a file in tmp2 (outside watched dir)
         let outside_file = create_temp_jsonl(tmp2.path(), "outside.jsonl", "");
 
-        let result = validate_path_within_dir(&outside_file, &watched_dir, false);
+        let result = validate_path_within_dir(&outside_file, &watched_dir);
         assert!(
             result.is_none(),
             "path outside watched dir should be rejected"
@@ -1039,7 +1507,7 @@ mod tests {
 
         let inside_file = create_temp_jsonl(tmp.path(), "inside.jsonl", "");
 
-        let result = validate_path_within_dir(&inside_file, &watched_dir, false);
+        let result = validate_path_within_dir(&inside_file, &watched_dir);
         assert!(
             result.is_some(),
             "path inside watched dir should be accepted"
@@ -1051,8 +1519,7 @@ mod tests {
         let tmp = TempDir::new().unwrap();
         let watched_dir = tmp.path().canonicalize().unwrap();
 
-        let result =
-            validate_path_within_dir(Path::new("/nonexistent/path.jsonl"), &watched_dir, false);
+        let result = validate_path_within_dir(Path::new("/nonexistent/path.jsonl"), &watched_dir);
         assert!(result.is_none(), "nonexistent path should be rejected");
     }
 
@@ -1070,7 +1537,7 @@ mod tests {
         let symlink_path = watched.path().join("link.jsonl");
         std::os::unix::fs::symlink(&real_file, &symlink_path).unwrap();
 
-        let result = validate_path_within_dir(&symlink_path, &watched_dir, false);
+        let result = validate_path_within_dir(&symlink_path, &watched_dir);
         assert!(
             result.is_none(),
             "symlink pointing outside watched dir should be rejected"
@@ -1102,7 +1569,7 @@ mod tests {
             paths: vec![path.clone()],
             attrs: Default::default(),
         };
-        process_notify_event(&modify_event, &mut file_states, &tx, false, &watched_dir);
+        process_notify_event(&modify_event, &mut file_states, &tx, &watched_dir);
         assert!(
             file_states.contains_key(&canonical_path),
             "file_states should contain the file after Modify event"
@@ -1114,7 +1581,7 @@ mod tests {
             paths: vec![path.clone()],
             attrs: Default::default(),
         };
-        process_notify_event(&remove_event, &mut file_states, &tx, false, &watched_dir);
+        process_notify_event(&remove_event, &mut file_states, &tx, &watched_dir);
 
         // The file_states entry should be pruned.
         // Note: the file still exists on disk in the test (we didn't actually delete it),
@@ -1142,7 +1609,7 @@ mod tests {
         std::fs::write(&path, fragment1).unwrap();
 
         let mut state = FileWatchState::new();
-        let entries1 = read_new_entries(&path, &mut state, false);
+        let entries1 = read_new_entries(&path, &mut state);
         assert_eq!(entries1.len(), 0, "phase 1: no complete line yet");
         assert!(
             !state.incomplete_line_buf.is_empty(),
@@ -1158,7 +1625,7 @@ mod tests {
         write!(file, "{}", fragment2).unwrap();
         drop(file);
 
-        let entries2 = read_new_entries(&path, &mut state, false);
+        let entries2 = read_new_entries(&path, &mut state);
         assert_eq!(entries2.len(), 0, "phase 2: still no complete line");
         assert!(
             !state.incomplete_line_buf.is_empty(),
@@ -1174,7 +1641,7 @@ mod tests {
         write!(file, "{}", fragment3).unwrap();
         drop(file);
 
-        let entries3 = read_new_entries(&path, &mut state, false);
+        let entries3 = read_new_entries(&path, &mut state);
         assert_eq!(entries3.len(), 1, "phase 3: one complete entry");
         assert_eq!(entries3[0].entry_type, crate::log_entry::EntryType::User);
         assert_eq!(entries3[0].session_id.as_deref(), Some("s1"));
@@ -1208,7 +1675,7 @@ mod tests {
             .unwrap();
             drop(file);
 
-            let entries = read_new_entries(&path, &mut state, false);
+            let entries = read_new_entries(&path, &mut state);
             assert_eq!(entries.len(), 1, "cycle 1: one complete entry");
             assert_eq!(entries[0].session_id.as_deref(), Some("c1"));
             assert!(
@@ -1226,7 +1693,7 @@ mod tests {
             write!(file, "{}\n", r#"tant", "sessionId": "c2"}"#).unwrap();
             drop(file);
 
-            let entries = read_new_entries(&path, &mut state, false);
+            let entries = read_new_entries(&path, &mut state);
             assert_eq!(entries.len(), 1, "cycle 2: completed partial becomes entry");
             assert_eq!(
                 entries[0].entry_type,
@@ -1253,7 +1720,7 @@ mod tests {
             .unwrap();
             drop(file);
 
-            let entries = read_new_entries(&path, &mut state, false);
+            let entries = read_new_entries(&path, &mut state);
             assert_eq!(entries.len(), 1, "cycle 3: one complete entry");
             assert_eq!(entries[0].entry_type, crate::log_entry::EntryType::System);
             assert_eq!(entries[0].session_id.as_deref(), Some("c3"));
@@ -1277,7 +1744,7 @@ mod tests {
             .unwrap();
             drop(file);
 
-            let entries = read_new_entries(&path, &mut state, false);
+            let entries = read_new_entries(&path, &mut state);
             assert_eq!(
                 entries.len(),
                 2,
@@ -1324,7 +1791,7 @@ mod tests {
             paths: vec![path.clone()],
             attrs: Default::default(),
         };
-        process_notify_event(&remove_event, &mut file_states, &tx, false, &watched_dir);
+        process_notify_event(&remove_event, &mut file_states, &tx, &watched_dir);
 
         // file_states should be pruned using the fallback raw path
         assert!(
@@ -1332,4 +1799,363 @@ mod tests {
             "file_states should be pruned even when file is already deleted"
         );
     }
+
+    // -- 26. WatchBackend parsing -------------------------------------------
+
+    #[test]
+    fn test_watch_backend_parse() {
+        assert_eq!(WatchBackend::parse("auto"), Some(WatchBackend::Auto));
+        assert_eq!(WatchBackend::parse("Native"), Some(WatchBackend::Native));
+        assert_eq!(WatchBackend::parse("POLL"), Some(WatchBackend::Poll));
+        assert_eq!(WatchBackend::parse("inotify"), None);
+        assert_eq!(WatchBackend::default(), WatchBackend::Auto);
+    }
+
+    // -- 27. Directory snapshots diff into synthetic events ------------------
+
+    #[test]
+    fn test_jsonl_file_sizes_and_diff() {
+        let tmp = TempDir::new().unwrap();
+        let kept = create_temp_jsonl(tmp.path(), "kept.jsonl", "{}\n");
+        let grown = create_temp_jsonl(tmp.path(), "grown.jsonl", "{}\n");
+        let removed = create_temp_jsonl(tmp.path(), "removed.jsonl", "{}\n");
+        create_temp_jsonl(tmp.path(), "notes.txt", "ignored");
+        let before = jsonl_file_sizes(tmp.path());
+        assert_eq!(before.len(), 3);
+
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(&grown)
+            .unwrap()
+            .write_all(b"{}\n")
+            .unwrap();
+        std::fs::remove_file(&removed).unwrap();
+        std::fs::create_dir_all(tmp.path().join("s1/subagents")).unwrap();
+        let created = create_temp_jsonl(&tmp.path().join("s1/subagents"), "agent-a.jsonl", "");
+
+        let events = diff_file_sizes(&before, &jsonl_file_sizes(tmp.path()));
+        let kind_of = |path: &PathBuf| {
+            events
+                .iter()
+                .find(|e| e.paths.contains(path))
+                .map(|e| e.kind)
+        };
+        assert_eq!(events.len(), 3);
+        assert!(matches!(kind_of(&grown), Some(EventKind::Modify(_))));
+        assert!(matches!(kind_of(&removed), Some(EventKind::Remove(_))));
+        assert!(matches!(kind_of(&created), Some(EventKind::Create(_))));
+        assert_eq!(kind_of(&kept), None);
+    }
+
+    // -- 28. Poll backend delivers appended entries --------------------------
+
+    #[tokio::test]
+    async fn test_poll_backend_reads_appended_entries() {
+        let tmp = TempDir::new().unwrap();
+        let path = create_temp_jsonl(tmp.path(), "session.jsonl", "");
+        let canonical = path.canonicalize().unwrap();
+        let (mut rx, handle) = start_watching(
+            tmp.path().to_path_buf(),
+            16,
+            HashMap::from([(canonical, 0)]),
+            WatchBackend::Poll,
+            Duration::from_millis(20),
+        )
+        .unwrap();
+
+        std::fs::write(
+            &path,
+            "{\"type\": \"user\", \"uuid\": \"u1\", \"message\": {\"role\": \"user\", \"content\": \"hi\"}}\n",
+        )
+        .unwrap();
+
+        let event = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                match rx.recv().await {
+                    Some(WatcherEvent::NewEntry { entry, .. }) => break Some(entry),
+                    Some(_) => continue,
+                    None => break None,
+                }
+            }
+        })
+        .await
+        .expect("poll backend should report the new entry");
+        assert_eq!(event.unwrap().uuid.as_deref(), Some("u1"));
+        handle.shutdown();
+    }
+
+    // -- 29. Probe gives up on native events after two unannounced scans -----
+
+    #[test]
+    fn test_native_probe_needs_two_scans() {
+        let tmp = TempDir::new().unwrap();
+        let path = create_temp_jsonl(tmp.path(), "session.jsonl", "{}\n");
+        let mut probe = NativeProbe::new(tmp.path());
+        let backdate = |probe: &mut NativeProbe| {
+            probe.scan.last_scan = Instant::now() - PROBE_INTERVAL;
+        };
+
+        // Not due yet, and nothing changed.
+        assert!(probe.check().is_none());
+        backdate(&mut probe);
+        assert!(probe.check().is_none());
+
+        std::fs::write(&path, "{}\n{}\n").unwrap();
+        backdate(&mut probe);
+        assert!(probe.check().is_none(), "first sighting may be in flight");
+        backdate(&mut probe);
+        let missed = probe.check().expect("second sighting falls back");
+        assert_eq!(missed.len(), 1);
+        assert!(matches!(missed[0].kind, EventKind::Modify(_)));
+
+        // Probing stops after a while without a verdict.
+        assert!(!probe.expired());
+        probe.started = Instant::now() - PROBE_DURATION;
+        assert!(probe.expired());
+    }
+
+    // -- 30. Malformed lines are reported as ParseError events ---------------
+
+    #[test]
+    fn test_process_notify_event_reports_parse_errors() {
+        let tmp = TempDir::new().unwrap();
+        let watched_dir = tmp.path().canonicalize().unwrap();
+        let content = "{not json}\n{\"type\": \"user\", \"sessionId\": \"s1\"}\n";
+        let path = create_temp_jsonl(tmp.path(), "bad.jsonl", content);
+        let canonical_path = path.canonicalize().unwrap();
+
+        let (tx, mut rx) = mpsc::channel::<WatcherEvent>(16);
+        let mut file_states = HashMap::new();
+        let event = Event::new(EventKind::Modify(notify::event::ModifyKind::Data(
+            notify::event::DataChange::Content,
+        )))
+        .add_path(path.clone());
+
+        process_notify_event(&event, &mut file_states, &tx, &watched_dir);
+
+        match rx.try_recv() {
+            Ok(WatcherEvent::ParseError { source, error }) => {
+                assert_eq!(source, canonical_path);
+                assert!(!error.is_empty());
+            }
+            other => panic!("expected ParseError, got: {:?}", other),
+        }
+        assert!(matches!(rx.try_recv(), Ok(WatcherEvent::NewEntry { .. })));
+
+        let status = WatcherStatus::new(WatchBackend::Native, &file_states);
+        assert_eq!(status.offsets, vec![(canonical_path, content.len() as u64)]);
+    }
+
+    // -- 31. Truncation is reported before the re-read entries ---------------
+
+    #[test]
+    fn test_process_notify_event_reports_truncation() {
+        let tmp = TempDir::new().unwrap();
+        let watched_dir = tmp.path().canonicalize().unwrap();
+        let path = create_temp_jsonl(
+            tmp.path(),
+            "s1.jsonl",
+            "{\"type\": \"user\", \"uuid\": \"u1\"}\n{\"type\": \"user\", \"uuid\": \"u2\"}\n",
+        );
+
+        let (tx, mut rx) = mpsc::channel::<WatcherEvent>(16);
+        let mut file_states = HashMap::new();
+        let event = Event::new(EventKind::Modify(notify::event::ModifyKind::Data(
+            notify::event::DataChange::Content,
+        )))
+        .add_path(path.clone());
+        process_notify_event(&event, &mut file_states, &tx, &watched_dir);
+        while rx.try_recv().is_ok() {}
+
+        std::fs::write(&path, "{\"type\": \"user\", \"uuid\": \"u3\"}\n").unwrap();
+        process_notify_event(&event, &mut file_states, &tx, &watched_dir);
+
+        match rx.try_recv() {
+            Ok(WatcherEvent::FileTruncated { path: p }) => {
+                assert_eq!(p, path.canonicalize().unwrap())
+            }
+            other => panic!("expected FileTruncated, got: {:?}", other),
+        }
+        match rx.try_recv() {
+            Ok(WatcherEvent::NewEntry { entry, .. }) => {
+                assert_eq!(entry.uuid.as_deref(), Some("u3"))
+            }
+            other => panic!("expected NewEntry, got: {:?}", other),
+        }
+        assert!(rx.try_recv().is_err());
+    }
+
+    // -- 32. Renames keep the read offset, removals are reported -------------
+
+    #[test]
+    fn test_process_notify_event_rename_and_remove() {
+        let tmp = TempDir::new().unwrap();
+        let watched_dir = tmp.path().canonicalize().unwrap();
+        let old = create_temp_jsonl(tmp.path(), "old.jsonl", "{\"type\": \"user\"}\n");
+        let new = tmp.path().join("new.jsonl");
+
+        let (tx, mut rx) = mpsc::channel::<WatcherEvent>(16);
+        let mut file_states = HashMap::new();
+        let modify = |path: &Path| {
+            Event::new(EventKind::Modify(notify::event::ModifyKind::Data(
+                notify::event::DataChange::Content,
+            )))
+            .add_path(path.to_path_buf())
+        };
+        let rename = |from: &Path, to: &Path| {
+            Event::new(EventKind::Modify(notify::event::ModifyKind::Name(
+                notify::event::RenameMode::Both,
+            )))
+            .add_path(from.to_path_buf())
+            .add_path(to.to_path_buf())
+        };
+        process_notify_event(&modify(&old), &mut file_states, &tx, &watched_dir);
+        assert!(matches!(rx.try_recv(), Ok(WatcherEvent::NewEntry { .. })));
+
+        // Renamed: reported, and the content is not sent again.
+        std::fs::rename(&old, &new).unwrap();
+        process_notify_event(&rename(&old, &new), &mut file_states, &tx, &watched_dir);
+        match rx.try_recv() {
+            Ok(WatcherEvent::FileRenamed { from, to }) => {
+                assert_eq!(from, watched_dir.join("old.jsonl"));
+                assert_eq!(to, watched_dir.join("new.jsonl"));
+            }
+            other => panic!("expected FileRenamed, got: {:?}", other),
+        }
+        assert!(rx.try_recv().is_err());
+        assert_eq!(file_states.len(), 1);
+
+        // Renamed away from `.jsonl`: reported as removed.
+        let backup = tmp.path().join("new.jsonl.bak");
+        std::fs::rename(&new, &backup).unwrap();
+        process_notify_event(&rename(&new, &backup), &mut file_states, &tx, &watched_dir);
+        match rx.try_recv() {
+            Ok(WatcherEvent::FileRemoved { path }) => {
+                assert_eq!(path, watched_dir.join("new.jsonl"))
+            }
+            other => panic!("expected FileRemoved, got: {:?}", other),
+        }
+        assert!(file_states.is_empty());
+
+        // Renamed back: a new file, read from the start.
+        std::fs::rename(&backup, &new).unwrap();
+        process_notify_event(&rename(&backup, &new), &mut file_states, &tx, &watched_dir);
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(WatcherEvent::NewFileDetected { .. })
+        ));
+        assert!(matches!(rx.try_recv(), Ok(WatcherEvent::NewEntry { .. })));
+
+        // Deleted.
+        std::fs::remove_file(&new).unwrap();
+        let remove = Event::new(EventKind::Remove(notify::event::RemoveKind::File)).add_path(new);
+        process_notify_event(&remove, &mut file_states, &tx, &watched_dir);
+        match rx.try_recv() {
+            Ok(WatcherEvent::FileRemoved { path }) => {
+                assert_eq!(path, watched_dir.join("new.jsonl"))
+            }
+            other => panic!("expected FileRemoved, got: {:?}", other),
+        }
+        assert!(file_states.is_empty());
+    }
+
+    // -- 33. Rename halves settle into renames, removals or creations --------
+
+    #[test]
+    fn test_pending_renames_settle() {
+        let tmp = TempDir::new().unwrap();
+        let existing = create_temp_jsonl(tmp.path(), "here.jsonl", "");
+        let gone = tmp.path().join("gone.jsonl");
+        let half = |mode, path: &Path| {
+            Event::new(EventKind::Modify(notify::event::ModifyKind::Name(mode)))
+                .add_path(path.to_path_buf())
+        };
+
+        let mut renames = PendingRenames::default();
+        assert!(renames.defer(&half(notify::event::RenameMode::From, &gone)));
+        assert!(renames.defer(&half(notify::event::RenameMode::To, &existing)));
+        // The matching `Both` event drops both halves and is processed.
+        let both = Event::new(EventKind::Modify(notify::event::ModifyKind::Name(
+            notify::event::RenameMode::Both,
+        )))
+        .add_path(gone.clone())
+        .add_path(existing.clone());
+        assert!(!renames.defer(&both));
+        assert!(renames.paths.is_empty());
+
+        // Unmatched halves wait, then resolve by whether the path exists.
+        renames.defer(&half(notify::event::RenameMode::From, &gone));
+        renames.defer(&half(notify::event::RenameMode::To, &existing));
+        assert!(renames.take_settled().is_empty());
+        for (_, at) in &mut renames.paths {
+            *at -= RENAME_SETTLE;
+        }
+        let settled = renames.take_settled();
+        assert_eq!(settled.len(), 2);
+        assert!(matches!(settled[0].kind, EventKind::Remove(_)));
+        assert_eq!(settled[0].paths, [gone]);
+        assert!(matches!(settled[1].kind, EventKind::Create(_)));
+        assert_eq!(settled[1].paths, [existing]);
+        assert!(renames.paths.is_empty());
+    }
+
+    // -- 34. Polling pairs a removed and a created file into a rename --------
+
+    #[test]
+    fn test_diff_file_sizes_detects_rename() {
+        let tmp = TempDir::new().unwrap();
+        let old = create_temp_jsonl(tmp.path(), "old.jsonl", "{}\n");
+        let before = jsonl_file_sizes(tmp.path());
+        let new = tmp.path().join("new.jsonl");
+        std::fs::rename(&old, &new).unwrap();
+
+        let events = diff_file_sizes(&before, &jsonl_file_sizes(tmp.path()));
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            events[0].kind,
+            EventKind::Modify(notify::event::ModifyKind::Name(
+                notify::event::RenameMode::Both
+            ))
+        ));
+        assert_eq!(events[0].paths, [old, new]);
+    }
+
+    // -- 35. Native renames arrive as one FileRenamed ------------------------
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_native_backend_reports_rename() {
+        let tmp = TempDir::new().unwrap();
+        let old = create_temp_jsonl(tmp.path(), "old.jsonl", "{\"type\": \"user\"}\n");
+        let canonical = old.canonicalize().unwrap();
+        let (mut rx, handle) = start_watching(
+            tmp.path().to_path_buf(),
+            16,
+            HashMap::from([(canonical.clone(), 17)]),
+            WatchBackend::Native,
+            Duration::from_millis(DEFAULT_POLL_INTERVAL_MS),
+        )
+        .unwrap();
+
+        std::fs::rename(&old, tmp.path().join("new.jsonl")).unwrap();
+
+        let event = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                match rx.recv().await {
+                    Some(WatcherEvent::Status(_)) => continue,
+                    other => break other,
+                }
+            }
+        })
+        .await
+        .expect("native backend should report the rename");
+        match event {
+            Some(WatcherEvent::FileRenamed { from, to }) => {
+                assert_eq!(from, canonical);
+                assert_eq!(to, canonical.with_file_name("new.jsonl"));
+            }
+            other => panic!("expected FileRenamed, got: {:?}", other),
+        }
+        handle.shutdown();
+    }
 }
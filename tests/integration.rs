@@ -84,7 +84,7 @@ fn test_session_discovery_to_replay() {
 
     // -- Replay session --
     let session = &sessions[0];
-    let (entries, eof_offsets) = replay_session(session, &FilterState::default(), 20, false);
+    let (entries, eof_offsets) = replay_session(session, &FilterState::default(), 20);
 
     // Visible types: User, Assistant, System (result maps to System).
     // Progress should be excluded.
@@ -161,7 +161,7 @@ fn test_incremental_reading() {
 
     // -- First read: should return 2 entries --
     let mut state = FileWatchState::new();
-    let entries1 = read_new_entries(&path, &mut state, false);
+    let entries1 = read_new_entries(&path, &mut state);
     assert_eq!(entries1.len(), 2, "first read should return 2 entries");
     assert_eq!(entries1[0].entry_type, EntryType::User);
     assert_eq!(entries1[1].entry_type, EntryType::Assistant);
@@ -180,7 +180,7 @@ fn test_incremental_reading() {
     }
 
     // -- Second read: should return only the 2 NEW entries --
-    let entries2 = read_new_entries(&path, &mut state, false);
+    let entries2 = read_new_entries(&path, &mut state);
     assert_eq!(
         entries2.len(),
         2,
@@ -199,7 +199,7 @@ fn test_incremental_reading() {
     );
 
     // -- Third read with no changes: should return 0 entries --
-    let entries3 = read_new_entries(&path, &mut state, false);
+    let entries3 = read_new_entries(&path, &mut state);
     assert_eq!(
         entries3.len(),
         0,
@@ -250,6 +250,7 @@ fn test_filter_interaction_end_to_end() {
     let filter_a = FilterState {
         hide_tool_calls: false,
         selected_agent: Some("sub-A".to_string()),
+        ..Default::default()
     };
     let sub_a_entries: Vec<_> = buf.iter_filtered(|e| filter_a.matches(e)).collect();
     assert_eq!(
@@ -265,6 +266,7 @@ fn test_filter_interaction_end_to_end() {
     let filter_b = FilterState {
         hide_tool_calls: false,
         selected_agent: Some("sub-B".to_string()),
+        ..Default::default()
     };
     let sub_b_entries: Vec<_> = buf.iter_filtered(|e| filter_b.matches(e)).collect();
     assert_eq!(